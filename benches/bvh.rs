@@ -0,0 +1,74 @@
+use std::{hint::black_box, time::Instant};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use polypath::ObjObject;
+
+/// Rays from points on a sphere around the mesh, towards its center.
+#[allow(clippy::cast_precision_loss)]
+fn rays(obj: &ObjObject, count: usize) -> Vec<([f32; 3], [f32; 3])> {
+    let aabb = obj.aabb();
+    let center = [0, 1, 2].map(|i| f32::midpoint(aabb.min[i], aabb.max[i]));
+    let radius = (0..3)
+        .map(|i| aabb.max[i] - aabb.min[i])
+        .fold(0.0, f32::max);
+    let golden_angle = std::f32::consts::PI * (3.0 - 5.0_f32.sqrt());
+
+    (0..count)
+        .map(|i| {
+            let y = 1.0 - (i as f32 + 0.5) / count as f32 * 2.0;
+            let r = y.mul_add(-y, 1.0).sqrt();
+            let theta = golden_angle * i as f32;
+
+            let dir = [theta.cos() * r, y, theta.sin() * r];
+            let origin = [0, 1, 2].map(|a| center[a] - dir[a] * radius);
+            (origin, dir)
+        })
+        .collect()
+}
+
+fn benchmarks(c: &mut Criterion) {
+    for name in ["cheburashka.obj", "armadillo.obj"] {
+        let obj = ObjObject::read_from_file(format!("./meshes/{name}")).unwrap();
+        let bvh = obj.build_bvh();
+        let rays = rays(&obj, 64);
+
+        let start = Instant::now();
+        for (origin, dir) in &rays {
+            black_box(obj.ray_cast(*origin, *dir));
+        }
+        let brute_force = start.elapsed();
+
+        let start = Instant::now();
+        for (origin, dir) in &rays {
+            black_box(bvh.ray_cast(*origin, *dir));
+        }
+        let accelerated = start.elapsed();
+
+        println!(
+            "{name}: {} faces, {} rays, brute force {brute_force:?}, bvh {accelerated:?} ({:.0}x)",
+            obj.face_count(),
+            rays.len(),
+            brute_force.as_secs_f64() / accelerated.as_secs_f64()
+        );
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("build bvh", |b| b.iter(|| black_box(&obj).build_bvh()));
+        group.bench_function("ray cast brute force", |b| {
+            b.iter(|| {
+                rays.iter()
+                    .filter(|(origin, dir)| black_box(&obj).ray_cast(*origin, *dir).is_some())
+                    .count()
+            });
+        });
+        group.bench_function("ray cast bvh", |b| {
+            b.iter(|| {
+                rays.iter()
+                    .filter(|(origin, dir)| black_box(&bvh).ray_cast(*origin, *dir).is_some())
+                    .count()
+            });
+        });
+    }
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,103 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use polypath::{
+    ObjObject,
+    bounding::{Aabb, Sphere},
+    meshlet::{self, Meshlet},
+};
+
+type Meshlets = Vec<Meshlet<64, 124>>;
+
+/// Planes through the center of the mesh, with normals evenly distributed on a sphere.
+#[allow(clippy::cast_precision_loss)]
+fn planes(center: [f32; 3], count: usize) -> Vec<([f32; 3], f32)> {
+    let golden_angle = std::f32::consts::PI * (3.0 - 5.0_f32.sqrt());
+
+    (0..count)
+        .map(|i| {
+            let y = 1.0 - (i as f32 + 0.5) / count as f32 * 2.0;
+            let r = y.mul_add(-y, 1.0).sqrt();
+            let theta = golden_angle * i as f32;
+
+            let normal = [theta.cos() * r, y, theta.sin() * r];
+            let d = -normal.iter().zip(center).map(|(n, c)| n * c).sum::<f32>();
+            (normal, d)
+        })
+        .collect()
+}
+
+fn sphere_culled(sphere: &Sphere, (normal, d): &([f32; 3], f32)) -> bool {
+    let distance = normal
+        .iter()
+        .zip(sphere.center)
+        .map(|(n, c)| n * c)
+        .sum::<f32>()
+        + d;
+
+    distance < -sphere.radius
+}
+
+fn aabb_culled(aabb: &Aabb, (normal, d): &([f32; 3], f32)) -> bool {
+    // the corner of the box furthest along the normal
+    let distance = (0..3)
+        .map(|i| {
+            normal[i]
+                * if normal[i] > 0.0 {
+                    aabb.max[i]
+                } else {
+                    aabb.min[i]
+                }
+        })
+        .sum::<f32>()
+        + d;
+
+    distance < 0.0
+}
+
+fn count_culled(meshlets: &Meshlets, planes: &[([f32; 3], f32)], aabb: bool) -> usize {
+    planes
+        .iter()
+        .map(|plane| {
+            meshlets
+                .iter()
+                .filter(|m| {
+                    if aabb {
+                        aabb_culled(&m.aabb, plane)
+                    } else {
+                        sphere_culled(&m.bounding, plane)
+                    }
+                })
+                .count()
+        })
+        .sum()
+}
+
+fn benchmarks(c: &mut Criterion) {
+    for name in ["cubes.obj", "cheburashka.obj", "armadillo.obj"] {
+        let obj = ObjObject::read_from_file(format!("./meshes/{name}")).unwrap();
+        let meshlets: Meshlets = meshlet::build_meshlets_from_obj(&obj, 0.5);
+        let bounds = obj.aabb();
+        let center = [0, 1, 2].map(|i| f32::midpoint(bounds.min[i], bounds.max[i]));
+        let planes = planes(center, 64);
+
+        let sphere = count_culled(&meshlets, &planes, false);
+        let aabb = count_culled(&meshlets, &planes, true);
+        println!(
+            "{name}: of {} meshlets x {} planes, culled by sphere {sphere}, by aabb {aabb}",
+            meshlets.len(),
+            planes.len()
+        );
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("sphere culling", |b| {
+            b.iter(|| count_culled(black_box(&meshlets), black_box(&planes), false))
+        });
+        group.bench_function("aabb culling", |b| {
+            b.iter(|| count_culled(black_box(&meshlets), black_box(&planes), true))
+        });
+    }
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
-use std::hint::black_box;
+use std::{hint::black_box, ops::ControlFlow};
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use polypath::ObjObject;
+use polypath::{ObjObject, ParseOptions};
 
 fn read_obj(path: &str) -> ObjObject {
     ObjObject::read_from_file(path).expect(path)
@@ -19,6 +19,38 @@ fn benchmark(c: &mut Criterion) {
     c.bench_function("armadillo", |b| {
         b.iter(|| read_obj(black_box("./meshes/armadillo.obj")))
     });
+
+    // only positions, like a collision mesh
+    let positions_only = ParseOptions::new()
+        .keep_normals(false)
+        .keep_texture_coords(false)
+        .keep_colors(false);
+    c.bench_function("armadillo positions only", |b| {
+        b.iter(|| {
+            ObjObject::read_from_file_with(black_box("./meshes/armadillo.obj"), &positions_only)
+                .unwrap()
+        })
+    });
+
+    // compare with "armadillo", reporting progress should be cheap
+    c.bench_function("armadillo with progress", |b| {
+        b.iter(|| {
+            ObjObject::read_from_file_with_progress(
+                black_box("./meshes/armadillo.obj"),
+                &ParseOptions::default(),
+                |progress| {
+                    black_box(progress);
+                    ControlFlow::Continue(())
+                },
+            )
+            .unwrap()
+        })
+    });
+
+    #[cfg(feature = "rayon")]
+    c.bench_function("armadillo parallel", |b| {
+        b.iter(|| ObjObject::parse_parallel(black_box("./meshes/armadillo.obj"), 0).unwrap())
+    });
 }
 
 criterion_group!(benches, benchmark);
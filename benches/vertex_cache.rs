@@ -0,0 +1,53 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use polypath::{ObjObject, VertexTextureData, opt};
+
+const CACHE_SIZE: usize = 32;
+
+fn to_u32(indices: Vec<usize>) -> Vec<u32> {
+    indices
+        .into_iter()
+        .map(|i| u32::try_from(i).unwrap())
+        .collect()
+}
+
+fn greedy(vertices: Vec<VertexTextureData>) -> Vec<u32> {
+    let vertices = opt::optimize_vertex_order(vertices);
+    to_u32(opt::indexed_vertices(&vertices).0)
+}
+
+fn forsyth(vertices: &[VertexTextureData]) -> Vec<u32> {
+    let (indices, vertices) = opt::indexed_vertices(vertices);
+    let mut indices = to_u32(indices);
+    opt::optimize_forsyth(&mut indices, vertices.len());
+    indices
+}
+
+fn benchmarks(c: &mut Criterion) {
+    for name in ["cheburashka.obj", "armadillo.obj"] {
+        let obj = ObjObject::read_from_file(format!("./meshes/{name}")).unwrap();
+        // the meshes do not share any vertices, so they are welded first
+        let (vertices, _) = obj.vertices();
+        let (indices, welded) = opt::weld_preserving_seams(&vertices, 1e-6, 30.0, None);
+        let vertices = indices.iter().map(|i| welded[*i]).collect::<Vec<_>>();
+
+        println!(
+            "{name}: ACMR unoptimized {:.3}, greedy {:.3}, forsyth {:.3}",
+            opt::acmr(&to_u32(opt::indexed_vertices(&vertices).0), CACHE_SIZE),
+            opt::acmr(&greedy(vertices.clone()), CACHE_SIZE),
+            opt::acmr(&forsyth(&vertices), CACHE_SIZE),
+        );
+
+        let mut group = c.benchmark_group(name);
+        group.bench_function("optimize_vertex_order", |b| {
+            b.iter(|| greedy(black_box(vertices.clone())))
+        });
+        group.bench_function("optimize_forsyth", |b| {
+            b.iter(|| forsyth(black_box(&vertices)))
+        });
+    }
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
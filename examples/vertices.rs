@@ -16,7 +16,7 @@ fn main() {
         println!(
             "[{mesh}] took [{}ms] with [{} vertices]",
             start.elapsed().as_millis(),
-            obj.vert_count()
+            obj.unique_vertex_count()
         );
 
         // extract all the vertices (position, ?color, ?normal, ?texture coord, ?material index)
@@ -3,54 +3,282 @@ use crate::vec3::Vec3;
 /// A bounding sphere around a cluster of points.
 #[derive(Debug, Clone, Copy)]
 pub struct Sphere {
-    pub center: (f32, f32, f32),
+    pub center: [f32; 3],
     pub radius: f32,
 }
 
-/// Builds a bounding sphere around the given points.
-pub fn build_bounding_sphere(vertices: impl Iterator<Item = (f32, f32, f32)> + Clone) -> Sphere {
-    let mut min_x = f32::MIN;
-    let mut max_x = f32::MAX;
+impl Sphere {
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "the center is an array now, use `center` directly"
+    )]
+    /// Returns the center as a tuple.
+    pub fn center_tuple(&self) -> (f32, f32, f32) {
+        self.center.into()
+    }
+}
 
-    let mut min_y = f32::MIN;
-    let mut max_y = f32::MAX;
+/// Builds the minimum bounding sphere around the given points, using Welzl's algorithm.
+pub fn build_bounding_sphere(vertices: impl Iterator<Item = [f32; 3]> + Clone) -> Sphere {
+    let mut points = vertices.map(Vec3::from).collect::<Vec<_>>();
 
-    let mut min_z = f32::MIN;
-    let mut max_z = f32::MAX;
+    // the expected linear runtime relies on a random order, while meshes tend to list neighboring points together
+    shuffle(&mut points);
 
-    // find min/max for every axis (x,y,z)
-    for p in vertices.clone().map(Vec3::from) {
-        // x
-        min_x = f32::min(min_x, p.x);
-        max_x = f32::max(max_x, p.x);
+    let mut ball = Ball::point(points.first().copied().unwrap_or_default());
+    for (i, &first) in points.iter().enumerate() {
+        if ball.contains(first) {
+            continue;
+        }
 
-        // y
-        min_y = f32::min(min_y, p.y);
-        max_y = f32::max(max_y, p.y);
+        ball = Ball::point(first);
+        for (j, &second) in points[..i].iter().enumerate() {
+            if ball.contains(second) {
+                continue;
+            }
 
-        // z
-        min_z = f32::min(min_z, p.z);
-        max_z = f32::max(max_z, p.z);
+            ball = Ball::diameter(first, second);
+            for (k, &third) in points[..j].iter().enumerate() {
+                if ball.contains(third) {
+                    continue;
+                }
+
+                ball = Ball::circumscribed(first, second, third);
+                for &fourth in &points[..k] {
+                    if !ball.contains(fourth) {
+                        ball = Ball::circumscribed_tetrahedron(first, second, third, fourth);
+                    }
+                }
+            }
+        }
     }
 
-    // find axis with greatest diameter
-    let center = Vec3::new(
-        f32::midpoint(min_x, max_x),
-        f32::midpoint(min_y, max_y),
-        f32::midpoint(min_z, max_z),
-    );
+    // rounding errors could leave points slightly outside
+    let radius = points
+        .iter()
+        .fold(0.0, |radius, &p| f32::max(radius, p.distance(ball.center)));
+
+    Sphere {
+        center: ball.center.to_array(),
+        radius,
+    }
+}
 
-    // got bounding box with corners (Vec3<min_x, min_y, min_z> , Vec3<max_x, max_y, max_z>)
-    // now find a sphere and make sure, each point is contained in it
+/// Shuffles the points in a fixed pseudo random order, so the same points always result in the same sphere.
+fn shuffle(points: &mut [Vec3]) {
+    let mut state = 0x9E37_79B9_u32;
+    for i in (1..points.len()).rev() {
+        // xorshift
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
 
-    let mut radius = 0.0;
-    for p in vertices.clone().map(Vec3::from) {
-        let distance = Vec3::distance(p, center);
-        radius = f32::max(radius, distance);
+        points.swap(i, state as usize % (i + 1));
     }
+}
 
-    Sphere {
-        center: (center.x, center.y, center.z),
-        radius,
+/// A sphere with its squared radius, while searching the minimum bounding sphere.
+#[derive(Debug, Clone, Copy)]
+struct Ball {
+    center: Vec3,
+    radius_squared: f32,
+}
+
+impl Ball {
+    /// The relative tolerance of [`Ball::contains`], so points on the surface are not repeatedly added.
+    const TOLERANCE: f32 = 1e-5;
+
+    const fn point(center: Vec3) -> Self {
+        Self {
+            center,
+            radius_squared: 0.0,
+        }
     }
+
+    fn diameter(a: Vec3, b: Vec3) -> Self {
+        let center = (a + b) * 0.5;
+
+        Self {
+            center,
+            radius_squared: (a - center).length_squared(),
+        }
+    }
+
+    /// The smallest sphere with all three points on its surface.
+    fn circumscribed(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let normal = ab.cross(&ac);
+        let denominator = 2.0 * normal.length_squared();
+
+        // collinear points are enclosed by the sphere around the two points furthest apart
+        if denominator <= f32::EPSILON * ab.length_squared() * ac.length_squared() {
+            return Self::largest([
+                Self::diameter(a, b),
+                Self::diameter(a, c),
+                Self::diameter(b, c),
+            ]);
+        }
+
+        let offset = (normal.cross(&ab) * ac.length_squared()
+            + ac.cross(&normal) * ab.length_squared())
+            / denominator;
+
+        Self {
+            center: a + offset,
+            radius_squared: offset.length_squared(),
+        }
+    }
+
+    /// The sphere with all four points on its surface.
+    fn circumscribed_tetrahedron(a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let ad = d - a;
+        let denominator = 2.0 * ab.dot(&ac.cross(&ad));
+
+        // coplanar points are enclosed by the sphere around the three points spanning the largest circle
+        if denominator.abs()
+            <= f32::EPSILON * ab.length() * ac.length() * ad.length() * (ab + ac + ad).length()
+        {
+            return Self::largest([
+                Self::circumscribed(a, b, c),
+                Self::circumscribed(a, b, d),
+                Self::circumscribed(a, c, d),
+                Self::circumscribed(b, c, d),
+            ]);
+        }
+
+        let offset = (ac.cross(&ad) * ab.length_squared()
+            + ad.cross(&ab) * ac.length_squared()
+            + ab.cross(&ac) * ad.length_squared())
+            / denominator;
+
+        Self {
+            center: a + offset,
+            radius_squared: offset.length_squared(),
+        }
+    }
+
+    fn largest<const N: usize>(balls: [Self; N]) -> Self {
+        balls
+            .into_iter()
+            .reduce(|a, b| {
+                if b.radius_squared > a.radius_squared {
+                    b
+                } else {
+                    a
+                }
+            })
+            .expect("at least one ball")
+    }
+
+    fn contains(&self, point: Vec3) -> bool {
+        (point - self.center).length_squared() <= self.radius_squared * (1.0 + Self::TOLERANCE)
+    }
+}
+
+/// An axis aligned bounding box.
+///
+/// A box without any points is empty, with `min` at [`f32::MAX`] and `max` at [`f32::MIN`], see [`Aabb::EMPTY`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Default for Aabb {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Aabb {
+    /// A bounding box that does not contain any point.
+    pub const EMPTY: Self = Self {
+        min: [f32::MAX; 3],
+        max: [f32::MIN; 3],
+    };
+
+    #[inline]
+    #[must_use]
+    /// Returns `true` if this box does not contain any point.
+    pub fn is_empty(&self) -> bool {
+        (0..3).any(|i| self.min[i] > self.max[i])
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: [0, 1, 2].map(|i| f32::min(self.min[i], other.min[i])),
+            max: [0, 1, 2].map(|i| f32::max(self.max[i], other.max[i])),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the overlap of both boxes, `None` if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            min: [0, 1, 2].map(|i| f32::max(self.min[i], other.min[i])),
+            max: [0, 1, 2].map(|i| f32::min(self.max[i], other.max[i])),
+        };
+
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns `true` if the point is inside of this box, or on its surface.
+    pub fn contains_point(&self, point: [f32; 3]) -> bool {
+        (0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns `true` if the whole sphere is inside of this box.
+    pub fn contains_sphere(&self, sphere: &Sphere) -> bool {
+        (0..3).all(|i| {
+            self.min[i] <= sphere.center[i] - sphere.radius
+                && sphere.center[i] + sphere.radius <= self.max[i]
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the surface area of this box, `0.0` if it is empty.
+    pub fn surface_area(&self) -> f32 {
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        let [x, y, z] = [0, 1, 2].map(|i| self.max[i] - self.min[i]);
+
+        2.0 * z.mul_add(x, x.mul_add(y, y * z))
+    }
+
+    /// Grows this box to contain the point.
+    #[inline]
+    pub(crate) fn extend(&mut self, point: [f32; 3]) {
+        self.min = [0, 1, 2].map(|i| f32::min(self.min[i], point[i]));
+        self.max = [0, 1, 2].map(|i| f32::max(self.max[i], point[i]));
+    }
+}
+
+/// Builds an axis aligned bounding box around the given points.
+///
+/// Returns [`Aabb::EMPTY`] if there are no points.
+pub fn build_aabb(vertices: impl Iterator<Item = [f32; 3]>) -> Aabb {
+    let mut aabb = Aabb::EMPTY;
+
+    for p in vertices {
+        aabb.extend(p);
+    }
+
+    aabb
 }
@@ -0,0 +1,344 @@
+use std::ops::Range;
+
+use crate::{
+    ObjObject,
+    bounding::{Aabb, build_aabb},
+    intersect::ray_triangle_intersect,
+};
+
+/// A bounding volume hierarchy over triangles, built with the surface area heuristic.
+///
+/// Speeds up ray casts from testing every triangle to roughly logarithmic time, see [`Bvh::ray_cast`].
+/// The triangles are copied into the hierarchy, so it stays valid if the source gets changed or dropped.
+///
+/// ## Example
+/// ```rust
+/// use polypath::bvh::Bvh;
+///
+/// let bvh = Bvh::build(&[[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]]);
+///
+/// let hit = bvh.ray_cast([0.25, 0.25, 1.0], [0.0, 0.0, -1.0]).unwrap();
+/// assert_eq!(hit.face, 0);
+/// assert_eq!(hit.distance, 1.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Bvh {
+    /// The root is the first node, if there are any triangles.
+    nodes: Vec<BvhNode>,
+    /// The triangles, ordered so every leaf references a contiguous range.
+    triangles: Vec<[[f32; 3]; 3]>,
+    /// The index of every triangle in the input of [`Bvh::build`].
+    faces: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum BvhNode {
+    Leaf { aabb: Aabb, triangles: Range<usize> },
+    Internal { aabb: Aabb, children: [usize; 2] },
+}
+
+impl BvhNode {
+    #[inline]
+    const fn aabb(&self) -> &Aabb {
+        match self {
+            Self::Leaf { aabb, .. } | Self::Internal { aabb, .. } => aabb,
+        }
+    }
+}
+
+/// The nearest intersection of a ray with the triangles of a [`Bvh`], see [`Bvh::ray_cast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BvhHit {
+    /// Index of the hit triangle, in the input of [`Bvh::build`].
+    pub face: usize,
+    /// Distance along the ray, in multiples of the ray direction.
+    pub distance: f32,
+    /// Barycentric coordinate of the hit, weighting the second corner of the triangle.
+    pub u: f32,
+    /// Barycentric coordinate of the hit, weighting the third corner of the triangle.
+    pub v: f32,
+}
+
+/// The number of bins, candidate splits are evaluated at along each axis.
+const BIN_COUNT: usize = 16;
+/// Leaves with more triangles always get split, if their triangles can be separated at all.
+const MAX_LEAF_SIZE: usize = 8;
+/// The cost of visiting a node, relative to intersecting a single triangle.
+const TRAVERSAL_COST: f32 = 1.0;
+
+impl Bvh {
+    /// Builds a hierarchy over the given triangles.
+    ///
+    /// Nodes are split where the surface area heuristic estimates the cheapest ray casts,
+    /// evaluated at a fixed number of bins along every axis.
+    #[must_use]
+    pub fn build(faces: &[[[f32; 3]; 3]]) -> Self {
+        let bounds = faces
+            .iter()
+            .map(|face| build_aabb(face.iter().copied()))
+            .collect::<Vec<_>>();
+        let centroids = bounds.iter().map(center).collect::<Vec<_>>();
+
+        let mut order = (0..faces.len()).collect::<Vec<_>>();
+        let mut nodes = Vec::new();
+
+        if !faces.is_empty() {
+            build_node(&mut nodes, &mut order, 0, &bounds, &centroids);
+        }
+
+        Self {
+            nodes,
+            triangles: order.iter().map(|i| faces[*i]).collect(),
+            faces: order,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the number of triangles in this hierarchy.
+    pub const fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the axis aligned bounding box around all triangles, [`Aabb::EMPTY`] if there are none.
+    pub fn aabb(&self) -> Aabb {
+        self.nodes.first().map_or(Aabb::EMPTY, |root| *root.aabb())
+    }
+
+    /// Casts a ray against all triangles, returning the nearest hit.
+    ///
+    /// Nodes are visited nearest first, skipping every node further away than the nearest hit so far.
+    /// See [`ray_triangle_intersect`] for which triangles count as hit.
+    #[must_use]
+    pub fn ray_cast(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<BvhHit> {
+        let inv_dir = dir.map(f32::recip);
+
+        let mut nearest: Option<BvhHit> = None;
+        let mut stack = Vec::with_capacity(64);
+
+        if let Some(root) = self.nodes.first()
+            && ray_aabb_intersect(origin, inv_dir, root.aabb()).is_some()
+        {
+            stack.push((0, 0.0));
+        }
+
+        while let Some((index, entry)) = stack.pop() {
+            if nearest.is_some_and(|hit| hit.distance < entry) {
+                continue;
+            }
+
+            match &self.nodes[index] {
+                BvhNode::Leaf { triangles, .. } => {
+                    for i in triangles.clone() {
+                        let [v0, v1, v2] = self.triangles[i];
+
+                        let Some((distance, u, v)) =
+                            ray_triangle_intersect(origin, dir, v0, v1, v2)
+                        else {
+                            continue;
+                        };
+
+                        if nearest.is_none_or(|hit| distance < hit.distance) {
+                            nearest = Some(BvhHit {
+                                face: self.faces[i],
+                                distance,
+                                u,
+                                v,
+                            });
+                        }
+                    }
+                }
+                BvhNode::Internal { children, .. } => {
+                    let [near, far] = children.map(|child| {
+                        let entry = ray_aabb_intersect(origin, inv_dir, self.nodes[child].aabb());
+                        (child, entry)
+                    });
+
+                    // the nearer child gets pushed last, so it is visited first
+                    let (near, far) = match (near.1, far.1) {
+                        (Some(a), Some(b)) if b < a => (far, near),
+                        _ => (near, far),
+                    };
+
+                    for (child, entry) in [far, near] {
+                        if let Some(entry) = entry {
+                            stack.push((child, entry));
+                        }
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+}
+
+impl ObjObject {
+    /// Builds a [`Bvh`] over all faces, to speed up repeated ray casts.
+    ///
+    /// Face indices of the hits count the faces of all objects and groups in order, the same as [`ObjObject::ray_cast`].
+    #[must_use]
+    pub fn build_bvh(&self) -> Bvh {
+        let faces = self
+            .faces
+            .iter()
+            .map(|face| <[u32; 3]>::from(face.indicies).map(|i| self.vertices[i as usize - 1]))
+            .collect::<Vec<_>>();
+
+        Bvh::build(&faces)
+    }
+}
+
+/// Adds a node for the triangles in `order` (starting at `offset` in the final order), splitting it recursively.
+///
+/// Returns the index of the added node.
+fn build_node(
+    nodes: &mut Vec<BvhNode>,
+    order: &mut [usize],
+    offset: usize,
+    bounds: &[Aabb],
+    centroids: &[[f32; 3]],
+) -> usize {
+    let aabb = order
+        .iter()
+        .fold(Aabb::EMPTY, |aabb, i| aabb.union(&bounds[*i]));
+
+    let index = nodes.len();
+    nodes.push(BvhNode::Leaf {
+        aabb,
+        triangles: offset..offset + order.len(),
+    });
+
+    if order.len() <= 1 {
+        return index;
+    }
+
+    let Some(split) = split_triangles(order, &aabb, bounds, centroids) else {
+        return index;
+    };
+
+    let (left, right) = order.split_at_mut(split);
+    let left = build_node(nodes, left, offset, bounds, centroids);
+    let right = build_node(nodes, right, offset + split, bounds, centroids);
+
+    nodes[index] = BvhNode::Internal {
+        aabb,
+        children: [left, right],
+    };
+
+    index
+}
+
+/// Partitions the triangles at the split with the lowest surface area heuristic cost.
+///
+/// Returns the number of triangles in the first half, `None` if keeping a leaf is cheaper or the triangles can not be separated.
+#[allow(clippy::cast_precision_loss)]
+fn split_triangles(
+    order: &mut [usize],
+    aabb: &Aabb,
+    bounds: &[Aabb],
+    centroids: &[[f32; 3]],
+) -> Option<usize> {
+    let extent = build_aabb(order.iter().map(|i| centroids[*i]));
+
+    // (cost, axis, first bin of the second half)
+    let mut best: Option<(f32, usize, usize)> = None;
+
+    for axis in 0..3 {
+        let (min, max) = (extent.min[axis], extent.max[axis]);
+        if max <= min {
+            continue;
+        }
+
+        let mut bins = [(0_usize, Aabb::EMPTY); BIN_COUNT];
+        for i in order.iter() {
+            let bin = &mut bins[bin_index(centroids[*i][axis], min, max)];
+            bin.0 += 1;
+            bin.1 = bin.1.union(&bounds[*i]);
+        }
+
+        // cost of every split after the first i + 1 bins, from the right
+        let mut right_costs = [0.0; BIN_COUNT];
+        let (mut count, mut area) = (0, Aabb::EMPTY);
+        for i in (1..BIN_COUNT).rev() {
+            count += bins[i].0;
+            area = area.union(&bins[i].1);
+            right_costs[i - 1] = count as f32 * area.surface_area();
+        }
+
+        let (mut count, mut area) = (0, Aabb::EMPTY);
+        for i in 0..BIN_COUNT - 1 {
+            count += bins[i].0;
+            area = area.union(&bins[i].1);
+
+            let cost = (count as f32).mul_add(area.surface_area(), right_costs[i]);
+            if best.is_none_or(|(best, ..)| cost < best) {
+                best = Some((cost, axis, i + 1));
+            }
+        }
+    }
+
+    let (cost, axis, split_bin) = best?;
+
+    let area = aabb.surface_area();
+    let leaf_cost = order.len() as f32;
+    let split_cost = if area > 0.0 {
+        TRAVERSAL_COST + cost / area
+    } else {
+        TRAVERSAL_COST
+    };
+    if order.len() <= MAX_LEAF_SIZE && split_cost >= leaf_cost {
+        return None;
+    }
+
+    let (min, max) = (extent.min[axis], extent.max[axis]);
+
+    // move every triangle before the split bin to the front
+    let mut split = 0;
+    for i in 0..order.len() {
+        if bin_index(centroids[order[i]][axis], min, max) < split_bin {
+            order.swap(i, split);
+            split += 1;
+        }
+    }
+
+    (split != 0 && split != order.len()).then_some(split)
+}
+
+#[inline]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn bin_index(value: f32, min: f32, max: f32) -> usize {
+    let bin = ((value - min) / (max - min) * BIN_COUNT as f32) as usize;
+    bin.min(BIN_COUNT - 1)
+}
+
+#[inline]
+fn center(aabb: &Aabb) -> [f32; 3] {
+    [0, 1, 2].map(|i| f32::midpoint(aabb.min[i], aabb.max[i]))
+}
+
+/// Intersects a ray with a box, using the slab method.
+///
+/// Returns the distance along the ray where it enters the box (0 if the origin is inside), `None` if the box is missed.
+#[inline]
+fn ray_aabb_intersect(origin: [f32; 3], inv_dir: [f32; 3], aabb: &Aabb) -> Option<f32> {
+    let mut near = 0.0_f32;
+    let mut far = f32::INFINITY;
+
+    for i in 0..3 {
+        let t0 = (aabb.min[i] - origin[i]) * inv_dir[i];
+        let t1 = (aabb.max[i] - origin[i]) * inv_dir[i];
+
+        // NaN (a ray inside a slab plane) is ignored by min and max
+        near = near.max(t0.min(t1));
+        far = far.min(t0.max(t1));
+    }
+
+    (near <= far).then_some(near)
+}
@@ -0,0 +1,689 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, hash_map::Entry},
+    fs::{self, File},
+    hash::BuildHasher,
+    io::{BufRead, BufReader, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use rustc_hash::FxBuildHasher;
+
+use crate::{
+    Error, LineParser, ParseLimits, ParseOptions, QuadSplit,
+    parse::{FaceData, Line},
+};
+
+/// Configures the out-of-core pipeline of [`index_out_of_core`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalConfig {
+    /// The (approximate) maximum number of bytes any pass holds in memory, at least 4 KiB.
+    ///
+    /// Lines of the .obj file may be at most half of the budget long (see [`ParseLimits::max_line_len`]).
+    /// The passes split their data into at most 1024 files, so inputs larger than about 1024 times the budget
+    /// are rejected with [`Error::MemoryBudgetTooSmall`].
+    pub memory_budget: usize,
+    /// The directory in which temporary spill files are created.
+    pub temp_dir: PathBuf,
+}
+
+impl Default for ExternalConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            memory_budget: 256 * 1024 * 1024,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+/// Describes the output written by [`index_out_of_core`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExternalStats {
+    /// The number of unique vertices written.
+    pub vertices: usize,
+    /// The number of (triangulated) faces written.
+    pub faces: usize,
+}
+
+/// Reads a .obj file and writes it back as an indexed .obj file, with every unique vertex written exactly once.
+///
+/// Unlike parsing into an [`ObjObject`] and calling [`ObjObject::vertices_indexed`], this never holds the whole mesh in memory.
+/// All vertex attributes and faces are spilled into temporary files in [`ExternalConfig::temp_dir`],
+/// deduplication runs on hash-partitions of these files, that each fit into [`ExternalConfig::memory_budget`],
+/// and the faces are restored to their original order by merging the sorted partitions.
+///
+/// Only vertex positions, texture coords and normals are kept. Objects, groups, materials and vertex colors are dropped.
+/// Every unique vertex is written with the same index for its position (v), texture coord (vt) and normal (vn).
+///
+/// # Errors
+/// - Returns an `Error` if the .obj file is not as structured as expected.
+/// - Returns [`Error::MemoryBudgetTooSmall`] if the input can not be processed within [`ExternalConfig::memory_budget`].
+/// - Returns [`Error::IndexOverflow`] if there are more unique vertices than a `u32` index can address.
+/// - Returns an [Error][std::io::Error] if reading, writing or any of the temporary files fails.
+pub fn index_out_of_core(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    config: &ExternalConfig,
+) -> Result<ExternalStats, Error> {
+    let budget = config.memory_budget.max(4096);
+    let dir = SpillDir::create(&config.temp_dir)?;
+
+    let spilled = spill(reader, &dir, budget)?;
+    let corners = resolve(&dir, &spilled, budget)?;
+    let partitions = partition(&dir, corners, budget)?;
+
+    let vertices = deduplicate(&dir, partitions, &spilled, budget, &mut writer)?;
+    let faces = write_faces(&dir, partitions, corners, budget, &mut writer)?;
+
+    writer.flush()?;
+
+    Ok(ExternalStats { vertices, faces })
+}
+
+const POSITION_SIZE: usize = 12;
+const TEXTURE_SIZE: usize = 8;
+const NORMAL_SIZE: usize = 12;
+const CORNER_SIZE: usize = 12;
+
+/// position + texture coord + normal + flags
+const RECORD_SIZE: usize = POSITION_SIZE + TEXTURE_SIZE + NORMAL_SIZE + 1;
+/// corner index + record
+const PARTITION_SIZE: usize = 8 + RECORD_SIZE;
+/// corner index + vertex index + flags
+const MAPPING_SIZE: usize = 8 + 4 + 1;
+
+const HAS_TEXTURE: u8 = 1;
+const HAS_NORMAL: u8 = 2;
+
+type Record = [u8; RECORD_SIZE];
+
+/// A temporary directory, that gets removed again on drop.
+struct SpillDir {
+    path: PathBuf,
+}
+
+impl SpillDir {
+    fn create(base: &Path) -> Result<Self, Error> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = base.join(format!("polypath-external-{}-{id}", std::process::id()));
+        fs::create_dir_all(&path)?;
+
+        Ok(Self { path })
+    }
+
+    fn file(&self, name: &str) -> PathBuf {
+        self.path.join(name)
+    }
+
+    fn writer(&self, name: &str, capacity: usize) -> Result<BufWriter<File>, Error> {
+        let file = File::create(self.file(name))?;
+        Ok(BufWriter::with_capacity(capacity, file))
+    }
+
+    fn reader(&self, name: &str, capacity: usize) -> Result<BufReader<File>, Error> {
+        let file = File::open(self.file(name))?;
+        Ok(BufReader::with_capacity(capacity, file))
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Counts of the spilled data.
+struct Spilled {
+    corners: u64,
+    positions: usize,
+    texture_coords: usize,
+    normals: usize,
+    any_texture: bool,
+    any_normal: bool,
+}
+
+/// Parses the .obj file, writing positions, texture coords, normals and triangle corners into separate spill files.
+fn spill(reader: impl BufRead, dir: &SpillDir, budget: usize) -> Result<Spilled, Error> {
+    let capacity = (budget / 8).clamp(512, 64 * 1024);
+
+    let mut positions = dir.writer("positions", capacity)?;
+    let mut texture_coords = dir.writer("texture_coords", capacity)?;
+    let mut normals = dir.writer("normals", capacity)?;
+    let mut corners = dir.writer("corners", capacity)?;
+
+    // positions are not kept in memory, so quads always get split along the fixed diagonal,
+    // and a line has to fit into the budget next to the buffers of the spill files
+    let options = ParseOptions {
+        limits: ParseLimits {
+            max_line_len: budget / 2,
+            ..Default::default()
+        },
+        quad_split: QuadSplit::Fixed02,
+        keep_colors: false,
+        progress_interval: 0,
+        ..ParseOptions::default()
+    };
+    let mut parser = LineParser::new(reader, &options);
+
+    let mut spilled = Spilled {
+        corners: 0,
+        positions: 0,
+        texture_coords: 0,
+        normals: 0,
+        any_texture: false,
+        any_normal: false,
+    };
+
+    let mut write_face = |face: FaceData, spilled: &mut Spilled| -> Result<(), Error> {
+        let (i1, i2, i3) = face.indicies;
+        let (t1, t2, t3) = face.texture_indcicies.unwrap_or((0, 0, 0));
+        let (n1, n2, n3) = face.normal_indicies.unwrap_or((0, 0, 0));
+
+        spilled.any_texture |= face.texture_indcicies.is_some();
+        spilled.any_normal |= face.normal_indicies.is_some();
+
+        for corner in [[i1, t1, n1], [i2, t2, n2], [i3, t3, n3]] {
+            write_u32s(&mut corners, &corner)?;
+        }
+        spilled.corners += 3;
+
+        Ok(())
+    };
+
+    while let Some(line) = parser.next_line()? {
+        match line {
+            Line::Vertex(vertex) => {
+                write_f32s(&mut positions, &vertex.position)?;
+                spilled.positions += 1;
+            }
+            Line::TextureCoord(texture) => {
+                write_f32s(&mut texture_coords, &texture[..2])?;
+                spilled.texture_coords += 1;
+            }
+            Line::Normal(normal) => {
+                write_f32s(&mut normals, &normal)?;
+                spilled.normals += 1;
+            }
+            Line::Faces(faces, _) => {
+                for face in faces {
+                    write_face(face, &mut spilled)?;
+                }
+            }
+            // no grouping fidelity
+            Line::PolyLine(..)
+            | Line::PointElement(_)
+            | Line::SmoothGroup(_)
+            | Line::MaterialLib(_)
+            | Line::MaterialUse(_)
+            | Line::TextureMapLib(_)
+            | Line::TextureMapUse(_)
+            | Line::DisplayAttribute(_)
+            | Line::Group(_)
+            | Line::Object(_)
+            | Line::FreeForm(_) => {}
+        }
+    }
+
+    positions.flush()?;
+    texture_coords.flush()?;
+    normals.flush()?;
+    corners.flush()?;
+
+    Ok(spilled)
+}
+
+/// A spilled vertex attribute, and where it is stored in a corner and a [`Record`].
+struct Attribute {
+    /// The name of the spill file.
+    name: &'static str,
+    /// The size of a single value in bytes.
+    size: usize,
+    /// The number of values in the spill file.
+    count: usize,
+    /// The index of the attribute in a corner.
+    slot: usize,
+    /// The offset of the value in a record.
+    offset: usize,
+    flag: u8,
+}
+
+/// The maximum number of buckets or partitions any pass splits its data into, bounding the number of open files.
+const MAX_BUCKETS: usize = 1024;
+
+/// Resolves every corner into a full vertex record.
+///
+/// Each attribute is resolved on its own, see [`resolve_attribute`], so every attribute spill file is only read once.
+/// The resolved attributes are then combined into records, in the original corner order.
+///
+/// Returns the number of corners.
+fn resolve(dir: &SpillDir, spilled: &Spilled, budget: usize) -> Result<u64, Error> {
+    let capacity = (budget / 8).clamp(512, 64 * 1024);
+
+    let attributes = [
+        Attribute {
+            name: "positions",
+            size: POSITION_SIZE,
+            count: spilled.positions,
+            slot: 0,
+            offset: 0,
+            flag: 0,
+        },
+        Attribute {
+            name: "texture_coords",
+            size: TEXTURE_SIZE,
+            count: spilled.texture_coords,
+            slot: 1,
+            offset: POSITION_SIZE,
+            flag: HAS_TEXTURE,
+        },
+        Attribute {
+            name: "normals",
+            size: NORMAL_SIZE,
+            count: spilled.normals,
+            slot: 2,
+            offset: POSITION_SIZE + TEXTURE_SIZE,
+            flag: HAS_NORMAL,
+        },
+    ];
+
+    for attribute in &attributes {
+        resolve_attribute(dir, attribute, spilled.corners, budget)?;
+    }
+
+    let mut corners = dir.reader("corners", capacity)?;
+    let mut values = attributes
+        .iter()
+        .map(|attribute| dir.reader(&format!("{}-resolved", attribute.name), capacity))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut resolved = dir.writer("resolved", capacity)?;
+
+    let mut corner = [0u8; CORNER_SIZE];
+    while read_record(&mut corners, &mut corner)? {
+        let mut record = [0u8; RECORD_SIZE];
+
+        for (attribute, values) in attributes.iter().zip(&mut values) {
+            values.read_exact(&mut record[attribute.offset..][..attribute.size])?;
+
+            if u32_at(&corner, attribute.slot * 4) != 0 {
+                record[RECORD_SIZE - 1] |= attribute.flag;
+            }
+        }
+
+        resolved.write_all(&record)?;
+    }
+
+    resolved.flush()?;
+
+    for attribute in &attributes {
+        _ = fs::remove_file(dir.file(&format!("{}-resolved", attribute.name)));
+    }
+
+    Ok(spilled.corners)
+}
+
+/// Writes the value of an attribute for every corner into a spill file, zeroed for corners without the attribute.
+///
+/// The attribute spill file is split into windows that fit into the memory budget.
+/// First the corners referencing each window are written into a bucket, then every window is read once (in order)
+/// and joined with its bucket, and finally the joined buckets are merged back into the original corner order.
+fn resolve_attribute(
+    dir: &SpillDir,
+    attribute: &Attribute,
+    corners: u64,
+    budget: usize,
+) -> Result<(), Error> {
+    let capacity = (budget / 8).clamp(512, 64 * 1024);
+
+    // half of the budget for the window, the other half for the buffers of the buckets
+    let window_len = (budget / 2 / attribute.size).max(1);
+    let buckets = attribute.count.div_ceil(window_len);
+    if buckets > MAX_BUCKETS {
+        let window = attribute.count.div_ceil(MAX_BUCKETS) * attribute.size;
+        return Err(Error::MemoryBudgetTooSmall {
+            budget,
+            required: window as u64 * 2,
+        });
+    }
+    let bucket_capacity = (budget / 2 / buckets.max(1)).clamp(64, 64 * 1024);
+
+    let bucket = |b: usize| format!("{}-bucket-{b}", attribute.name);
+    let joined = |b: usize| format!("{}-joined-{b}", attribute.name);
+
+    // (corner, index) for every corner referencing the attribute, into the bucket of the window containing the index
+    let mut writers = (0..buckets)
+        .map(|b| dir.writer(&bucket(b), bucket_capacity))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut reader = dir.reader("corners", capacity)?;
+    let mut corner = [0u8; CORNER_SIZE];
+    let mut id = 0u64;
+    while read_record(&mut reader, &mut corner)? {
+        let index = u32_at(&corner, attribute.slot * 4);
+
+        // every corner has a position
+        if index as usize > attribute.count || (index == 0 && attribute.flag == 0) {
+            return Err(Error::Io(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "face references a vertex attribute that does not exist",
+            )));
+        }
+
+        if index != 0 {
+            let writer = &mut writers[(index as usize - 1) / window_len];
+            writer.write_all(&id.to_le_bytes())?;
+            writer.write_all(&index.to_le_bytes())?;
+        }
+
+        id += 1;
+    }
+
+    for mut writer in writers {
+        writer.flush()?;
+    }
+
+    // (corner, value), still sorted by corner within each bucket
+    let mut values = dir.reader(attribute.name, capacity)?;
+    let mut window = vec![0u8; window_len * attribute.size];
+
+    for b in 0..buckets {
+        let len = window_len.min(attribute.count - b * window_len);
+        values.read_exact(&mut window[..len * attribute.size])?;
+
+        let mut reader = dir.reader(&bucket(b), bucket_capacity)?;
+        let mut writer = dir.writer(&joined(b), bucket_capacity)?;
+
+        let mut entry = [0u8; 12];
+        while read_record(&mut reader, &mut entry)? {
+            let index = u32_at(&entry, 8) as usize - 1 - b * window_len;
+
+            writer.write_all(&entry[..8])?;
+            writer.write_all(&window[index * attribute.size..][..attribute.size])?;
+        }
+
+        writer.flush()?;
+        drop(reader);
+        _ = fs::remove_file(dir.file(&bucket(b)));
+    }
+
+    drop(window);
+
+    // merges the buckets, like the partitions in `write_faces`
+    let mut readers = (0..buckets)
+        .map(|b| dir.reader(&joined(b), bucket_capacity))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut heap = BinaryHeap::with_capacity(buckets);
+    for (b, reader) in readers.iter_mut().enumerate() {
+        if let Some(entry) = read_joined(reader, attribute.size)? {
+            heap.push(Reverse((entry, b)));
+        }
+    }
+
+    let mut resolved = dir.writer(&format!("{}-resolved", attribute.name), capacity)?;
+    for corner in 0..corners {
+        match heap.peek() {
+            Some(Reverse(((next, _), _))) if *next == corner => {
+                let Reverse(((_, value), b)) = heap.pop().expect("peeked");
+                resolved.write_all(&value[..attribute.size])?;
+
+                if let Some(entry) = read_joined(&mut readers[b], attribute.size)? {
+                    heap.push(Reverse((entry, b)));
+                }
+            }
+            _ => resolved.write_all(&[0; 12][..attribute.size])?,
+        }
+    }
+    resolved.flush()?;
+
+    drop(readers);
+    for b in 0..buckets {
+        _ = fs::remove_file(dir.file(&joined(b)));
+    }
+
+    Ok(())
+}
+
+/// Reads a (corner, value) entry of a joined bucket, see [`resolve_attribute`].
+fn read_joined(reader: &mut impl Read, size: usize) -> Result<Option<(u64, [u8; 12])>, Error> {
+    let mut corner = [0u8; 8];
+    if !read_record(reader, &mut corner)? {
+        return Ok(None);
+    }
+
+    let mut value = [0u8; 12];
+    reader.read_exact(&mut value[..size])?;
+
+    Ok(Some((u64::from_le_bytes(corner), value)))
+}
+
+/// Distributes the resolved corners into hash-partitions, where each partition fits into the memory budget.
+///
+/// Returns the number of partitions.
+fn partition(dir: &SpillDir, corners: u64, budget: usize) -> Result<usize, Error> {
+    // entries + hash map (key, value & some overhead) for deduplication
+    let per_corner = (PARTITION_SIZE + RECORD_SIZE + 16) as u64;
+    let partitions = (corners * per_corner).div_ceil(budget as u64).max(1);
+    if partitions > MAX_BUCKETS as u64 {
+        return Err(Error::MemoryBudgetTooSmall {
+            budget,
+            required: (corners * per_corner).div_ceil(MAX_BUCKETS as u64),
+        });
+    }
+    let partitions = usize::try_from(partitions).unwrap_or(MAX_BUCKETS);
+
+    let capacity = (budget / 4 / partitions).clamp(64, 64 * 1024);
+
+    let mut writers = (0..partitions)
+        .map(|p| dir.writer(&format!("partition-{p}"), capacity))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut resolved = dir.reader("resolved", (budget / 4).clamp(512, 64 * 1024))?;
+
+    let mut record = [0u8; RECORD_SIZE];
+    let mut corner = 0u64;
+    while read_record(&mut resolved, &mut record)? {
+        let p = usize::try_from(FxBuildHasher.hash_one(record) % partitions as u64).unwrap_or(0);
+
+        let writer = &mut writers[p];
+        writer.write_all(&corner.to_le_bytes())?;
+        writer.write_all(&record)?;
+
+        corner += 1;
+    }
+
+    for mut writer in writers {
+        writer.flush()?;
+    }
+
+    Ok(partitions)
+}
+
+/// Deduplicates every partition, writes the unique vertices to the output,
+/// and writes a mapping from corner to vertex index, sorted by corner, for every partition.
+///
+/// Returns the number of unique vertices.
+fn deduplicate(
+    dir: &SpillDir,
+    partitions: usize,
+    spilled: &Spilled,
+    budget: usize,
+    writer: &mut impl Write,
+) -> Result<usize, Error> {
+    let capacity = (budget / 8).clamp(512, 64 * 1024);
+
+    let mut next_index = 0u32;
+
+    for p in 0..partitions {
+        let mut reader = dir.reader(&format!("partition-{p}"), capacity)?;
+
+        let mut unique = HashMap::<Record, u32, _>::with_hasher(FxBuildHasher);
+        let mut mapping = Vec::new();
+
+        let mut entry = [0u8; PARTITION_SIZE];
+        while read_record(&mut reader, &mut entry)? {
+            let corner = u64::from_le_bytes(entry[..8].try_into().expect("8 bytes"));
+            let record: Record = entry[8..].try_into().expect("Record size");
+
+            let index = match unique.entry(record) {
+                Entry::Occupied(occupied) => *occupied.get(),
+                Entry::Vacant(vacant) => {
+                    // .obj indices start at 1, so every u32 but 0 is a valid index
+                    next_index = next_index.checked_add(1).ok_or(Error::IndexOverflow {
+                        count: u32::MAX as usize + 1,
+                        limit: u32::MAX as usize,
+                    })?;
+                    write_vertex(writer, &record, spilled)?;
+                    *vacant.insert(next_index)
+                }
+            };
+
+            mapping.push((corner, index, record[RECORD_SIZE - 1]));
+        }
+
+        drop(unique);
+        mapping.sort_unstable_by_key(|(corner, _, _)| *corner);
+
+        let mut out = dir.writer(&format!("mapping-{p}"), capacity)?;
+        for (corner, index, flags) in mapping {
+            out.write_all(&corner.to_le_bytes())?;
+            out.write_all(&index.to_le_bytes())?;
+            out.write_all(&[flags])?;
+        }
+        out.flush()?;
+
+        _ = fs::remove_file(dir.file(&format!("partition-{p}")));
+    }
+
+    Ok(next_index as usize)
+}
+
+/// Merges the sorted mappings of all partitions, restoring the original corner order, and writes the faces.
+///
+/// Returns the number of faces.
+fn write_faces(
+    dir: &SpillDir,
+    partitions: usize,
+    corners: u64,
+    budget: usize,
+    writer: &mut impl Write,
+) -> Result<usize, Error> {
+    let capacity = (budget / 2 / partitions).clamp(64, 64 * 1024);
+
+    let mut readers = (0..partitions)
+        .map(|p| dir.reader(&format!("mapping-{p}"), capacity))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut heap = BinaryHeap::with_capacity(partitions);
+    for (p, reader) in readers.iter_mut().enumerate() {
+        if let Some(entry) = read_mapping(reader)? {
+            heap.push(Reverse((entry, p)));
+        }
+    }
+
+    let mut face = [(0u32, 0u8); 3];
+    let mut expected = 0u64;
+
+    while let Some(Reverse(((corner, index, flags), p))) = heap.pop() {
+        debug_assert_eq!(corner, expected);
+
+        face[usize::try_from(expected % 3).unwrap_or(0)] = (index, flags);
+        expected += 1;
+
+        if expected.is_multiple_of(3) {
+            write_face(writer, face)?;
+        }
+
+        if let Some(entry) = read_mapping(&mut readers[p])? {
+            heap.push(Reverse((entry, p)));
+        }
+    }
+
+    debug_assert_eq!(expected, corners);
+
+    Ok(usize::try_from(corners / 3).unwrap_or(usize::MAX))
+}
+
+fn write_vertex(writer: &mut impl Write, record: &Record, spilled: &Spilled) -> Result<(), Error> {
+    let position = [f32_at(record, 0), f32_at(record, 4), f32_at(record, 8)];
+    writeln!(writer, "v {} {} {}", position[0], position[1], position[2])?;
+
+    // keep the texture coord and normal indices in sync with the vertex index
+    if spilled.any_texture {
+        let texture = [f32_at(record, 12), f32_at(record, 16)];
+        writeln!(writer, "vt {} {}", texture[0], texture[1])?;
+    }
+
+    if spilled.any_normal {
+        let normal = [f32_at(record, 20), f32_at(record, 24), f32_at(record, 28)];
+        writeln!(writer, "vn {} {} {}", normal[0], normal[1], normal[2])?;
+    }
+
+    Ok(())
+}
+
+fn write_face(writer: &mut impl Write, face: [(u32, u8); 3]) -> Result<(), Error> {
+    // every corner of a face has the same attributes
+    let flags = face[0].1;
+
+    write!(writer, "f")?;
+    for (i, _) in face {
+        match (flags & HAS_TEXTURE != 0, flags & HAS_NORMAL != 0) {
+            (true, true) => write!(writer, " {i}/{i}/{i}")?,
+            (true, false) => write!(writer, " {i}/{i}")?,
+            (false, true) => write!(writer, " {i}//{i}")?,
+            (false, false) => write!(writer, " {i}")?,
+        }
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn read_mapping(reader: &mut impl Read) -> Result<Option<(u64, u32, u8)>, Error> {
+    let mut entry = [0u8; MAPPING_SIZE];
+    if !read_record(reader, &mut entry)? {
+        return Ok(None);
+    }
+
+    let corner = u64::from_le_bytes(entry[..8].try_into().expect("8 bytes"));
+    let index = u32_at(&entry, 8);
+
+    Ok(Some((corner, index, entry[12])))
+}
+
+/// Reads a fixed size record. Returns `false` if the end of the file is reached.
+fn read_record(reader: &mut impl Read, buffer: &mut [u8]) -> Result<bool, Error> {
+    match reader.read_exact(buffer) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(Error::Io(err)),
+    }
+}
+
+fn write_f32s(writer: &mut impl Write, values: &[f32]) -> Result<(), Error> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_u32s(writer: &mut impl Write, values: &[u32]) -> Result<(), Error> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4 bytes"))
+}
+
+#[inline]
+fn f32_at(bytes: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4 bytes"))
+}
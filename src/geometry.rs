@@ -0,0 +1,545 @@
+use rustc_hash::FxHashMap;
+
+use crate::{
+    Error, FaceData, ObjObject,
+    bounding::build_aabb,
+    opt::{grid_cell, neighbour_cells, within},
+    parse::index_count,
+    vec3::Vec3,
+};
+
+/// Generates smooth per-vertex normals, if the .obj file did not contain any normals (vn).
+///
+/// The normal of every face is accumulated at each of its vertices, weighted by the interior angle at that vertex.
+/// Every vertex position gets exactly one normal, at the same index. Vertices not used by any face get a zero normal.
+///
+/// Does nothing if there already are normals.
+pub fn generate_normals(obj: &mut ObjObject) {
+    if !obj.vertex_normals.is_empty() {
+        return;
+    }
+
+    let mut normals = vec![Vec3::zero(); obj.vertices.len()];
+
+    for face in &mut obj.faces {
+        let indices = <[u32; 3]>::from(face.indicies);
+        let [p0, p1, p2] = indices.map(|i| Vec3::from(obj.vertices[i as usize - 1]));
+
+        let normal = face_normal(p0, p1, p2);
+        let angles = [
+            interior_angle(p0, p1, p2),
+            interior_angle(p1, p2, p0),
+            interior_angle(p2, p0, p1),
+        ];
+
+        for (i, angle) in indices.into_iter().zip(angles) {
+            normals[i as usize - 1] += normal * angle;
+        }
+
+        face.normal_indicies = Some(face.indicies);
+    }
+
+    obj.vertex_normals = normals
+        .into_iter()
+        .map(|normal| normalized_or_zero(normal).to_array())
+        .collect();
+
+    for corner in &mut obj.polygon_corners {
+        corner.normal = Some(corner.position);
+    }
+}
+
+/// Generates flat normals, if the .obj file did not contain any normals (vn).
+///
+/// Every face gets three new normals, all being the normal of the face.
+/// Kept polygons (see [`ParseOptions::keep_polygons`][crate::ParseOptions::keep_polygons])
+/// use the normal of their first triangle.
+///
+/// Does nothing if there already are normals.
+pub fn generate_flat_normals(obj: &mut ObjObject) {
+    if !obj.vertex_normals.is_empty() {
+        return;
+    }
+
+    obj.vertex_normals.reserve(obj.faces.len() * 3);
+
+    for face in &mut obj.faces {
+        let [p0, p1, p2] =
+            <[u32; 3]>::from(face.indicies).map(|i| Vec3::from(obj.vertices[i as usize - 1]));
+
+        let normal = face_normal(p0, p1, p2).to_array();
+        obj.vertex_normals.extend([normal; 3]);
+
+        let last = index_count(obj.vertex_normals.len());
+        face.normal_indicies = Some((last - 2, last - 1, last));
+    }
+
+    for polygon in &obj.polygons {
+        // the normals of face `i` start at (1-based) index `3 * i + 1`
+        let first = index_count(polygon.face * 3 + 1);
+
+        for corner in &mut obj.polygon_corners[polygon.start..polygon.finish] {
+            corner.normal = Some(first);
+        }
+    }
+}
+
+/// Generates a tangent for every corner of every face, used for normal mapping.
+///
+/// The tangents of all faces sharing the same position, texture coord and normal are accumulated,
+/// and orthogonalized against the normal of that corner (or the face normal, if there are no normals).
+/// The w component holds the handedness of the tangent space (`1.0` or `-1.0`),
+/// the bitangent is `cross(normal, tangent) * w`.
+///
+/// Tangents are stored per face corner and exposed via [`Face::vert_tangents`][crate::Face::vert_tangents]
+/// and [`VertexData::tangent`][crate::VertexData::tangent].
+/// They are not updated by other modifications (e.g. [`ObjObject::apply_object_transforms`]),
+/// so they should be generated last.
+///
+/// # Errors
+/// Returns [`Error::MissingTextureCoords`], if any face has no texture coords.
+pub fn generate_tangents(obj: &mut ObjObject) -> Result<(), Error> {
+    if obj
+        .faces
+        .iter()
+        .any(|face| face.texture_indcicies.is_none())
+    {
+        return Err(Error::MissingTextureCoords);
+    }
+
+    // (position, texture coord, normal) -> (normal, tangent, bitangent)
+    let mut accumulated: FxHashMap<(u32, u32, u32), (Vec3, Vec3, Vec3)> = FxHashMap::default();
+    let mut corners = Vec::with_capacity(obj.faces.len() * 3);
+
+    for face in &obj.faces {
+        let indices = <[u32; 3]>::from(face.indicies);
+        let texture_indices = face.texture_indcicies.map_or([0; 3], <[u32; 3]>::from);
+        let normal_indices = face.normal_indicies.map_or([0; 3], <[u32; 3]>::from);
+
+        let [p0, p1, p2] = indices.map(|i| Vec3::from(obj.vertices[i as usize - 1]));
+        let [t0, t1, t2] = texture_indices.map(|t| obj.texture_coords[t as usize - 1]);
+
+        let (tangent, bitangent) = face_tangent([p0, p1, p2], [t0, t1, t2]);
+        let face_normal = face_normal(p0, p1, p2);
+
+        for i in 0..3 {
+            let key = (indices[i], texture_indices[i], normal_indices[i]);
+            let normal = match normal_indices[i] {
+                0 => face_normal,
+                n => Vec3::from(obj.vertex_normals[n as usize - 1]),
+            };
+
+            let entry =
+                accumulated
+                    .entry(key)
+                    .or_insert((Vec3::zero(), Vec3::zero(), Vec3::zero()));
+            entry.0 += normal;
+            entry.1 += tangent;
+            entry.2 += bitangent;
+
+            corners.push(key);
+        }
+    }
+
+    let tangents = accumulated
+        .into_iter()
+        .map(|(key, (normal, tangent, bitangent))| {
+            (key, orthogonal_tangent(normal, tangent, bitangent))
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    obj.vertex_tangents = corners.into_iter().map(|key| tangents[&key]).collect();
+
+    Ok(())
+}
+
+/// Returns the (unnormalized) tangent and bitangent of a triangle, derived from its texture coords.
+/// Triangles with degenerate texture coords have no tangent.
+fn face_tangent([p0, p1, p2]: [Vec3; 3], [t0, t1, t2]: [[f32; 2]; 3]) -> (Vec3, Vec3) {
+    let e1 = p1 - p0;
+    let e2 = p2 - p0;
+
+    let (du1, dv1) = (t1[0] - t0[0], t1[1] - t0[1]);
+    let (du2, dv2) = (t2[0] - t0[0], t2[1] - t0[1]);
+
+    let det = du1.mul_add(dv2, -(du2 * dv1));
+    if det == 0.0 {
+        return (Vec3::zero(), Vec3::zero());
+    }
+
+    let r = det.recip();
+    ((e1 * dv2 - e2 * dv1) * r, (e2 * du1 - e1 * du2) * r)
+}
+
+/// Orthogonalizes the tangent against the normal (Gram-Schmidt) and computes its handedness.
+fn orthogonal_tangent(normal: Vec3, tangent: Vec3, bitangent: Vec3) -> [f32; 4] {
+    let normal = normalized_or_zero(normal);
+    let mut t = normalized_or_zero(tangent - normal * normal.dot(&tangent));
+
+    if t.length_squared() == 0.0 {
+        // no usable tangent, pick any vector perpendicular to the normal
+        let axis = if normal.x.abs() < 0.9 {
+            Vec3::new(1.0, 0.0, 0.0)
+        } else {
+            Vec3::new(0.0, 1.0, 0.0)
+        };
+        t = normalized_or_zero(axis - normal * normal.dot(&axis));
+    }
+
+    let w = if normal.cross(&t).dot(&bitangent) < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+
+    [t.x, t.y, t.z, w]
+}
+
+/// Maps every edge of a set of faces to the faces using it, see [`compute_edge_map`].
+///
+/// Edges are identified by the (1-based) position indices of their end points, the smaller one first.
+/// Faces sharing a position without sharing its index (e.g. at uv seams of unwelded meshes) do not share edges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EdgeMap {
+    edges: FxHashMap<(u32, u32), Vec<usize>>,
+}
+
+impl EdgeMap {
+    #[inline]
+    #[must_use]
+    /// Returns the number of distinct edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the indices of all faces using an edge, in ascending order. The order of the end points does not matter.
+    pub fn faces(&self, a: u32, b: u32) -> &[usize] {
+        self.edges
+            .get(&(a.min(b), a.max(b)))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns all edges, that are only used by a single face, in no particular order.
+    pub fn boundary_edges(&self) -> impl Iterator<Item = (u32, u32)> {
+        self.edges
+            .iter()
+            .filter(|(_, faces)| faces.len() == 1)
+            .map(|(edge, _)| *edge)
+    }
+
+    /// Returns all edges, that are used by three or more faces, in no particular order.
+    pub fn non_manifold_edges(&self) -> impl Iterator<Item = (u32, u32)> {
+        self.edges
+            .iter()
+            .filter(|(_, faces)| faces.len() > 2)
+            .map(|(edge, _)| *edge)
+    }
+}
+
+/// Builds a map from every edge of the given faces to the (indices of the) faces using it.
+///
+/// Faces are triangles, so the diagonals of triangulated polygons are edges as well.
+/// Edges of degenerate triangles, that start and end at the same position, are skipped.
+#[must_use]
+pub fn compute_edge_map(faces: &[FaceData]) -> EdgeMap {
+    let mut edges: FxHashMap<(u32, u32), Vec<usize>> = FxHashMap::default();
+
+    for (index, face) in faces.iter().enumerate() {
+        let [a, b, c] = face.indices();
+
+        for (start, end) in [(a, b), (b, c), (c, a)] {
+            if start == end {
+                continue;
+            }
+
+            let faces = edges.entry((start.min(end), start.max(end))).or_default();
+            // a degenerate triangle may contain the same edge twice
+            if faces.last() != Some(&index) {
+                faces.push(index);
+            }
+        }
+    }
+
+    EdgeMap { edges }
+}
+
+impl ObjObject {
+    #[must_use]
+    /// Returns `true` if no edge is shared by more than two faces, see [`compute_edge_map`].
+    pub fn is_manifold(&self) -> bool {
+        compute_edge_map(&self.faces)
+            .non_manifold_edges()
+            .next()
+            .is_none()
+    }
+
+    #[must_use]
+    /// Returns `true` if every edge is shared by at least two faces, so the surface has no holes, see [`compute_edge_map`].
+    pub fn is_closed(&self) -> bool {
+        compute_edge_map(&self.faces)
+            .boundary_edges()
+            .next()
+            .is_none()
+    }
+}
+
+/// Returns the (0-based) indices of all vertex positions (v), that are not referenced by any face, in ascending order.
+///
+/// Positions used by line (l) or point (p) elements are not isolated, as removing them would break these elements.
+#[must_use]
+pub fn find_isolated_vertices(obj: &ObjObject) -> Vec<usize> {
+    referenced_vertices(obj)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, referenced)| !referenced)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Removes all vertex positions found by [`find_isolated_vertices`], together with their colors, alphas and w components.
+///
+/// The indices of faces, kept polygons, lines and points are adjusted to the remaining positions.
+/// Texture coords and normals are not touched.
+pub fn remove_isolated_vertices(obj: &mut ObjObject) {
+    let referenced = referenced_vertices(obj);
+    if referenced.iter().all(|r| *r) {
+        return;
+    }
+
+    let target = (0..referenced.len()).collect::<Vec<_>>();
+    compact_positions(obj, &referenced, &target);
+}
+
+/// Merges vertex positions (v), that differ by at most `epsilon` per component, returning the number of removed positions.
+///
+/// Positions are only merged, if their colors, alphas and w components are identical. Merged positions keep the first position.
+/// The indices of faces, kept polygons, lines and points are adjusted to the remaining positions.
+/// This closes seams, that are visually watertight but topologically open, e.g. before generating smooth normals.
+pub fn weld_positions(obj: &mut ObjObject, epsilon: f32) -> usize {
+    let attribute = |attributes: &[f32], i: usize| attributes.get(i).map(|a| a.to_bits());
+
+    // the unique positions inside of every cell
+    let mut grid = FxHashMap::<[i64; 3], Vec<usize>>::default();
+    let mut keep = vec![true; obj.vertices.len()];
+    let mut target = Vec::with_capacity(obj.vertices.len());
+
+    for (i, position) in obj.vertices.iter().enumerate() {
+        let cell = grid_cell(*position, epsilon);
+
+        let existing = neighbour_cells(cell)
+            .filter_map(|cell| grid.get(&cell))
+            .flatten()
+            .copied()
+            .find(|other| {
+                within(obj.vertices[*other], *position, epsilon)
+                    && obj.vertex_colors.get(*other) == obj.vertex_colors.get(i)
+                    && attribute(&obj.vertex_alphas, *other) == attribute(&obj.vertex_alphas, i)
+                    && attribute(&obj.vertices_w, *other) == attribute(&obj.vertices_w, i)
+            });
+
+        if let Some(existing) = existing {
+            keep[i] = false;
+            target.push(existing);
+        } else {
+            grid.entry(cell).or_default().push(i);
+            target.push(i);
+        }
+    }
+
+    let removed = keep.iter().filter(|keep| !**keep).count();
+    if removed != 0 {
+        compact_positions(obj, &keep, &target);
+    }
+
+    removed
+}
+
+/// Removes all vertex positions (and their colors, alphas and w components), that are not kept,
+/// and points every reference to a position at the (kept) position `target` of it.
+fn compact_positions(obj: &mut ObjObject, keep: &[bool], target: &[usize]) {
+    // the new (1-based) index of every remaining position
+    let mut remap = vec![0; keep.len()];
+    let mut count = 0;
+    for (i, keep) in keep.iter().enumerate() {
+        if *keep {
+            count += 1;
+            remap[i] = count;
+        }
+    }
+    let remap = |i: u32| remap[target[i as usize - 1]];
+
+    let mut kept = keep.iter();
+    obj.vertices.retain(|_| *kept.next().unwrap_or(&true));
+    for attributes in [&mut obj.vertex_alphas, &mut obj.vertices_w] {
+        if attributes.len() == keep.len() {
+            let mut kept = keep.iter();
+            attributes.retain(|_| *kept.next().unwrap_or(&true));
+        }
+    }
+    if obj.vertex_colors.len() == keep.len() {
+        let mut kept = keep.iter();
+        obj.vertex_colors.retain(|_| *kept.next().unwrap_or(&true));
+    }
+    if obj.vertices_f64.len() == keep.len() {
+        let mut kept = keep.iter();
+        obj.vertices_f64.retain(|_| *kept.next().unwrap_or(&true));
+    }
+
+    for face in &mut obj.faces {
+        face.indicies = <[u32; 3]>::from(face.indicies).map(remap).into();
+    }
+    for corner in &mut obj.polygon_corners {
+        corner.position = remap(corner.position);
+    }
+    for index in obj
+        .line_elements
+        .iter_mut()
+        .chain(&mut obj.point_elements)
+        .flatten()
+    {
+        *index = remap(*index);
+    }
+}
+
+/// Returns for every vertex position, whether it is referenced by any face, line or point element.
+fn referenced_vertices(obj: &ObjObject) -> Vec<bool> {
+    let mut referenced = vec![false; obj.vertices.len()];
+
+    let faces = obj
+        .faces
+        .iter()
+        .flat_map(|face| <[u32; 3]>::from(face.indicies));
+    let elements = obj
+        .line_elements
+        .iter()
+        .chain(&obj.point_elements)
+        .flatten()
+        .copied();
+
+    for i in faces.chain(elements) {
+        referenced[i as usize - 1] = true;
+    }
+
+    referenced
+}
+
+/// Returns the area-weighted centroid of all faces, treating the mesh as a thin shell.
+///
+/// Returns the origin, if there are no faces or all of them are degenerate.
+#[must_use]
+pub fn center_of_mass(obj: &ObjObject) -> [f32; 3] {
+    let mut weighted = Vec3::zero();
+    let mut total_area = 0.0;
+
+    for face in &obj.faces {
+        let [p0, p1, p2] =
+            <[u32; 3]>::from(face.indicies).map(|i| Vec3::from(obj.vertices[i as usize - 1]));
+
+        let area = (p1 - p0).cross(&(p2 - p0)).length() * 0.5;
+        weighted += (p0 + p1 + p2) * (area / 3.0);
+        total_area += area;
+    }
+
+    if total_area > 0.0 {
+        (weighted / total_area).to_array()
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Translates and scales all vertex positions, so their axis aligned bounding box is centered at the origin,
+/// and its longest side has a length of `1.0`.
+///
+/// Positions not used by any face are included. If all positions are the same, they are only moved to the origin.
+pub fn normalize_to_unit_cube(obj: &mut ObjObject) {
+    let aabb = build_aabb(obj.vertices.iter().copied());
+    if aabb.is_empty() {
+        return;
+    }
+
+    let center = [0, 1, 2].map(|i| -f32::midpoint(aabb.min[i], aabb.max[i]));
+    translate(obj, center);
+
+    let longest = (0..3)
+        .map(|i| aabb.max[i] - aabb.min[i])
+        .fold(0.0, f32::max);
+    if longest > 0.0 {
+        scale_uniform(obj, longest.recip());
+    }
+}
+
+/// Moves all vertex positions by `delta`.
+///
+/// Double precision positions (see [`ObjObject::positions_f64`]) are moved as well.
+pub fn translate(obj: &mut ObjObject, delta: [f32; 3]) {
+    for position in &mut obj.vertices {
+        *position = (Vec3::from(*position) + Vec3::from(delta)).to_array();
+    }
+
+    for position in &mut obj.vertices_f64 {
+        for (value, delta) in position.iter_mut().zip(delta) {
+            *value += f64::from(delta);
+        }
+    }
+}
+
+/// Scales all vertex positions by `s`, relative to the origin.
+///
+/// Double precision positions (see [`ObjObject::positions_f64`]) are scaled as well.
+///
+/// A negative factor mirrors the mesh through the origin, so the normals and tangents
+/// (see [`generate_tangents`]) get flipped as well, including the handedness of the tangents.
+/// The winding of the faces is kept, so they are turned inside out.
+pub fn scale_uniform(obj: &mut ObjObject, s: f32) {
+    for position in &mut obj.vertices {
+        *position = (Vec3::from(*position) * s).to_array();
+    }
+
+    for position in &mut obj.vertices_f64 {
+        for value in position {
+            *value *= f64::from(s);
+        }
+    }
+
+    if s < 0.0 {
+        for normal in &mut obj.vertex_normals {
+            *normal = (-Vec3::from(*normal)).to_array();
+        }
+
+        // the bitangent is `cross(normal, tangent) * w`, so it only flips with the handedness flipped too
+        for tangent in &mut obj.vertex_tangents {
+            *tangent = tangent.map(|value| -value);
+        }
+    }
+}
+
+/// Returns the normalized normal of a triangle, or a zero vector for degenerate triangles.
+#[inline]
+pub(crate) fn face_normal(p0: Vec3, p1: Vec3, p2: Vec3) -> Vec3 {
+    normalized_or_zero((p1 - p0).cross(&(p2 - p0)))
+}
+
+/// Returns the interior angle of a triangle at corner `p0`, or `0.0` for degenerate triangles.
+#[inline]
+fn interior_angle(p0: Vec3, p1: Vec3, p2: Vec3) -> f32 {
+    let a = p1 - p0;
+    let b = p2 - p0;
+
+    let len = a.length() * b.length();
+    if len == 0.0 {
+        return 0.0;
+    }
+
+    (a.dot(&b) / len).clamp(-1.0, 1.0).acos()
+}
+
+#[inline]
+fn normalized_or_zero(v: Vec3) -> Vec3 {
+    if v.length_squared() == 0.0 {
+        v
+    } else {
+        v.normalized()
+    }
+}
@@ -0,0 +1,55 @@
+//! Conversions between the vertex types of this crate and [`glam`].
+
+use crate::{Vertex, VertexData, VertexTextureData, vec3::Vec3};
+
+impl From<Vec3> for glam::Vec3 {
+    #[inline]
+    fn from(value: Vec3) -> Self {
+        Self::new(value.x, value.y, value.z)
+    }
+}
+
+impl From<glam::Vec3> for Vec3 {
+    #[inline]
+    fn from(value: glam::Vec3) -> Self {
+        Self::new(value.x, value.y, value.z)
+    }
+}
+
+impl From<VertexData> for glam::Vec3 {
+    /// Extracts the position of a vertex.
+    #[inline]
+    fn from(value: VertexData) -> Self {
+        Self::from_array(value.position)
+    }
+}
+
+impl From<VertexData> for glam::Vec3A {
+    /// Extracts the position of a vertex.
+    #[inline]
+    fn from(value: VertexData) -> Self {
+        Self::from_array(value.position)
+    }
+}
+
+impl From<VertexTextureData> for glam::Vec3 {
+    /// Extracts the position of a vertex.
+    #[inline]
+    fn from(value: VertexTextureData) -> Self {
+        Self::from_array(value.vertex.position)
+    }
+}
+
+impl Vertex for glam::Vec3 {
+    #[inline]
+    fn position(&self) -> [f32; 3] {
+        self.to_array()
+    }
+}
+
+impl Vertex for glam::Vec3A {
+    #[inline]
+    fn position(&self) -> [f32; 3] {
+        self.to_array()
+    }
+}
@@ -0,0 +1,485 @@
+use std::{
+    collections::hash_map::Entry,
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{Error, Face, ObjObject};
+
+/// `componentType` of an accessor with `f32` components.
+const FLOAT: u32 = 5126;
+/// `componentType` of an accessor with `u32` components.
+const UNSIGNED_INT: u32 = 5125;
+/// `target` of a buffer view containing vertex data.
+const ARRAY_BUFFER: u32 = 34962;
+/// `target` of a buffer view containing indices.
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+impl ObjObject {
+    /// Writes this `ObjObject` as a glTF 2.0 file, with the binary buffer in a `.bin` file next to it.
+    ///
+    /// The buffer is written to `path` with the extension `.bin`, and referenced by its file name.
+    ///
+    /// Every object (o) becomes a mesh with its own node, and one primitive per material use (usemtl).
+    /// Primitives contain indexed positions, and normals and texture coords if all of their faces have them.
+    /// Texture coords are flipped vertically, as glTF starts at the top left of an image.
+    ///
+    /// Every material (combination of mtllib and usemtl) becomes a material, with a `pbrMetallicRoughness` `baseColorFactor`
+    /// of the average vertex color of its faces, if the vertices have colors.
+    /// Object transforms (see [`ObjObject::set_object_transform`]) become the matrix of the node.
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if creating or writing the files fails
+    /// - Returns [`Error::IndexOverflow`] if the buffer exceeds the size a glTF file can address
+    pub fn write_gltf<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let bin_path = path.with_extension("bin");
+        let uri = bin_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let gltf = Gltf::build(self)?;
+        std::fs::write(&bin_path, &gltf.buffer)?;
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(gltf.json(Some(&uri)).as_bytes())?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes this `ObjObject` as a binary glTF 2.0 file (.glb), containing the JSON and the binary buffer,
+    /// see [`ObjObject::write_gltf`].
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if creating or writing the file fails
+    /// - Returns [`Error::IndexOverflow`] if the file exceeds the size a .glb file can address
+    pub fn write_glb<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.write_glb_to_writer(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Same as [`ObjObject::write_glb`], but writing to some sort of writer.
+    ///
+    /// The writer is not buffered, consider wrapping it in a [`BufWriter`].
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if writing fails
+    /// - Returns [`Error::IndexOverflow`] if the file exceeds the size a .glb file can address
+    pub fn write_glb_to_writer(&self, mut writer: impl Write) -> Result<(), Error> {
+        let gltf = Gltf::build(self)?;
+
+        // chunks are padded to 4 bytes, the JSON with spaces and the buffer with zeros
+        let mut json = gltf.json(None).into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut buffer = gltf.buffer;
+        buffer.resize(buffer.len().next_multiple_of(4), 0);
+
+        let bin_chunk = if buffer.is_empty() {
+            0
+        } else {
+            8 + buffer.len()
+        };
+        let len = to_u32(12 + 8 + json.len() + bin_chunk)?;
+
+        writer.write_all(b"glTF")?;
+        writer.write_all(&2_u32.to_le_bytes())?;
+        writer.write_all(&len.to_le_bytes())?;
+
+        writer.write_all(&to_u32(json.len())?.to_le_bytes())?;
+        writer.write_all(b"JSON")?;
+        writer.write_all(&json)?;
+
+        if !buffer.is_empty() {
+            writer.write_all(&to_u32(buffer.len())?.to_le_bytes())?;
+            writer.write_all(b"BIN\0")?;
+            writer.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The parts of a glTF file, before they are written.
+#[derive(Default)]
+struct Gltf {
+    buffer: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    materials: Vec<String>,
+    meshes: Vec<String>,
+    nodes: Vec<String>,
+}
+
+/// A material of a glTF file, identified by its material library (mtllib) and name (usemtl).
+type MaterialKey<'a> = (Option<&'a str>, &'a str);
+
+/// The vertices of a single primitive, deduplicated.
+#[derive(Default)]
+struct Primitive {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    texture_coords: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+    /// The index of every vertex, by the bit patterns of its position, normal and texture coord.
+    lookup: FxHashMap<[u32; 8], u32>,
+    /// The sum of the vertex colors of all corners, and the number of corners with a color.
+    color: ([f32; 3], usize),
+    has_normals: bool,
+    has_texture_coords: bool,
+}
+
+impl Gltf {
+    fn build(obj: &ObjObject) -> Result<Self, Error> {
+        let mut gltf = Self::default();
+        // the index of the material of each material use, by material library and name,
+        // as materials with the same name from different libraries are different materials
+        let mut materials: FxHashMap<MaterialKey<'_>, (usize, [f32; 3], usize)> =
+            FxHashMap::default();
+
+        for object in obj.objects_iter() {
+            // faces using the same material are merged, even across groups
+            let mut primitives: Vec<(Option<&str>, Primitive)> = Vec::new();
+
+            for group in object.group_iter() {
+                let mut faces = group.faces_iter();
+
+                for (range, mtluse) in group.material_ranges() {
+                    let index = primitives
+                        .iter()
+                        .position(|(mtl, _)| *mtl == mtluse)
+                        .unwrap_or_else(|| {
+                            primitives.push((mtluse, Primitive::new()));
+                            primitives.len() - 1
+                        });
+
+                    for face in faces.by_ref().take(range.len()) {
+                        primitives[index].1.push(&face)?;
+                    }
+                }
+            }
+
+            let mut json = Vec::new();
+            for (mtluse, primitive) in primitives {
+                if primitive.indices.is_empty() {
+                    continue;
+                }
+
+                let material = mtluse.map(|name| {
+                    let count = materials.len();
+                    let (index, color, corners) = materials
+                        .entry((object.mtllib(), name))
+                        .or_insert((count, [0.0; 3], 0));
+                    for (sum, value) in color.iter_mut().zip(primitive.color.0) {
+                        *sum += value;
+                    }
+                    *corners += primitive.color.1;
+                    *index
+                });
+
+                json.push(gltf.push_primitive(&primitive, material)?);
+            }
+
+            if json.is_empty() {
+                continue;
+            }
+
+            let mesh = gltf.meshes.len();
+            gltf.meshes.push(format!(
+                r#"{{"name":{},"primitives":[{}]}}"#,
+                string(object.name()),
+                json.join(",")
+            ));
+
+            let mut node = format!(r#"{{"name":{},"mesh":{mesh}"#, string(object.name()));
+            if let Some(transform) = object.object_transform() {
+                let values = transform.as_flattened().iter().map(|v| number(*v));
+                let _ = write!(
+                    node,
+                    r#","matrix":[{}]"#,
+                    values.collect::<Vec<_>>().join(",")
+                );
+            }
+            node.push('}');
+            gltf.nodes.push(node);
+        }
+
+        let mut materials = materials.into_iter().collect::<Vec<_>>();
+        materials.sort_unstable_by_key(|(_, (index, ..))| *index);
+        gltf.materials = materials
+            .into_iter()
+            .map(|((_, name), (_, color, corners))| {
+                let mut material = format!(r#"{{"name":{}"#, string(name));
+                if corners > 0 {
+                    #[allow(clippy::cast_precision_loss)]
+                    let [r, g, b] = color.map(|sum| sum / corners as f32);
+                    let _ = write!(
+                        material,
+                        r#","pbrMetallicRoughness":{{"baseColorFactor":[{},{},{},1]}}"#,
+                        number(r),
+                        number(g),
+                        number(b)
+                    );
+                }
+                material.push('}');
+                material
+            })
+            .collect();
+
+        Ok(gltf)
+    }
+
+    /// Adds the data of a primitive to the buffer, returning the JSON of the primitive.
+    fn push_primitive(
+        &mut self,
+        primitive: &Primitive,
+        material: Option<usize>,
+    ) -> Result<String, Error> {
+        let (min, max) = primitive.positions.iter().fold(
+            ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+            |(mut min, mut max), position| {
+                for axis in 0..3 {
+                    min[axis] = min[axis].min(position[axis]);
+                    max[axis] = max[axis].max(position[axis]);
+                }
+                (min, max)
+            },
+        );
+        let bounds = format!(
+            r#","min":[{}],"max":[{}]"#,
+            min.map(number).join(","),
+            max.map(number).join(",")
+        );
+
+        let position = self.push_accessor(
+            primitive.positions.as_flattened(),
+            "VEC3",
+            ARRAY_BUFFER,
+            &bounds,
+        )?;
+        let mut attributes = format!(r#""POSITION":{position}"#);
+
+        if primitive.has_normals {
+            let normal =
+                self.push_accessor(primitive.normals.as_flattened(), "VEC3", ARRAY_BUFFER, "")?;
+            let _ = write!(attributes, r#","NORMAL":{normal}"#);
+        }
+        if primitive.has_texture_coords {
+            let texture_coord = self.push_accessor(
+                primitive.texture_coords.as_flattened(),
+                "VEC2",
+                ARRAY_BUFFER,
+                "",
+            )?;
+            let _ = write!(attributes, r#","TEXCOORD_0":{texture_coord}"#);
+        }
+
+        let indices = self.push_accessor(&primitive.indices, "SCALAR", ELEMENT_ARRAY_BUFFER, "")?;
+
+        let mut json = format!(r#"{{"attributes":{{{attributes}}},"indices":{indices}"#);
+        if let Some(material) = material {
+            let _ = write!(json, r#","material":{material}"#);
+        }
+        json.push('}');
+
+        Ok(json)
+    }
+
+    /// Adds values to the buffer, with their own buffer view and accessor, returning the index of the accessor.
+    fn push_accessor<T: Component>(
+        &mut self,
+        values: &[T],
+        kind: &str,
+        target: u32,
+        extra: &str,
+    ) -> Result<usize, Error> {
+        let components = match kind {
+            "VEC3" => 3,
+            "VEC2" => 2,
+            _ => 1,
+        };
+
+        let offset = self.buffer.len();
+        for value in values {
+            self.buffer.extend(value.bytes());
+        }
+        let len = self.buffer.len() - offset;
+        to_u32(self.buffer.len())?;
+
+        let view = self.buffer_views.len();
+        self.buffer_views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{len},"target":{target}}}"#
+        ));
+
+        let accessor = self.accessors.len();
+        self.accessors.push(format!(
+            r#"{{"bufferView":{view},"componentType":{},"count":{},"type":"{kind}"{extra}}}"#,
+            T::COMPONENT_TYPE,
+            values.len() / components
+        ));
+
+        Ok(accessor)
+    }
+
+    /// Returns the JSON of the glTF file, referencing the buffer by `uri`, or the binary chunk of a .glb file if `None`.
+    fn json(&self, uri: Option<&str>) -> String {
+        let mut json = String::from(r#"{"asset":{"version":"2.0","generator":"polypath"}"#);
+
+        // every node is a root node of the only scene, arrays are not allowed to be empty
+        let nodes = (0..self.nodes.len())
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            json.push_str(r#","scene":0,"scenes":[{}]"#);
+        } else {
+            let _ = write!(
+                json,
+                r#","scene":0,"scenes":[{{"nodes":[{}]}}]"#,
+                nodes.join(",")
+            );
+        }
+
+        for (name, values) in [
+            ("nodes", &self.nodes),
+            ("meshes", &self.meshes),
+            ("materials", &self.materials),
+            ("accessors", &self.accessors),
+            ("bufferViews", &self.buffer_views),
+        ] {
+            if !values.is_empty() {
+                let _ = write!(json, r#","{name}":[{}]"#, values.join(","));
+            }
+        }
+
+        if !self.buffer.is_empty() {
+            let _ = write!(json, r#","buffers":[{{"byteLength":{}"#, self.buffer.len());
+            if let Some(uri) = uri {
+                let _ = write!(json, r#","uri":{}"#, string(uri));
+            }
+            json.push_str("}]");
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+impl Primitive {
+    fn new() -> Self {
+        Self {
+            has_normals: true,
+            has_texture_coords: true,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the corners of a face, reusing vertices that were already added.
+    fn push(&mut self, face: &Face) -> Result<(), Error> {
+        self.has_normals &= face.vert_normals.is_some();
+        self.has_texture_coords &= face.vert_uv_coords.is_some();
+
+        for corner in 0..3 {
+            let position = face.vert_positions[corner];
+            let normal = face
+                .vert_normals
+                .map_or([0.0; 3], |normals| normals[corner]);
+            // glTF starts at the top left of an image, .obj at the bottom left
+            let [u, v] = face.vert_uv_coords.map_or([0.0; 2], |uvs| uvs[corner]);
+            let texture_coord = [u, 1.0 - v];
+
+            if let Some(colors) = face.vert_colors {
+                for (sum, value) in self.color.0.iter_mut().zip(colors[corner]) {
+                    *sum += value;
+                }
+                self.color.1 += 1;
+            }
+
+            // vertices are only the same, if all of their attributes are
+            let mut key = [0; 8];
+            let values = position.iter().chain(&normal).chain(&texture_coord);
+            for (bits, value) in key.iter_mut().zip(values) {
+                *bits = value.to_bits();
+            }
+
+            let index = match self.lookup.entry(key) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    let index = *entry.insert(to_u32(self.positions.len())?);
+                    self.positions.push(position);
+                    self.normals.push(normal);
+                    self.texture_coords.push(texture_coord);
+                    index
+                }
+            };
+            self.indices.push(index);
+        }
+
+        Ok(())
+    }
+}
+
+/// A component of an accessor.
+trait Component: Copy {
+    const COMPONENT_TYPE: u32;
+
+    fn bytes(self) -> [u8; 4];
+}
+
+impl Component for f32 {
+    const COMPONENT_TYPE: u32 = FLOAT;
+
+    fn bytes(self) -> [u8; 4] {
+        self.to_le_bytes()
+    }
+}
+
+impl Component for u32 {
+    const COMPONENT_TYPE: Self = UNSIGNED_INT;
+
+    fn bytes(self) -> [u8; 4] {
+        self.to_le_bytes()
+    }
+}
+
+fn to_u32(value: usize) -> Result<u32, Error> {
+    u32::try_from(value).map_err(|_| Error::IndexOverflow {
+        count: value,
+        limit: u32::MAX as usize,
+    })
+}
+
+/// Formats a number as JSON, which does not support NaN or infinite values.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("0")
+    }
+}
+
+/// Formats a string as JSON.
+fn string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -0,0 +1,166 @@
+use crate::{VertexData, VertexTextureData, parse::index_count};
+
+/// A [`VertexData`] without optional attributes, so it can be uploaded to the GPU as is.
+///
+/// Missing attributes are replaced by sentinel values:
+/// - a missing color is opaque white (`[1.0, 1.0, 1.0, 1.0]`), a missing alpha is `1.0`
+/// - a missing normal is `[0.0, 0.0, 0.0]`, which is never a valid normal
+/// - a missing texture coord is `[0.0, 0.0]`, which can not be told apart from an actual texture coord at the origin
+///
+/// The layout is `#[repr(C)]` without any padding (48 bytes).
+/// With the `bytemuck` feature, it implements [`bytemuck::Pod`] and [`bytemuck::Zeroable`].
+/// With the `wgpu` feature, [`wgpu_vertex_buffer_layout`] describes this layout.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(C)]
+pub struct GpuVertexData {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+    pub normal: [f32; 3],
+    pub texture_coord: [f32; 2],
+}
+
+impl From<VertexData> for GpuVertexData {
+    #[inline]
+    fn from(value: VertexData) -> Self {
+        let [red, green, blue] = value.color.unwrap_or([1.0; 3]);
+
+        Self {
+            position: value.position,
+            color: [red, green, blue, value.alpha.unwrap_or(1.0)],
+            normal: value.normal.unwrap_or([0.0; 3]),
+            texture_coord: value.texture_coord.unwrap_or([0.0; 2]),
+        }
+    }
+}
+
+/// A [`VertexTextureData`] without optional attributes, so it can be uploaded to the GPU as is.
+///
+/// Uses the same sentinel values as [`GpuVertexData`]. The material index is stored as `u32`,
+/// an index that does not fit is clamped to `u32::MAX`.
+///
+/// The layout is `#[repr(C)]` without any padding (52 bytes).
+/// With the `bytemuck` feature, it implements [`bytemuck::Pod`] and [`bytemuck::Zeroable`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(C)]
+pub struct GpuVertexTextureData {
+    pub vertex: GpuVertexData,
+    pub material_index: u32,
+}
+
+impl From<VertexTextureData> for GpuVertexTextureData {
+    #[inline]
+    fn from(value: VertexTextureData) -> Self {
+        Self {
+            vertex: value.vertex.into(),
+            material_index: index_count(value.material_index),
+        }
+    }
+}
+
+/// The vertex attributes of vertices in separate buffers (structure of arrays), so every attribute can be uploaded on its own.
+///
+/// Uses the same sentinel values for missing attributes as [`GpuVertexData`].
+/// Every buffer is a slice of plain arrays, so it can be cast to bytes directly (e.g. with `bytemuck::cast_slice`).
+/// All buffers have the same length, one entry per vertex.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SplitBuffers {
+    pub positions: Vec<[f32; 3]>,
+    /// Normals, `[0.0, 0.0, 0.0]` for a vertex without a normal.
+    pub normals: Vec<[f32; 3]>,
+    /// Texture coords (uvs), `[0.0, 0.0]` for a vertex without a texture coord.
+    pub texture_coords: Vec<[f32; 2]>,
+    /// Colors including alpha (RGBA), opaque white `[1.0, 1.0, 1.0, 1.0]` for a vertex without a color,
+    /// so multiplying it with a texture or material color leaves that color unchanged.
+    pub colors: Vec<[f32; 4]>,
+}
+
+/// Converts vertices into a single interleaved buffer (array of structures), see [`GpuVertexData`].
+///
+/// Missing attributes are replaced by the sentinel values of [`GpuVertexData`]:
+/// a missing color becomes opaque white, a missing normal or texture coord becomes zero.
+/// The material index of each vertex is dropped.
+#[must_use]
+pub fn interleaved_buffer(vertices: &[VertexTextureData]) -> Vec<GpuVertexData> {
+    vertices
+        .iter()
+        .map(|v| GpuVertexData::from(v.vertex))
+        .collect()
+}
+
+/// Converts vertices into one buffer per attribute (structure of arrays), see [`SplitBuffers`].
+///
+/// Missing attributes are replaced by the same sentinel values as in [`interleaved_buffer`].
+/// The material index of each vertex is dropped.
+#[must_use]
+pub fn split_buffers(vertices: &[VertexTextureData]) -> SplitBuffers {
+    let mut buffers = SplitBuffers {
+        positions: Vec::with_capacity(vertices.len()),
+        normals: Vec::with_capacity(vertices.len()),
+        texture_coords: Vec::with_capacity(vertices.len()),
+        colors: Vec::with_capacity(vertices.len()),
+    };
+
+    for vertex in interleaved_buffer(vertices) {
+        buffers.positions.push(vertex.position);
+        buffers.normals.push(vertex.normal);
+        buffers.texture_coords.push(vertex.texture_coord);
+        buffers.colors.push(vertex.color);
+    }
+
+    buffers
+}
+
+/// The vertex attributes of [`GpuVertexData`], by shader location.
+#[cfg(feature = "wgpu")]
+const WGPU_ATTRIBUTES: [wgpu::VertexAttribute; 4] = [
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x3,
+        offset: std::mem::offset_of!(GpuVertexData, position) as u64,
+        shader_location: 0,
+    },
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x3,
+        offset: std::mem::offset_of!(GpuVertexData, normal) as u64,
+        shader_location: 1,
+    },
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x2,
+        offset: std::mem::offset_of!(GpuVertexData, texture_coord) as u64,
+        shader_location: 2,
+    },
+    wgpu::VertexAttribute {
+        format: wgpu::VertexFormat::Float32x4,
+        offset: std::mem::offset_of!(GpuVertexData, color) as u64,
+        shader_location: 3,
+    },
+];
+
+/// Returns the [`wgpu::VertexBufferLayout`] of a vertex buffer containing [`GpuVertexData`],
+/// e.g. as written by [`wgpu_vertex_buffer_contents`].
+///
+/// The attributes use the shader locations
+/// - `0`: position (`float32x3`)
+/// - `1`: normal (`float32x3`)
+/// - `2`: texture coord (`float32x2`)
+/// - `3`: color, including alpha (`float32x4`)
+#[cfg(feature = "wgpu")]
+#[must_use]
+pub const fn wgpu_vertex_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
+    wgpu::VertexBufferLayout {
+        array_stride: size_of::<GpuVertexData>() as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &WGPU_ATTRIBUTES,
+    }
+}
+
+/// Converts vertices into the raw bytes of a vertex buffer, matching [`wgpu_vertex_buffer_layout`].
+///
+/// Every vertex is converted into [`GpuVertexData`], see there for how missing attributes are represented.
+/// The material index of each vertex is dropped.
+#[cfg(feature = "wgpu")]
+#[must_use]
+pub fn wgpu_vertex_buffer_contents(vertices: &[VertexTextureData]) -> Vec<u8> {
+    bytemuck::cast_slice(&interleaved_buffer(vertices)).to_vec()
+}
@@ -0,0 +1,99 @@
+use crate::{ObjObject, vec3::Vec3};
+
+/// The nearest intersection of a ray with the faces of an [`ObjObject`], see [`ObjObject::ray_cast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<'a> {
+    /// Index of the hit face, counting the faces of all objects and groups in order.
+    pub face: usize,
+    /// Name of the object containing the hit face.
+    pub object: &'a str,
+    /// Name of the group containing the hit face.
+    pub group: &'a str,
+    /// Distance along the ray, in multiples of the ray direction.
+    pub distance: f32,
+    /// Barycentric coordinate of the hit, weighting the second corner of the face.
+    pub u: f32,
+    /// Barycentric coordinate of the hit, weighting the third corner of the face.
+    pub v: f32,
+}
+
+/// Intersects a ray with a triangle, using the Möller–Trumbore algorithm.
+///
+/// Returns `(t, u, v)`, where `t` is the distance along the ray (in multiples of `ray_dir`),
+/// and `u` and `v` are the barycentric coordinates of the hit, weighting `v1` and `v2`.
+/// Triangles are hit from both sides, but not behind the ray origin or if the ray is parallel to the triangle.
+#[must_use]
+pub fn ray_triangle_intersect(
+    ray_origin: [f32; 3],
+    ray_dir: [f32; 3],
+    v0: [f32; 3],
+    v1: [f32; 3],
+    v2: [f32; 3],
+) -> Option<(f32, f32, f32)> {
+    let dir = Vec3::from(ray_dir);
+    let v0 = Vec3::from(v0);
+
+    let edge1 = Vec3::from(v1) - v0;
+    let edge2 = Vec3::from(v2) - v0;
+
+    let pvec = dir.cross(&edge2);
+    let det = edge1.dot(&pvec);
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+    let inv_det = det.recip();
+
+    let tvec = Vec3::from(ray_origin) - v0;
+    let u = tvec.dot(&pvec) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let qvec = tvec.cross(&edge1);
+    let v = dir.dot(&qvec) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(&qvec) * inv_det;
+    (t > f32::EPSILON).then_some((t, u, v))
+}
+
+impl ObjObject {
+    /// Casts a ray against all faces, returning the nearest hit.
+    ///
+    /// Every face is tested, there is no acceleration structure.
+    /// For many ray casts against the same faces, build a [`Bvh`][crate::bvh::Bvh] first, see [`ObjObject::build_bvh`].
+    /// See [`ray_triangle_intersect`] for which faces count as hit.
+    #[must_use]
+    pub fn ray_cast(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<RayHit<'_>> {
+        let mut nearest: Option<RayHit<'_>> = None;
+
+        for object in &self.objects {
+            for group in &self.groups[object.start..object.finish] {
+                for face in group.start..group.finish {
+                    let [v0, v1, v2] = <[u32; 3]>::from(self.faces[face].indicies)
+                        .map(|i| self.vertices[i as usize - 1]);
+
+                    let Some((distance, u, v)) = ray_triangle_intersect(origin, dir, v0, v1, v2)
+                    else {
+                        continue;
+                    };
+
+                    if nearest.is_none_or(|hit| distance < hit.distance) {
+                        nearest = Some(RayHit {
+                            face,
+                            object: &object.name,
+                            group: &group.name,
+                            distance,
+                            u,
+                            v,
+                        });
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+}
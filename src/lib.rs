@@ -2,57 +2,348 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::cargo)]
 
+pub mod bounding;
+pub mod bvh;
+pub mod external;
+pub mod geometry;
+pub mod intersect;
 pub mod meshlet;
 pub mod opt;
-pub mod bounding;
+pub mod write;
 
+#[cfg(feature = "glam")]
+mod glam_interop;
+#[cfg(feature = "gltf")]
+mod gltf;
+mod gpu;
+mod merge;
+mod mtl;
 mod obj;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod parse;
+#[cfg(feature = "ply")]
+mod ply;
+mod reader;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "stl")]
+mod stl;
+mod transform;
+mod vec2;
 mod vec3;
 
+pub use gpu::{
+    GpuVertexData, GpuVertexTextureData, SplitBuffers, interleaved_buffer, split_buffers,
+};
+#[cfg(feature = "wgpu")]
+pub use gpu::{wgpu_vertex_buffer_contents, wgpu_vertex_buffer_layout};
+pub use merge::MaterialConflict;
+pub use mtl::{
+    Material, MaterialLibraries, MaterialLibrary, TextureMap, TextureOptions, UnresolvedMaterial,
+};
 pub use obj::Face;
+pub use obj::FaceContext;
+pub use obj::LineSegment;
 pub use obj::MaterialIdent;
+pub use obj::MaterialIdentOwned;
 pub use obj::ObjObject;
+pub use obj::OwnedGroup;
+pub use obj::Point;
+pub use obj::PolygonCorner;
+pub use obj::PolygonFace;
 pub use obj::VertexData;
 pub use obj::VertexTextureData;
+pub use parse::{
+    DEFAULT_GROUP_NAME, DisplayAttribute, DisplayAttributes, FaceData, Line, LineParser,
+    ParseLimits, ParseOptions, ParseProgress, ParseResult, ParseWarning, ParsedVertex, QuadSplit,
+    WeldEpsilon,
+};
+#[cfg(feature = "ply")]
+pub use ply::PlyObject;
+pub use transform::ObjectId;
+pub use vec2::Vec2;
+pub use vec3::Vec3;
+pub use write::WriteConfig;
 
-use std::num::{ParseFloatError, ParseIntError};
+use std::{
+    num::{ParseFloatError, ParseIntError},
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents different kind of errors that can happen while reading and parsing a .obj object.
+///
+/// With the `serde` feature, the wrapped io and parse errors are (de)serialized via their message,
+/// so a deserialized io error keeps its message, but not its [`ErrorKind`][std::io::ErrorKind].
 pub enum Error {
-    Io(std::io::Error),
-    UnkownLine(String),
+    Io(#[cfg_attr(feature = "serde", serde(with = "serialize::io_error"))] std::io::Error),
+    UnknownLine(String),
     UnexpectedEoL,
-    ParseF(ParseFloatError),
-    ParseI(ParseIntError),
+    ParseF(
+        #[cfg_attr(feature = "serde", serde(with = "serialize::parse_float_error"))]
+        ParseFloatError,
+    ),
+    ParseI(
+        #[cfg_attr(feature = "serde", serde(with = "serialize::parse_int_error"))] ParseIntError,
+    ),
     EmptyMtl,
-    OjectMultipleMtl(String),
-    GroupMultipleMTl(String),
     NonUniformColors,
+    /// A .mtl statement appeared before the first material (newmtl).
+    MissingNewMtl,
+    /// A texture map in a .mtl file has an unknown option, or an invalid argument of an option.
+    InvalidTextureOption(String),
+    /// A .ply file is not as structured as expected, see [`PlyObject`][crate::PlyObject] (`ply` feature).
+    InvalidPly(String),
+    /// A .stl file is not as structured as expected, see [`ObjObject::parse_stl`] (`stl` feature).
+    InvalidStl(String),
+    LineTooLong {
+        line: usize,
+        limit: usize,
+    },
+    TooManyFaceVertices {
+        count: usize,
+        limit: usize,
+    },
+    /// A file contains more vertex positions (v) than [`ParseLimits::max_vertices`].
+    TooManyVertices {
+        limit: usize,
+    },
+    /// A file contains more faces (counting triangles) than [`ParseLimits::max_faces`].
+    TooManyFaces {
+        limit: usize,
+    },
+    /// A file is larger than [`ParseLimits::max_file_size`].
+    FileTooLarge {
+        size: u64,
+        limit: u64,
+    },
+    /// The corners of a face (f) do not all have the same format (e.g. `f 1 2/2 3//3`).
+    InconsistentFaceFormat(String),
+    /// A face, line or point element references index 0.
+    /// Contains the (1-based) corner of the element and which of its indices it was.
+    ZeroIndex {
+        corner: usize,
+        kind: IndexKind,
+    },
+    /// A face, line or point element references a relative (negative) index before the first element.
+    /// Contains the index as written, and the number of elements of that kind defined so far.
+    RelativeIndexOutOfRange {
+        index: i32,
+        count: u32,
+        corner: usize,
+        kind: IndexKind,
+    },
+    /// There are more unique vertices than an index buffer of the requested type can address.
+    IndexOverflow {
+        count: usize,
+        limit: usize,
+    },
+    /// A meshlet is not internally consistent, containing every violation found.
+    InvalidMeshlet(Vec<meshlet::MeshletViolation>),
+    /// Material uses (usemtl) that could not be resolved, see [`ObjObject::vertices_with_materials`].
+    UnresolvedMaterials(Vec<UnresolvedMaterial>),
+    /// Tangents can only be generated, if every face has texture coords (vt).
+    MissingTextureCoords,
+    /// Parsing was cancelled by a progress callback, see [`ObjObject::parse_with_progress`].
+    Cancelled,
+    /// The input is too large to be processed within the memory budget of an [`ExternalConfig`][external::ExternalConfig],
+    /// see [`index_out_of_core`][external::index_out_of_core].
+    /// Contains the budget, and the (approximate) minimum budget required for the input.
+    MemoryBudgetTooSmall {
+        budget: usize,
+        required: u64,
+    },
+    /// A vertex position (v), normal (vn) or texture coord (vt) contains NaN or an infinite value,
+    /// see [`ParseOptions::reject_non_finite`].
+    ///
+    /// For data decoded from other formats (e.g. .stl or .ply files), the line is `0`,
+    /// and the statement is the equivalent .obj statement.
+    NonFiniteValue {
+        statement: String,
+        line: usize,
+    },
+    /// Wraps an error encountered while parsing a specific (1-based) line.
+    AtLine {
+        line: usize,
+        source: Box<Self>,
+    },
+    /// Wraps an error encountered while opening or reading a file.
+    File {
+        path: PathBuf,
+        source: Box<Self>,
+    },
+}
+
+/// The kind of an index of a face, line or point element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexKind {
+    /// The index of the vertex position (v).
+    Vertex,
+    /// The index of the texture coord (vt).
+    TextureCoord,
+    /// The index of the normal (vn).
+    Normal,
+}
+
+impl std::fmt::Display for IndexKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vertex => write!(f, "vertex"),
+            Self::TextureCoord => write!(f, "texture coord"),
+            Self::Normal => write!(f, "normal"),
+        }
+    }
+}
+
+impl Error {
+    /// Wraps this error with the line it was encountered on.
+    #[inline]
+    pub(crate) fn at_line(self, line: usize) -> Self {
+        Self::AtLine {
+            line,
+            source: Box::new(self),
+        }
+    }
+
+    /// Wraps this error with the file it was encountered in.
+    #[inline]
+    pub(crate) fn in_file(self, path: &Path) -> Self {
+        Self::File {
+            path: path.to_path_buf(),
+            source: Box::new(self),
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Io(error) => writeln!(f, "{error}"),
-            Self::UnkownLine(line) => writeln!(f, "Encounterd a unknown line: [{line}]"),
+            // wrapped errors are available via `source`
+            Self::Io(_) => writeln!(f, "I/O error"),
+            Self::UnknownLine(line) => writeln!(f, "Encounterd a unknown line: [{line}]"),
             Self::UnexpectedEoL => writeln!(f, "Unexpected end-of-line"),
-            Self::ParseF(error) => writeln!(f, "{error}"),
-            Self::ParseI(error) => writeln!(f, "{error}"),
+            Self::ParseF(_) => writeln!(f, "Invalid float"),
+            Self::ParseI(_) => writeln!(f, "Invalid integer"),
             Self::EmptyMtl => writeln!(f, "Empty material [lib/use]"),
-            Self::OjectMultipleMtl(object) => {
-                writeln!(f, "Multiple material lib defined for object [{object}]")
-            }
-            Self::GroupMultipleMTl(group) => {
-                writeln!(f, "Multiple material uses defined for group [{group}]")
-            }
             Self::NonUniformColors => {
                 writeln!(
                     f,
                     "Vertex colors are specified for some vertices, but not all"
                 )
             }
+            Self::MissingNewMtl => {
+                writeln!(f, "Material statement before any material (newmtl)")
+            }
+            Self::InvalidTextureOption(option) => {
+                writeln!(f, "Invalid texture map option: [{option}]")
+            }
+            Self::InvalidPly(reason) => writeln!(f, "Invalid .ply file: {reason}"),
+            Self::InvalidStl(reason) => writeln!(f, "Invalid .stl file: {reason}"),
+            Self::MissingTextureCoords => {
+                writeln!(f, "Tangents require texture coords (vt) for every face")
+            }
+            Self::Cancelled => writeln!(f, "Parsing was cancelled"),
+            Self::MemoryBudgetTooSmall { budget, required } => {
+                writeln!(
+                    f,
+                    "Memory budget of [{budget}] bytes is too small, at least [{required}] bytes are required"
+                )
+            }
+            Self::InconsistentFaceFormat(face) => {
+                writeln!(f, "Face corners use inconsistent formats: [{face}]")
+            }
+            Self::ZeroIndex { corner, kind } => {
+                writeln!(
+                    f,
+                    "The {kind} index of corner [{corner}] resolves to 0, but indices start at 1"
+                )
+            }
+            Self::RelativeIndexOutOfRange {
+                index,
+                count,
+                corner,
+                kind,
+            } => {
+                writeln!(
+                    f,
+                    "The relative {kind} index [{index}] of corner [{corner}] is out of range, only [{count}] are defined"
+                )
+            }
+            Self::IndexOverflow { count, limit } => {
+                writeln!(
+                    f,
+                    "[{count}] unique vertices exceed the maximum of [{limit}] addressable vertices"
+                )
+            }
+            Self::InvalidMeshlet(violations) => {
+                writeln!(f, "Invalid meshlet with [{}] violations:", violations.len())?;
+                for violation in violations {
+                    writeln!(f, "  {violation}")?;
+                }
+                Ok(())
+            }
+            Self::UnresolvedMaterials(materials) => {
+                writeln!(f, "[{}] materials could not be resolved:", materials.len())?;
+                for material in materials {
+                    writeln!(f, "  {material}")?;
+                }
+                Ok(())
+            }
+            Self::LineTooLong { line, limit } => {
+                writeln!(
+                    f,
+                    "Line [{line}] exceeds the maximum line length of [{limit}] bytes"
+                )
+            }
+            Self::TooManyFaceVertices { count, limit } => {
+                writeln!(
+                    f,
+                    "Face with [{count}] vertices exceeds the maximum of [{limit}] vertices per face"
+                )
+            }
+            Self::NonFiniteValue { statement, line: 0 } => {
+                writeln!(f, "Contains a non-finite value: [{statement}]")
+            }
+            Self::NonFiniteValue { statement, line } => {
+                writeln!(
+                    f,
+                    "Line [{line}] contains a non-finite value: [{statement}]"
+                )
+            }
+            Self::TooManyVertices { limit } => {
+                writeln!(f, "Exceeds the maximum of [{limit}] vertices")
+            }
+            Self::TooManyFaces { limit } => {
+                writeln!(f, "Exceeds the maximum of [{limit}] faces")
+            }
+            Self::FileTooLarge { size, limit } => {
+                writeln!(
+                    f,
+                    "File with [{size}] bytes exceeds the maximum file size of [{limit}] bytes"
+                )
+            }
+            Self::AtLine { line, source } => write!(f, "on line {line}: {source}"),
+            Self::File { path, source } => write!(f, "in file [{}]: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    /// Returns the wrapped io or parse error.
+    ///
+    /// [`Error::AtLine`] and [`Error::File`] already include the message of the error they wrap,
+    /// so they return the source of that error instead.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::ParseF(error) => Some(error),
+            Self::ParseI(error) => Some(error),
+            Self::AtLine { source, .. } | Self::File { source, .. } => source.source(),
+            _ => None,
         }
     }
 }
@@ -79,12 +370,21 @@ impl From<ParseIntError> for Error {
 }
 
 pub trait Vertex {
-    fn position(&self) -> (f32, f32, f32);
+    fn position(&self) -> [f32; 3];
+
+    #[inline]
+    #[deprecated(
+        since = "0.2.0",
+        note = "positions are arrays now, use `position` instead"
+    )]
+    fn position_tuple(&self) -> (f32, f32, f32) {
+        self.position().into()
+    }
 }
 
 impl Vertex for VertexTextureData {
     #[inline]
-    fn position(&self) -> (f32, f32, f32) {
+    fn position(&self) -> [f32; 3] {
         self.vertex.position
     }
 }
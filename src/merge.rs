@@ -0,0 +1,249 @@
+use std::mem;
+
+use crate::{
+    ObjObject,
+    parse::{FaceData, GroupingData, PolygonData},
+};
+
+/// Two ranges of a group, merged by [`ObjObject::merge_named_groups`], that start with different materials (usemtl).
+///
+/// The faces keep their materials, the merged group switches materials where the later range starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialConflict {
+    /// Name of the object containing the group.
+    pub object: String,
+    /// Name of the merged group.
+    pub group: String,
+    /// The material active at the end of the previous range.
+    pub mtluse: Option<String>,
+    /// The material of the later range.
+    pub conflicting: Option<String>,
+}
+
+impl std::fmt::Display for MaterialConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Group [{}] of object [{}] uses material [{}] and [{}]",
+            self.group,
+            self.object,
+            self.mtluse.as_deref().unwrap_or_default(),
+            self.conflicting.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+/// The elements of an [`ObjObject`] before merging, moved into the merged groups one group at a time.
+struct Unmerged {
+    faces: Vec<FaceData>,
+    tangents: Vec<[f32; 4]>,
+    polygons: Vec<PolygonData>,
+    line_elements: Vec<Option<Vec<u32>>>,
+    line_texture_indices: Vec<Option<Vec<u32>>>,
+    point_elements: Vec<Option<Vec<u32>>>,
+}
+
+impl Unmerged {
+    fn take(obj: &mut ObjObject) -> Self {
+        Self {
+            faces: mem::take(&mut obj.faces),
+            tangents: mem::take(&mut obj.vertex_tangents),
+            polygons: mem::take(&mut obj.polygons),
+            line_elements: mem::take(&mut obj.line_elements)
+                .into_iter()
+                .map(Some)
+                .collect(),
+            line_texture_indices: mem::take(&mut obj.line_texture_indices),
+            point_elements: mem::take(&mut obj.point_elements)
+                .into_iter()
+                .map(Some)
+                .collect(),
+        }
+    }
+
+    /// Appends the faces (with their tangents and polygons), lines and points of a group to `obj`.
+    fn move_group(&mut self, obj: &mut ObjObject, group: &GroupingData) {
+        let offset = obj.faces.len();
+
+        obj.faces
+            .extend_from_slice(&self.faces[group.start..group.finish]);
+        if self.tangents.len() == self.faces.len() * 3 {
+            obj.vertex_tangents
+                .extend_from_slice(&self.tangents[group.start * 3..group.finish * 3]);
+        }
+
+        let polygons = self.polygons.partition_point(|p| p.face < group.start)
+            ..self.polygons.partition_point(|p| p.face < group.finish);
+        obj.polygons
+            .extend(self.polygons[polygons].iter().map(|polygon| PolygonData {
+                face: polygon.face - group.start + offset,
+                ..*polygon
+            }));
+
+        let lines = group.line_start..group.line_finish;
+        obj.line_elements.extend(
+            self.line_elements[lines.clone()]
+                .iter_mut()
+                .filter_map(Option::take),
+        );
+        obj.line_texture_indices.extend(
+            self.line_texture_indices[lines]
+                .iter_mut()
+                .map(Option::take),
+        );
+
+        obj.point_elements.extend(
+            self.point_elements[group.point_start..group.point_finish]
+                .iter_mut()
+                .filter_map(Option::take),
+        );
+    }
+}
+
+impl ObjObject {
+    /// Merges all groups (g) with the same name inside of an object into one group.
+    ///
+    /// Groups with multiple names (see [`GroupRef::names`][crate::obj::GroupRef::names]) are only merged, if all names are the same.
+    /// Groups using different texture maps (usemap) are never merged.
+    ///
+    /// The .obj format allows a group to be continued later in the file, which otherwise results in multiple groups with the same name.
+    /// Merged groups take the position of their first occurrence, and their faces, lines and points are reordered to be contiguous,
+    /// so faces are no longer in file order. The relative order of groups and of the elements inside a group is kept.
+    ///
+    /// Every face keeps its material. Returns every place where a continued group starts with a different material,
+    /// than the one active at the end of the previous part of the group.
+    /// If such a part has no material at all, its faces use the material of the previous part.
+    pub fn merge_named_groups(&mut self) -> Vec<MaterialConflict> {
+        let mut conflicts = Vec::new();
+
+        let mut elements = Unmerged::take(self);
+        let mut groups = mem::take(&mut self.groups)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut objects = mem::take(&mut self.objects);
+
+        for object in &mut objects {
+            let start = self.groups.len();
+
+            // the groups sharing all names, in the order of their first occurrence
+            let mut merged: Vec<Vec<usize>> = Vec::new();
+            for (index, group) in groups
+                .iter()
+                .enumerate()
+                .take(object.finish)
+                .skip(object.start)
+            {
+                let Some(group) = group else {
+                    continue;
+                };
+
+                let same_names = |members: &&mut Vec<usize>| {
+                    groups[members[0]].as_ref().is_some_and(|first| {
+                        first.name == group.name
+                            && first.other_names == group.other_names
+                            && first.map == group.map
+                    })
+                };
+                match merged.iter_mut().find(same_names) {
+                    Some(members) => members.push(index),
+                    None => merged.push(vec![index]),
+                }
+            }
+
+            for members in merged {
+                let mut target = GroupingData {
+                    start: self.faces.len(),
+                    line_start: self.line_elements.len(),
+                    point_start: self.point_elements.len(),
+                    ..Default::default()
+                };
+
+                for (i, member) in members.into_iter().enumerate() {
+                    let Some(group) = groups[member].take() else {
+                        continue;
+                    };
+
+                    if i == 0 {
+                        target.name.clone_from(&group.name);
+                        target.other_names.clone_from(&group.other_names);
+                        target.mtl.clone_from(&group.mtl);
+                        target.map.clone_from(&group.map);
+                        target.smooth = group.smooth;
+                    } else if let Some(conflict) = self.continue_material(&mut target, &group) {
+                        conflicts.push(MaterialConflict {
+                            object: object.name.clone(),
+                            ..conflict
+                        });
+                    }
+
+                    let offset = self.faces.len();
+                    target.mtl_changes.extend(
+                        group
+                            .mtl_changes
+                            .iter()
+                            .map(|(face, mtl)| (face - group.start + offset, mtl.clone())),
+                    );
+
+                    elements.move_group(self, &group);
+                    self.recycle(group);
+                }
+
+                target.finish = self.faces.len();
+                target.line_finish = self.line_elements.len();
+                target.point_finish = self.point_elements.len();
+
+                self.groups.push(target);
+            }
+
+            object.start = start;
+            object.finish = self.groups.len();
+        }
+
+        self.objects = objects;
+
+        conflicts
+    }
+
+    /// Lets the faces of `group` keep their material, when appended to `target`.
+    ///
+    /// Returns a conflict (without the object name), if the material of `group` differs from the one active at the end of `target`.
+    fn continue_material(
+        &mut self,
+        target: &mut GroupingData,
+        group: &GroupingData,
+    ) -> Option<MaterialConflict> {
+        let offset = self.faces.len();
+
+        // a material use after the last face of the previous part does not apply to any face
+        if let Some((face, _)) = target.mtl_changes.last()
+            && *face == offset
+            && let Some((_, mtl)) = target.mtl_changes.pop()
+        {
+            self.string_pool.push(mtl);
+        }
+
+        let active = target
+            .mtl_changes
+            .last()
+            .map(|(_, mtl)| mtl)
+            .or(target.mtl.as_ref());
+
+        if active == group.mtl.as_ref() {
+            return None;
+        }
+
+        let conflict = MaterialConflict {
+            object: String::new(),
+            group: target.name.clone(),
+            mtluse: active.cloned(),
+            conflicting: group.mtl.clone(),
+        };
+
+        if let Some(mtl) = &group.mtl {
+            target.mtl_changes.push((offset, mtl.clone()));
+        }
+
+        Some(conflict)
+    }
+}
@@ -1,4 +1,10 @@
-use crate::bounding::{Sphere, build_bounding_sphere};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    Error, ObjObject,
+    bounding::{Aabb, Sphere, build_aabb, build_bounding_sphere},
+    parse::index_count,
+};
 
 use super::vec3::Vec3;
 
@@ -9,29 +15,50 @@ use super::Vertex;
 /// A cluster of triangle indices into a vertex buffer.
 ///
 /// The cone component represents the average Meshlet normal (x,y,z) and an angle (w).
+/// The apex is the tip of that cone, see [`compute_meshlet_cone_apex`].
+///
+/// The bounding sphere and the axis aligned bounding box contain all vertices for this meshlet.
+/// The box is tighter for elongated meshlets, the sphere is cheaper to test against.
 ///
-/// The bounding sphere contains all vertices for this meshlet.
-#[derive(Debug)]
+/// A meshlet does not know the source faces of its triangles, see [`build_meshlets_with_face_ids`].
+#[derive(Debug, Clone)]
 pub struct Meshlet<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize> {
-    pub cone: (f32, f32, f32, f32),
+    pub cone: [f32; 4],
+    pub apex: [f32; 3],
     pub bounding: Sphere,
+    pub aabb: Aabb,
     pub vertices: [u32; VERTEX_COUNT],
     pub triangles: [[u8; 3]; TRIANGLE_COUNT],
     pub vertex_count: u8,
     pub triangle_count: u8,
 }
 
+impl<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize> Meshlet<VERTEX_COUNT, TRIANGLE_COUNT> {
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "the cone is an array now, use `cone` directly"
+    )]
+    /// Returns the cone as a tuple.
+    pub fn cone_tuple(&self) -> (f32, f32, f32, f32) {
+        self.cone.into()
+    }
+}
+
 impl<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize> Default
     for Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>
 {
     #[inline]
     fn default() -> Self {
         Self {
-            cone: (0.0, 0.0, 0.0, 0.0),
+            cone: [0.0; 4],
+            apex: [0.0; 3],
             bounding: Sphere {
-                center: (0.0, 0.0, 0.0),
+                center: [0.0; 3],
                 radius: 0.0,
             },
+            aabb: Aabb::EMPTY,
             vertices: [0; VERTEX_COUNT],
             triangles: [[0; 3]; TRIANGLE_COUNT],
             vertex_count: 0,
@@ -40,13 +67,212 @@ impl<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize> Default
     }
 }
 
+/// A single inconsistency of a [`Meshlet`], found by [`validate_meshlet`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeshletViolation {
+    /// `vertex_count` is larger than the number of vertices a meshlet can hold.
+    VertexCountExceeded { count: u8, capacity: usize },
+    /// `triangle_count` is larger than the number of triangles a meshlet can hold.
+    TriangleCountExceeded { count: u8, capacity: usize },
+    /// A triangle references a local vertex that is not below `vertex_count`.
+    TriangleIndexOutOfRange { triangle: usize, index: u8 },
+    /// A vertex of the meshlet is not a valid index into the vertex buffer.
+    VertexIndexOutOfRange { vertex: usize, index: u32 },
+    /// A triangle references the same local vertex more than once.
+    DegenerateTriangle { triangle: usize },
+    /// A vertex lies outside of the bounding sphere, by the given distance.
+    VertexOutsideBounds { vertex: usize, distance: f32 },
+}
+
+impl std::fmt::Display for MeshletViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VertexCountExceeded { count, capacity } => {
+                write!(
+                    f,
+                    "vertex count [{count}] exceeds the capacity of [{capacity}]"
+                )
+            }
+            Self::TriangleCountExceeded { count, capacity } => {
+                write!(
+                    f,
+                    "triangle count [{count}] exceeds the capacity of [{capacity}]"
+                )
+            }
+            Self::TriangleIndexOutOfRange { triangle, index } => {
+                write!(
+                    f,
+                    "triangle [{triangle}] references vertex [{index}], which is out of range"
+                )
+            }
+            Self::VertexIndexOutOfRange { vertex, index } => {
+                write!(
+                    f,
+                    "vertex [{vertex}] references index [{index}], which is out of range of the vertex buffer"
+                )
+            }
+            Self::DegenerateTriangle { triangle } => {
+                write!(f, "triangle [{triangle}] is degenerate")
+            }
+            Self::VertexOutsideBounds { vertex, distance } => {
+                write!(
+                    f,
+                    "vertex [{vertex}] lies [{distance}] outside of the bounding sphere"
+                )
+            }
+        }
+    }
+}
+
+/// Checks that a meshlet is internally consistent:
+/// - `vertex_count` and `triangle_count` do not exceed the capacity of the meshlet,
+/// - every triangle only references vertices below `vertex_count`,
+/// - every vertex is a valid index into `vertices`,
+/// - no triangle references the same vertex more than once,
+/// - the bounding sphere contains all vertices.
+///
+/// # Errors
+/// Returns [`Error::InvalidMeshlet`] containing every violation found.
+pub fn validate_meshlet<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlet: &Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>,
+    vertices: &[impl Vertex],
+) -> Result<(), Error> {
+    let mut violations = Vec::new();
+
+    let vertex_count = meshlet.vertex_count as usize;
+    if vertex_count > VERTEX_COUNT {
+        violations.push(MeshletViolation::VertexCountExceeded {
+            count: meshlet.vertex_count,
+            capacity: VERTEX_COUNT,
+        });
+    }
+
+    let triangle_count = meshlet.triangle_count as usize;
+    if triangle_count > TRIANGLE_COUNT {
+        violations.push(MeshletViolation::TriangleCountExceeded {
+            count: meshlet.triangle_count,
+            capacity: TRIANGLE_COUNT,
+        });
+    }
+
+    for (triangle, indices) in meshlet.triangles.iter().take(triangle_count).enumerate() {
+        for index in indices {
+            if *index as usize >= vertex_count {
+                violations.push(MeshletViolation::TriangleIndexOutOfRange {
+                    triangle,
+                    index: *index,
+                });
+            }
+        }
+
+        let [a, b, c] = *indices;
+        if a == b || b == c || a == c {
+            violations.push(MeshletViolation::DegenerateTriangle { triangle });
+        }
+    }
+
+    let center = Vec3::from(meshlet.bounding.center);
+    // the bounding sphere is computed with floating point precision
+    let tolerance = meshlet.bounding.radius.mul_add(1e-5, 1e-6);
+
+    for (vertex, index) in meshlet.vertices.iter().take(vertex_count).enumerate() {
+        let Some(position) = vertices.get(*index as usize).map(Vertex::position) else {
+            violations.push(MeshletViolation::VertexIndexOutOfRange {
+                vertex,
+                index: *index,
+            });
+            continue;
+        };
+
+        let distance = Vec3::from(position).distance(center) - meshlet.bounding.radius;
+        if distance > tolerance {
+            violations.push(MeshletViolation::VertexOutsideBounds { vertex, distance });
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidMeshlet(violations))
+    }
+}
+
 /// Generates Meshlets from index and vertex data. Takes an additional cone threshold, that controls how wide the normal cone can be.
 ///
 /// The cone threshold can be between \[0.1, 0.9\]. A larger cone threshold means more meshlets (meshlets don't get filled), but a more uniform triangle normal direction.
+///
+/// Degenerate triangles, that reference the same vertex more than once, are skipped, as they do not cover any area.
+///
+/// # Panics
+/// Panics if any index is out of bounds for the given vertices.
 pub fn build_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V: Vertex>(
+    indices: &[u32],
+    vertices: &[V],
+    cone_threshold: f32,
+) -> Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>> {
+    build_meshlets_inner(indices, vertices, cone_threshold, None)
+}
+
+#[must_use]
+/// Generates Meshlets for all faces of an [`ObjObject`], see [`build_meshlets`].
+///
+/// The vertices of the meshlets index into the unique vertices returned by [`ObjObject::vertices_indexed`].
+/// As identical vertices are merged, faces with coincident corners become degenerate triangles, which are skipped.
+///
+/// # Panics
+/// Panics if there are more than `u32::MAX` unique vertices.
+pub fn build_meshlets_from_obj<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    obj: &ObjObject,
+    cone_threshold: f32,
+) -> Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>> {
+    let (indices, vertices, _) = obj.vertices_indexed();
+
+    let indices = indices
+        .into_iter()
+        .map(|i| u32::try_from(i).expect("Less than u32::MAX unique vertices"))
+        .collect::<Vec<_>>();
+
+    build_meshlets(&indices, &vertices, cone_threshold)
+}
+
+/// Same as [`build_meshlets`], but additionally returns the source face of every meshlet triangle.
+///
+/// The returned face ids are parallel to the returned meshlets: `face_ids[m][t]` is the index of the triangle in `indices`
+/// (so the face starting at `indices[face_ids[m][t] * 3]`) that became triangle `t` of meshlet `m`.
+/// Only the first `triangle_count` entries of each meshlet are valid, the rest are 0.
+/// Skipped degenerate triangles do not appear in any meshlet.
+///
+/// The face ids are not part of [`Meshlet`], so meshlets without them do not pay for the memory.
+/// This crate has no packed GPU or serialized format of meshlets, so they are only returned here.
+/// To upload them next to the triangles, write `face_ids[m][..meshlets[m].triangle_count]` for every meshlet `m`
+/// in the same order as its triangles.
+///
+/// # Panics
+/// Panics if any index is out of bounds for the given vertices, or if there are more than `u32::MAX` faces.
+pub fn build_meshlets_with_face_ids<
+    const VERTEX_COUNT: usize,
+    const TRIANGLE_COUNT: usize,
+    V: Vertex,
+>(
+    indices: &[u32],
+    vertices: &[V],
+    cone_threshold: f32,
+) -> (
+    Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>>,
+    Vec<[u32; TRIANGLE_COUNT]>,
+) {
+    let mut face_ids = Vec::new();
+    let meshlets = build_meshlets_inner(indices, vertices, cone_threshold, Some(&mut face_ids));
+
+    (meshlets, face_ids)
+}
+
+fn build_meshlets_inner<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V: Vertex>(
     indices: &[u32],
     vertices: &[V],
     mut cone_threshold: f32,
+    mut face_ids: Option<&mut Vec<[u32; TRIANGLE_COUNT]>>,
 ) -> Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>> {
     cone_threshold = f32::clamp(cone_threshold, 0.1, 0.9);
 
@@ -55,16 +281,21 @@ pub fn build_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V:
     // state of the current meshlet
     let mut meshlet: Meshlet<VERTEX_COUNT, TRIANGLE_COUNT> = Meshlet::default();
     let mut contained: Vec<i32> = vec![-1i32; vertices.len()];
-    let mut current_vertices: Vec<(f32, f32, f32)> = Vec::with_capacity(VERTEX_COUNT);
+    let mut current_vertices: Vec<[f32; 3]> = Vec::with_capacity(VERTEX_COUNT);
     let mut current_normals: Vec<Vec3> = Vec::with_capacity(TRIANGLE_COUNT);
+    let mut current_face_ids = [0; TRIANGLE_COUNT];
 
     // iterate of faces (set of 3 indices)
     let faces = indices
         .chunks_exact(3)
         .map(|f| <[u32; 3]>::try_from(f).unwrap());
 
-    for [i0, i1, i2] in faces {
-        //
+    for (face, [i0, i1, i2]) in faces.enumerate() {
+        // a triangle with repeated vertices has no area (and can not borrow its vertices disjointly)
+        if i0 == i1 || i1 == i2 || i0 == i2 {
+            continue;
+        }
+
         let normal = triangle_normal(
             Vec3::from(vertices[i0 as usize].position()),
             Vec3::from(vertices[i1 as usize].position()),
@@ -92,10 +323,22 @@ pub fn build_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V:
             current_normals.clear();
 
             meshlet.bounding = build_bounding_sphere(current_vertices.iter().copied());
+            meshlet.aabb = build_aabb(current_vertices.iter().copied());
             current_vertices.clear();
 
+            meshlet.apex = compute_meshlet_cone_apex(&meshlet, vertices);
+
+            // `contained` maps every vertex of the buffer to its local index, so it never needs to grow
+            debug_assert_eq!(contained.len(), vertices.len());
             contained.fill(-1);
             meshlets.push(std::mem::take(&mut meshlet));
+
+            if let Some(face_ids) = face_ids.as_deref_mut() {
+                face_ids.push(std::mem::replace(
+                    &mut current_face_ids,
+                    [0; TRIANGLE_COUNT],
+                ));
+            }
         }
 
         // reborrow here - implicit drop of av, bv, cv
@@ -134,6 +377,7 @@ pub fn build_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V:
             u8::try_from(*vb).unwrap(),
             u8::try_from(*vc).unwrap(),
         ];
+        current_face_ids[meshlet.triangle_count as usize] = u32::try_from(face).unwrap();
         meshlet.triangle_count += 1;
 
         // add positions & normal for this face
@@ -153,13 +397,420 @@ pub fn build_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize, V:
         debug_assert!(check_cone(&current_normals, cone_threshold));
         meshlet.cone = calc_cone(&current_normals);
         meshlet.bounding = build_bounding_sphere(current_vertices.iter().copied());
+        meshlet.aabb = build_aabb(current_vertices.iter().copied());
+        meshlet.apex = compute_meshlet_cone_apex(&meshlet, vertices);
 
         meshlets.push(meshlet);
+
+        if let Some(face_ids) = face_ids {
+            face_ids.push(current_face_ids);
+        }
     }
 
     meshlets
 }
 
+/// A hierarchy of meshlets with decreasing level of detail, see [`build_meshlet_lod_tree`].
+///
+/// Level 0 contains the base meshlets, every following level covers the same surface with roughly half as many triangles.
+/// The meshlets of all levels index into the same vertex buffer.
+#[derive(Debug, Clone)]
+pub struct MeshletLodTree<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize> {
+    /// The meshlets of every level, from the finest to the coarsest.
+    pub levels: Vec<Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>>>,
+    /// The node of every meshlet, parallel to `levels`.
+    pub nodes: Vec<Vec<MeshletLodNode>>,
+}
+
+/// Links a meshlet of a [`MeshletLodTree`] to the finer meshlets it was simplified from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshletLodNode {
+    /// Indices of the meshlets in the previous level, that cover the same surface as this meshlet.
+    /// All meshlets simplified from the same group share their children. Empty for the base meshlets.
+    pub children: Vec<usize>,
+    /// The geometric error of this meshlet, in units of the vertex positions.
+    /// It is never smaller than the error of its children, and 0 for the base meshlets.
+    pub lod_error: f32,
+}
+
+/// The number of meshlets, that get simplified together.
+const LOD_GROUP_SIZE: usize = 4;
+/// Meshlets of coarser levels are filled as much as possible, as their triangles are larger anyway.
+const LOD_CONE_THRESHOLD: f32 = 0.1;
+
+/// Builds a level of detail hierarchy on top of the given meshlets, adding at most `levels` coarser levels.
+///
+/// For every level, the meshlets of the previous level are grouped with their neighbours (the meshlets they share the most vertices with).
+/// The union of every group is simplified to half its triangles, by collapsing its shortest edges, and split into new meshlets.
+/// Vertices shared with other groups are locked, so the simplified groups still fit together without cracks.
+/// Edges are only collapsed onto existing vertices, so every level indexes into the same `vertices`.
+///
+/// Stops early, once a level consists of a single meshlet or can not be simplified any further.
+///
+/// # Panics
+/// Panics if any vertex index of the meshlets is out of bounds for the given vertices.
+pub fn build_meshlet_lod_tree<
+    const VERTEX_COUNT: usize,
+    const TRIANGLE_COUNT: usize,
+    Vert: Vertex,
+>(
+    base_meshlets: &[Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>],
+    vertices: &[Vert],
+    levels: usize,
+) -> MeshletLodTree<VERTEX_COUNT, TRIANGLE_COUNT> {
+    let base_nodes = vec![
+        MeshletLodNode {
+            children: Vec::new(),
+            lod_error: 0.0,
+        };
+        base_meshlets.len()
+    ];
+
+    let mut tree = MeshletLodTree {
+        levels: vec![base_meshlets.to_vec()],
+        nodes: vec![base_nodes],
+    };
+
+    for _ in 0..levels {
+        let (Some(current), Some(current_nodes)) = (tree.levels.last(), tree.nodes.last()) else {
+            break;
+        };
+        if current.len() <= 1 {
+            break;
+        }
+
+        let users = vertex_users(current);
+
+        let mut meshlets = Vec::new();
+        let mut nodes = Vec::new();
+        let mut simplified = false;
+
+        for group in group_meshlets(current, &users) {
+            let triangles = group
+                .iter()
+                .flat_map(|m| meshlet_triangles(&current[*m]))
+                .collect::<Vec<_>>();
+
+            // vertices shared with other groups have to stay in place
+            let locked = group
+                .iter()
+                .flat_map(|m| meshlet_vertices(&current[*m]))
+                .filter(|vertex| users[vertex].iter().any(|user| !group.contains(user)))
+                .copied()
+                .collect::<FxHashSet<_>>();
+
+            let target = (triangles.len() / 2).max(1);
+            let count = triangles.len();
+            let (triangles, error) = simplify_triangles(triangles, vertices, &locked, target);
+            simplified |= triangles.len() < count;
+
+            let lod_error = group
+                .iter()
+                .map(|m| current_nodes[*m].lod_error)
+                .fold(error, f32::max);
+
+            for meshlet in meshlets_from_triangles(&triangles, vertices) {
+                meshlets.push(meshlet);
+                nodes.push(MeshletLodNode {
+                    children: group.clone(),
+                    lod_error,
+                });
+            }
+        }
+
+        if !simplified {
+            break;
+        }
+
+        tree.levels.push(meshlets);
+        tree.nodes.push(nodes);
+    }
+
+    tree
+}
+
+#[inline]
+fn meshlet_vertices<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlet: &Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>,
+) -> &[u32] {
+    &meshlet.vertices[..meshlet.vertex_count as usize]
+}
+
+/// Returns the triangles of a meshlet, indexing into the vertex buffer.
+#[inline]
+fn meshlet_triangles<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlet: &Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>,
+) -> impl Iterator<Item = [u32; 3]> {
+    meshlet.triangles[..meshlet.triangle_count as usize]
+        .iter()
+        .map(|triangle| triangle.map(|i| meshlet.vertices[i as usize]))
+}
+
+/// Maps every vertex to the meshlets using it.
+fn vertex_users<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlets: &[Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>],
+) -> FxHashMap<u32, Vec<usize>> {
+    let mut users: FxHashMap<u32, Vec<usize>> = FxHashMap::default();
+
+    for (index, meshlet) in meshlets.iter().enumerate() {
+        for vertex in meshlet_vertices(meshlet) {
+            users.entry(*vertex).or_default().push(index);
+        }
+    }
+
+    users
+}
+
+/// Greedily groups every meshlet with up to [`LOD_GROUP_SIZE`] - 1 ungrouped neighbours, preferring the ones sharing the most vertices.
+fn group_meshlets<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlets: &[Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>],
+    users: &FxHashMap<u32, Vec<usize>>,
+) -> Vec<Vec<usize>> {
+    let mut grouped = vec![false; meshlets.len()];
+    let mut groups = Vec::new();
+
+    for seed in 0..meshlets.len() {
+        if grouped[seed] {
+            continue;
+        }
+        grouped[seed] = true;
+
+        let mut group = vec![seed];
+
+        while group.len() < LOD_GROUP_SIZE {
+            let mut shared: FxHashMap<usize, usize> = FxHashMap::default();
+
+            for vertex in group.iter().flat_map(|m| meshlet_vertices(&meshlets[*m])) {
+                for user in &users[vertex] {
+                    if !grouped[*user] {
+                        *shared.entry(*user).or_default() += 1;
+                    }
+                }
+            }
+
+            // ties are broken by the lower index, to stay deterministic
+            let Some((next, _)) = shared
+                .into_iter()
+                .max_by_key(|(user, count)| (*count, std::cmp::Reverse(*user)))
+            else {
+                break;
+            };
+
+            grouped[next] = true;
+            group.push(next);
+        }
+
+        groups.push(group);
+    }
+
+    groups
+}
+
+/// Simplifies triangles down to `target` triangles (if possible), by repeatedly collapsing the shortest edges onto one of their vertices.
+///
+/// Locked vertices are never removed, and collapses that would flip a triangle are skipped.
+/// Returns the remaining triangles, and the length of the longest collapsed edge.
+fn simplify_triangles(
+    mut triangles: Vec<[u32; 3]>,
+    vertices: &[impl Vertex],
+    locked: &FxHashSet<u32>,
+    target: usize,
+) -> (Vec<[u32; 3]>, f32) {
+    let position = |i: u32| Vec3::from(vertices[i as usize].position());
+
+    let mut error = 0.0_f32;
+
+    while triangles.len() > target {
+        let mut edges = triangles
+            .iter()
+            .flat_map(|[a, b, c]| [(*a, *b), (*b, *c), (*c, *a)])
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut edges = edges
+            .into_iter()
+            .map(|(a, b)| (position(a).distance(position(b)), a, b))
+            .collect::<Vec<_>>();
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut adjacent: FxHashMap<u32, Vec<usize>> = FxHashMap::default();
+        for (index, triangle) in triangles.iter().enumerate() {
+            for vertex in triangle {
+                adjacent.entry(*vertex).or_default().push(index);
+            }
+        }
+
+        // every triangle is changed at most once per pass, so the adjacency stays valid
+        let mut touched = FxHashSet::default();
+        let mut removed = vec![false; triangles.len()];
+        let mut remaining = triangles.len();
+
+        for (length, a, b) in edges {
+            if remaining <= target {
+                break;
+            }
+
+            let (remove, keep) = if !locked.contains(&a) {
+                (a, b)
+            } else if !locked.contains(&b) {
+                (b, a)
+            } else {
+                continue;
+            };
+
+            if touched.contains(&remove) || touched.contains(&keep) {
+                continue;
+            }
+
+            let flips = adjacent[&remove].iter().any(|t| {
+                let triangle = triangles[*t];
+                if triangle.contains(&keep) {
+                    return false;
+                }
+
+                let [p0, p1, p2] = triangle.map(position);
+                let [q0, q1, q2] = triangle.map(|i| position(if i == remove { keep } else { i }));
+
+                (p1 - p0)
+                    .cross(&(p2 - p0))
+                    .dot(&(q1 - q0).cross(&(q2 - q0)))
+                    <= 0.0
+            });
+            if flips {
+                continue;
+            }
+
+            for t in &adjacent[&remove] {
+                let triangle = &mut triangles[*t];
+                touched.extend(*triangle);
+
+                if triangle.contains(&keep) {
+                    removed[*t] = true;
+                    remaining -= 1;
+                } else {
+                    for vertex in triangle.iter_mut().filter(|v| **v == remove) {
+                        *vertex = keep;
+                    }
+                }
+            }
+
+            error = error.max(length);
+        }
+
+        if remaining == triangles.len() {
+            break;
+        }
+
+        triangles = triangles
+            .into_iter()
+            .zip(removed)
+            .filter_map(|(triangle, removed)| (!removed).then_some(triangle))
+            .collect();
+    }
+
+    (triangles, error)
+}
+
+/// A vertex buffer local to a group of triangles, so building their meshlets does not scale with the whole vertex buffer.
+struct LocalPosition([f32; 3]);
+
+impl Vertex for LocalPosition {
+    #[inline]
+    fn position(&self) -> [f32; 3] {
+        self.0
+    }
+}
+
+/// Builds meshlets from triangles indexing into `vertices`, returning meshlets that also index into `vertices`.
+fn meshlets_from_triangles<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    triangles: &[[u32; 3]],
+    vertices: &[impl Vertex],
+) -> Vec<Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>> {
+    let mut local: FxHashMap<u32, u32> = FxHashMap::default();
+    let mut global = Vec::new();
+    let mut positions = Vec::new();
+
+    let indices = triangles
+        .iter()
+        .flatten()
+        .map(|vertex| {
+            *local.entry(*vertex).or_insert_with(|| {
+                global.push(*vertex);
+                positions.push(LocalPosition(vertices[*vertex as usize].position()));
+                index_count(global.len()) - 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut meshlets =
+        build_meshlets::<VERTEX_COUNT, TRIANGLE_COUNT, _>(&indices, &positions, LOD_CONE_THRESHOLD);
+
+    for meshlet in &mut meshlets {
+        let count = meshlet.vertex_count as usize;
+        for vertex in &mut meshlet.vertices[..count] {
+            *vertex = global[*vertex as usize];
+        }
+    }
+
+    meshlets
+}
+
+/// Computes the axis aligned bounding box around all vertices of a meshlet.
+///
+/// # Panics
+/// Panics if any vertex index of the meshlet is out of bounds for the given vertices.
+pub fn meshlet_aabb<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlet: &Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>,
+    vertices: &[impl Vertex],
+) -> Aabb {
+    build_aabb(
+        meshlet.vertices[..meshlet.vertex_count as usize]
+            .iter()
+            .map(|i| vertices[*i as usize].position()),
+    )
+}
+
+/// Computes the apex of the normal cone of a meshlet, using its cone axis and bounding sphere.
+///
+/// The apex is the point on the cone axis, that lies behind the planes of all triangles of the meshlet
+/// (as seen from the direction of the cone axis). A meshlet can be culled, if the camera lies inside the cone, mirrored at the apex:
+/// `dot(normalize(apex - camera), axis) >= sin(angle)`, where the axis and `sin(angle)` are the (x,y,z) and w of the cone.
+///
+/// Returns the bounding sphere center, if the meshlet has no triangles facing along the cone axis.
+///
+/// # Panics
+/// Panics if any vertex index of the meshlet is out of bounds for the given vertices.
+pub fn compute_meshlet_cone_apex<const VERTEX_COUNT: usize, const TRIANGLE_COUNT: usize>(
+    meshlet: &Meshlet<VERTEX_COUNT, TRIANGLE_COUNT>,
+    vertices: &[impl Vertex],
+) -> [f32; 3] {
+    let [x, y, z, _] = meshlet.cone;
+    let axis = Vec3::new(x, y, z);
+    let center = Vec3::from(meshlet.bounding.center);
+
+    let mut max_t = 0.0_f32;
+
+    for triangle in &meshlet.triangles[..meshlet.triangle_count as usize] {
+        let [p0, p1, p2] = triangle
+            .map(|i| Vec3::from(vertices[meshlet.vertices[i as usize] as usize].position()));
+
+        let normal = triangle_normal(p0, p1, p2);
+        let dn = axis.dot(&normal);
+        // degenerate triangles, or ones not facing along the axis, do not constrain the apex
+        if dn <= 0.0 {
+            continue;
+        }
+
+        // move along the axis, until the plane of this triangle is behind the apex
+        let t = (center - p1).dot(&normal) / dn;
+        max_t = max_t.max(t);
+    }
+
+    (center - axis * max_t).to_array()
+}
+
 fn triangle_normal(p0: Vec3, p1: Vec3, p2: Vec3) -> Vec3 {
     let p10 = p0 - p1;
     let p20 = p2 - p1;
@@ -221,7 +872,7 @@ fn check_cone_next(normals: &[Vec3], next: Vec3, th: f32) -> bool {
     true
 }
 
-fn calc_cone(normals: &[Vec3]) -> (f32, f32, f32, f32) {
+fn calc_cone(normals: &[Vec3]) -> [f32; 4] {
     let mut avg = Vec3::zero();
 
     for n in normals {
@@ -246,5 +897,5 @@ fn calc_cone(normals: &[Vec3]) -> (f32, f32, f32, f32) {
         f32::sqrt(mdot.mul_add(-mdot, 1.0))
     };
 
-    (avg.x, avg.y, avg.z, conew)
+    [avg.x, avg.y, avg.z, conew]
 }
@@ -0,0 +1,704 @@
+use std::{
+    fs::File,
+    io::{BufReader, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    Error, MaterialIdent, ObjObject, ParseOptions, ParseWarning, VertexTextureData,
+    reader::LineReader,
+};
+
+/// Represents a parsed .mtl file, containing all of its materials (newmtl).
+///
+/// ## Example
+/// ```rust
+/// use polypath::MaterialLibrary;
+///
+/// let data = "newmtl red\nKd 1.0 0.0 0.0\nd 0.5\n";
+/// let mtl = MaterialLibrary::parse(std::io::Cursor::new(data)).unwrap();
+///
+/// let red = mtl.material("red").unwrap();
+/// assert_eq!(red.diffuse, Some([1.0, 0.0, 0.0]));
+/// assert_eq!(red.dissolve, Some(0.5));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialLibrary {
+    materials: Vec<Material>,
+    ignored: Vec<ParseWarning>,
+    /// The path of the file, if read from one.
+    path: Option<PathBuf>,
+}
+
+/// All material libraries (mtllib) referenced by a .obj file, see [`ObjObject::read_from_file_with_materials`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MaterialLibraries {
+    libraries: Vec<MaterialLibrary>,
+    missing: Vec<PathBuf>,
+    /// Whether names that only differ in ASCII case match, if there is no exact match.
+    case_insensitive: bool,
+}
+
+/// A material use (usemtl) that could not be resolved, see [`ObjObject::vertices_with_materials`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnresolvedMaterial {
+    /// The first material library (mtllib) of the object, see [`MaterialIdent`].
+    pub mtllib: Option<String>,
+    /// The name of the material.
+    pub mtluse: String,
+    /// Whether the material library was not loaded, e.g. because it is missing.
+    pub missing_library: bool,
+}
+
+/// A single material (newmtl) of a .mtl file.
+///
+/// Every property is `None`, if it was not specified in the file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Material {
+    pub name: String,
+    /// Ambient color (Ka).
+    pub ambient: Option<[f32; 3]>,
+    /// Diffuse color (Kd).
+    pub diffuse: Option<[f32; 3]>,
+    /// Specular color (Ks).
+    pub specular: Option<[f32; 3]>,
+    /// Emissive color (Ke).
+    pub emissive: Option<[f32; 3]>,
+    /// Specular exponent (Ns).
+    pub shininess: Option<f32>,
+    /// Index of refraction (Ni).
+    pub optical_density: Option<f32>,
+    /// Opacity (d), where `1.0` is fully opaque.
+    ///
+    /// Some exporters write the transparency (Tr) instead, which is stored as `1.0 - Tr`.
+    /// If a material specifies both, the opacity (d) is used.
+    pub dissolve: Option<f32>,
+    /// Transparency (Tr), where `0.0` is fully opaque, as written in the file.
+    pub transparency: Option<f32>,
+    /// Transmission filter color (Tf).
+    pub transmission_filter: Option<[f32; 3]>,
+    /// Illumination model (illum).
+    pub illumination_model: Option<u32>,
+    /// Roughness (Pr) of the PBR extension.
+    pub roughness: Option<f32>,
+    /// Metallic (Pm) of the PBR extension.
+    pub metallic: Option<f32>,
+    /// Sheen (Ps) of the PBR extension.
+    pub sheen: Option<f32>,
+    /// Clearcoat thickness (Pc) of the PBR extension.
+    pub clearcoat_thickness: Option<f32>,
+    /// Clearcoat roughness (Pcr) of the PBR extension.
+    pub clearcoat_roughness: Option<f32>,
+    /// Anisotropy (aniso) of the PBR extension.
+    pub anisotropy: Option<f32>,
+    /// Anisotropy rotation (anisor) of the PBR extension.
+    pub anisotropy_rotation: Option<f32>,
+    /// Ambient texture (`map_Ka`).
+    pub ambient_map: Option<TextureMap>,
+    /// Diffuse texture (`map_Kd`).
+    pub diffuse_map: Option<TextureMap>,
+    /// Specular texture (`map_Ks`).
+    pub specular_map: Option<TextureMap>,
+    /// Specular exponent texture (`map_Ns`).
+    pub shininess_map: Option<TextureMap>,
+    /// Opacity texture (`map_d`).
+    pub dissolve_map: Option<TextureMap>,
+    /// Bump map (`map_bump`, `map_Bump` or `bump`).
+    pub bump_map: Option<TextureMap>,
+    /// Displacement map (`disp`).
+    pub displacement_map: Option<TextureMap>,
+    /// Decal texture (`decal`).
+    pub decal_map: Option<TextureMap>,
+    /// Reflection map (`refl`). A cube map consists of multiple `refl` statements, only the last one is kept.
+    pub reflection_map: Option<TextureMap>,
+    /// Emissive texture (`map_Ke`).
+    pub emissive_map: Option<TextureMap>,
+    /// Roughness texture (`map_Pr`) of the PBR extension.
+    pub roughness_map: Option<TextureMap>,
+    /// Metallic texture (`map_Pm`) of the PBR extension.
+    pub metallic_map: Option<TextureMap>,
+    /// Sheen texture (`map_Ps`) of the PBR extension.
+    pub sheen_map: Option<TextureMap>,
+    /// Normal map (`norm`) of the PBR extension.
+    pub normal_map: Option<TextureMap>,
+}
+
+/// A texture map statement (e.g. `map_Kd`) of a material.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextureMap {
+    /// Path of the texture, as written in the file. May contain spaces.
+    pub path: String,
+    pub options: TextureOptions,
+}
+
+/// The options of a texture map, every option is `None`, if it was not specified in the file.
+///
+/// For options taking up to three values (e.g. `-s`), missing values are filled in with the default of the option.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextureOptions {
+    /// Horizontal blending (`-blendu`).
+    pub blend_u: Option<bool>,
+    /// Vertical blending (`-blendv`).
+    pub blend_v: Option<bool>,
+    /// Multiplier of the bump map values (`-bm`).
+    pub bump_multiplier: Option<f32>,
+    /// Mip-map sharpness (`-boost`).
+    pub boost: Option<f32>,
+    /// Color correction (`-cc`).
+    pub color_correction: Option<bool>,
+    /// Whether texture coords are clamped to `0.0..=1.0` (`-clamp`).
+    pub clamp: Option<bool>,
+    /// The channel of a scalar texture (`-imfchan`): `r`, `g`, `b`, `m` (matte), `l` (luminance) or `z` (depth).
+    pub channel: Option<char>,
+    /// Base value and gain of the texture values (`-mm`), the gain defaults to `1.0`.
+    pub base_gain: Option<[f32; 2]>,
+    /// Offset of the texture origin (`-o`), missing values default to `0.0`.
+    pub offset: Option<[f32; 3]>,
+    /// Scale of the texture (`-s`), missing values default to `1.0`.
+    pub scale: Option<[f32; 3]>,
+    /// Turbulence (`-t`), missing values default to `0.0`.
+    pub turbulence: Option<[f32; 3]>,
+    /// Resolution of the texture (`-texres`).
+    pub resolution: Option<f32>,
+    /// Type of a reflection map (`-type`), e.g. `sphere` or `cube_top`.
+    pub kind: Option<String>,
+}
+
+impl MaterialLibrary {
+    /// Reads and parses a .mtl file.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the file could not be read, or the .mtl file is not as structured as expected.
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|file| Self::parse(BufReader::new(file)))
+            .map(|library| Self {
+                path: Some(path.to_path_buf()),
+                ..library
+            })
+            .map_err(|err| err.in_file(path))
+    }
+
+    /// Parses a .mtl file from some sort of input reader.
+    ///
+    /// Statements that are not supported (e.g. `sharpness` or `map_aat`) are skipped, see [`MaterialLibrary::ignored_lines`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .mtl file is not as structured as expected.
+    pub fn parse(reader: impl std::io::BufRead) -> Result<Self, Error> {
+        let mut reader = LineReader::new(reader, usize::MAX);
+        let mut materials = Vec::new();
+        let mut ignored = Vec::new();
+
+        while let Some((line_number, line)) = reader.next_line()? {
+            let supported =
+                Self::parse_line(line, &mut materials).map_err(|err| err.at_line(line_number))?;
+
+            if !supported {
+                ignored.push(ParseWarning::IgnoredLine {
+                    line: line_number,
+                    data: String::from(line.trim()),
+                });
+            }
+        }
+
+        Ok(Self {
+            materials,
+            ignored,
+            path: None,
+        })
+    }
+
+    /// Parses a single statement into the last material.
+    ///
+    /// Returns `false`, if the statement is not supported and was skipped.
+    fn parse_line(line: &str, materials: &mut Vec<Material>) -> Result<bool, Error> {
+        // comments may also follow a statement, like in a .obj file
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+
+        if line.is_empty() {
+            return Ok(true);
+        }
+
+        let (keyword, data) = line
+            .split_once(char::is_whitespace)
+            .map_or((line, ""), |(keyword, data)| (keyword, data.trim()));
+
+        if keyword == "newmtl" {
+            if data.is_empty() {
+                return Err(Error::EmptyMtl);
+            }
+
+            materials.push(Material {
+                name: String::from(data),
+                ..Default::default()
+            });
+
+            return Ok(true);
+        }
+
+        let supported = matches!(
+            keyword,
+            "Ka" | "Kd"
+                | "Ks"
+                | "Ke"
+                | "Ns"
+                | "Ni"
+                | "d"
+                | "Tr"
+                | "Tf"
+                | "illum"
+                | "Pr"
+                | "Pm"
+                | "Ps"
+                | "Pc"
+                | "Pcr"
+                | "aniso"
+                | "anisor"
+                | "map_Ka"
+                | "map_Kd"
+                | "map_Ks"
+                | "map_Ns"
+                | "map_d"
+                | "map_bump"
+                | "map_Bump"
+                | "bump"
+                | "disp"
+                | "decal"
+                | "refl"
+                | "map_Ke"
+                | "map_Pr"
+                | "map_Pm"
+                | "map_Ps"
+                | "norm"
+        );
+        if !supported {
+            return Ok(false);
+        }
+
+        let material = materials.last_mut().ok_or(Error::MissingNewMtl)?;
+
+        match keyword {
+            "Ka" => material.ambient = Some(parse_color(data)?),
+            "Kd" => material.diffuse = Some(parse_color(data)?),
+            "Ks" => material.specular = Some(parse_color(data)?),
+            "Ke" => material.emissive = Some(parse_color(data)?),
+            "Ns" => material.shininess = Some(parse_float(data)?),
+            "Ni" => material.optical_density = Some(parse_float(data)?),
+            "d" => material.dissolve = Some(parse_float(data)?),
+            "Tr" => {
+                let transparency = parse_float(data)?;
+
+                // the opacity (d) takes precedence
+                if material.dissolve.is_none() || material.transparency.is_some() {
+                    material.dissolve = Some(1.0 - transparency);
+                }
+                material.transparency = Some(transparency);
+            }
+            "Tf" => material.transmission_filter = Some(parse_color(data)?),
+            "illum" => material.illumination_model = Some(data.parse::<u32>()?),
+            "Pr" => material.roughness = Some(parse_float(data)?),
+            "Pm" => material.metallic = Some(parse_float(data)?),
+            "Ps" => material.sheen = Some(parse_float(data)?),
+            "Pc" => material.clearcoat_thickness = Some(parse_float(data)?),
+            "Pcr" => material.clearcoat_roughness = Some(parse_float(data)?),
+            "aniso" => material.anisotropy = Some(parse_float(data)?),
+            "anisor" => material.anisotropy_rotation = Some(parse_float(data)?),
+            "map_Ka" => material.ambient_map = Some(parse_map(data)?),
+            "map_Kd" => material.diffuse_map = Some(parse_map(data)?),
+            "map_Ks" => material.specular_map = Some(parse_map(data)?),
+            "map_Ns" => material.shininess_map = Some(parse_map(data)?),
+            "map_d" => material.dissolve_map = Some(parse_map(data)?),
+            "map_bump" | "map_Bump" | "bump" => material.bump_map = Some(parse_map(data)?),
+            "disp" => material.displacement_map = Some(parse_map(data)?),
+            "decal" => material.decal_map = Some(parse_map(data)?),
+            "refl" => material.reflection_map = Some(parse_map(data)?),
+            "map_Ke" => material.emissive_map = Some(parse_map(data)?),
+            "map_Pr" => material.roughness_map = Some(parse_map(data)?),
+            "map_Pm" => material.metallic_map = Some(parse_map(data)?),
+            "map_Ps" => material.sheen_map = Some(parse_map(data)?),
+            "norm" => material.normal_map = Some(parse_map(data)?),
+            _ => unreachable!(),
+        }
+
+        Ok(true)
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns all materials, in the order of the .mtl file.
+    pub fn materials(&self) -> &[Material] {
+        &self.materials
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the first material with the given name.
+    pub fn material(&self, name: &str) -> Option<&Material> {
+        self.materials.iter().find(|m| m.name == name)
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the statements that are not supported and were skipped, as [`ParseWarning::IgnoredLine`]s.
+    pub fn ignored_lines(&self) -> &[ParseWarning] {
+        &self.ignored
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the path of the .mtl file, `None` if it was not read from a file.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns the absolute path of a texture map, resolved relative to the directory of the .mtl file.
+    ///
+    /// For a library not read from a file, the path is resolved relative to the current directory.
+    /// The file system is not accessed, so the texture is not required to exist.
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error], if the current directory is required, but could not be determined.
+    pub fn texture_path(&self, map: &TextureMap) -> Result<PathBuf, Error> {
+        let dir = self
+            .path
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or_else(|| Path::new(""));
+
+        Ok(std::path::absolute(dir.join(&map.path))?)
+    }
+}
+
+impl MaterialLibraries {
+    #[inline]
+    #[must_use]
+    /// Returns all loaded libraries, in the order they are first referenced.
+    pub fn libraries(&self) -> &[MaterialLibrary] {
+        &self.libraries
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the paths of the referenced libraries that do not exist.
+    pub fn missing(&self) -> &[PathBuf] {
+        &self.missing
+    }
+
+    #[must_use]
+    /// Returns the first material with the given name, together with the library containing it.
+    pub fn material(&self, name: &str) -> Option<(&MaterialLibrary, &Material)> {
+        self.libraries
+            .iter()
+            .find_map(|library| library.material(name).map(|material| (library, material)))
+    }
+
+    #[inline]
+    #[must_use]
+    /// Sets whether material names that only differ in ASCII case match, if there is no exact match
+    /// (e.g. `usemtl Red` for `newmtl red`), see [`MaterialLibraries::resolve`].
+    pub const fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    #[must_use]
+    /// Returns the material a [`MaterialIdent`] refers to, `None` if it has no material use (usemtl) or the material was not found.
+    ///
+    /// The material is looked up in the library of the ident first, then in all other libraries, in order.
+    /// Names have to match exactly, unless [`MaterialLibraries::case_insensitive`] is set.
+    pub fn resolve(&self, ident: &MaterialIdent) -> Option<&Material> {
+        self.try_resolve(ident).ok().flatten()
+    }
+
+    /// Same as [`MaterialLibraries::resolve`], but describing why a material use could not be resolved.
+    fn try_resolve(&self, ident: &MaterialIdent) -> Result<Option<&Material>, UnresolvedMaterial> {
+        let Some(name) = ident.mtluse else {
+            return Ok(None);
+        };
+
+        // the path of a library ends with the name it was referenced by
+        let named = ident.mtllib.and_then(|mtllib| {
+            self.libraries
+                .iter()
+                .find(|library| library.path().is_some_and(|path| path.ends_with(mtllib)))
+        });
+        let find = |matches: &dyn Fn(&str) -> bool| {
+            named
+                .into_iter()
+                .chain(&self.libraries)
+                .find_map(|library| library.materials.iter().find(|m| matches(&m.name)))
+        };
+
+        let material = find(&|other| other == name).or_else(|| {
+            self.case_insensitive
+                .then(|| find(&|other| other.eq_ignore_ascii_case(name)))
+                .flatten()
+        });
+
+        material.map(Some).ok_or_else(|| UnresolvedMaterial {
+            mtllib: ident.mtllib.map(String::from),
+            mtluse: String::from(name),
+            missing_library: ident.mtllib.is_some() && named.is_none(),
+        })
+    }
+}
+
+impl std::fmt::Display for UnresolvedMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.mtllib, self.missing_library) {
+            (Some(mtllib), true) => write!(
+                f,
+                "material [{}] not found, library [{mtllib}] is missing",
+                self.mtluse
+            ),
+            (Some(mtllib), false) => write!(
+                f,
+                "material [{}] not found in library [{mtllib}]",
+                self.mtluse
+            ),
+            (None, _) => write!(f, "material [{}] not found in any library", self.mtluse),
+        }
+    }
+}
+
+impl ObjObject {
+    /// Reads a .obj file together with all material libraries (mtllib) it references,
+    /// resolving their paths relative to the directory of the .obj file.
+    ///
+    /// Many files reference libraries that were never shipped with them, so missing libraries are skipped
+    /// and listed in [`MaterialLibraries::missing`], unless [`ParseOptions::require_mtllibs`] is set.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use polypath::{ObjObject, ParseOptions};
+    ///
+    /// let (obj, materials) =
+    ///     ObjObject::read_from_file_with_materials("./meshes/scene.obj", &ParseOptions::default()).unwrap();
+    ///
+    /// for ident in obj.material_table() {
+    ///     let Some((library, material)) = ident.mtluse.as_deref().and_then(|name| materials.material(name)) else {
+    ///         continue;
+    ///     };
+    ///     if let Some(map) = &material.diffuse_map {
+    ///         println!("{}: {}", material.name, library.texture_path(map).unwrap().display());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading or parsing the .obj file or any existing .mtl file fails.
+    /// Errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn read_from_file_with_materials<P: AsRef<Path>>(
+        path: P,
+        options: &ParseOptions,
+    ) -> Result<(Self, MaterialLibraries), Error> {
+        let path = path.as_ref();
+        let obj = Self::read_from_file_with(path, options)?;
+
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut libraries = MaterialLibraries::default();
+
+        for path in obj.mtllib_paths(base_dir) {
+            match MaterialLibrary::read_from_file(&path) {
+                Ok(library) => libraries.libraries.push(library),
+                Err(Error::File { source, .. })
+                    if !options.require_mtllibs
+                        && matches!(&*source, Error::Io(err) if err.kind() == ErrorKind::NotFound) =>
+                {
+                    libraries.missing.push(path);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((obj, libraries))
+    }
+
+    /// Same as [`ObjObject::vertices`], but with the materials resolved from the given libraries,
+    /// see [`MaterialLibraries::resolve`].
+    ///
+    /// A material is `None`, if faces do not have a material use (usemtl).
+    ///
+    /// # Errors
+    /// Returns [`Error::UnresolvedMaterials`], listing every material use that could not be resolved.
+    pub fn vertices_with_materials<'m>(
+        &self,
+        materials: &'m MaterialLibraries,
+    ) -> Result<(Vec<VertexTextureData>, Vec<Option<&'m Material>>), Error> {
+        let (vertices, idents) = self.vertices();
+
+        let mut resolved = Vec::with_capacity(idents.len());
+        let mut unresolved = Vec::new();
+        for ident in &idents {
+            match materials.try_resolve(ident) {
+                Ok(material) => resolved.push(material),
+                Err(material) => unresolved.push(material),
+            }
+        }
+
+        if !unresolved.is_empty() {
+            return Err(Error::UnresolvedMaterials(unresolved));
+        }
+
+        Ok((vertices, resolved))
+    }
+
+    /// Reads all material libraries (mtllib) referenced by any object, resolving their paths relative to `base_dir`.
+    ///
+    /// Every file is only loaded once, in the order they are first referenced.
+    ///
+    /// # Errors
+    /// Returns an `Error` if any referenced .mtl file could not be read or parsed.
+    pub fn load_materials(&self, base_dir: &Path) -> Result<Vec<MaterialLibrary>, Error> {
+        self.mtllib_paths(base_dir)
+            .iter()
+            .map(MaterialLibrary::read_from_file)
+            .collect()
+    }
+
+    /// Returns the paths of all material libraries (mtllib) referenced by any object, without duplicates.
+    fn mtllib_paths(&self, base_dir: &Path) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Vec::new();
+
+        for file in self.objects.iter().flat_map(|o| &o.mtllibs) {
+            let path = base_dir.join(file);
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+
+        paths
+    }
+}
+
+#[inline]
+fn parse_float(data: &str) -> Result<f32, Error> {
+    let str = data.split_whitespace().next().ok_or(Error::UnexpectedEoL)?;
+    Ok(str.parse::<f32>()?)
+}
+
+/// Parses a color, where green and blue default to red if omitted.
+fn parse_color(data: &str) -> Result<[f32; 3], Error> {
+    let mut split = data.split_whitespace();
+
+    let str = split.next().ok_or(Error::UnexpectedEoL)?;
+    let red = str.parse::<f32>()?;
+
+    let Some(str) = split.next() else {
+        return Ok([red; 3]);
+    };
+    let green = str.parse::<f32>()?;
+
+    let str = split.next().ok_or(Error::UnexpectedEoL)?;
+    let blue = str.parse::<f32>()?;
+
+    Ok([red, green, blue])
+}
+
+/// Parses a texture map, its options (e.g. `-s 1 1 1`) followed by its path.
+///
+/// Everything after the options is the path, so it may contain spaces.
+fn parse_map(mut data: &str) -> Result<TextureMap, Error> {
+    let mut options = TextureOptions::default();
+
+    while let Some(rest) = data.strip_prefix('-') {
+        let (option, rest) = next_token(rest);
+        data = rest;
+
+        match option {
+            "blendu" => options.blend_u = Some(parse_switch(&mut data)?),
+            "blendv" => options.blend_v = Some(parse_switch(&mut data)?),
+            "cc" => options.color_correction = Some(parse_switch(&mut data)?),
+            "clamp" => options.clamp = Some(parse_switch(&mut data)?),
+            "bm" => options.bump_multiplier = Some(parse_floats::<1>(&mut data, 0.0)?[0]),
+            "boost" => options.boost = Some(parse_floats::<1>(&mut data, 0.0)?[0]),
+            "texres" => options.resolution = Some(parse_floats::<1>(&mut data, 0.0)?[0]),
+            "mm" => options.base_gain = Some(parse_floats(&mut data, 1.0)?),
+            "o" => options.offset = Some(parse_floats(&mut data, 0.0)?),
+            "s" => options.scale = Some(parse_floats(&mut data, 1.0)?),
+            "t" => options.turbulence = Some(parse_floats(&mut data, 0.0)?),
+            "imfchan" => {
+                let (channel, rest) = next_token(data);
+                data = rest;
+
+                options.channel = match channel {
+                    "r" | "g" | "b" | "m" | "l" | "z" => channel.chars().next(),
+                    "" => return Err(Error::UnexpectedEoL),
+                    _ => return Err(Error::InvalidTextureOption(format!("-imfchan {channel}"))),
+                };
+            }
+            "type" => {
+                let (kind, rest) = next_token(data);
+                data = rest;
+
+                if kind.is_empty() {
+                    return Err(Error::UnexpectedEoL);
+                }
+                options.kind = Some(String::from(kind));
+            }
+            _ => return Err(Error::InvalidTextureOption(format!("-{option}"))),
+        }
+    }
+
+    if data.is_empty() {
+        return Err(Error::UnexpectedEoL);
+    }
+
+    Ok(TextureMap {
+        path: String::from(data),
+        options,
+    })
+}
+
+/// Splits off the first whitespace separated token, returning it and the (trimmed) rest.
+fn next_token(data: &str) -> (&str, &str) {
+    data.split_once(char::is_whitespace)
+        .map_or((data, ""), |(token, rest)| (token, rest.trim_start()))
+}
+
+/// Parses the `on` or `off` argument of a texture option.
+fn parse_switch(data: &mut &str) -> Result<bool, Error> {
+    let (value, rest) = next_token(data);
+    *data = rest;
+
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        "" => Err(Error::UnexpectedEoL),
+        _ => Err(Error::InvalidTextureOption(String::from(value))),
+    }
+}
+
+/// Parses one up to `N` float arguments of a texture option, filling in missing values with `default`.
+///
+/// Arguments end at the first token that is not a float, which is either the next option or the path.
+fn parse_floats<const N: usize>(data: &mut &str, default: f32) -> Result<[f32; N], Error> {
+    let mut values = [default; N];
+
+    for (i, value) in values.iter_mut().enumerate() {
+        let (token, rest) = next_token(data);
+
+        match token.parse::<f32>() {
+            Ok(parsed) => *value = parsed,
+            // the first value is required
+            Err(err) if i == 0 => {
+                return Err(if token.is_empty() {
+                    Error::UnexpectedEoL
+                } else {
+                    Error::ParseF(err)
+                });
+            }
+            Err(_) => break,
+        }
+
+        *data = rest;
+    }
+
+    Ok(values)
+}
@@ -1,11 +1,21 @@
-use std::{fs::File, io::BufReader, path::Path};
+use std::{
+    fs::File,
+    io::BufReader,
+    ops::{ControlFlow, Range},
+    path::Path,
+};
+
+use rustc_hash::FxHashMap;
 
 use crate::{
-    Error,
-    parse::{FaceData, GroupingData},
+    Error, ParseOptions, ParseProgress, Vec2,
+    bounding::{Aabb, build_aabb},
+    parse::{
+        Corner, DisplayAttributes, FaceData, GroupingData, PolygonData, Progress, index_count,
+    },
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// A representation of a .obj file.
 ///
 /// This library interprets the .obj format with the following hierarchy:
@@ -16,10 +26,14 @@ use crate::{
 /// Every object (o) can contain multiple groups (g).
 /// If there are no explicit groups contained in the object, a unnamed group for the whole object (o) is assumed.
 ///
+/// After a successful parse there always is at least one object, and every object contains at least one (possibly empty) group,
+/// even if the file contains no faces at all.
+///
 /// Every group (g) can contain multiple faces (f).
 ///
 /// And each face (f) contains 3 vertices. If more then 3 vertices are specified for any face (f), they are automatically triangularized.
-/// A maximum of 4 vertices in a face can be triangularized.
+/// Faces with more than 4 vertices are fan triangulated around their first vertex.
+/// The original polygons can be kept as well, see [`ParseOptions::keep_polygons`][crate::ParseOptions::keep_polygons].
 ///
 /// # Example
 /// ```rust
@@ -28,7 +42,7 @@ use crate::{
 /// let obj = ObjObject::read_from_file(mesh).unwrap();
 ///
 /// // read .obj file
-/// // automatic triangulates faces (from max 4 vertices per face)
+/// // automatic triangulates faces
 /// for o in obj.objects_iter() {
 ///     println!("Object name: {}", o.name());
 ///     println!("Object material: {:?}", o.mtllib());
@@ -51,29 +65,144 @@ use crate::{
 ///     }
 /// }
 /// ```
+///
+/// With the `serde` feature, an `ObjObject` is (de)serialized through a separate, validated format,
+/// so a deserialized `ObjObject` never contains an out of range index.
 pub struct ObjObject {
-    pub(crate) vertices: Vec<(f32, f32, f32)>,
-    pub(crate) vertex_colors: Vec<(f32, f32, f32)>,
-    pub(crate) vertex_normals: Vec<(f32, f32, f32)>,
-    pub(crate) texture_coords: Vec<(f32, f32)>,
+    pub(crate) vertices: Vec<[f32; 3]>,
+    /// The w component of each position, only filled if requested via [`ParseOptions::keep_vertex_w`][crate::ParseOptions::keep_vertex_w].
+    pub(crate) vertices_w: Vec<f32>,
+    /// Each position with double precision, only filled if requested via [`ParseOptions::keep_f64_positions`][crate::ParseOptions::keep_f64_positions].
+    pub(crate) vertices_f64: Vec<[f64; 3]>,
+    pub(crate) vertex_colors: Vec<[f32; 3]>,
+    /// The alpha component of each vertex color, only filled if the colors are RGBA.
+    pub(crate) vertex_alphas: Vec<f32>,
+    pub(crate) vertex_normals: Vec<[f32; 3]>,
+    pub(crate) texture_coords: Vec<[f32; 2]>,
+    /// The w component of each texture coord, only filled if requested via [`ParseOptions::keep_texture_w`][crate::ParseOptions::keep_texture_w].
+    pub(crate) texture_coords_w: Vec<f32>,
+    /// The tangent of each face corner (3 per face), only filled by [`generate_tangents`][crate::geometry::generate_tangents].
+    pub(crate) vertex_tangents: Vec<[f32; 4]>,
 
     pub(crate) faces: Vec<FaceData>,
+    pub(crate) line_elements: Vec<Vec<u32>>,
+    pub(crate) line_texture_indices: Vec<Option<Vec<u32>>>,
+    pub(crate) point_elements: Vec<Vec<u32>>,
+    pub(crate) polygons: Vec<PolygonData>,
+    pub(crate) polygon_corners: Vec<PolygonCorner>,
 
     pub(crate) groups: Vec<GroupingData>,
     pub(crate) objects: Vec<GroupingData>,
+
+    /// Allocations kept around for [`ObjObject::reparse`].
+    pub(crate) line_buffer: Vec<u8>,
+    pub(crate) string_pool: Vec<String>,
 }
 
 impl ObjObject {
     /// Reads a .obj file and returns a `ObjObject`.
     ///
-    /// # Error
+    /// # Errors
     /// - Returns an [Error][std::io::Error] if reading from file fails
     /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
     pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let file = File::open(path)?;
-        let buffer = BufReader::new(file);
+        Self::read_from_file_with(path, &ParseOptions::default())
+    }
 
-        Self::parse(buffer)
+    /// Same as [`ObjObject::read_from_file`], but using the given [`ParseOptions`].
+    ///
+    /// Files larger than [`ParseLimits::max_file_size`][crate::ParseLimits::max_file_size] are rejected before reading them.
+    ///
+    /// With the `flate2` feature, gzip compressed files (starting with the gzip magic bytes, or ending in `.gz`)
+    /// are decompressed while parsing, see [`ObjObject::parse_gzip_with`].
+    /// The size limit applies to the compressed file.
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if reading from file fails
+    /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn read_from_file_with<P: AsRef<Path>>(
+        path: P,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        Self::read_from_file_with_optional_progress(path.as_ref(), options, None)
+    }
+
+    /// Same as [`ObjObject::read_from_file_with`], but reporting the progress to a callback,
+    /// see [`ObjObject::parse_with_progress`].
+    ///
+    /// The reported [`ParseProgress::total_bytes`] is the size of the file.
+    /// For gzip compressed files, the decompressed size is not known in advance, so it is `None`.
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if reading from file fails
+    /// - Returns [`Error::Cancelled`] if the callback cancelled parsing
+    /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
+    pub fn read_from_file_with_progress<P: AsRef<Path>>(
+        path: P,
+        options: &ParseOptions,
+        mut progress: impl FnMut(ParseProgress) -> ControlFlow<()>,
+    ) -> Result<Self, Error> {
+        Self::read_from_file_with_optional_progress(path.as_ref(), options, Some(&mut progress))
+    }
+
+    fn read_from_file_with_optional_progress(
+        path: &Path,
+        options: &ParseOptions,
+        progress: Option<&mut dyn FnMut(ParseProgress) -> ControlFlow<()>>,
+    ) -> Result<Self, Error> {
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|file| {
+                let size = file.metadata()?.len();
+                let limit = options.limits.max_file_size;
+                if size > limit {
+                    return Err(Error::FileTooLarge { size, limit });
+                }
+
+                #[cfg_attr(not(feature = "flate2"), allow(unused_mut))]
+                let mut reader = BufReader::new(file);
+
+                #[cfg(feature = "flate2")]
+                if is_gzip(path, &mut reader)? {
+                    let reader = BufReader::new(flate2::read::GzDecoder::new(reader));
+                    let progress = progress
+                        .map(|callback| Progress::new(callback, None, options.progress_interval));
+                    return Self::parse_with_optional_progress(reader, options, progress);
+                }
+
+                let progress = progress
+                    .map(|callback| Progress::new(callback, Some(size), options.progress_interval));
+                Self::parse_with_optional_progress(reader, options, progress)
+            })
+            .map_err(|err| err.in_file(path))
+    }
+
+    /// Same as [`ObjObject::read_from_file`], but also keeps the vertex positions with double precision,
+    /// see [`ObjObject::positions_f64`] and [`ObjObject::to_f32_with_origin_rebase`].
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if reading from file fails
+    /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
+    pub fn read_from_file_f64<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::read_from_file_with(path, &ParseOptions::default().keep_f64_positions(true))
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the axis aligned bounding box around all vertex positions (v), whether they are used by any element or not.
+    pub fn aabb(&self) -> Aabb {
+        build_aabb(self.vertices.iter().copied())
     }
 
     #[inline]
@@ -99,6 +228,10 @@ impl ObjObject {
 
     #[inline]
     #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "counts 3 vertices per face, use `unique_vertex_count` or `referenced_vertex_count` instead"
+    )]
     /// Returns the raw number of individual verticies contained in the .obj file.
     ///
     /// This function is not 100% prezise, as it just calculates 3 verticices for each face.
@@ -107,52 +240,318 @@ impl ObjObject {
         self.faces.len() * 3
     }
 
+    #[inline]
+    #[must_use]
+    /// Returns the number of vertex positions (v) contained in the .obj file, each counted once, no matter how many faces share it.
+    pub const fn unique_vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    #[must_use]
+    /// Returns the number of vertex positions (v), that are referenced by at least one face.
+    ///
+    /// Some .obj files declare vertices that are never used, or only used by line (l) and point (p) elements.
+    pub fn referenced_vertex_count(&self) -> usize {
+        let mut referenced = vec![false; self.vertices.len()];
+
+        for face in &self.faces {
+            for i in <[u32; 3]>::from(face.indicies) {
+                referenced[i as usize - 1] = true;
+            }
+        }
+
+        referenced.into_iter().filter(|r| *r).count()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the vertex indices of each line (l) element contained in the .obj file.
+    ///
+    /// Indices are 1-based, like in the .obj file, with negative (relative) indices already resolved.
+    /// Use [`GroupRef::lines_iter`] to get the line segments of a group, including texture coords.
+    pub fn line_elements(&self) -> &[Vec<u32>] {
+        &self.line_elements
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the vertex indices of each point (p) element contained in the .obj file.
+    ///
+    /// Indices are 1-based, like in the .obj file, with negative (relative) indices already resolved.
+    /// Use [`GroupRef::points_iter`] to get the points of a group.
+    pub fn point_elements(&self) -> &[Vec<u32>] {
+        &self.point_elements
+    }
+
+    #[must_use]
+    /// Returns all vertex positions (v) with their x, y, z and w components, in the order of the .obj file.
+    ///
+    /// Returns `None` if the w components were not kept while parsing, see [`ParseOptions::keep_vertex_w`][crate::ParseOptions::keep_vertex_w].
+    /// A w component missing in the file is `1.0`.
+    pub fn positions_4d(&self) -> Option<Vec<[f32; 4]>> {
+        if self.vertices_w.len() != self.vertices.len() {
+            return None;
+        }
+
+        Some(
+            self.vertices
+                .iter()
+                .zip(&self.vertices_w)
+                .map(|([x, y, z], w)| [*x, *y, *z, *w])
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    /// Returns all vertex positions (v) with double precision, as written in the .obj file.
+    ///
+    /// Returns `None` if they were not kept while parsing, see [`ParseOptions::keep_f64_positions`][crate::ParseOptions::keep_f64_positions],
+    /// or if positions were added since (e.g. by [`ObjObject::apply_object_transforms`]).
+    /// [`translate`][crate::geometry::translate] and [`scale_uniform`][crate::geometry::scale_uniform] move these as well,
+    /// other transformations only apply to the single precision positions.
+    pub fn positions_f64(&self) -> Option<&[[f64; 3]]> {
+        (self.vertices_f64.len() == self.vertices.len()).then_some(self.vertices_f64.as_slice())
+    }
+
+    #[must_use]
+    /// Returns the minimum and maximum of all vertex positions (v) with double precision, see [`ObjObject::positions_f64`].
+    ///
+    /// Returns `None` if there are no double precision positions.
+    pub fn aabb_f64(&self) -> Option<([f64; 3], [f64; 3])> {
+        let positions = self
+            .positions_f64()
+            .filter(|positions| !positions.is_empty())?;
+
+        let mut min = [f64::MAX; 3];
+        let mut max = [f64::MIN; 3];
+        for position in positions {
+            for i in 0..3 {
+                min[i] = min[i].min(position[i]);
+                max[i] = max[i].max(position[i]);
+            }
+        }
+
+        Some((min, max))
+    }
+
+    /// Replaces the single precision positions by the double precision positions relative to `origin`.
+    ///
+    /// Large coordinates (e.g. geospatial or CAD data) lose detail as `f32`. Rebased around an origin close to the mesh
+    /// (e.g. the center of [`ObjObject::aabb_f64`]), the remaining offsets fit into `f32`, so faces, bounding volumes
+    /// and everything else working on `f32` positions keep that detail.
+    ///
+    /// Returns `false` without changing anything, if there are no double precision positions, see [`ObjObject::positions_f64`].
+    pub fn to_f32_with_origin_rebase(&mut self, origin: [f64; 3]) -> bool {
+        if self.vertices_f64.len() != self.vertices.len() {
+            return false;
+        }
+
+        for (position, position_f64) in self.vertices.iter_mut().zip(&self.vertices_f64) {
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                *position = [0, 1, 2].map(|i| (position_f64[i] - origin[i]) as f32);
+            }
+        }
+
+        true
+    }
+
+    #[inline]
+    /// Returns all texture coords (vt), in the order of the .obj file.
+    pub fn texture_coord_iter(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.texture_coords.iter().copied().map(Vec2::from)
+    }
+
+    #[must_use]
+    /// Returns all texture coords (vt) with their u, v and w components, in the order of the .obj file.
+    ///
+    /// Returns `None` if the w components were not kept while parsing, see [`ParseOptions::keep_texture_w`][crate::ParseOptions::keep_texture_w].
+    /// Components missing in the file are `0.0`.
+    pub fn texture_coords_3d(&self) -> Option<Vec<[f32; 3]>> {
+        if self.texture_coords_w.len() != self.texture_coords.len() {
+            return None;
+        }
+
+        Some(
+            self.texture_coords
+                .iter()
+                .zip(&self.texture_coords_w)
+                .map(|([u, v], w)| [*u, *v, *w])
+                .collect(),
+        )
+    }
+
+    /// Fills in missing normal and texture coordinate indices of faces from their position indices.
+    ///
+    /// Some exporters write `f 1 2 3` even though there is exactly one normal (vn) and one texture coord (vt) per vertex, in matching order.
+    /// Normal indices are only inferred if the number of normals equals the number of vertex positions,
+    /// and texture coordinate indices only if the number of texture coords equals the number of vertex positions.
+    /// Faces that already reference normals or texture coords are left untouched.
+    ///
+    /// Returns `true` if any indices were inferred.
+    pub fn infer_attribute_indices(&mut self) -> bool {
+        let infer_normals = self.vertex_normals.len() == self.vertices.len();
+        let infer_texture = self.texture_coords.len() == self.vertices.len();
+
+        if self.vertices.is_empty() || !(infer_normals || infer_texture) {
+            return false;
+        }
+
+        let mut inferred = false;
+
+        for face in &mut self.faces {
+            if infer_normals && face.normal_indicies.is_none() {
+                face.normal_indicies = Some(face.indicies);
+                inferred = true;
+            }
+
+            if infer_texture && face.texture_indcicies.is_none() {
+                face.texture_indcicies = Some(face.indicies);
+                inferred = true;
+            }
+        }
+
+        for corner in &mut self.polygon_corners {
+            if infer_normals && corner.normal.is_none() {
+                corner.normal = Some(corner.position);
+            }
+
+            if infer_texture && corner.texture_coord.is_none() {
+                corner.texture_coord = Some(corner.position);
+            }
+        }
+
+        inferred
+    }
+
     /// Returns an [Iterator][std::iter::Iterator] over each object.
-    pub fn objects_iter(&self) -> impl Iterator<Item = ObjectRef> {
+    pub fn objects_iter(&self) -> impl Iterator<Item = ObjectRef<'_>> {
         self.objects.iter().map(|obj| ObjectRef {
             vertices: &self.vertices,
             vertex_colors: vec_to_option(&self.vertex_colors),
+            vertex_alphas: vec_to_option(&self.vertex_alphas),
             vertex_normals: &self.vertex_normals,
             texture_coords: &self.texture_coords,
+            vertex_tangents: vec_to_option(&self.vertex_tangents),
 
             faces: &self.faces,
+            polygons: &self.polygons,
+            polygon_corners: &self.polygon_corners,
+            line_elements: &self.line_elements,
+            line_texture_indices: &self.line_texture_indices,
+            point_elements: &self.point_elements,
 
             name: &obj.name,
-            mtllib: obj.mtl.as_ref(),
+            mtllibs: &obj.mtllibs,
+            maplibs: &obj.maplibs,
+            display: &obj.display,
+            transform: obj.transform,
 
             groups: &self.groups[obj.start..obj.finish],
         })
     }
 
+    /// Returns an [Iterator][std::iter::Iterator] over the faces of all groups of all objects, in file order.
+    pub fn faces_iter(&self) -> impl Iterator<Item = Face> {
+        self.objects_iter()
+            .flat_map(ObjectRef::group_iter)
+            .flat_map(GroupRef::faces_iter)
+    }
+
+    /// Same as [`ObjObject::faces_iter`], but also returns the object, group and material of each face.
+    pub fn faces_with_context_iter(&self) -> impl Iterator<Item = (FaceContext<'_>, Face)> {
+        self.objects_iter()
+            .flat_map(|object| object.group_iter().map(move |group| (object, group)))
+            .flat_map(|(object, group)| {
+                group
+                    .material_ranges()
+                    .flat_map(|(range, mtluse)| std::iter::repeat_n(mtluse, range.len()))
+                    .zip(group.faces_iter())
+                    .map(move |(mtluse, face)| {
+                        let context = FaceContext {
+                            object: object.name,
+                            group: group.name,
+                            mtllib: object.mtllibs.first().map(String::as_str),
+                            mtluse,
+                        };
+
+                        (context, face)
+                    })
+            })
+    }
+
+    /// Returns the first object with the given name.
+    #[must_use]
+    pub fn find_object_by_name(&self, name: &str) -> Option<ObjectRef<'_>> {
+        self.objects_iter().find(|object| object.name() == name)
+    }
+
+    /// Returns the first group of any object, that has the given name among its names, see [`GroupRef::names`].
+    #[must_use]
+    pub fn find_group_by_name(&self, name: &str) -> Option<GroupRef<'_>> {
+        self.objects_iter()
+            .find_map(|object| object.groups_named(name).next())
+    }
+
+    /// Returns the name of each object, in file order.
+    ///
+    /// Names are not unique, an object name appearing multiple times in a file is returned for each of them.
+    pub fn object_names(&self) -> impl Iterator<Item = &str> {
+        self.objects.iter().map(|object| object.name.as_str())
+    }
+
+    /// Returns the (first) name of each group across all objects, in file order, see [`GroupRef::name`].
+    ///
+    /// Names are not unique, e.g. a group continued later in an object is returned again,
+    /// unless the groups were merged by [`ObjObject::merge_named_groups`].
+    pub fn group_names(&self) -> impl Iterator<Item = &str> {
+        self.groups.iter().map(|group| group.name.as_str())
+    }
+
+    #[must_use]
+    /// Returns every distinct material (combination of mtllib and mtluse) used by any group.
+    ///
+    /// The table has a stable ordering, that does not depend on the order of objects or groups in the file:
+    /// entries are sorted by `mtllib`, then by `mtluse`, where `None` sorts after any name.
+    ///
+    /// The `material_index` returned by [`ObjObject::vertices`], [`ObjObject::vertices_indexed`] and [`ObjObject::face_materials`]
+    /// indexes into this table.
+    pub fn material_table(&self) -> Vec<MaterialIdentOwned> {
+        self.material_idents()
+            .into_iter()
+            .map(MaterialIdentOwned::from)
+            .collect()
+    }
+
+    #[must_use]
     /// Returns:
     ///     - a [Vec][std::vec::Vec] containing 3 vertices for each face. Vertices that are shared are duplicated. Every 3 vertices build a face.
     ///     - a [Vec][std::vec::Vec] containing [`MaterialIdent`]. Each returned vertex contains a `material_index` that can be used to index into this list, to retrive the [`MaterialIdent`].
     /// This ignores any grouping done via objects (o) or groups (g).
     /// If keeping these groupings is important, consider iterating manually over each object/group/face.
-    pub fn vertices(&self) -> (Vec<VertexTextureData>, Vec<MaterialIdent>) {
-        let mut vertices = Vec::with_capacity(self.vert_count());
-        let mut materials = Vec::<MaterialIdent>::new();
+    ///
+    /// The returned materials are ordered like [`ObjObject::material_table`].
+    /// Previously materials were ordered by first use, so persisted material indices may need to be remapped.
+    pub fn vertices(&self) -> (Vec<VertexTextureData>, Vec<MaterialIdent<'_>>) {
+        let mut vertices = Vec::with_capacity(self.faces.len() * 3);
+        let materials = self.material_idents();
 
         for obj in self.objects_iter() {
-            let mtllib = obj.mtllib.map(String::as_str);
-
             for group in obj.group_iter() {
-                let mtluse = group.mtluse.map(String::as_str);
-
-                let t = MaterialIdent { mtllib, mtluse };
-                let texture_index = materials.iter().position(|m| *m == t).unwrap_or_else(|| {
-                    materials.push(t);
-                    materials.len() - 1
-                });
-
-                for f in group.faces_iter() {
-                    for v in f.vertices() {
-                        let vert = VertexTextureData {
-                            material_index: texture_index,
-                            vertex: v,
-                        };
+                let mut faces = group.faces_iter();
 
-                        vertices.push(vert);
+                for (range, mtluse) in group.material_ranges() {
+                    let material_index = material_index(&materials, obj.material(mtluse));
+
+                    for f in faces.by_ref().take(range.len()) {
+                        for v in f.vertices() {
+                            let vert = VertexTextureData {
+                                material_index,
+                                vertex: v,
+                            };
+
+                            vertices.push(vert);
+                        }
                     }
                 }
             }
@@ -160,30 +559,180 @@ impl ObjObject {
 
         (vertices, materials)
     }
+
+    #[must_use]
+    /// Returns:
+    ///     - a [Vec][std::vec::Vec] containing indicies into the vertex buffer. Every 3 indicies build a face.
+    ///     - a [Vec][std::vec::Vec] containing each unique vertex.
+    ///     - a [Vec][std::vec::Vec] containing [`MaterialIdent`], ordered like [`ObjObject::material_table`].
+    ///
+    /// Same as [`ObjObject::vertices`], but deduplicates the vertices via [`opt::indexed_vertices`][crate::opt::indexed_vertices].
+    pub fn vertices_indexed(&self) -> (Vec<usize>, Vec<VertexTextureData>, Vec<MaterialIdent<'_>>) {
+        let (vertices, materials) = self.vertices();
+        let (indicies, vertices) = crate::opt::indexed_vertices(&vertices);
+
+        (indicies, vertices, materials)
+    }
+
+    #[must_use]
+    /// Returns the material index of each face, indexing into [`ObjObject::material_table`].
+    ///
+    /// The faces are in the same order as returned by iterating over all objects and groups.
+    pub fn face_materials(&self) -> Vec<usize> {
+        let materials = self.material_idents();
+        let mut out = Vec::with_capacity(self.face_count());
+
+        for obj in self.objects_iter() {
+            for group in obj.group_iter() {
+                for (range, mtluse) in group.material_ranges() {
+                    let material_index = material_index(&materials, obj.material(mtluse));
+                    out.extend(std::iter::repeat_n(material_index, range.len()));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn material_idents(&self) -> Vec<MaterialIdent<'_>> {
+        let mut materials = Vec::new();
+
+        for obj in self.objects_iter() {
+            for group in obj.group_iter() {
+                // the material of a group is part of the table, even if it has no faces
+                materials.push(obj.material(group.mtluse));
+                materials.extend(
+                    group
+                        .material_ranges()
+                        .map(|(_, mtluse)| obj.material(mtluse)),
+                );
+            }
+        }
+
+        materials.sort_unstable_by_key(MaterialIdent::sort_key);
+        materials.dedup();
+
+        materials
+    }
+}
+
+/// The object, group and material a face belongs to, see [`ObjObject::faces_with_context_iter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceContext<'a> {
+    /// The name of the object (o).
+    pub object: &'a str,
+    /// The (first) name of the group (g), see [`GroupRef::name`].
+    pub group: &'a str,
+    /// The first material library (mtllib) of the object, see [`MaterialIdent`].
+    pub mtllib: Option<&'a str>,
+    /// The material use (usemtl) of the face, which may differ between faces of the same group.
+    pub mtluse: Option<&'a str>,
+}
+
+/// Whether a file has a `.gz` extension, or starts with the gzip magic bytes.
+#[cfg(feature = "flate2")]
+fn is_gzip(path: &Path, reader: &mut impl std::io::BufRead) -> Result<bool, Error> {
+    const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+    {
+        return Ok(true);
+    }
+
+    Ok(reader.fill_buf()?.starts_with(&MAGIC))
+}
+
+#[inline]
+fn material_index(materials: &[MaterialIdent<'_>], material: MaterialIdent<'_>) -> usize {
+    materials
+        .binary_search_by_key(&material.sort_key(), MaterialIdent::sort_key)
+        .expect("Material table contains every used material")
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Identifies a material.
 ///
 /// Consists of
 /// - a material library (mtllib)
 /// - a material use (mtluse)
+///
+/// If an object references multiple material libraries, `mtllib` is the first one,
+/// as the file does not specify which library a material use refers to.
+/// The material itself should be looked up in all libraries of the object, in order
+/// (see `ObjectRef::mtllibs`, [`ObjObject::load_materials`] and [`MaterialLibraries::resolve`][crate::MaterialLibraries::resolve]).
 pub struct MaterialIdent<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub mtllib: Option<&'a str>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub mtluse: Option<&'a str>,
 }
 
+impl<'a> MaterialIdent<'a> {
+    /// Sorts by `mtllib`, then by `mtluse`, with `None` after any name.
+    #[inline]
+    const fn sort_key(&self) -> (bool, Option<&'a str>, bool, Option<&'a str>) {
+        (
+            self.mtllib.is_none(),
+            self.mtllib,
+            self.mtluse.is_none(),
+            self.mtluse,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An owned version of [`MaterialIdent`].
+pub struct MaterialIdentOwned {
+    pub mtllib: Option<String>,
+    pub mtluse: Option<String>,
+}
+
+impl MaterialIdentOwned {
+    #[inline]
+    #[must_use]
+    pub fn as_ident(&self) -> MaterialIdent<'_> {
+        MaterialIdent {
+            mtllib: self.mtllib.as_deref(),
+            mtluse: self.mtluse.as_deref(),
+        }
+    }
+}
+
+impl From<MaterialIdent<'_>> for MaterialIdentOwned {
+    #[inline]
+    fn from(value: MaterialIdent<'_>) -> Self {
+        Self {
+            mtllib: value.mtllib.map(String::from),
+            mtluse: value.mtluse.map(String::from),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ObjectRef<'a> {
-    vertices: &'a [(f32, f32, f32)],
-    vertex_colors: Option<&'a [(f32, f32, f32)]>,
-    vertex_normals: &'a [(f32, f32, f32)],
-    texture_coords: &'a [(f32, f32)],
+    vertices: &'a [[f32; 3]],
+    vertex_colors: Option<&'a [[f32; 3]]>,
+    vertex_alphas: Option<&'a [f32]>,
+    vertex_normals: &'a [[f32; 3]],
+    texture_coords: &'a [[f32; 2]],
+    vertex_tangents: Option<&'a [[f32; 4]]>,
 
     faces: &'a [FaceData],
+    polygons: &'a [PolygonData],
+    polygon_corners: &'a [PolygonCorner],
+    line_elements: &'a [Vec<u32>],
+    line_texture_indices: &'a [Option<Vec<u32>>],
+    point_elements: &'a [Vec<u32>],
 
     name: &'a str,
-    mtllib: Option<&'a String>,
+    mtllibs: &'a [String],
+    maplibs: &'a [String],
+    display: &'a DisplayAttributes,
+    transform: Option<[[f32; 4]; 4]>,
 
     groups: &'a [GroupingData],
 }
@@ -194,9 +743,47 @@ impl<'a> ObjectRef<'a> {
         self.name
     }
 
+    /// Returns the first material library (mtllib) of this object.
+    #[inline]
+    pub fn mtllib(&self) -> Option<&'a str> {
+        self.mtllibs.first().map(String::as_str)
+    }
+
+    /// Returns all material libraries (mtllib) of this object, in the order they are referenced.
+    ///
+    /// A mtllib statement may list multiple files, and an object may contain multiple mtllib statements.
+    /// Material libraries are file-scoped, so this includes the libraries referenced by previous objects,
+    /// unless [`ParseOptions::reset_mtllib_per_object`][crate::ParseOptions::reset_mtllib_per_object] was set.
+    /// Every file is only listed once.
+    #[inline]
+    pub const fn mtllibs(&self) -> &[String] {
+        self.mtllibs
+    }
+
+    /// Returns the first texture map library (maplib) of this object.
+    #[inline]
+    pub fn maplib(&self) -> Option<&str> {
+        self.maplibs.first().map(String::as_str)
+    }
+
+    /// Returns all texture map libraries (maplib) of this object, in the order they are referenced.
+    ///
+    /// Like [`ObjectRef::mtllibs`], this includes the libraries referenced by previous objects.
+    #[inline]
+    pub const fn maplibs(&self) -> &[String] {
+        self.maplibs
+    }
+
+    /// Returns the level of detail (lod) of this object, if specified.
     #[inline]
-    pub fn mtllib(&self) -> Option<&str> {
-        self.mtllib.map(String::as_str)
+    pub const fn lod(&self) -> Option<u8> {
+        self.display.lod
+    }
+
+    /// Returns all display and render attributes (lod, `shadow_obj`, `trace_obj`, bevel, `c_interp`, `d_interp`) of this object.
+    #[inline]
+    pub const fn display_attributes(&self) -> &DisplayAttributes {
+        self.display
     }
 
     #[inline]
@@ -204,19 +791,62 @@ impl<'a> ObjectRef<'a> {
         self.groups.len()
     }
 
-    pub fn group_iter(&self) -> impl Iterator<Item = GroupRef<'a>> {
-        self.groups.iter().map(|group| GroupRef {
+    /// Returns the column-major transform of this object, if one was set via [`ObjObject::set_object_transform`]
+    /// and not yet baked via [`ObjObject::apply_object_transforms`].
+    #[inline]
+    pub const fn object_transform(&self) -> Option<[[f32; 4]; 4]> {
+        self.transform
+    }
+
+    /// Returns the axis aligned bounding box around all vertices used by the faces, lines and points of this object.
+    #[must_use]
+    pub fn aabb(&self) -> Aabb {
+        self.group_iter()
+            .fold(Aabb::EMPTY, |aabb, group| aabb.union(&group.aabb()))
+    }
+
+    /// Returns the [`MaterialIdent`] of a material use (usemtl) inside this object.
+    #[inline]
+    fn material(&self, mtluse: Option<&'a str>) -> MaterialIdent<'a> {
+        MaterialIdent {
+            mtllib: self.mtllibs.first().map(String::as_str),
+            mtluse,
+        }
+    }
+
+    pub fn group_iter(self) -> impl Iterator<Item = GroupRef<'a>> {
+        self.groups.iter().map(move |group| GroupRef {
             vertices: self.vertices,
             vertex_colors: self.vertex_colors,
+            vertex_alphas: self.vertex_alphas,
             vertex_normals: self.vertex_normals,
             texture_coords: self.texture_coords,
+            vertex_tangents: self
+                .vertex_tangents
+                .map(|tangents| &tangents[group.start * 3..group.finish * 3]),
 
             name: &group.name,
-            mtluse: group.mtl.as_ref(),
+            other_names: &group.other_names,
+            mtluse: group.mtl.as_deref(),
+            usemap: group.map.as_deref(),
+            mtl_changes: &group.mtl_changes,
+            face_start: group.start,
+            smooth_group: group.smooth,
             faces: &self.faces[group.start..group.finish],
+            polygons: &self.polygons[self.polygons.partition_point(|p| p.face < group.start)
+                ..self.polygons.partition_point(|p| p.face < group.finish)],
+            polygon_corners: self.polygon_corners,
+            line_elements: &self.line_elements[group.line_start..group.line_finish],
+            line_texture_indices: &self.line_texture_indices[group.line_start..group.line_finish],
+            point_elements: &self.point_elements[group.point_start..group.point_finish],
         })
     }
 
+    /// Returns all groups of this object, that have the given name among their names, see [`GroupRef::names`].
+    pub fn groups_named(&self, name: &str) -> impl Iterator<Item = GroupRef<'a>> {
+        self.group_iter().filter(move |group| group.has_name(name))
+    }
+
     #[inline]
     pub fn faces(&self) -> Vec<&[FaceData]> {
         let mut faces = 0;
@@ -236,25 +866,95 @@ impl<'a> ObjectRef<'a> {
 
 #[derive(Debug, Clone, Copy)]
 pub struct GroupRef<'a> {
-    vertices: &'a [(f32, f32, f32)],
-    vertex_colors: Option<&'a [(f32, f32, f32)]>,
-    vertex_normals: &'a [(f32, f32, f32)],
-    texture_coords: &'a [(f32, f32)],
+    vertices: &'a [[f32; 3]],
+    vertex_colors: Option<&'a [[f32; 3]]>,
+    vertex_alphas: Option<&'a [f32]>,
+    vertex_normals: &'a [[f32; 3]],
+    texture_coords: &'a [[f32; 2]],
+    /// The tangents of the faces of this group, 3 per face.
+    vertex_tangents: Option<&'a [[f32; 4]]>,
 
     name: &'a str,
-    mtluse: Option<&'a String>,
+    other_names: &'a [String],
+    mtluse: Option<&'a str>,
+    /// Material uses after the first face, with the absolute index of their first face.
+    mtl_changes: &'a [(usize, String)],
+    usemap: Option<&'a str>,
+    /// Absolute index of the first face of this group.
+    face_start: usize,
+    smooth_group: Option<u32>,
     faces: &'a [FaceData],
+    polygons: &'a [PolygonData],
+    polygon_corners: &'a [PolygonCorner],
+    line_elements: &'a [Vec<u32>],
+    line_texture_indices: &'a [Option<Vec<u32>>],
+    point_elements: &'a [Vec<u32>],
 }
 
-impl GroupRef<'_> {
+impl<'a> GroupRef<'a> {
+    /// Returns the first name of this group.
     #[inline]
     pub const fn name(&self) -> &str {
         self.name
     }
 
+    /// Returns all names of this group, starting with [`GroupRef::name`].
+    ///
+    /// A group statement may list multiple names (e.g. `g body wheels`), all of them apply to the following elements.
     #[inline]
-    pub fn mtluse(&self) -> Option<&str> {
-        self.mtluse.map(String::as_str)
+    pub fn names(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        std::iter::once(self.name).chain(self.other_names.iter().map(String::as_str))
+    }
+
+    /// Returns `true` if any name of this group is the given name.
+    #[inline]
+    #[must_use]
+    pub fn has_name(&self, name: &str) -> bool {
+        self.names().any(|n| n == name)
+    }
+
+    /// Returns the material use (usemtl) of the first face of this group.
+    ///
+    /// A group may switch materials between its faces, see [`GroupRef::material_ranges`].
+    #[inline]
+    pub const fn mtluse(&self) -> Option<&str> {
+        self.mtluse
+    }
+
+    /// Returns the texture map use (usemap) of this group, `None` if there is none or it was turned off (`usemap off`).
+    ///
+    /// A texture map stays in effect across groups and objects. Switching it after the first element of a group
+    /// continues the group as a new group with the same names.
+    #[inline]
+    pub const fn usemap(&self) -> Option<&str> {
+        self.usemap
+    }
+
+    /// Returns the ranges of faces sharing the same material use (usemtl), in the order of the faces.
+    ///
+    /// The ranges index into the faces of this group (as returned by [`GroupRef::faces_iter`]), and are never empty.
+    /// Most groups only use a single material, but exporters may switch materials between faces of the same group.
+    pub fn material_ranges(
+        &self,
+    ) -> impl Iterator<Item = (Range<usize>, Option<&'a str>)> + use<'a> {
+        let face_start = self.face_start;
+        let face_count = self.faces.len();
+
+        let starts = std::iter::once((0, self.mtluse)).chain(
+            self.mtl_changes
+                .iter()
+                .map(move |(start, mtl)| (start - face_start, Some(mtl.as_str()))),
+        );
+        let ends = self
+            .mtl_changes
+            .iter()
+            .map(move |(start, _)| start - face_start)
+            .chain(std::iter::once(face_count));
+
+        starts
+            .zip(ends)
+            .map(|((start, mtl), end)| (start..end, mtl))
+            .filter(|(range, _)| !range.is_empty())
     }
 
     #[inline]
@@ -262,8 +962,58 @@ impl GroupRef<'_> {
         self.faces.len()
     }
 
-    pub fn faces_iter(&self) -> impl Iterator<Item = Face> {
-        self.faces.iter().map(|face| {
+    /// Returns the number of polygon faces in this group.
+    ///
+    /// Always `0`, unless the file was parsed with [`ParseOptions::keep_polygons`][crate::ParseOptions::keep_polygons].
+    #[inline]
+    pub const fn polygon_count(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Returns the number of line (l) elements in this group.
+    #[inline]
+    pub const fn line_count(&self) -> usize {
+        self.line_elements.len()
+    }
+
+    /// Returns the number of point (p) elements in this group.
+    ///
+    /// A single point element can contain multiple points.
+    #[inline]
+    pub const fn point_element_count(&self) -> usize {
+        self.point_elements.len()
+    }
+
+    /// Returns the smoothing group (s) that was active at the start of this group, `None` if smoothing was off.
+    ///
+    /// Faces inside the group may still switch smoothing groups, see [`Face::smooth_group`].
+    #[inline]
+    pub const fn smooth_group(&self) -> Option<u32> {
+        self.smooth_group
+    }
+
+    /// Returns the axis aligned bounding box around all vertices used by the faces, lines and points of this group.
+    #[must_use]
+    pub fn aabb(&self) -> Aabb {
+        let faces = self
+            .faces
+            .iter()
+            .flat_map(|face| <[u32; 3]>::from(face.indicies));
+        let elements = self
+            .line_elements
+            .iter()
+            .chain(self.point_elements)
+            .flatten();
+
+        build_aabb(
+            faces
+                .chain(elements.copied())
+                .map(|i| self.vertices[i as usize - 1]),
+        )
+    }
+
+    pub fn faces_iter(self) -> impl Iterator<Item = Face> + 'a {
+        self.faces.iter().enumerate().map(move |(i, face)| {
             let (i1, i2, i3) = face.indicies;
 
             Face {
@@ -280,6 +1030,13 @@ impl GroupRef<'_> {
                         colors[i3 as usize - 1],
                     ]
                 }),
+                vert_alphas: self.vertex_alphas.map(|alphas| {
+                    [
+                        alphas[i1 as usize - 1],
+                        alphas[i2 as usize - 1],
+                        alphas[i3 as usize - 1],
+                    ]
+                }),
                 vert_normals: face.normal_indicies.map(|(n1, n2, n3)| {
                     [
                         self.vertex_normals[n1 as usize - 1],
@@ -295,113 +1052,520 @@ impl GroupRef<'_> {
                         self.texture_coords[t3 as usize - 1],
                     ]
                 }),
+                vert_tangents: self
+                    .vertex_tangents
+                    .map(|tangents| [tangents[i * 3], tangents[i * 3 + 1], tangents[i * 3 + 2]]),
+
+                smooth_group: face.smooth_group,
+            }
+        })
+    }
+
+    /// Returns an [Iterator][std::iter::Iterator] over the segments of all line (l) elements in this group.
+    ///
+    /// A line element with `n` vertices yields `n - 1` segments.
+    pub fn lines_iter(&self) -> impl Iterator<Item = LineSegment> {
+        self.line_elements
+            .iter()
+            .zip(self.line_texture_indices)
+            .flat_map(move |(indices, texture_indices)| {
+                (0..indices.len() - 1).map(move |i| {
+                    let (i1, i2) = (indices[i] as usize - 1, indices[i + 1] as usize - 1);
+
+                    LineSegment {
+                        vert_positions: [self.vertices[i1], self.vertices[i2]],
+                        vert_colors: self.vertex_colors.map(|colors| [colors[i1], colors[i2]]),
+                        vert_alphas: self.vertex_alphas.map(|alphas| [alphas[i1], alphas[i2]]),
+                        vert_uv_coords: texture_indices.as_ref().map(|t| {
+                            [
+                                self.texture_coords[t[i] as usize - 1],
+                                self.texture_coords[t[i + 1] as usize - 1],
+                            ]
+                        }),
+                    }
+                })
+            })
+    }
+
+    /// Returns an [Iterator][std::iter::Iterator] over the points of all point (p) elements in this group.
+    pub fn points_iter(&self) -> impl Iterator<Item = Point> {
+        self.point_elements.iter().flatten().map(|i| {
+            let i = *i as usize - 1;
+
+            Point {
+                position: self.vertices[i],
+                color: self.vertex_colors.map(|colors| colors[i]),
+                alpha: self.vertex_alphas.map(|alphas| alphas[i]),
+            }
+        })
+    }
+
+    /// Returns an [Iterator][std::iter::Iterator] over the untriangulated polygon faces of this group.
+    ///
+    /// Polygons are only stored if the file was parsed with [`ParseOptions::keep_polygons`][crate::ParseOptions::keep_polygons],
+    /// otherwise the iterator is empty. [`GroupRef::faces_iter`] always returns the triangulated faces.
+    pub fn polygons_iter(&self) -> impl Iterator<Item = PolygonFace<'_>> {
+        self.polygons.iter().map(|polygon| PolygonFace {
+            vertices: self.vertices,
+            vertex_colors: self.vertex_colors,
+            vertex_alphas: self.vertex_alphas,
+            vertex_normals: self.vertex_normals,
+            texture_coords: self.texture_coords,
+
+            corners: &self.polygon_corners[polygon.start..polygon.finish],
+            smooth_group: polygon.smooth_group,
+        })
+    }
+}
+
+impl GroupRef<'_> {
+    /// Copies this group into an [`OwnedGroup`], that does not borrow from the [`ObjObject`].
+    ///
+    /// Only the positions, normals and texture coords used by the faces of this group are copied,
+    /// and the indices of the faces remapped accordingly. Line (l) and point (p) elements are not copied.
+    #[must_use]
+    pub fn to_owned(self) -> OwnedGroup {
+        let mut positions = FxHashMap::default();
+        let mut normals = FxHashMap::default();
+        let mut texture_coords = FxHashMap::default();
+        let (mut used_positions, mut used_normals, mut used_texture_coords) =
+            (Vec::new(), Vec::new(), Vec::new());
+
+        let faces = self
+            .faces
+            .iter()
+            .map(|face| FaceData {
+                indicies: remap_triangle(face.indicies, &mut positions, &mut used_positions),
+                texture_indcicies: face.texture_indcicies.map(|indices| {
+                    remap_triangle(indices, &mut texture_coords, &mut used_texture_coords)
+                }),
+                normal_indicies: face
+                    .normal_indicies
+                    .map(|indices| remap_triangle(indices, &mut normals, &mut used_normals)),
+                smooth_group: face.smooth_group,
+            })
+            .collect();
+
+        let copy = |used: &[u32], data: &[_]| used.iter().map(|i| data[*i as usize - 1]).collect();
+
+        OwnedGroup {
+            name: self.name.to_string(),
+            mtluse: self.mtluse.map(String::from),
+            smooth_group: self.smooth_group,
+            vertices: copy(&used_positions, self.vertices),
+            vertex_colors: self
+                .vertex_colors
+                .map(|colors| copy(&used_positions, colors)),
+            vertex_alphas: self.vertex_alphas.map(|alphas| {
+                used_positions
+                    .iter()
+                    .map(|i| alphas[*i as usize - 1])
+                    .collect()
+            }),
+            vertex_normals: copy(&used_normals, self.vertex_normals),
+            texture_coords: used_texture_coords
+                .iter()
+                .map(|i| self.texture_coords[*i as usize - 1])
+                .collect(),
+            vertex_tangents: self.vertex_tangents.map(<[_]>::to_vec),
+            faces,
+        }
+    }
+}
+
+/// Returns the new (1-based) indices of a triangle, assigning the next free index to every index seen for the first time.
+fn remap_triangle(
+    indices: (u32, u32, u32),
+    remap: &mut FxHashMap<u32, u32>,
+    used: &mut Vec<u32>,
+) -> (u32, u32, u32) {
+    <[u32; 3]>::from(indices)
+        .map(|index| {
+            *remap.entry(index).or_insert_with(|| {
+                used.push(index);
+                index_count(used.len())
+            })
+        })
+        .into()
+}
+
+/// A copy of a single group, that does not borrow from the [`ObjObject`] it was parsed into, see [`GroupRef::to_owned`].
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedGroup {
+    name: String,
+    mtluse: Option<String>,
+    smooth_group: Option<u32>,
+    vertices: Vec<[f32; 3]>,
+    vertex_colors: Option<Vec<[f32; 3]>>,
+    vertex_alphas: Option<Vec<f32>>,
+    vertex_normals: Vec<[f32; 3]>,
+    texture_coords: Vec<[f32; 2]>,
+    /// The tangents of the faces, 3 per face.
+    vertex_tangents: Option<Vec<[f32; 4]>>,
+    faces: Vec<FaceData>,
+}
+
+impl OwnedGroup {
+    /// Returns the first name of the group, see [`GroupRef::name`].
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the material use (usemtl) of the first face of the group, see [`GroupRef::mtluse`].
+    #[inline]
+    #[must_use]
+    pub fn mtluse(&self) -> Option<&str> {
+        self.mtluse.as_deref()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns the positions used by the faces of the group, in the order they are first used.
+    #[inline]
+    #[must_use]
+    pub fn positions(&self) -> &[[f32; 3]] {
+        &self.vertices
+    }
+
+    /// Returns the normals used by the faces of the group, in the order they are first used.
+    #[inline]
+    #[must_use]
+    pub fn normals(&self) -> &[[f32; 3]] {
+        &self.vertex_normals
+    }
+
+    /// Returns the texture coords used by the faces of the group, in the order they are first used.
+    #[inline]
+    #[must_use]
+    pub fn texture_coords(&self) -> &[[f32; 2]] {
+        &self.texture_coords
+    }
+
+    /// Returns the faces of the group, indexing into [`OwnedGroup::positions`], [`OwnedGroup::normals`] and [`OwnedGroup::texture_coords`].
+    #[inline]
+    #[must_use]
+    pub fn faces(&self) -> &[FaceData] {
+        &self.faces
+    }
+
+    pub fn faces_iter(&self) -> impl Iterator<Item = Face> {
+        self.as_group_ref().faces_iter()
+    }
+
+    /// Returns a borrowed view of this group, to use the rest of the [`GroupRef`] API (e.g. [`GroupRef::aabb`]).
+    #[must_use]
+    pub fn as_group_ref(&self) -> GroupRef<'_> {
+        GroupRef {
+            vertices: &self.vertices,
+            vertex_colors: self.vertex_colors.as_deref(),
+            vertex_alphas: self.vertex_alphas.as_deref(),
+            vertex_normals: &self.vertex_normals,
+            texture_coords: &self.texture_coords,
+            vertex_tangents: self.vertex_tangents.as_deref(),
+
+            name: &self.name,
+            other_names: &[],
+            mtluse: self.mtluse.as_deref(),
+            mtl_changes: &[],
+            usemap: None,
+            face_start: 0,
+            smooth_group: self.smooth_group,
+            faces: &self.faces,
+            polygons: &[],
+            polygon_corners: &[],
+            line_elements: &[],
+            line_texture_indices: &[],
+            point_elements: &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A single point of a point (p) element.
+pub struct Point {
+    pub position: [f32; 3],
+    pub color: Option<[f32; 3]>,
+    pub alpha: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A single segment of a line (l) element, connecting 2 vertices.
+pub struct LineSegment {
+    pub vert_positions: [[f32; 3]; 2],
+    pub vert_colors: Option<[[f32; 3]; 2]>,
+    pub vert_alphas: Option<[f32; 2]>,
+    pub vert_uv_coords: Option<[[f32; 2]; 2]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A single corner of a [`PolygonFace`].
+///
+/// Indices are 1-based, like in the .obj file, with negative (relative) indices already resolved.
+pub struct PolygonCorner {
+    pub position: u32,
+    pub texture_coord: Option<u32>,
+    pub normal: Option<u32>,
+}
+
+impl From<Corner> for PolygonCorner {
+    #[inline]
+    fn from((position, texture_coord, normal): Corner) -> Self {
+        Self {
+            position,
+            texture_coord,
+            normal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A face (f) as written in the .obj file, with 3 or more corners.
+pub struct PolygonFace<'a> {
+    vertices: &'a [[f32; 3]],
+    vertex_colors: Option<&'a [[f32; 3]]>,
+    vertex_alphas: Option<&'a [f32]>,
+    vertex_normals: &'a [[f32; 3]],
+    texture_coords: &'a [[f32; 2]],
+
+    corners: &'a [PolygonCorner],
+    smooth_group: Option<u32>,
+}
+
+impl<'a> PolygonFace<'a> {
+    #[inline]
+    #[must_use]
+    /// Returns the corners of this polygon, in the order they were written in.
+    pub const fn corners(&self) -> &'a [PolygonCorner] {
+        self.corners
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the number of corners of this polygon.
+    pub const fn corner_count(&self) -> usize {
+        self.corners.len()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the smoothing group (s) of this polygon, `None` if smoothing is off.
+    pub const fn smooth_group(&self) -> Option<u32> {
+        self.smooth_group
+    }
+
+    /// Returns an [Iterator][std::iter::Iterator] over the vertex data of each corner.
+    pub fn vertices(&self) -> impl Iterator<Item = VertexData> + 'a {
+        let Self {
+            vertices,
+            vertex_colors,
+            vertex_alphas,
+            vertex_normals,
+            texture_coords,
+            corners,
+            ..
+        } = *self;
+
+        corners.iter().map(move |corner| {
+            let i = corner.position as usize - 1;
+
+            VertexData {
+                position: vertices[i],
+                color: vertex_colors.map(|colors| colors[i]),
+                alpha: vertex_alphas.map(|alphas| alphas[i]),
+                normal: corner.normal.map(|n| vertex_normals[n as usize - 1]),
+                texture_coord: corner.texture_coord.map(|t| texture_coords[t as usize - 1]),
+                tangent: None,
             }
         })
     }
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents 3 vertices.
 ///
 /// Contains:
 ///     - the vertex position for each vertex
 ///     - the vertex color for each vertex (optional)
+///     - the vertex color alpha for each vertex (optional)
 ///     - the vertex normals for each vertex (optional)
 ///     - the vertex uv coordinates for each vertex (optional)
+///     - the vertex tangents for each vertex (optional)
+///     - the smoothing group of the face (optional)
 ///
 /// # Examples
 /// ```rust
 /// # use polypath::Face;
 /// let face = Face {
 ///     vert_positions: [
-///         (0.0, 0.0, 0.0),
-///         (1.0, 0.0, 0.0),
-///         (1.0, 1.0, 0.0),
+///         [0.0, 0.0, 0.0],
+///         [1.0, 0.0, 0.0],
+///         [1.0, 1.0, 0.0],
 ///     ],
 ///     vert_colors: None,
+///     vert_alphas: None,
 ///     vert_normals: None,
 ///     vert_uv_coords: None,
+///     vert_tangents: None,
+///     smooth_group: None,
 /// };
 /// ```
 pub struct Face {
-    pub vert_positions: [(f32, f32, f32); 3],
-    pub vert_colors: Option<[(f32, f32, f32); 3]>,
-    pub vert_normals: Option<[(f32, f32, f32); 3]>,
-    pub vert_uv_coords: Option<[(f32, f32); 3]>,
+    pub vert_positions: [[f32; 3]; 3],
+    pub vert_colors: Option<[[f32; 3]; 3]>,
+    /// The alpha component of the vertex colors, if the colors are RGBA.
+    pub vert_alphas: Option<[f32; 3]>,
+    pub vert_normals: Option<[[f32; 3]; 3]>,
+    pub vert_uv_coords: Option<[[f32; 2]; 3]>,
+    /// The tangent of each vertex, with the handedness (`1.0` or `-1.0`) in w,
+    /// only present after [`generate_tangents`][crate::geometry::generate_tangents].
+    pub vert_tangents: Option<[[f32; 4]; 3]>,
+    /// The smoothing group (s) this face belongs to, `None` if smoothing is off.
+    pub smooth_group: Option<u32>,
 }
 
 impl Face {
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "positions are arrays now, use `vert_positions` directly"
+    )]
+    /// Returns the vertex positions as tuples.
+    pub fn vert_positions_tuple(&self) -> [(f32, f32, f32); 3] {
+        self.vert_positions.map(<(f32, f32, f32)>::from)
+    }
+
     #[must_use]
     pub const fn vertices(&self) -> [VertexData; 3] {
         let [v1p, v2p, v3p] = self.vert_positions;
 
         let [v1c, v2c, v3c] = option_to_array(self.vert_colors);
 
+        let [v1a, v2a, v3a] = option_to_array(self.vert_alphas);
+
         let [v1n, v2n, v3n] = option_to_array(self.vert_normals);
 
         let [v1t, v2t, v3t] = option_to_array(self.vert_uv_coords);
 
+        let [v1g, v2g, v3g] = option_to_array(self.vert_tangents);
+
         [
             VertexData {
                 position: v1p,
                 color: v1c,
+                alpha: v1a,
                 normal: v1n,
                 texture_coord: v1t,
+                tangent: v1g,
             },
             VertexData {
                 position: v2p,
                 color: v2c,
+                alpha: v2a,
                 normal: v2n,
                 texture_coord: v2t,
+                tangent: v2g,
             },
             VertexData {
                 position: v3p,
                 color: v3c,
+                alpha: v3a,
                 normal: v3n,
                 texture_coord: v3t,
+                tangent: v3g,
             },
         ]
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents the data of a single vertex.
 ///
 /// Contains:
 ///     - the vertex position
 ///     - the vertex color (optional)
+///     - the vertex color alpha (optional)
 ///     - the vertex normals (optional)
 ///     - the vertex uv coordinates (optional)
+///     - the vertex tangent, with the handedness in w (optional)
 pub struct VertexData {
-    pub position: (f32, f32, f32),
-    pub color: Option<(f32, f32, f32)>,
-    pub normal: Option<(f32, f32, f32)>,
-    pub texture_coord: Option<(f32, f32)>,
+    pub position: [f32; 3],
+    pub color: Option<[f32; 3]>,
+    pub alpha: Option<f32>,
+    pub normal: Option<[f32; 3]>,
+    pub texture_coord: Option<[f32; 2]>,
+    pub tangent: Option<[f32; 4]>,
 }
 
-impl Eq for VertexData {}
+impl VertexData {
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "positions are arrays now, use `position` directly"
+    )]
+    /// Returns the position as a tuple.
+    pub fn position_tuple(&self) -> (f32, f32, f32) {
+        self.position.into()
+    }
 
-impl std::hash::Hash for VertexData {
     #[inline]
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let (x, y, z) = self.position;
-        (f32::to_bits(x), f32::to_bits(y), f32::to_bits(z)).hash(state);
+    #[must_use]
+    #[deprecated(since = "0.2.0", note = "colors are arrays now, use `color` directly")]
+    /// Returns the color as a tuple.
+    pub fn color_tuple(&self) -> Option<(f32, f32, f32)> {
+        self.color.map(Into::into)
+    }
 
-        self.color
-            .map(|(r, g, b)| (f32::to_bits(r), f32::to_bits(g), f32::to_bits(b)))
-            .hash(state);
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "normals are arrays now, use `normal` directly"
+    )]
+    /// Returns the normal as a tuple.
+    pub fn normal_tuple(&self) -> Option<(f32, f32, f32)> {
+        self.normal.map(Into::into)
+    }
 
-        self.normal
-            .map(|(u, v, w)| (f32::to_bits(u), f32::to_bits(v), f32::to_bits(w)))
-            .hash(state);
+    #[inline]
+    #[must_use]
+    #[deprecated(
+        since = "0.2.0",
+        note = "texture coords are arrays now, use `texture_coord` directly"
+    )]
+    /// Returns the texture coordinate as a tuple.
+    pub fn texture_coord_tuple(&self) -> Option<(f32, f32)> {
+        self.texture_coord.map(Into::into)
+    }
+}
+
+impl Eq for VertexData {}
 
-        self.texture_coord
-            .map(|(u, v)| (f32::to_bits(u), f32::to_bits(v)))
-            .hash(state);
+impl std::hash::Hash for VertexData {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.position.map(f32::to_bits).hash(state);
+        self.color.map(|c| c.map(f32::to_bits)).hash(state);
+        self.alpha.map(f32::to_bits).hash(state);
+        self.normal.map(|n| n.map(f32::to_bits)).hash(state);
+        self.texture_coord.map(|t| t.map(f32::to_bits)).hash(state);
+        self.tangent.map(|t| t.map(f32::to_bits)).hash(state);
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a single vertex, included associated material.
 pub struct VertexTextureData {
     /// Can be used to index into a [Vec][std::vec::Vec] of [`MaterialIdent`].
@@ -2,12 +2,15 @@ use std::collections::{HashMap, HashSet, hash_map::Entry};
 
 use rustc_hash::FxBuildHasher;
 
-use crate::VertexTextureData;
+use crate::{Error, VertexTextureData, parse::index_count, vec3::Vec3};
 
 #[must_use]
 /// Optimizes the ordering of vertices.
 ///
 /// Takes a list of verticies, where every set of 3 vertices is assumed 1 triangle. Reorders the vertices for optimal cache reuse.
+///
+/// # Panics
+/// Panics if the number of vertices is not a multiple of 3.
 pub fn optimize_vertex_order(mut vertices: Vec<VertexTextureData>) -> Vec<VertexTextureData> {
     if vertices.is_empty() {
         return Vec::new();
@@ -93,10 +96,201 @@ pub fn optimize_vertex_order(mut vertices: Vec<VertexTextureData>) -> Vec<Vertex
     new_vertices
 }
 
+/// Size of the simulated vertex cache used for scoring by [`optimize_forsyth`].
+const FORSYTH_CACHE_SIZE: usize = 32;
+const FORSYTH_CACHE_DECAY_POWER: f32 = 1.5;
+const FORSYTH_LAST_TRIANGLE_SCORE: f32 = 0.75;
+const FORSYTH_VALENCE_BOOST_SCALE: f32 = 2.0;
+const FORSYTH_VALENCE_BOOST_POWER: f32 = 0.5;
+
+/// Reorders the triangles of an index buffer for optimal post-transform vertex cache reuse,
+/// using Tom Forsyth's linear-speed vertex cache optimization.
+///
+/// Every vertex gets a score based on its position in a simulated LRU cache and the number of triangles still using it.
+/// Triangles are then greedily emitted, always picking the one with the highest score of its vertices.
+/// The winding of every triangle is kept.
+///
+/// Takes a list of indices, where every set of 3 indices is assumed 1 triangle.
+///
+/// # Panics
+/// Panics if the number of indices is not a multiple of 3, or any index is not smaller than `vertex_count`.
+pub fn optimize_forsyth(indices: &mut [u32], vertex_count: usize) {
+    assert_eq!(indices.len() % 3, 0, "Every 3 indices are 1 triangle");
+
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return;
+    }
+
+    // the triangles using each vertex, vertex `v` owns `triangles[offsets[v]..offsets[v] + remaining[v]]`
+    let mut remaining = vec![0_usize; vertex_count];
+    for index in indices.iter() {
+        remaining[*index as usize] += 1;
+    }
+
+    let mut offsets = Vec::with_capacity(vertex_count);
+    let mut sum = 0;
+    for count in &remaining {
+        offsets.push(sum);
+        sum += count;
+    }
+
+    let mut triangles = vec![0_usize; indices.len()];
+    let mut filled = vec![0_usize; vertex_count];
+    for (i, index) in indices.iter().enumerate() {
+        let v = *index as usize;
+        triangles[offsets[v] + filled[v]] = i / 3;
+        filled[v] += 1;
+    }
+
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut vertex_score: Vec<f32> = remaining
+        .iter()
+        .map(|count| forsyth_vertex_score(None, *count))
+        .collect();
+    let mut triangle_score: Vec<f32> = indices
+        .chunks_exact(3)
+        .map(|t| t.iter().map(|v| vertex_score[*v as usize]).sum())
+        .collect();
+    let mut emitted = vec![false; triangle_count];
+
+    let mut output = Vec::with_capacity(indices.len());
+    let mut cache: Vec<u32> = Vec::with_capacity(FORSYTH_CACHE_SIZE + 3);
+
+    let mut best =
+        (0..triangle_count).max_by(|a, b| triangle_score[*a].total_cmp(&triangle_score[*b]));
+    // triangles before the cursor are all emitted
+    let mut cursor = 0;
+
+    while let Some(triangle) = best {
+        let corners = [
+            indices[triangle * 3],
+            indices[triangle * 3 + 1],
+            indices[triangle * 3 + 2],
+        ];
+
+        output.extend(corners);
+        emitted[triangle] = true;
+
+        // remove the triangle from its vertices
+        for v in corners.map(|v| v as usize) {
+            let owned = &mut triangles[offsets[v]..offsets[v] + remaining[v]];
+            if let Some(i) = owned.iter().position(|t| *t == triangle) {
+                owned.swap(i, owned.len() - 1);
+                remaining[v] -= 1;
+            }
+        }
+
+        // move the corners to the front of the cache
+        cache.retain(|v| !corners.contains(v));
+        for v in corners.iter().rev() {
+            cache.insert(0, *v);
+        }
+
+        // update the scores of every vertex whose cache position changed
+        for (position, v) in cache.iter().enumerate() {
+            let v = *v as usize;
+            let position = (position < FORSYTH_CACHE_SIZE).then_some(position);
+
+            cache_position[v] = position;
+            let score = forsyth_vertex_score(position, remaining[v]);
+            let delta = score - vertex_score[v];
+            vertex_score[v] = score;
+
+            for t in &triangles[offsets[v]..offsets[v] + remaining[v]] {
+                triangle_score[*t] += delta;
+            }
+        }
+        cache.truncate(FORSYTH_CACHE_SIZE);
+
+        // the next triangle is picked from the ones using a cached vertex
+        best = cache
+            .iter()
+            .flat_map(|v| {
+                let v = *v as usize;
+                &triangles[offsets[v]..offsets[v] + remaining[v]]
+            })
+            .copied()
+            .max_by(|a, b| triangle_score[*a].total_cmp(&triangle_score[*b]));
+
+        if best.is_none() {
+            while cursor < triangle_count && emitted[cursor] {
+                cursor += 1;
+            }
+            best = (cursor < triangle_count).then_some(cursor);
+        }
+    }
+
+    debug_assert_eq!(output.len(), indices.len());
+
+    indices.copy_from_slice(&output);
+}
+
+/// Returns the score of a vertex, given its position in the simulated cache and the number of triangles still using it.
+#[allow(clippy::cast_precision_loss)]
+fn forsyth_vertex_score(cache_position: Option<usize>, remaining: usize) -> f32 {
+    if remaining == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        None => 0.0,
+        // the vertices of the last triangle get a fixed score, so the order they were added in does not matter
+        Some(0..3) => FORSYTH_LAST_TRIANGLE_SCORE,
+        Some(position) => {
+            let scale = 1.0 / (FORSYTH_CACHE_SIZE - 3) as f32;
+            ((position - 3) as f32)
+                .mul_add(-scale, 1.0)
+                .powf(FORSYTH_CACHE_DECAY_POWER)
+        }
+    };
+
+    // vertices with few remaining triangles get boosted, to get rid of them quickly
+    let valence_boost =
+        FORSYTH_VALENCE_BOOST_SCALE * (remaining as f32).powf(-FORSYTH_VALENCE_BOOST_POWER);
+
+    cache_score + valence_boost
+}
+
+#[must_use]
+/// Returns the average cache miss ratio (ACMR) of an index buffer, simulating a FIFO vertex cache of `cache_size` entries.
+///
+/// The ACMR is the number of cache misses per triangle, ranging from `3.0` (no reuse at all) to about `0.5` for large, regular meshes.
+/// Returns `0.0` if there are no triangles.
+#[allow(clippy::cast_precision_loss)]
+pub fn acmr(indices: &[u32], cache_size: usize) -> f32 {
+    let triangle_count = indices.len() / 3;
+    if triangle_count == 0 {
+        return 0.0;
+    }
+
+    let mut cache = std::collections::VecDeque::with_capacity(cache_size);
+    let mut misses = 0_usize;
+
+    for index in indices {
+        if cache.contains(index) {
+            continue;
+        }
+
+        misses += 1;
+        if cache_size > 0 {
+            if cache.len() == cache_size {
+                cache.pop_front();
+            }
+            cache.push_back(*index);
+        }
+    }
+
+    misses as f32 / triangle_count as f32
+}
+
 #[must_use]
 /// Returns:
 /// - a [Vec][std::vec::Vec] containing each unqiue vertex.
 /// - a [Vec][std::vec::Vec] containing indicies into the vertex buffer. Every 3 indicies build a face.
+///
+/// # Panics
+/// Panics if the number of vertices is not a multiple of 3.
 pub fn indexed_vertices(vertices: &[VertexTextureData]) -> (Vec<usize>, Vec<VertexTextureData>) {
     let mut indicies = Vec::with_capacity(vertices.len());
     let mut vertices_new = Vec::with_capacity(vertices.len() / 3);
@@ -132,3 +326,363 @@ pub fn indexed_vertices(vertices: &[VertexTextureData]) -> (Vec<usize>, Vec<Vert
 
     (indicies, vertices_new)
 }
+
+/// Same as [`indexed_vertices`], but returns `u16` indices, halving the size of the index buffer.
+///
+/// Vertices are only deduplicated if they are identical, including their `material_index`.
+/// So the same position used with multiple materials counts as multiple unique vertices,
+/// and a mesh with many materials can overflow, even if it has less than [`u16::MAX`] positions.
+///
+/// # Errors
+/// Returns [`Error::IndexOverflow`], if there are more than [`u16::MAX`] unique vertices.
+///
+/// # Panics
+/// Panics if the number of vertices is not a multiple of 3.
+#[allow(clippy::cast_possible_truncation)]
+pub fn indexed_vertices_u16(
+    vertices: &[VertexTextureData],
+) -> Result<(Vec<u16>, Vec<VertexTextureData>), Error> {
+    let (indicies, vertices) = indexed_vertices(vertices);
+
+    let limit = usize::from(u16::MAX);
+    if vertices.len() > limit {
+        return Err(Error::IndexOverflow {
+            count: vertices.len(),
+            limit,
+        });
+    }
+
+    Ok((indicies.into_iter().map(|i| i as u16).collect(), vertices))
+}
+
+/// An index buffer together with its unique vertices, using the smallest index type that can address all of them.
+///
+/// Returned by [`indexed_vertices_auto`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexBuffer {
+    U16(Vec<u16>, Vec<VertexTextureData>),
+    U32(Vec<u32>, Vec<VertexTextureData>),
+}
+
+impl IndexBuffer {
+    #[inline]
+    #[must_use]
+    /// Returns the unique vertices, addressed by the indices.
+    pub fn vertices(&self) -> &[VertexTextureData] {
+        match self {
+            Self::U16(_, vertices) | Self::U32(_, vertices) => vertices,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the number of indices, every 3 indices build a face.
+    pub const fn index_count(&self) -> usize {
+        match self {
+            Self::U16(indices, _) => indices.len(),
+            Self::U32(indices, _) => indices.len(),
+        }
+    }
+}
+
+#[must_use]
+/// Same as [`indexed_vertices`], but picks `u16` indices if there are at most [`u16::MAX`] unique vertices, otherwise `u32`.
+///
+/// See [`indexed_vertices_u16`] on how the `material_index` affects the number of unique vertices.
+///
+/// # Panics
+/// Panics if the number of vertices is not a multiple of 3.
+#[allow(clippy::cast_possible_truncation)]
+pub fn indexed_vertices_auto(vertices: &[VertexTextureData]) -> IndexBuffer {
+    let (indicies, vertices) = indexed_vertices(vertices);
+
+    if u16::try_from(vertices.len()).is_ok() {
+        IndexBuffer::U16(indicies.into_iter().map(|i| i as u16).collect(), vertices)
+    } else {
+        // a mesh with more than u32::MAX unique vertices is not supported by any gpu
+        IndexBuffer::U32(indicies.into_iter().map(|i| i as u32).collect(), vertices)
+    }
+}
+
+#[must_use]
+/// Welds vertices that are within `pos_eps` of each other, without flattening shading across hard edges and seams.
+///
+/// Vertices are first clustered by position. All vertices of a cluster get snapped to a common position, even if their attributes differ,
+/// so that cracks between coincident edges close. Inside a cluster, vertices are only merged into the same output vertex if
+/// - their normals differ by at most `max_normal_angle_deg` degrees (or both have no normal),
+/// - their uv coordinates differ by at most `uv_eps` per component (or are identical, if `uv_eps` is `None`),
+/// - their colors and material indices are identical.
+///
+/// Returns:
+/// - a [Vec][std::vec::Vec] containing indicies into the vertex buffer. Every 3 indicies build a face.
+/// - a [Vec][std::vec::Vec] containing each unqiue vertex.
+pub fn weld_preserving_seams(
+    vertices: &[VertexTextureData],
+    pos_eps: f32,
+    max_normal_angle_deg: f32,
+    uv_eps: Option<f32>,
+) -> (Vec<usize>, Vec<VertexTextureData>) {
+    // a position cluster, containing all attribute variants of that position
+    struct Cluster {
+        position: [f32; 3],
+        variants: Vec<usize>,
+    }
+
+    let min_cos = max_normal_angle_deg.to_radians().cos();
+
+    let mut indicies = Vec::with_capacity(vertices.len());
+    let mut vertices_new: Vec<VertexTextureData> = Vec::with_capacity(vertices.len() / 3);
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let mut grid = HashMap::<[i64; 3], Vec<usize>, _>::with_hasher(FxBuildHasher);
+
+    for vertex in vertices {
+        let position = vertex.vertex.position;
+        let cell = grid_cell(position, pos_eps);
+
+        // search the neighbouring cells for a cluster within pos_eps
+        let cluster = neighbour_cells(cell)
+            .filter_map(|cell| grid.get(&cell))
+            .flatten()
+            .copied()
+            .find(|c| within(clusters[*c].position, position, pos_eps));
+
+        let cluster = cluster.unwrap_or_else(|| {
+            clusters.push(Cluster {
+                position,
+                variants: Vec::new(),
+            });
+            grid.entry(cell).or_default().push(clusters.len() - 1);
+            clusters.len() - 1
+        });
+
+        let cluster = &mut clusters[cluster];
+
+        // snap to the position of the cluster
+        let mut snapped = *vertex;
+        snapped.vertex.position = cluster.position;
+
+        let variant = cluster.variants.iter().copied().find(|v| {
+            let other = &vertices_new[*v];
+
+            other.material_index == snapped.material_index
+                && other.vertex.color == snapped.vertex.color
+                && other.vertex.alpha == snapped.vertex.alpha
+                && normals_agree(other.vertex.normal, snapped.vertex.normal, min_cos)
+                && uvs_agree(
+                    other.vertex.texture_coord,
+                    snapped.vertex.texture_coord,
+                    uv_eps,
+                )
+        });
+
+        let index = variant.unwrap_or_else(|| {
+            vertices_new.push(snapped);
+            cluster.variants.push(vertices_new.len() - 1);
+            vertices_new.len() - 1
+        });
+
+        indicies.push(index);
+    }
+
+    (indicies, vertices_new)
+}
+
+#[must_use]
+/// Welds vertices, whose attributes only differ by floating point noise.
+///
+/// Unlike [`indexed_vertices`], vertices don't need to be bitwise identical. Positions are hashed into a grid of `pos_eps` sized cells,
+/// and a vertex is merged into a previous vertex of its own or a neighbouring cell, if
+/// - their positions differ by at most `pos_eps` per component,
+/// - their normals differ by at most `normal_eps` per component (or both have no normal),
+/// - their uv coordinates differ by at most `uv_eps` per component (or both have no uv coordinates),
+/// - their colors and material indices are identical.
+///
+/// Merged vertices keep the attributes of the first vertex. This closes seams, that are visually watertight but topologically open.
+/// See [`weld_preserving_seams`] for welding by the angle between normals instead.
+///
+/// Returns:
+/// - a [Vec][std::vec::Vec] containing indices into the vertex buffer. Every 3 indices build a face.
+/// - a [Vec][std::vec::Vec] containing each unique vertex.
+pub fn weld_vertices(
+    vertices: &[VertexTextureData],
+    pos_eps: f32,
+    normal_eps: f32,
+    uv_eps: f32,
+) -> (Vec<u32>, Vec<VertexTextureData>) {
+    let mut indices = Vec::with_capacity(vertices.len());
+    let mut vertices_new: Vec<VertexTextureData> = Vec::with_capacity(vertices.len() / 3);
+
+    // the unique vertices inside of every cell
+    let mut grid = HashMap::<[i64; 3], Vec<u32>, _>::with_hasher(FxBuildHasher);
+
+    for vertex in vertices {
+        let cell = grid_cell(vertex.vertex.position, pos_eps);
+
+        let existing = neighbour_cells(cell)
+            .filter_map(|cell| grid.get(&cell))
+            .flatten()
+            .copied()
+            .find(|i| {
+                let other = &vertices_new[*i as usize];
+
+                other.material_index == vertex.material_index
+                    && other.vertex.color == vertex.vertex.color
+                    && other.vertex.alpha == vertex.vertex.alpha
+                    && within(other.vertex.position, vertex.vertex.position, pos_eps)
+                    && match (other.vertex.normal, vertex.vertex.normal) {
+                        (None, None) => true,
+                        (Some(a), Some(b)) => within(a, b, normal_eps),
+                        _ => false,
+                    }
+                    && uvs_agree(
+                        other.vertex.texture_coord,
+                        vertex.vertex.texture_coord,
+                        Some(uv_eps),
+                    )
+            });
+
+        let index = existing.unwrap_or_else(|| {
+            vertices_new.push(*vertex);
+            let index = index_count(vertices_new.len() - 1);
+            grid.entry(cell).or_default().push(index);
+            index
+        });
+
+        indices.push(index);
+    }
+
+    (indices, vertices_new)
+}
+
+#[must_use]
+/// Removes duplicate faces, keeping the first occurrence of each.
+///
+/// Two faces are duplicates, if they reference the same three vertex positions in any winding order.
+/// Positions are compared by their bit pattern, so only exactly equal positions match. All other attributes are ignored.
+///
+/// Takes a list of vertices, where every set of 3 vertices is assumed 1 triangle.
+///
+/// # Panics
+/// Panics if the number of vertices is not a multiple of 3.
+pub fn remove_duplicate_faces(vertices: &[VertexTextureData]) -> Vec<VertexTextureData> {
+    assert_eq!(vertices.len() % 3, 0, "Every 3 vertices are 1 triangle");
+
+    let mut seen = HashSet::with_capacity_and_hasher(vertices.len() / 3, FxBuildHasher);
+
+    vertices
+        .chunks_exact(3)
+        .filter(|face| seen.insert(face_key([0, 1, 2].map(|i| face[i].vertex.position))))
+        .flatten()
+        .copied()
+        .collect()
+}
+
+#[must_use]
+/// Same as [`remove_duplicate_faces`], but for an index buffer. Returns the indices of the remaining faces.
+///
+/// Faces are compared by the positions of their vertices, so faces using different vertices at the same positions are still duplicates.
+///
+/// # Panics
+/// Panics if the number of indices is not a multiple of 3, or any index is out of bounds for `vertices`.
+pub fn remove_duplicate_faces_indexed(indices: &[u32], vertices: &[VertexTextureData]) -> Vec<u32> {
+    assert_eq!(indices.len() % 3, 0, "Every 3 indices are 1 triangle");
+
+    let mut seen = HashSet::with_capacity_and_hasher(indices.len() / 3, FxBuildHasher);
+
+    indices
+        .chunks_exact(3)
+        .filter(|face| {
+            seen.insert(face_key(
+                [0, 1, 2].map(|i| vertices[face[i] as usize].vertex.position),
+            ))
+        })
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Returns a key that is the same for every winding order of a face, made of the bit patterns of its positions.
+#[inline]
+fn face_key(positions: [[f32; 3]; 3]) -> [[u32; 3]; 3] {
+    let mut key = positions.map(|position| position.map(f32::to_bits));
+    key.sort_unstable();
+    key
+}
+
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn grid_cell(position: [f32; 3], eps: f32) -> [i64; 3] {
+    let [x, y, z] = position;
+
+    if eps > 0.0 {
+        [
+            (x / eps).floor() as i64,
+            (y / eps).floor() as i64,
+            (z / eps).floor() as i64,
+        ]
+    } else {
+        // without a tolerance, only identical positions get clustered
+        [
+            i64::from(x.to_bits()),
+            i64::from(y.to_bits()),
+            i64::from(z.to_bits()),
+        ]
+    }
+}
+
+/// Returns the cell and its 26 neighbours.
+///
+/// Huge or infinite positions saturate to the cells at the edge of the grid,
+/// so some of their neighbours are the same cell, which only makes a search visit it again.
+#[inline]
+pub(crate) fn neighbour_cells(cell: [i64; 3]) -> impl Iterator<Item = [i64; 3]> {
+    let [x, y, z] = cell;
+
+    (-1..=1).flat_map(move |dx: i64| {
+        (-1..=1).flat_map(move |dy: i64| {
+            (-1..=1).map(move |dz: i64| {
+                [
+                    x.saturating_add(dx),
+                    y.saturating_add(dy),
+                    z.saturating_add(dz),
+                ]
+            })
+        })
+    })
+}
+
+#[inline]
+pub(crate) fn within(a: [f32; 3], b: [f32; 3], eps: f32) -> bool {
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() <= eps)
+}
+
+#[inline]
+fn normals_agree(a: Option<[f32; 3]>, b: Option<[f32; 3]>, min_cos: f32) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            let a = Vec3::from(a);
+            let b = Vec3::from(b);
+
+            let len = a.length() * b.length();
+            if len == 0.0 {
+                return a == b;
+            }
+
+            a.dot(&b) / len >= min_cos
+        }
+        _ => false,
+    }
+}
+
+#[inline]
+#[allow(clippy::float_cmp)]
+fn uvs_agree(a: Option<[f32; 2]>, b: Option<[f32; 2]>, eps: Option<f32>) -> bool {
+    match (a, b, eps) {
+        (None, None, _) => true,
+        (Some(a), Some(b), Some(eps)) => a.iter().zip(b).all(|(a, b)| (a - b).abs() <= eps),
+        (Some(a), Some(b), None) => a == b,
+        _ => false,
+    }
+}
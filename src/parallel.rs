@@ -0,0 +1,378 @@
+use std::{fs::File, io::Read, path::Path};
+
+use rayon::prelude::*;
+
+use crate::{
+    Error, Line, ObjObject, ParseOptions, QuadSplit,
+    parse::StatementParser,
+    reader::{SliceReader, split_lines},
+};
+
+/// The minimum number of bytes parsed by a single task.
+const CHUNK_LEN: usize = 1 << 20;
+
+impl ObjObject {
+    /// Reads a .obj file and parses it using multiple threads, using the default [`ParseOptions`].
+    ///
+    /// The whole file is read into memory (it is not memory-mapped), and split at line boundaries into chunks.
+    /// So parsing needs the size of the file in memory, in addition to the parsed `ObjObject`.
+    /// Vertex data (v, vt, vn) and elements (f, l, p) of all chunks are parsed in parallel,
+    /// everything else (e.g. groups, objects and materials) is applied in the order of the file afterward.
+    /// Faces are also parsed in order, if [`ParseOptions::quad_split`] splits quads depending on their positions.
+    /// So the result is exactly the same as from [`ObjObject::read_from_file`], including errors.
+    ///
+    /// The lines are split up multiple times, so this is only faster than [`ObjObject::read_from_file`]
+    /// with multiple CPU cores available.
+    ///
+    /// Uses `n_threads` threads, or one per CPU core, if `n_threads` is `0`.
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if reading from file fails, or the threads could not be spawned
+    /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
+    pub fn parse_parallel<P: AsRef<Path>>(path: P, n_threads: usize) -> Result<Self, Error> {
+        Self::parse_parallel_with(path, n_threads, &ParseOptions::default())
+    }
+
+    /// Same as [`ObjObject::parse_parallel`], but using the given [`ParseOptions`].
+    ///
+    /// The result is exactly the same as from [`ObjObject::read_from_file_with`] with the same options.
+    /// Files larger than [`ParseLimits::max_file_size`][crate::ParseLimits::max_file_size] are rejected before reading them.
+    /// There is no progress reporting, so [`ParseOptions::progress_interval`] does not apply.
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if reading from file fails, or the threads could not be spawned
+    /// - Returns other errors encountered when parsing the file
+    ///
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn parse_parallel_with<P: AsRef<Path>>(
+        path: P,
+        n_threads: usize,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|mut file| {
+                let size = file.metadata()?.len();
+                let limit = options.limits.max_file_size;
+                if size > limit {
+                    return Err(Error::FileTooLarge { size, limit });
+                }
+
+                let mut data = Vec::with_capacity(usize::try_from(size).unwrap_or_default());
+                file.read_to_end(&mut data)?;
+
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(n_threads)
+                    .build()
+                    .map_err(|err| Error::Io(std::io::Error::other(err)))?;
+
+                pool.install(|| Self::parse_bytes_parallel(&data, CHUNK_LEN, options))
+            })
+            .map_err(|err| err.in_file(path))
+    }
+
+    /// Parses a .obj file in memory on the current rayon thread pool, see [`ObjObject::parse_parallel_with`].
+    fn parse_bytes_parallel(
+        data: &[u8],
+        chunk_len: usize,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let chunks = split_lines(data, chunk_len);
+
+        // the number of lines and vertex data before each chunk, so chunks can resolve relative indices on their own
+        let mut offsets = chunks
+            .par_iter()
+            .map(|chunk| Offsets::count(chunk, options))
+            .collect::<Vec<_>>();
+        let mut total = Offsets::default();
+        for offset in &mut offsets {
+            let count = *offset;
+            *offset = total;
+            total.add(count);
+        }
+
+        let mut obj = Self::empty();
+        let mut state = obj.start_parsing(options, None);
+        let mut statements = StatementParser::new(options);
+        let mut reader = SliceReader::with_buffer(data, options.limits.max_line_len, Vec::new())
+            .lossy(options.lossy_utf8);
+
+        // only a few chunks are parsed at once, so parsed statements do not take up more memory than the file
+        let batch = rayon::current_num_threads();
+        let mut res = Ok(());
+        'batches: for (chunks, offsets) in chunks.chunks(batch).zip(offsets.chunks(batch)) {
+            let parsed = chunks
+                .par_iter()
+                .zip(offsets)
+                .map(|(chunk, offset)| parse_chunk(chunk, *offset, options))
+                .collect::<Vec<_>>();
+
+            for statement in parsed.into_iter().flatten() {
+                // chunks stop at the first error, which the reader or the statement itself reports here
+                let (line_number, line) = match reader.next_line() {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break 'batches,
+                    Err(err) => {
+                        res = Err(err);
+                        break 'batches;
+                    }
+                };
+
+                let pushed = match statement {
+                    Some(parsed) => {
+                        // counted in order as well, for the statements parsed in order and the limits of the whole file
+                        let parsed = statements.count_parsed(parsed);
+                        obj.push_statement(&mut state, line_number, parsed)
+                    }
+                    None => obj.parse_statement(&mut statements, &mut state, line_number, line),
+                };
+                if let Err(err) = pushed {
+                    res = Err(err);
+                    break 'batches;
+                }
+            }
+        }
+
+        // a chunk could have stopped early at an error of the reader
+        if res.is_ok()
+            && let Err(err) = reader.next_line()
+        {
+            res = Err(err);
+        }
+
+        obj.finish_parsing(state, options, res)?;
+
+        Ok(obj)
+    }
+}
+
+/// The number of physical lines and vertex data statements in the chunks before a chunk.
+#[derive(Debug, Clone, Copy, Default)]
+struct Offsets {
+    lines: usize,
+    positions: u32,
+    texture_coords: u32,
+    normals: u32,
+}
+
+impl Offsets {
+    /// Counts the lines and vertex data statements of a chunk, up to the first line that could not be read.
+    fn count(chunk: &[u8], options: &ParseOptions) -> Self {
+        let mut reader = SliceReader::with_buffer(chunk, options.limits.max_line_len, Vec::new())
+            .lossy(options.lossy_utf8);
+        let mut count = Self::default();
+
+        while let Ok(Some((_, line))) = reader.next_line() {
+            match statement_kind(line) {
+                Kind::Position => count.positions = count.positions.saturating_add(1),
+                Kind::TextureCoord => count.texture_coords = count.texture_coords.saturating_add(1),
+                Kind::Normal => count.normals = count.normals.saturating_add(1),
+                Kind::Face | Kind::Element | Kind::Other => {}
+            }
+        }
+        count.lines = reader.lines_read();
+
+        count
+    }
+
+    const fn add(&mut self, other: Self) {
+        self.lines += other.lines;
+        self.positions = self.positions.saturating_add(other.positions);
+        self.texture_coords = self.texture_coords.saturating_add(other.texture_coords);
+        self.normals = self.normals.saturating_add(other.normals);
+    }
+}
+
+/// Parses the vertex data and elements of a chunk, one entry per line.
+///
+/// Other statements are `None`, they depend on the state of the file at this point and get parsed in order afterward.
+/// This includes faces, if splitting quads depends on the positions parsed so far, see [`ParseOptions::quad_split`].
+/// Stops after the first error, except for non-finite values, see [`ParseOptions::reject_non_finite`].
+fn parse_chunk(
+    chunk: &[u8],
+    offsets: Offsets,
+    options: &ParseOptions,
+) -> Vec<Option<Result<Line<'static>, Error>>> {
+    let mut reader = SliceReader::with_buffer(chunk, options.limits.max_line_len, Vec::new())
+        .lossy(options.lossy_utf8)
+        .after_lines(offsets.lines);
+    let mut statements = StatementParser::new(options).starting_after(
+        offsets.positions,
+        offsets.texture_coords,
+        offsets.normals,
+    );
+
+    let in_order = |kind| match kind {
+        Kind::Face => options.quad_split != QuadSplit::Fixed02,
+        Kind::Other => true,
+        _ => false,
+    };
+
+    let mut parsed = Vec::new();
+    while let Ok(Some((line_number, line))) = reader.next_line() {
+        if in_order(statement_kind(line)) {
+            parsed.push(None);
+            continue;
+        }
+
+        // vertex data and elements do not borrow from the line
+        let line = statements
+            .parse(line, line_number, &[])
+            .map(|line| match line {
+                Line::Vertex(vertex) => Line::Vertex(vertex),
+                Line::Normal(normal) => Line::Normal(normal),
+                Line::TextureCoord(texture_coord) => Line::TextureCoord(texture_coord),
+                Line::Faces(faces, corners) => Line::Faces(faces, corners),
+                Line::PolyLine(indices, texture_indices) => {
+                    Line::PolyLine(indices, texture_indices)
+                }
+                Line::PointElement(indices) => Line::PointElement(indices),
+                _ => unreachable!("not vertex data or an element"),
+            });
+
+        // non-finite values are counted anyways, and may still be kept with a placeholder
+        let failed = matches!(&line, Err(err) if !matches!(err, Error::NonFiniteValue { .. }));
+        parsed.push(Some(line));
+        if failed {
+            break;
+        }
+    }
+
+    parsed
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Position,
+    TextureCoord,
+    Normal,
+    Face,
+    /// A line (l) or point (p) element.
+    Element,
+    Other,
+}
+
+/// Returns the kind of a statement, matching the statements recognized by [`ObjObject::parse_line`].
+fn statement_kind(line: &str) -> Kind {
+    let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+
+    match line.split_once(' ') {
+        Some(("v", _)) => Kind::Position,
+        Some(("vt", _)) => Kind::TextureCoord,
+        Some(("vn", _)) => Kind::Normal,
+        Some(("f", _)) => Kind::Face,
+        Some(("l" | "p", _)) => Kind::Element,
+        _ => Kind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ObjObject, ParseLimits, ParseOptions, QuadSplit};
+
+    const DATA: &str = "mtllib a.mtl
+v 0 0 0
+v 1 0 0\r
+v 0 1 0\r\n\
+v 1 1 0
+vt 0 0
+vt 1 1 # comment
+vn 0 0 1
+o first
+usemtl red
+s 1
+f 1/1/1 2/2/1 \\
+  3/1/1 # joined
+
+g second
+f -4 -3 -2
+s off
+l 1 2 -1
+usemtl blue
+p 1 -1
+o second
+v 2 2 2
+f -1 1 2 4
+";
+
+    /// Parses the data with every chunk length, comparing it to the sequential result.
+    fn assert_same(data: &str) {
+        assert_same_with(data, &ParseOptions::default());
+    }
+
+    /// Same as [`assert_same`], but using the given [`ParseOptions`].
+    fn assert_same_with(data: &str, options: &ParseOptions) {
+        let expected = ObjObject::parse_bytes_with(data.as_bytes(), options);
+
+        for chunk_len in 1..=data.len() {
+            let parallel = ObjObject::parse_bytes_parallel(data.as_bytes(), chunk_len, options);
+
+            match (&expected, parallel) {
+                (Ok(expected), Ok(mut parallel)) => {
+                    // only a reused allocation
+                    parallel.line_buffer.clone_from(&expected.line_buffer);
+                    assert_eq!(
+                        format!("{expected:?}"),
+                        format!("{parallel:?}"),
+                        "{chunk_len}"
+                    );
+                }
+                (Err(expected), Err(parallel)) => {
+                    assert_eq!(expected.to_string(), parallel.to_string(), "{chunk_len}");
+                }
+                (expected, parallel) => panic!("{chunk_len}: {expected:?} != {parallel:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_parallel() {
+        assert_same(DATA);
+        assert_same("");
+        assert_same("v 0 0 0\\\n");
+    }
+
+    #[test]
+    fn test_parse_parallel_errors() {
+        assert_same(&DATA.replace("f -4 -3 -2", "f -4 -3 -9"));
+        assert_same(&DATA.replace("s off", "s maybe"));
+        assert_same(&DATA.replace("vt 1 1", "vt nan 1"));
+        assert_same(&DATA.replace("usemtl blue", "unknown"));
+    }
+    #[test]
+    fn test_parse_parallel_with() {
+        let lenient = ParseOptions::default().strict(false);
+        assert_same_with(&DATA.replace("usemtl blue", "unknown"), &lenient);
+        assert_same_with(&DATA.replace("vt 1 1", "vt nan 1"), &lenient);
+
+        let options = ParseOptions::default()
+            .keep_normals(false)
+            .keep_texture_coords(false);
+        assert_same_with(DATA, &options);
+
+        let options = ParseOptions::default().limits(ParseLimits {
+            max_vertices: 4,
+            ..Default::default()
+        });
+        assert_same_with(DATA, &options);
+
+        let options = ParseOptions::default().limits(ParseLimits {
+            max_faces: 2,
+            ..Default::default()
+        });
+        assert_same_with(DATA, &options);
+
+        // the split depends on the positions of the quad
+        let quad = "v 0 0 0\nv 10 0 0\nv 10 1 0\nv 9 1 0\nf 1 2 3 4\n";
+        for quad_split in [QuadSplit::ShortestDiagonal, QuadSplit::MostPlanar] {
+            let options = ParseOptions::default().quad_split(quad_split);
+            assert_same_with(quad, &options);
+            assert_same_with(DATA, &options);
+        }
+    }
+}
@@ -1,6 +1,15 @@
-use crate::{Error, ObjObject};
+use crate::{
+    Error, IndexKind, ObjObject, PolygonCorner, geometry,
+    reader::{LineReader, Lines, SliceReader},
+    vec3::Vec3,
+};
 
-use std::mem;
+use smallvec::{SmallVec, smallvec};
+
+#[cfg(feature = "tokio")]
+use crate::reader::AsyncLineReader;
+
+use std::{mem, ops::ControlFlow};
 
 impl ObjObject {
     /// Parses a .obj file from some sort of input reader.
@@ -9,440 +18,2447 @@ impl ObjObject {
     ///
     /// # Errors
     /// Returns an `Error` if the .obj file is not as structured as expected.
-    pub fn parse(mut reader: impl std::io::BufRead) -> Result<Self, Error> {
-        let mut buffer = String::with_capacity(256);
+    #[inline]
+    pub fn parse(reader: impl std::io::BufRead) -> Result<Self, Error> {
+        Self::parse_with(reader, &ParseOptions::default())
+    }
 
-        let mut vertices = Vec::with_capacity(64);
-        let mut vertex_colors = Vec::new();
-        let mut vertex_normals = Vec::new();
-        let mut texture_coords = Vec::new();
-        let mut faces = Vec::with_capacity(32);
+    /// Parses a .obj file from a string, e.g. one embedded via [`include_str!`].
+    ///
+    /// Lines are parsed directly from the string, without copying them into an intermediate buffer.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected.
+    #[inline]
+    pub fn parse_str(s: &str) -> Result<Self, Error> {
+        Self::parse_bytes(s.as_bytes())
+    }
 
-        let mut groups = Vec::new();
-        let mut objects = Vec::new();
+    /// Parses a .obj file from a byte buffer, e.g. one embedded via [`include_bytes!`] or fetched over the network.
+    ///
+    /// Lines are parsed directly from the buffer, without copying them into an intermediate buffer.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, or is not valid UTF-8.
+    #[inline]
+    pub fn parse_bytes(b: &[u8]) -> Result<Self, Error> {
+        Self::parse_bytes_with(b, &ParseOptions::default())
+    }
 
-        let mut current_group = GroupingData::default();
-        let mut current_object = GroupingData::default();
+    /// Same as [`ObjObject::parse_bytes`], but using the given [`ParseOptions`].
+    ///
+    /// Invalid UTF-8 is only accepted, if [`ParseOptions::lossy_utf8`] is set.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, or if any limit is exceeded.
+    pub fn parse_bytes_with(b: &[u8], options: &ParseOptions) -> Result<Self, Error> {
+        let mut obj = Self::empty();
+
+        let mut reader = SliceReader::with_buffer(
+            b,
+            options.limits.max_line_len,
+            mem::take(&mut obj.line_buffer),
+        )
+        .lossy(options.lossy_utf8);
+        let res = obj.parse_lines_into(&mut reader, options, None, None);
+        obj.line_buffer = reader.into_buffer();
+
+        res.map(|()| obj)
+    }
 
-        loop {
-            let read = reader.read_line(&mut buffer)?;
+    /// Parses a .obj file from some sort of input reader, enforcing the given [`ParseLimits`].
+    ///
+    /// Lines are read in bounded chunks, so a line exceeding [`ParseLimits::max_line_len`] is rejected
+    /// before more than the limit has been buffered.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, or if any limit is exceeded.
+    #[inline]
+    pub fn parse_with_limits(
+        reader: impl std::io::BufRead,
+        limits: &ParseLimits,
+    ) -> Result<Self, Error> {
+        let options = ParseOptions {
+            limits: *limits,
+            ..Default::default()
+        };
 
-            if read == 0 {
-                break;
-            }
+        Self::parse_with(reader, &options)
+    }
 
-            let v_count = vertices.len() as u32;
-            let t_count = texture_coords.len() as u32;
-            let n_count = vertex_normals.len() as u32;
+    /// Parses a .obj file from some sort of input reader, using the given [`ParseOptions`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, or if any limit is exceeded.
+    pub fn parse_with(
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        Self::parse_with_optional_progress(reader, options, None)
+    }
 
-            let line = Self::parse_line(&buffer[..read], v_count, t_count, n_count)?;
-            buffer.clear();
+    /// Same as [`ObjObject::parse_with`], but reporting the progress to a callback every few thousand statements,
+    /// and once after the whole input was read.
+    ///
+    /// The callback can cancel parsing by returning [`ControlFlow::Break`], see [`ObjObject::read_from_file_with_progress`]
+    /// to also report the total size of a file.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    /// use polypath::{ObjObject, ParseOptions};
+    ///
+    /// let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+    /// let obj = ObjObject::parse_with_progress(std::io::Cursor::new(data), &ParseOptions::default(), |progress| {
+    ///     println!("{} bytes, {} faces", progress.bytes_read, progress.faces);
+    ///     ControlFlow::Continue(())
+    /// })
+    /// .unwrap();
+    /// assert_eq!(obj.face_count(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`Error::Cancelled`] if the callback cancelled parsing,
+    /// or an `Error` if the .obj file is not as structured as expected, or if any limit is exceeded.
+    pub fn parse_with_progress(
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+        mut progress: impl FnMut(ParseProgress) -> ControlFlow<()>,
+    ) -> Result<Self, Error> {
+        let progress = Progress::new(&mut progress, None, options.progress_interval);
+        Self::parse_with_optional_progress(reader, options, Some(progress))
+    }
+
+    /// Parses a .obj file, reporting the progress if requested, see [`ObjObject::parse_with_progress`].
+    pub(crate) fn parse_with_optional_progress(
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+        progress: Option<Progress<'_>>,
+    ) -> Result<Self, Error> {
+        let mut obj = Self::empty();
+        obj.parse_into(reader, options, None, progress)?;
+
+        Ok(obj)
+    }
+
+    /// Parses a gzip compressed .obj file (e.g. a `.obj.gz` file) from some sort of input reader.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the data is not valid gzip, or the .obj file is not as structured as expected.
+    #[cfg(feature = "flate2")]
+    #[inline]
+    pub fn parse_gzip(reader: impl std::io::Read) -> Result<Self, Error> {
+        Self::parse_gzip_with(reader, &ParseOptions::default())
+    }
 
-            match line {
-                Line::Empty | Line::Comment => {}
-                Line::Vertex(vertex_data) => {
-                    vertices.push(vertex_data.position);
-                    if let Some(color) = vertex_data.color {
-                        vertex_colors.push(color);
+    /// Same as [`ObjObject::parse_gzip`], but using the given [`ParseOptions`].
+    ///
+    /// Limits apply to the decompressed data, a line exceeding [`ParseLimits::max_line_len`] is rejected
+    /// before more than the limit has been decompressed.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the data is not valid gzip, the .obj file is not as structured as expected, or if any limit is exceeded.
+    #[cfg(feature = "flate2")]
+    pub fn parse_gzip_with(
+        reader: impl std::io::Read,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let decoder = flate2::read::GzDecoder::new(reader);
+        Self::parse_with(std::io::BufReader::new(decoder), options)
+    }
+
+    /// Parses a .obj file from an asynchronous input reader, without blocking the async runtime while waiting for input.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use polypath::ObjObject;
+    ///
+    /// # async fn load() -> Result<(), polypath::Error> {
+    /// let file = tokio::fs::File::open("./meshes/cubes.obj").await?;
+    /// let obj = ObjObject::parse_async(tokio::io::BufReader::new(file)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, or the .obj file is not as structured as expected.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub async fn parse_async(reader: impl tokio::io::AsyncBufRead + Unpin) -> Result<Self, Error> {
+        Self::parse_async_with(reader, &ParseOptions::default()).await
+    }
+
+    /// Same as [`ObjObject::parse_async`], but using the given [`ParseOptions`].
+    ///
+    /// Parsing itself is not split up, so the post-processing requested by the options
+    /// (e.g. [`ParseOptions::generate_normals`]) runs without yielding to the async runtime.
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, the .obj file is not as structured as expected, or if any limit is exceeded.
+    #[cfg(feature = "tokio")]
+    pub async fn parse_async_with(
+        reader: impl tokio::io::AsyncBufRead + Unpin,
+        options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let mut reader = AsyncLineReader::with_buffer(
+            reader,
+            options.limits.max_line_len,
+            Vec::with_capacity(256),
+        )
+        .lossy(options.lossy_utf8);
+
+        let mut obj = Self::empty();
+        let mut state = obj.start_parsing(options, None);
+        let mut statements = StatementParser::new(options);
+
+        let res = loop {
+            match reader.next_line().await {
+                Ok(Some((line_number, line))) => {
+                    if let Err(err) =
+                        obj.parse_statement(&mut statements, &mut state, line_number, line)
+                    {
+                        break Err(err);
                     }
                 }
-                Line::Normal(normal) => vertex_normals.push(normal),
-                Line::TextureCoord(tex) => texture_coords.push(tex),
-                Line::Face(face_data) => {
-                    faces.push(face_data);
-                    current_group.finish += 1;
+                Ok(None) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+
+        obj.line_buffer = reader.into_buffer();
+        obj.finish_parsing(state, options, res)?;
+
+        Ok(obj)
+    }
+
+    /// Parses a .obj file from some sort of input reader, collecting non-fatal issues as [`ParseWarning`]s.
+    ///
+    /// Instead of aborting, unknown lines are skipped.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, and the issue can not be reported as a warning.
+    #[inline]
+    pub fn parse_with_warnings(reader: impl std::io::BufRead) -> Result<ParseResult, Error> {
+        Self::parse_with_warnings_and_options(reader, &ParseOptions::default())
+    }
+
+    /// Same as [`ObjObject::parse_with_warnings`], but using the given [`ParseOptions`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, and the issue can not be reported as a warning.
+    pub fn parse_with_warnings_and_options(
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+    ) -> Result<ParseResult, Error> {
+        let mut obj = Self::empty();
+        let mut warnings = Vec::new();
+        obj.parse_into(reader, options, Some(&mut warnings), None)?;
+
+        Ok(ParseResult { obj, warnings })
+    }
+
+    pub(crate) fn empty() -> Self {
+        Self {
+            vertices: Vec::with_capacity(64),
+            vertices_w: Vec::new(),
+            vertices_f64: Vec::new(),
+            vertex_colors: Vec::new(),
+            vertex_alphas: Vec::new(),
+            vertex_normals: Vec::new(),
+            texture_coords: Vec::new(),
+            texture_coords_w: Vec::new(),
+            vertex_tangents: Vec::new(),
+            faces: Vec::with_capacity(32),
+            line_elements: Vec::new(),
+            line_texture_indices: Vec::new(),
+            point_elements: Vec::new(),
+            polygons: Vec::new(),
+            polygon_corners: Vec::new(),
+
+            groups: Vec::new(),
+            objects: Vec::new(),
+
+            line_buffer: Vec::with_capacity(256),
+            string_pool: Vec::new(),
+        }
+    }
+
+    /// Replaces the content of this `ObjObject` by parsing a .obj file from some sort of input reader.
+    ///
+    /// All internal buffers are cleared, but keep their capacity, so repeatedly reparsing similar files
+    /// (e.g. when hot-reloading) does not allocate once the buffers have grown large enough.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected.
+    /// On error the `ObjObject` is left empty, without any objects, groups or faces.
+    #[inline]
+    pub fn reparse(&mut self, reader: impl std::io::BufRead) -> Result<(), Error> {
+        self.reparse_with(reader, &ParseOptions::default())
+    }
+
+    /// Same as [`ObjObject::reparse`], but using the given [`ParseOptions`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the .obj file is not as structured as expected, or if any limit is exceeded.
+    /// On error the `ObjObject` is left empty, without any objects, groups or faces.
+    pub fn reparse_with(
+        &mut self,
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+    ) -> Result<(), Error> {
+        self.clear();
+
+        let res = self.parse_into(reader, options, None, None);
+        if res.is_err() {
+            self.clear();
+        }
+
+        res
+    }
+
+    /// Parses all lines into this (empty) `ObjObject`.
+    ///
+    /// If `warnings` are collected, recoverable issues are pushed there, instead of returning an error.
+    fn parse_into(
+        &mut self,
+        reader: impl std::io::BufRead,
+        options: &ParseOptions,
+        warnings: Option<&mut Vec<ParseWarning>>,
+        progress: Option<Progress<'_>>,
+    ) -> Result<(), Error> {
+        let mut reader = LineReader::with_buffer(
+            reader,
+            options.limits.max_line_len,
+            mem::take(&mut self.line_buffer),
+        )
+        .lossy(options.lossy_utf8);
+
+        let res = self.parse_lines_into(&mut reader, options, warnings, progress);
+        self.line_buffer = reader.into_buffer();
+
+        res
+    }
+
+    /// Parses all lines of a line source into this (empty) `ObjObject`, see [`ObjObject::parse_into`].
+    fn parse_lines_into(
+        &mut self,
+        reader: &mut impl Lines,
+        options: &ParseOptions,
+        warnings: Option<&mut Vec<ParseWarning>>,
+        progress: Option<Progress<'_>>,
+    ) -> Result<(), Error> {
+        let mut state = self.start_parsing(options, warnings);
+        let res = self.parse_lines(reader, &mut state, options, progress);
+        self.finish_parsing(state, options, res)
+    }
+
+    /// Creates the state for parsing lines into this (empty) `ObjObject`.
+    pub(crate) fn start_parsing<'w>(
+        &mut self,
+        options: &ParseOptions,
+        warnings: Option<&'w mut Vec<ParseWarning>>,
+    ) -> ParseState<'w> {
+        ParseState {
+            current_group: self.pooled_group(),
+            current_object: self.pooled_grouping(),
+            smooth_group: None,
+            options: *options,
+            line: 0,
+            warnings,
+        }
+    }
+
+    /// Stores the last group and object, and applies the post-processing requested by the options, if parsing succeeded.
+    pub(crate) fn finish_parsing(
+        &mut self,
+        state: ParseState,
+        options: &ParseOptions,
+        res: Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.finish(state);
+
+        if res.is_ok() {
+            if let Some(WeldEpsilon(epsilon)) = options.weld_epsilon {
+                geometry::weld_positions(self, epsilon);
+            }
+            if options.generate_normals {
+                geometry::generate_normals(self);
+            }
+        }
+
+        res
+    }
+
+    fn parse_lines(
+        &mut self,
+        reader: &mut impl Lines,
+        state: &mut ParseState,
+        options: &ParseOptions,
+        mut progress: Option<Progress<'_>>,
+    ) -> Result<(), Error> {
+        let mut statements = StatementParser::new(options);
+
+        while let Some((line_number, line)) = reader.next_line()? {
+            self.parse_statement(&mut statements, state, line_number, line)?;
+
+            if let Some(progress) = &mut progress {
+                progress.statement(reader.bytes_read(), self)?;
+            }
+        }
+
+        if let Some(progress) = &mut progress {
+            progress.finish(reader.bytes_read(), self);
+        }
+
+        Ok(())
+    }
+
+    /// Parses a single statement and adds it to this `ObjObject`, see [`ObjObject::push_statement`].
+    pub(crate) fn parse_statement(
+        &mut self,
+        statements: &mut StatementParser,
+        state: &mut ParseState,
+        line_number: usize,
+        line: &str,
+    ) -> Result<(), Error> {
+        let line = statements.parse(line, line_number, &self.vertices);
+        self.push_statement(state, line_number, line)
+    }
+
+    /// Adds an already parsed statement to this `ObjObject`.
+    ///
+    /// In lenient mode, or with [`ParseOptions::allow_unknown_lines`], unknown statements are skipped with a warning.
+    /// In lenient mode, non-finite values are replaced by a placeholder with a warning.
+    pub(crate) fn push_statement(
+        &mut self,
+        state: &mut ParseState,
+        line_number: usize,
+        line: Result<Line<'_>, Error>,
+    ) -> Result<(), Error> {
+        state.line = line_number;
+
+        let line = match line {
+            Err(Error::UnknownLine(data))
+                if state.lenient() || state.options.allow_unknown_lines =>
+            {
+                state.warn(ParseWarning::IgnoredLine {
+                    line: state.line,
+                    data,
+                });
+                return Ok(());
+            }
+            Err(Error::NonFiniteValue { statement, line }) if state.lenient() => {
+                // the statement was already counted, so a placeholder keeps the following indices in place
+                state.warn(ParseWarning::NonFiniteValue {
+                    line,
+                    statement: statement.clone(),
+                });
+
+                return Self::parse_line(&statement, 0, 0, 0, &[], &state.options)
+                    .map(replace_non_finite)
+                    .and_then(|placeholder| self.push_line(state, placeholder))
+                    .map_err(|err| err.at_line(line));
+            }
+            // already carries its line number
+            Err(err @ Error::NonFiniteValue { .. }) => return Err(err),
+            line => line,
+        };
+
+        line.and_then(|line| self.push_line(state, line))
+            .map_err(|err| err.at_line(state.line))
+    }
+
+    /// Adds a statement decoded from another file format (e.g. .stl or .ply) to this `ObjObject`.
+    ///
+    /// Non-finite values are handled like [`ParseOptions::reject_non_finite`] describes, like for parsed statements.
+    /// As the data does not consist of lines, errors are not wrapped in [`Error::AtLine`].
+    #[cfg(any(feature = "ply", feature = "stl"))]
+    pub(crate) fn push_decoded(
+        &mut self,
+        state: &mut ParseState,
+        line: Line<'_>,
+    ) -> Result<(), Error> {
+        state.line = 0;
+
+        if state.options.reject_non_finite && !is_finite(&line) {
+            let statement = non_finite_statement(&line);
+            if !state.lenient() {
+                return Err(Error::NonFiniteValue { statement, line: 0 });
+            }
+
+            state.warn(ParseWarning::NonFiniteValue { line: 0, statement });
+            return self.push_line(state, replace_non_finite(line));
+        }
+
+        self.push_line(state, line)
+    }
+
+    /// Removes all parsed data, keeping the allocations around for reuse.
+    fn clear(&mut self) {
+        self.vertices.clear();
+        self.vertices_w.clear();
+        self.vertices_f64.clear();
+        self.vertex_colors.clear();
+        self.vertex_alphas.clear();
+        self.vertex_normals.clear();
+        self.texture_coords.clear();
+        self.texture_coords_w.clear();
+        self.vertex_tangents.clear();
+        self.faces.clear();
+        self.line_elements.clear();
+        self.line_texture_indices.clear();
+        self.point_elements.clear();
+        self.polygons.clear();
+        self.polygon_corners.clear();
+
+        while let Some(grouping) = self.groups.pop().or_else(|| self.objects.pop()) {
+            self.recycle(grouping);
+        }
+    }
+
+    /// Keeps the allocations of a grouping around for reuse.
+    pub(crate) fn recycle(&mut self, grouping: GroupingData) {
+        self.string_pool.push(grouping.name);
+        self.string_pool.extend(grouping.other_names);
+        self.string_pool.extend(grouping.mtl);
+        self.string_pool.extend(grouping.mtllibs);
+        self.string_pool
+            .extend(grouping.mtl_changes.into_iter().map(|(_, mtl)| mtl));
+        self.string_pool.extend(grouping.map);
+        self.string_pool.extend(grouping.maplibs);
+        self.string_pool.extend(grouping.display.shadow_obj);
+        self.string_pool.extend(grouping.display.trace_obj);
+    }
+
+    /// Returns a `String` containing `data`, reusing a previously allocated `String` if possible.
+    fn pooled_string(&mut self, data: &str) -> String {
+        let mut string = self.string_pool.pop().unwrap_or_default();
+        string.clear();
+        string.push_str(data);
+        string
+    }
+
+    /// Returns an empty grouping, reusing a previously allocated name if possible.
+    fn pooled_grouping(&mut self) -> GroupingData {
+        GroupingData {
+            name: self.pooled_string(""),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an empty group named [`DEFAULT_GROUP_NAME`], reusing a previously allocated name if possible.
+    fn pooled_group(&mut self) -> GroupingData {
+        GroupingData {
+            name: self.pooled_string(DEFAULT_GROUP_NAME),
+            ..Default::default()
+        }
+    }
+
+    fn push_line(&mut self, state: &mut ParseState, line: Line<'_>) -> Result<(), Error> {
+        let ParseState {
+            current_group,
+            current_object,
+            smooth_group,
+            options,
+            line: line_number,
+            warnings,
+        } = state;
+
+        match line {
+            Line::Vertex(vertex_data) => self.push_vertex(vertex_data, options)?,
+            Line::FreeForm(statement) => warn(warnings, || ParseWarning::SkippedFreeForm {
+                line: *line_number,
+                statement: String::from(statement),
+            }),
+            Line::Normal(normal) if options.keep_normals => {
+                if let Some(warnings) = warnings
+                    && normal.iter().all(|c| *c == 0.0)
+                {
+                    warnings.push(ParseWarning::ZeroNormal { line: *line_number });
                 }
-                Line::DoubleFace(f1, f2) => {
-                    faces.push(f1);
-                    faces.push(f2);
-                    current_group.finish += 2;
+
+                self.vertex_normals.push(normal);
+            }
+            Line::TextureCoord([u, v, w]) if options.keep_texture_coords => {
+                self.texture_coords.push([u, v]);
+                if options.keep_texture_w {
+                    self.texture_coords_w.push(w);
                 }
-                Line::Group(data) => {
-                    if current_group.start == current_group.finish {
-                        current_group.name = data;
-                    } else {
-                        let finished = mem::take(&mut current_group);
-                        groups.push(finished);
-
-                        current_group.name = data;
-                        current_group.start = faces.len();
-                        current_group.finish = faces.len();
-
-                        current_object.finish += 1;
-                    }
+            }
+            Line::Normal(_) | Line::TextureCoord(_) => {}
+            Line::Faces(mut faces, mut corners) => {
+                discard_attributes(&mut faces, &mut corners, options);
+                let polygon = options.keep_polygons.then_some(corners);
+                self.push_face(current_group, faces, polygon, *smooth_group);
+            }
+            Line::PolyLine(indices, texture_indices) => {
+                current_group.line_finish += 1;
+
+                self.line_elements.push(indices);
+                self.line_texture_indices
+                    .push(texture_indices.filter(|_| options.keep_texture_coords));
+            }
+            Line::PointElement(indices) => {
+                current_group.point_finish += 1;
+                self.point_elements.push(indices);
+            }
+            Line::SmoothGroup(group) => {
+                *smooth_group = group;
+
+                // like the material, the smoothing group of a group is set before its first face
+                if current_group.start == current_group.finish {
+                    current_group.smooth = group;
                 }
-                Line::Object(data) => {
-                    if current_object.start == current_object.finish && faces.is_empty() {
-                        current_object.name = data;
-                    } else {
-                        if current_group.start != current_group.finish {
-                            current_object.finish += 1;
-
-                            let finished = mem::take(&mut current_group);
-                            groups.push(finished);
-
-                            current_group.start = faces.len();
-                            current_group.finish = faces.len();
-                        }
-
-                        let finished = mem::take(&mut current_object);
-                        objects.push(finished);
-
-                        current_object.name = data;
-                        current_object.start = groups.len();
-                        current_object.finish = groups.len();
+            }
+            Line::Group(data) => {
+                if current_group.is_empty() {
+                    self.assign_group_names(current_group, data);
+                } else {
+                    let finished = mem::replace(current_group, self.pooled_group());
+                    self.groups.push(finished);
+
+                    self.assign_group_names(current_group, data);
+                    self.start_group(current_group, *smooth_group);
+                    if !options.reset_mtl_per_group {
+                        self.inherit_material(current_group);
                     }
-                }
+                    self.inherit_texture_map(current_group);
 
-                Line::MaterialLib(data) => {
-                    if current_object.mtl.is_none() {
-                        current_object.mtl = Some(data);
-                    } else {
-                        return Err(Error::OjectMultipleMtl(current_object.name));
-                    }
+                    current_object.finish += 1;
                 }
-                Line::MaterialUse(data) => {
-                    if current_group.mtl.is_none() {
-                        current_group.mtl = Some(data);
-                    } else {
-                        return Err(Error::GroupMultipleMTl(current_group.name));
-                    }
+            }
+            Line::Object(data) => self.push_object(state, data),
+
+            Line::MaterialLib(data) => self.push_libraries(&mut current_object.mtllibs, data),
+            Line::TextureMapLib(data) => self.push_libraries(&mut current_object.maplibs, data),
+            Line::DisplayAttribute(DisplayAttribute::Invalid(statement)) => {
+                warn(warnings, || ParseWarning::InvalidDisplayAttribute {
+                    line: *line_number,
+                    statement: String::from(statement),
+                });
+            }
+            Line::DisplayAttribute(attribute) => {
+                self.set_display_attribute(&mut current_object.display, attribute);
+            }
+            Line::TextureMapUse(data) => {
+                // a texture map applies to a whole group, so switching it after the first element continues the group as a new group
+                if !current_group.is_empty() && current_group.map.as_deref() != data {
+                    self.continue_group(current_group, *smooth_group);
+                    current_object.finish += 1;
                 }
+
+                self.assign_texture_map(current_group, data);
+            }
+            Line::MaterialUse(data) => self.push_material_use(current_group, data),
+        }
+
+        Ok(())
+    }
+
+    /// Switches the material of the current group, starting at the next face.
+    fn push_material_use(&mut self, current_group: &mut GroupingData, data: &str) {
+        // a material use only applies to the faces following it,
+        // so it replaces a previous one, that has not been used by any face
+        let face = self.faces.len();
+
+        match current_group.mtl_changes.last_mut() {
+            Some((start, mtl)) if *start == face => assign(mtl, data),
+            None if current_group.start == current_group.finish => match &mut current_group.mtl {
+                Some(mtl) => assign(mtl, data),
+                None => current_group.mtl = Some(self.pooled_string(data)),
+            },
+            _ => {
+                let mtl = self.pooled_string(data);
+                current_group.mtl_changes.push((face, mtl));
+            }
+        }
+    }
+
+    /// Finishes the current object, and starts a new one with the given name.
+    ///
+    /// The implicit (unnamed) object at the start of the file is only renamed, as long as nothing was parsed into it.
+    /// Objects named by the file are always kept, even without any elements.
+    fn push_object(&mut self, state: &mut ParseState, data: &str) {
+        let ParseState {
+            current_group,
+            current_object,
+            smooth_group,
+            options,
+            ..
+        } = state;
+
+        // only the implicit object has no name
+        let implicit = current_object.name.is_empty() && self.objects.is_empty();
+
+        if implicit
+            && current_object.start == current_object.finish
+            && self.faces.is_empty()
+            && self.line_elements.is_empty()
+            && self.point_elements.is_empty()
+        {
+            assign(&mut current_object.name, data);
+        } else {
+            // objects always contain at least one (possibly empty) group
+            if !current_group.is_empty() || current_object.start == current_object.finish {
+                current_object.finish += 1;
+
+                let finished = mem::replace(current_group, self.pooled_group());
+                self.groups.push(finished);
+
+                self.start_group(current_group, *smooth_group);
+                if !options.reset_mtl_per_group {
+                    self.inherit_material(current_group);
+                }
+                self.inherit_texture_map(current_group);
+            }
+
+            let finished = mem::replace(current_object, self.pooled_grouping());
+            self.objects.push(finished);
+
+            if !options.reset_mtllib_per_object {
+                self.inherit_mtllibs(current_object);
+            }
+
+            assign(&mut current_object.name, data);
+            current_object.start = self.groups.len();
+            current_object.finish = self.groups.len();
+        }
+    }
+
+    /// Stores a vertex, with its color (and alpha) and its w component, if requested.
+    ///
+    /// Either all vertices have a color or none, the same goes for the alpha component.
+    fn push_vertex(&mut self, vertex: ParsedVertex, options: &ParseOptions) -> Result<(), Error> {
+        let ParsedVertex {
+            position,
+            w,
+            color,
+            alpha,
+            position_f64,
+        } = vertex;
+        let (color, alpha) = if options.keep_colors {
+            (color, alpha)
+        } else {
+            (None, None)
+        };
+
+        // the first vertex decides whether colors are present
+        let previous = self.vertices.len();
+        if previous != 0
+            && ((self.vertex_colors.len() == previous) != color.is_some()
+                || (self.vertex_alphas.len() == previous) != alpha.is_some())
+        {
+            return Err(Error::NonUniformColors);
+        }
+
+        self.vertices.push(position);
+        if options.keep_vertex_w {
+            self.vertices_w.push(w);
+        }
+        if let Some(position) = position_f64 {
+            self.vertices_f64.push(position);
+        }
+        if let Some(color) = color {
+            self.vertex_colors.push(color);
+        }
+        if let Some(alpha) = alpha {
+            self.vertex_alphas.push(alpha);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the triangles of a face to the current group, and the polygon itself if it should be kept.
+    fn push_face(
+        &mut self,
+        current_group: &mut GroupingData,
+        faces: Triangles,
+        polygon: Option<Corners>,
+        smooth_group: Option<u32>,
+    ) {
+        if let Some(corners) = polygon {
+            let start = self.polygon_corners.len();
+            self.polygon_corners
+                .extend(corners.into_iter().map(PolygonCorner::from));
+
+            self.polygons.push(PolygonData {
+                face: self.faces.len(),
+                start,
+                finish: self.polygon_corners.len(),
+                smooth_group,
+            });
+        }
+
+        current_group.finish += faces.len();
+
+        for mut face_data in faces {
+            face_data.smooth_group = smooth_group;
+            self.faces.push(face_data);
+        }
+    }
+
+    /// Lets an empty group start after everything parsed so far.
+    const fn start_group(&self, group: &mut GroupingData, smooth_group: Option<u32>) {
+        group.smooth = smooth_group;
+        group.start = self.faces.len();
+        group.finish = self.faces.len();
+        group.line_start = self.line_elements.len();
+        group.line_finish = self.line_elements.len();
+        group.point_start = self.point_elements.len();
+        group.point_finish = self.point_elements.len();
+    }
+
+    /// Sets the names of a group. A group statement may list multiple names, all of them apply to the following elements.
+    ///
+    /// A group statement without any name switches back to the [`DEFAULT_GROUP_NAME`].
+    fn assign_group_names(&mut self, group: &mut GroupingData, data: &str) {
+        let mut names = data.split_whitespace();
+
+        assign(&mut group.name, names.next().unwrap_or(DEFAULT_GROUP_NAME));
+
+        self.string_pool.append(&mut group.other_names);
+        for name in names {
+            let name = self.pooled_string(name);
+            group.other_names.push(name);
+        }
+    }
+
+    /// Finishes the current group, and starts a new one with the same names and material.
+    fn continue_group(&mut self, group: &mut GroupingData, smooth_group: Option<u32>) {
+        let mut continued = self.pooled_group();
+        assign(&mut continued.name, &group.name);
+        for name in &group.other_names {
+            let name = self.pooled_string(name);
+            continued.other_names.push(name);
+        }
+
+        let finished = mem::replace(group, continued);
+        self.groups.push(finished);
+
+        self.start_group(group, smooth_group);
+        self.inherit_material(group);
+        self.inherit_texture_map(group);
+    }
+
+    /// Adds the files of a library statement (mtllib or maplib), that are not yet referenced.
+    ///
+    /// A statement may list multiple files, and an object may have multiple statements.
+    fn push_libraries(&mut self, libraries: &mut Vec<String>, data: &str) {
+        for file in data.split_whitespace() {
+            if !libraries.iter().any(|lib| lib == file) {
+                let file = self.pooled_string(file);
+                libraries.push(file);
             }
         }
+    }
+
+    /// Stores a display or render attribute on an object, replacing a previous statement of the same kind.
+    fn set_display_attribute(
+        &mut self,
+        display: &mut DisplayAttributes,
+        attribute: DisplayAttribute<'_>,
+    ) {
+        match attribute {
+            DisplayAttribute::Lod(level) => display.lod = Some(level),
+            DisplayAttribute::ShadowObj(file) => {
+                self.string_pool.extend(display.shadow_obj.take());
+                display.shadow_obj = Some(self.pooled_string(file));
+            }
+            DisplayAttribute::TraceObj(file) => {
+                self.string_pool.extend(display.trace_obj.take());
+                display.trace_obj = Some(self.pooled_string(file));
+            }
+            DisplayAttribute::Bevel(on) => display.bevel = Some(on),
+            DisplayAttribute::ColorInterpolation(on) => display.color_interpolation = Some(on),
+            DisplayAttribute::DissolveInterpolation(on) => {
+                display.dissolve_interpolation = Some(on);
+            }
+            DisplayAttribute::Invalid(_) => {}
+        }
+    }
+
+    /// Sets the texture map of a group, `None` turns it off.
+    fn assign_texture_map(&mut self, group: &mut GroupingData, data: Option<&str>) {
+        match (&mut group.map, data) {
+            (Some(map), Some(data)) => assign(map, data),
+            (map @ None, Some(data)) => *map = Some(self.pooled_string(data)),
+            (map, None) => self.string_pool.extend(map.take()),
+        }
+    }
+
+    /// Lets a new group use the texture map (usemap) of the previous group.
+    fn inherit_texture_map(&mut self, group: &mut GroupingData) {
+        if let Some(map) = self.groups.last().and_then(|g| g.map.as_deref()) {
+            let mut string = self.string_pool.pop().unwrap_or_default();
+            string.clear();
+            string.push_str(map);
+            group.map = Some(string);
+        }
+    }
+
+    /// Lets a new group use the material, that was active at the end of the previous group.
+    fn inherit_material(&mut self, group: &mut GroupingData) {
+        let mut string = self.string_pool.pop().unwrap_or_default();
+
+        let active = self
+            .groups
+            .last()
+            .and_then(|g| g.mtl_changes.last().map(|(_, mtl)| mtl).or(g.mtl.as_ref()));
 
-        // store current group
-        if current_group.start != current_group.finish {
+        match active {
+            Some(mtl) => {
+                string.clear();
+                string.push_str(mtl);
+                group.mtl = Some(string);
+            }
+            None => self.string_pool.push(string),
+        }
+    }
+
+    /// Lets a new object reference all material libraries of the previous object.
+    fn inherit_mtllibs(&mut self, object: &mut GroupingData) {
+        let Self {
+            objects,
+            string_pool,
+            ..
+        } = self;
+
+        let Some(previous) = objects.last() else {
+            return;
+        };
+
+        for mtllib in &previous.mtllibs {
+            let mut string = string_pool.pop().unwrap_or_default();
+            string.clear();
+            string.push_str(mtllib);
+            object.mtllibs.push(string);
+        }
+        for maplib in &previous.maplibs {
+            let mut string = string_pool.pop().unwrap_or_default();
+            string.clear();
+            string.push_str(maplib);
+            object.maplibs.push(string);
+        }
+    }
+
+    fn finish(&mut self, state: ParseState) {
+        let ParseState {
+            current_group,
+            mut current_object,
+            ..
+        } = state;
+
+        // store current group, objects always contain at least one (possibly empty) group
+        if !current_group.is_empty() || current_object.start == current_object.finish {
             current_object.finish += 1;
-            let finished = std::mem::take(&mut current_group);
-            groups.push(finished);
+            self.groups.push(current_group);
+        } else {
+            self.recycle(current_group);
         }
 
-        // store current object
-        if current_object.start != current_object.finish {
-            let finished = std::mem::take(&mut current_object);
-            objects.push(finished);
+        // store current object, so there always is at least one object
+        self.objects.push(current_object);
+    }
+
+    /// Parses a single statement.
+    ///
+    /// Everything from the first `#` on is a trailing comment, so names (e.g. of groups or materials) can not contain a `#`.
+    pub(crate) fn parse_line<'a>(
+        line: &'a str,
+        v_count: u32,
+        t_count: u32,
+        n_count: u32,
+        positions: &[[f32; 3]],
+        options: &ParseOptions,
+    ) -> Result<Line<'a>, Error> {
+        // comments may follow any statement
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+
+        let t: &[u8] = line.as_bytes();
+        let out = match t {
+            [b'v', b' ', ..] => {
+                let data = line[2..].trim();
+
+                let mut vertex = Self::parse_vertex(data)?;
+                if options.keep_f64_positions {
+                    vertex.position_f64 = Some(Self::parse_position_f64(data)?);
+                }
+
+                Line::Vertex(vertex)
+            }
+            // not kept, so only counted for relative indices, without parsing the numbers
+            [b'v', b'n', b' ', ..] if !options.keep_normals => Line::Normal([0.0; 3]),
+            [b'v', b't', b' ', ..] if !options.keep_texture_coords => Line::TextureCoord([0.0; 3]),
+            [b'v', b'n', b' ', ..] => Line::Normal(Self::parse_normal(line[3..].trim())?),
+            [b'v', b't', b' ', ..] => {
+                Line::TextureCoord(Self::parse_texture_coord(line[3..].trim())?)
+            }
+            [b'f', b' ', ..] => {
+                let (faces, corners) = Self::parse_face(
+                    line[2..].trim(),
+                    v_count,
+                    t_count,
+                    n_count,
+                    positions,
+                    options,
+                )?;
+                Line::Faces(faces, corners)
+            }
+            [b'l', b' ', ..] => {
+                let (indices, texture_indices) =
+                    Self::parse_polyline(line[2..].trim(), v_count, t_count)?;
+                Line::PolyLine(indices, texture_indices)
+            }
+            [b'p', b' ', ..] => {
+                Line::PointElement(Self::parse_point_element(line[2..].trim(), v_count)?)
+            }
+            [b'o', b' ', ..] => Line::Object(Self::parse_grouping(line[2..].trim())),
+            [b'g', b' ', ..] => Line::Group(Self::parse_grouping(line[2..].trim())),
+            // some exporters write a bare group statement, to return to the default group
+            [b'g'] => Line::Group(""),
+            [b's', b' ', ..] => Line::SmoothGroup(Self::parse_smooth_group(line[2..].trim())?),
+            [b'm', b't', b'l', b'l', b'i', b'b', b' ', ..] => {
+                Line::MaterialLib(Self::parse_mtl(line[7..].trim()))
+            }
+            [b'u', b's', b'e', b'm', b't', b'l', b' ', ..] => {
+                Line::MaterialUse(Self::parse_mtl(line[7..].trim()))
+            }
+            [b'm', b'a', b'p', b'l', b'i', b'b', b' ', ..] => {
+                Line::TextureMapLib(Self::parse_mtl(line[7..].trim()))
+            }
+            [b'u', b's', b'e', b'm', b'a', b'p', b' ', ..] => {
+                let map = Self::parse_mtl(line[7..].trim());
+                Line::TextureMapUse((map != "off").then_some(map))
+            }
+            _ => {
+                let (statement, data) = line
+                    .split_once(char::is_whitespace)
+                    .map_or((line, ""), |(statement, data)| (statement, data.trim()));
+
+                if let Some(attribute) = Self::parse_display_attribute(line, statement, data) {
+                    Line::DisplayAttribute(attribute)
+                } else if is_free_form(statement) {
+                    Line::FreeForm(statement)
+                } else {
+                    return Err(Error::UnknownLine(String::from(line)));
+                }
+            }
+        };
+
+        Ok(out)
+    }
+
+    /// Parses a vertex position, followed by an optional w component or an optional color.
+    ///
+    /// 3 values are a position, 4 values a position with w, 6 values a position with a RGB color,
+    /// and 7 values a position with a RGBA color.
+    fn parse_vertex(data: &str) -> Result<ParsedVertex, Error> {
+        let mut split = data.split_whitespace();
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let x = str.parse::<f32>()?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let y = str.parse::<f32>()?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let z = str.parse::<f32>()?;
+
+        let mut rest = [0.0; 4];
+        let mut count = 0;
+        for str in split.take(4) {
+            rest[count] = str.parse::<f32>()?;
+            count += 1;
+        }
+
+        let (w, color, alpha) = match (count, rest) {
+            (0, _) => (1.0, None, None),
+            (1, [w, ..]) => (w, None, None),
+            (3, [red, green, blue, _]) => (1.0, Some([red, green, blue]), None),
+            (4, [red, green, blue, alpha]) => (1.0, Some([red, green, blue]), Some(alpha)),
+            _ => return Err(Error::NonUniformColors),
+        };
+
+        Ok(ParsedVertex {
+            position: [x, y, z],
+            w,
+            color,
+            alpha,
+            position_f64: None,
+        })
+    }
+
+    /// Parses the position of a vertex (v) with double precision, ignoring any further components.
+    fn parse_position_f64(data: &str) -> Result<[f64; 3], Error> {
+        let mut split = data.split_whitespace();
+
+        let mut position = [0.0; 3];
+        for component in &mut position {
+            let str = split.next().ok_or(Error::UnexpectedEoL)?;
+            *component = str.parse::<f64>()?;
+        }
+
+        Ok(position)
+    }
+
+    fn parse_normal(data: &str) -> Result<[f32; 3], Error> {
+        let mut split = data.split_whitespace();
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let x = str.parse::<f32>()?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let y = str.parse::<f32>()?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let z = str.parse::<f32>()?;
+
+        Ok([x, y, z])
+    }
+
+    /// Parses a texture coord with 1 to 3 components, missing components default to `0.0`.
+    fn parse_texture_coord(data: &str) -> Result<[f32; 3], Error> {
+        let mut split = data.split_whitespace();
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let u = str.parse::<f32>()?;
+
+        let v = split.next().map_or(Ok(0.0), str::parse::<f32>)?;
+        let w = split.next().map_or(Ok(0.0), str::parse::<f32>)?;
+
+        Ok([u, v, w])
+    }
+
+    fn parse_face(
+        data: &str,
+        v_count: u32,
+        t_count: u32,
+        n_count: u32,
+        positions: &[[f32; 3]],
+        options: &ParseOptions,
+    ) -> Result<(Triangles, Corners), Error> {
+        // i t n
+        fn parse_single(
+            data: &str,
+            corner: usize,
+            v_count: u32,
+            t_count: u32,
+            n_count: u32,
+        ) -> Result<Corner, Error> {
+            let mut split = data.split('/');
+
+            // vertex index
+            let str = split.next().ok_or(Error::UnexpectedEoL)?;
+            let i = parse_index(str, v_count, corner, IndexKind::Vertex)?;
+
+            // texture index
+            let t = match split.next() {
+                None => return Ok((i, None, None)),
+
+                // ....//0980
+                Some("") => None,
+
+                // 986/0980...
+                Some(str) => Some(parse_index(str, t_count, corner, IndexKind::TextureCoord)?),
+            };
+
+            // normal index
+            let n = match split.next() {
+                None => return Ok((i, t, None)),
+
+                // .../.../1231
+                Some(str) => Some(parse_index(str, n_count, corner, IndexKind::Normal)?),
+            };
+
+            Ok((i, t, n))
+        }
+
+        let mut split = data.split_whitespace();
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let (i1, t1, n1) = parse_single(str, 1, v_count, t_count, n_count)?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let (i2, t2, n2) = parse_single(str, 2, v_count, t_count, n_count)?;
+
+        let str = split.next().ok_or(Error::UnexpectedEoL)?;
+        let (i3, t3, n3) = parse_single(str, 3, v_count, t_count, n_count)?;
+
+        // make sure the face does not exceed the vertex limit, before doing any more work
+        let limits = &options.limits;
+        let count = 3 + split.clone().count();
+        if count > limits.max_face_vertices {
+            return Err(Error::TooManyFaceVertices {
+                count,
+                limit: limits.max_face_vertices,
+            });
+        }
+
+        let mut corners: Corners = smallvec![(i1, t1, n1), (i2, t2, n2), (i3, t3, n3)];
+        for (i, str) in split.enumerate() {
+            corners.push(parse_single(str, i + 4, v_count, t_count, n_count)?);
+        }
+
+        // every corner has to use the same format as the first one
+        let has_texture = t1.is_some();
+        let has_normal = n1.is_some();
+        if corners
+            .iter()
+            .any(|(_, t, n)| t.is_some() != has_texture || n.is_some() != has_normal)
+        {
+            return Err(Error::InconsistentFaceFormat(String::from(data.trim())));
+        }
+
+        let faces = Self::triangulate_face(&corners, has_texture, has_normal, positions, options);
+
+        Ok((faces, corners))
+    }
+
+    /// Splits a face into triangles, splitting quads as requested by [`ParseOptions::quad_split`].
+    pub(crate) fn triangulate_face(
+        corners: &[Corner],
+        has_texture: bool,
+        has_normal: bool,
+        positions: &[[f32; 3]],
+        options: &ParseOptions,
+    ) -> Triangles {
+        // splitting a quad along 1-3 is the same as splitting the rotated quad along 0-2
+        match corners[..] {
+            [c0, c1, c2, c3]
+                if split_along_13(options.quad_split, [c0.0, c1.0, c2.0, c3.0], positions) =>
+            {
+                Self::triangulate(&[c1, c2, c3, c0], has_texture, has_normal)
+            }
+            _ => Self::triangulate(corners, has_texture, has_normal),
+        }
+    }
+
+    /// Parses the vertex and texture indices of a line (l) element.
+    ///
+    /// Either every vertex of the line has a texture index, or none of them.
+    fn parse_polyline(
+        data: &str,
+        v_count: u32,
+        t_count: u32,
+    ) -> Result<(Vec<u32>, Option<Vec<u32>>), Error> {
+        let mut indices = Vec::new();
+        let mut texture_indices = Vec::new();
+
+        for (i, str) in data.split_whitespace().enumerate() {
+            let mut split = str.split('/');
+
+            let str = split.next().ok_or(Error::UnexpectedEoL)?;
+            indices.push(parse_index(str, v_count, i + 1, IndexKind::Vertex)?);
+
+            if let Some(str) = split.next() {
+                texture_indices.push(parse_index(str, t_count, i + 1, IndexKind::TextureCoord)?);
+            }
+        }
+
+        if indices.len() < 2 {
+            return Err(Error::UnexpectedEoL);
+        }
+
+        // like the corners of a face, every vertex has to use the same format
+        let texture_indices = match texture_indices.len() {
+            0 => None,
+            len if len == indices.len() => Some(texture_indices),
+            _ => return Err(Error::InconsistentFaceFormat(String::from(data))),
+        };
+
+        Ok((indices, texture_indices))
+    }
+
+    /// Parses the vertex indices of a point (p) element, ignoring any texture indices.
+    fn parse_point_element(data: &str, v_count: u32) -> Result<Vec<u32>, Error> {
+        let indices = data
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, str)| {
+                // vertex index, optionally followed by /texture index
+                let str = str.split('/').next().unwrap_or_default();
+                parse_index(str, v_count, i + 1, IndexKind::Vertex)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if indices.is_empty() {
+            return Err(Error::UnexpectedEoL);
+        }
+
+        Ok(indices)
+    }
+
+    fn parse_smooth_group(data: &str) -> Result<Option<u32>, Error> {
+        match data {
+            "" => Err(Error::UnexpectedEoL),
+            // smoothing group 0 is the same as off
+            "off" | "0" => Ok(None),
+            _ => Ok(Some(data.parse::<u32>()?)),
+        }
+    }
+
+    /// Parses a display or render attribute statement, `None` if the statement is none of them.
+    ///
+    /// Display attributes never fail a parse, a malformed one is [`DisplayAttribute::Invalid`].
+    fn parse_display_attribute<'a>(
+        line: &'a str,
+        statement: &str,
+        data: &'a str,
+    ) -> Option<DisplayAttribute<'a>> {
+        let attribute = match statement {
+            "lod" => data.parse::<u8>().ok().map(DisplayAttribute::Lod),
+            "shadow_obj" => Self::parse_file_name(data).map(DisplayAttribute::ShadowObj),
+            "trace_obj" => Self::parse_file_name(data).map(DisplayAttribute::TraceObj),
+            "bevel" => Self::parse_switch(data).map(DisplayAttribute::Bevel),
+            "c_interp" => Self::parse_switch(data).map(DisplayAttribute::ColorInterpolation),
+            "d_interp" => Self::parse_switch(data).map(DisplayAttribute::DissolveInterpolation),
+            _ => return None,
+        };
+
+        Some(attribute.unwrap_or(DisplayAttribute::Invalid(line)))
+    }
+
+    fn parse_file_name(data: &str) -> Option<&str> {
+        (!data.is_empty()).then_some(data)
+    }
+
+    /// Parses the `on` or `off` of a display attribute.
+    fn parse_switch(data: &str) -> Option<bool> {
+        match data {
+            "on" => Some(true),
+            "off" => Some(false),
+            _ => None,
+        }
+    }
+
+    fn parse_grouping(data: &str) -> &str {
+        data.trim()
+    }
+
+    fn parse_mtl(data: &str) -> &str {
+        data.trim()
+    }
+
+    /// Triangulates a (convex) polygon as a fan around its first corner.
+    fn triangulate(corners: &[Corner], has_texture: bool, has_normal: bool) -> Triangles {
+        let (i0, t0, n0) = corners[0];
+
+        corners[1..]
+            .windows(2)
+            .map(|pair| {
+                let (i1, t1, n1) = pair[0];
+                let (i2, t2, n2) = pair[1];
+
+                FaceData {
+                    indicies: (i0, i1, i2),
+                    texture_indcicies: has_texture.then(|| (t0.unwrap(), t1.unwrap(), t2.unwrap())),
+                    normal_indicies: has_normal.then(|| (n0.unwrap(), n1.unwrap(), n2.unwrap())),
+                    smooth_group: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The name of the group, that elements before the first group statement (g) belong to.
+///
+/// A group statement without any name switches back to this group.
+pub const DEFAULT_GROUP_NAME: &str = "default";
+
+/// The vertex, texture and normal index of a single face corner.
+pub type Corner = (u32, Option<u32>, Option<u32>);
+/// The corners of a single face, most faces are triangles or quads.
+pub type Corners = SmallVec<[Corner; 4]>;
+/// The triangles a single face gets split into.
+pub type Triangles = SmallVec<[FaceData; 2]>;
+
+/// Limits enforced while parsing, to guard against pathological or malicious input.
+///
+/// The default does not limit anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// The maximum length of a single line in bytes, excluding the line terminator.
+    pub max_line_len: usize,
+    /// The maximum number of vertices in a single face (f).
+    pub max_face_vertices: usize,
+    /// The maximum number of vertex positions (v) in a file.
+    pub max_vertices: usize,
+    /// The maximum number of faces in a file, counting the triangles faces get split into, like [`ObjObject::face_count`].
+    pub max_faces: usize,
+    /// The maximum size of a file in bytes, only enforced by [`ObjObject::read_from_file_with`].
+    ///
+    /// The size reported by the file system is checked before anything is read.
+    pub max_file_size: u64,
+}
+
+impl Default for ParseLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_line_len: usize::MAX,
+            max_face_vertices: usize::MAX,
+            max_vertices: usize::MAX,
+            max_faces: usize::MAX,
+            max_file_size: u64::MAX,
+        }
+    }
+}
+
+/// The distance up to which vertex positions get welded, see [`ParseOptions::weld_epsilon`].
+///
+/// Compared by its bits, so [`ParseOptions`] can implement `Eq`.
+#[derive(Debug, Clone, Copy)]
+pub struct WeldEpsilon(pub f32);
+
+impl PartialEq for WeldEpsilon {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for WeldEpsilon {}
+
+/// Options controlling how a .obj file is parsed.
+///
+/// The default options parse a file strictly, keeping vertex colors, and splitting quads along their first diagonal.
+///
+/// ## Example
+/// ```rust
+/// use polypath::{ObjObject, ParseOptions, QuadSplit};
+///
+/// let options = ParseOptions::new()
+///     .strict(false)
+///     .keep_colors(false)
+///     .quad_split(QuadSplit::ShortestDiagonal);
+///
+/// let data = "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nunknown\nf 1 2 3\n";
+/// let obj = ObjObject::parse_with(std::io::Cursor::new(data), &options).unwrap();
+/// assert_eq!(obj.face_count(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParseOptions {
+    /// Limits enforced while parsing.
+    pub limits: ParseLimits,
+    /// How quads get split into two triangles.
+    pub quad_split: QuadSplit,
+    /// Whether the original polygon faces get stored next to their triangles,
+    /// see [`GroupRef::polygons_iter`][crate::obj::GroupRef::polygons_iter].
+    pub keep_polygons: bool,
+    /// Whether the (optional) w component of texture coords (vt) gets stored,
+    /// see [`ObjObject::texture_coords_3d`].
+    pub keep_texture_w: bool,
+    /// Whether the (optional) w component of vertex positions (v) gets stored,
+    /// see [`ObjObject::positions_4d`].
+    pub keep_vertex_w: bool,
+    /// Whether vertex colors get stored. If not, colors are ignored entirely, and may also be specified for only some vertices.
+    pub keep_colors: bool,
+    /// Whether vertex normals (vn) get stored. If not, the result looks like the file did not contain any normals,
+    /// e.g. for collision meshes only needing positions.
+    ///
+    /// The statements are only counted, so relative (negative) indices resolve correctly, but not parsed.
+    /// A [`LineParser`] returns them as `[0.0; 3]`.
+    pub keep_normals: bool,
+    /// Whether texture coords (vt) get stored. If not, the result looks like the file did not contain any texture coords.
+    ///
+    /// The statements are only counted, so relative (negative) indices resolve correctly, but not parsed.
+    /// A [`LineParser`] returns them as `[0.0; 3]`.
+    pub keep_texture_coords: bool,
+    /// Whether recoverable issues abort parsing.
+    ///
+    /// If not, every recoverable issue is skipped, like [`ObjObject::parse_with_warnings`] does,
+    /// e.g. unknown lines and non-finite values (see [`ParseOptions::reject_non_finite`]).
+    /// To only skip unknown lines, use [`ParseOptions::allow_unknown_lines`] instead.
+    pub strict: bool,
+    /// Whether unknown lines are skipped (with a [`ParseWarning::IgnoredLine`]), instead of returning [`Error::UnknownLine`].
+    ///
+    /// Unlike unsetting [`ParseOptions::strict`], every other issue still aborts parsing,
+    /// so this only makes files of non-standard exporters load. Unknown lines are always skipped outside of strict mode.
+    pub allow_unknown_lines: bool,
+    /// Whether invalid UTF-8 gets replaced by `U+FFFD`, instead of returning an error.
+    ///
+    /// This lets files with e.g. Latin-1 names or comments load, names contain the replacement characters.
+    /// Numeric fields are still parsed strictly, so a replaced byte inside of a number is an error.
+    pub lossy_utf8: bool,
+    /// Whether every group (g) and object (o) starts without a material.
+    ///
+    /// By default, a material use (usemtl) stays in effect until the next one, regardless of new groups or objects.
+    pub reset_mtl_per_group: bool,
+    /// Whether every object (o) only references the material libraries (mtllib) and texture map libraries (maplib) listed inside of it.
+    ///
+    /// By default, libraries are file-scoped, every object also references the libraries of all previous objects.
+    pub reset_mtllib_per_object: bool,
+    /// Whether vertex positions (v), normals (vn) and texture coords (vt) containing NaN or an infinite value
+    /// are rejected with [`Error::NonFiniteValue`]. On by default, if unset such values are kept as they are.
+    ///
+    /// Outside of strict mode, or with [`ObjObject::parse_with_warnings_and_options`], the non-finite values are
+    /// replaced by `0.0` (`1.0` for a w component) with a [`ParseWarning::NonFiniteValue`] instead.
+    /// The statement is still counted, so indices referencing the vertex data after it stay the same.
+    pub reject_non_finite: bool,
+    /// Whether vertex positions (v) are also stored with double precision, see [`ObjObject::positions_f64`].
+    pub keep_f64_positions: bool,
+    /// Welds vertex positions (v) differing by at most this distance per component after parsing,
+    /// see [`weld_positions`][crate::geometry::weld_positions]. `None` keeps all positions.
+    ///
+    /// Like [`ParseOptions::generate_normals`], this does not apply to a [`LineParser`].
+    pub weld_epsilon: Option<WeldEpsilon>,
+    /// Whether smooth normals are generated after parsing (and welding), if the file does not contain any normals,
+    /// see [`generate_normals`][crate::geometry::generate_normals].
+    pub generate_normals: bool,
+    /// The number of statements between two progress reports, see [`ObjObject::parse_with_progress`].
+    ///
+    /// Statements are counted instead of faces, as most files list all vertices before the first face.
+    /// With `0`, the progress is only reported once, after the whole input was read.
+    pub progress_interval: usize,
+    /// Whether a missing material library (mtllib) is an error for [`ObjObject::read_from_file_with_materials`],
+    /// instead of being listed in [`MaterialLibraries::missing`][crate::MaterialLibraries::missing].
+    pub require_mtllibs: bool,
+}
+
+impl Default for ParseOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            limits: ParseLimits::default(),
+            quad_split: QuadSplit::default(),
+            keep_polygons: false,
+            keep_texture_w: false,
+            keep_vertex_w: false,
+            keep_colors: true,
+            keep_normals: true,
+            keep_texture_coords: true,
+            strict: true,
+            allow_unknown_lines: false,
+            lossy_utf8: false,
+            reset_mtl_per_group: false,
+            reset_mtllib_per_object: false,
+            reject_non_finite: true,
+            keep_f64_positions: false,
+            weld_epsilon: None,
+            generate_normals: false,
+            progress_interval: 4096,
+            require_mtllibs: false,
         }
+    }
+}
+
+impl ParseOptions {
+    /// Returns the default options, see [`ParseOptions::default`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`ParseOptions::limits`].
+    #[inline]
+    #[must_use]
+    pub const fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets [`ParseOptions::quad_split`].
+    #[inline]
+    #[must_use]
+    pub const fn quad_split(mut self, quad_split: QuadSplit) -> Self {
+        self.quad_split = quad_split;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_polygons`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_polygons(mut self, keep_polygons: bool) -> Self {
+        self.keep_polygons = keep_polygons;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_texture_w`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_texture_w(mut self, keep_texture_w: bool) -> Self {
+        self.keep_texture_w = keep_texture_w;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_vertex_w`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_vertex_w(mut self, keep_vertex_w: bool) -> Self {
+        self.keep_vertex_w = keep_vertex_w;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_colors`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_colors(mut self, keep_colors: bool) -> Self {
+        self.keep_colors = keep_colors;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_normals`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_normals(mut self, keep_normals: bool) -> Self {
+        self.keep_normals = keep_normals;
+        self
+    }
+
+    /// Sets [`ParseOptions::keep_texture_coords`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_texture_coords(mut self, keep_texture_coords: bool) -> Self {
+        self.keep_texture_coords = keep_texture_coords;
+        self
+    }
+
+    /// Sets [`ParseOptions::strict`].
+    #[inline]
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets [`ParseOptions::allow_unknown_lines`].
+    #[inline]
+    #[must_use]
+    pub const fn allow_unknown_lines(mut self, allow_unknown_lines: bool) -> Self {
+        self.allow_unknown_lines = allow_unknown_lines;
+        self
+    }
 
-        Ok(Self {
-            vertices,
-            vertex_colors,
-            vertex_normals,
-            texture_coords,
-            faces,
+    /// Sets [`ParseOptions::lossy_utf8`].
+    #[inline]
+    #[must_use]
+    pub const fn lossy_utf8(mut self, lossy_utf8: bool) -> Self {
+        self.lossy_utf8 = lossy_utf8;
+        self
+    }
 
-            groups,
-            objects,
-        })
+    /// Sets [`ParseOptions::reset_mtl_per_group`].
+    #[inline]
+    #[must_use]
+    pub const fn reset_mtl_per_group(mut self, reset_mtl_per_group: bool) -> Self {
+        self.reset_mtl_per_group = reset_mtl_per_group;
+        self
     }
 
-    fn parse_line(line: &str, v_count: u32, t_count: u32, n_count: u32) -> Result<Line, Error> {
-        let line = line.trim();
+    /// Sets [`ParseOptions::reset_mtllib_per_object`].
+    #[inline]
+    #[must_use]
+    pub const fn reset_mtllib_per_object(mut self, reset_mtllib_per_object: bool) -> Self {
+        self.reset_mtllib_per_object = reset_mtllib_per_object;
+        self
+    }
 
-        if line.is_empty() {
-            return Ok(Line::Empty);
-        }
+    /// Sets [`ParseOptions::reject_non_finite`].
+    #[inline]
+    #[must_use]
+    pub const fn reject_non_finite(mut self, reject_non_finite: bool) -> Self {
+        self.reject_non_finite = reject_non_finite;
+        self
+    }
 
-        if line.starts_with('#') {
-            return Ok(Line::Comment);
-        }
+    /// Sets [`ParseOptions::keep_f64_positions`].
+    #[inline]
+    #[must_use]
+    pub const fn keep_f64_positions(mut self, keep_f64_positions: bool) -> Self {
+        self.keep_f64_positions = keep_f64_positions;
+        self
+    }
 
-        let t: &[u8] = line.as_bytes();
-        let out = match t {
-            [b'v', b' ', ..] => Line::Vertex(Self::parse_vertex(line[2..].trim())?),
-            [b'v', b'n', b' ', ..] => Line::Normal(Self::parse_normal(line[3..].trim())?),
-            [b'v', b't', b' ', ..] => {
-                Line::TextureCoord(Self::parse_texture_coord(line[3..].trim())?)
-            }
-            [b'f', b' ', ..] => {
-                let (f1, f2) = Self::parse_face(line[2..].trim(), v_count, t_count, n_count)?;
-                f2.map_or(Line::Face(f1), |f2| Line::DoubleFace(f1, f2))
-            }
-            [b'o', b' ', ..] => Line::Object(Self::parse_grouping(line[2..].trim())),
-            [b'g', b' ', ..] => Line::Group(Self::parse_grouping(line[2..].trim())),
-            [b's', b' ', ..] => {
-                // TODO: smooth
-                Line::Empty
-            }
-            [b'm', b't', b'l', b'l', b'i', b'b', b' ', ..] => {
-                Line::MaterialLib(Self::parse_mtl(line[7..].trim())?)
-            }
-            [b'u', b's', b'e', b'm', b't', b'l', b' ', ..] => {
-                Line::MaterialUse(Self::parse_mtl(line[7..].trim())?)
-            }
-            _ => return Err(Error::UnkownLine(String::from(line))),
+    /// Sets [`ParseOptions::weld_epsilon`].
+    #[inline]
+    #[must_use]
+    pub const fn weld_epsilon(mut self, weld_epsilon: Option<f32>) -> Self {
+        self.weld_epsilon = match weld_epsilon {
+            Some(epsilon) => Some(WeldEpsilon(epsilon)),
+            None => None,
         };
+        self
+    }
 
-        Ok(out)
+    /// Sets [`ParseOptions::generate_normals`].
+    #[inline]
+    #[must_use]
+    pub const fn generate_normals(mut self, generate_normals: bool) -> Self {
+        self.generate_normals = generate_normals;
+        self
     }
 
-    fn parse_vertex(data: &str) -> Result<VertexData, Error> {
-        let mut split = data.split_whitespace();
+    /// Sets [`ParseOptions::progress_interval`].
+    #[inline]
+    #[must_use]
+    pub const fn progress_interval(mut self, progress_interval: usize) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let x = str.parse::<f32>()?;
+    /// Sets [`ParseOptions::require_mtllibs`].
+    #[inline]
+    #[must_use]
+    pub const fn require_mtllibs(mut self, require_mtllibs: bool) -> Self {
+        self.require_mtllibs = require_mtllibs;
+        self
+    }
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let y = str.parse::<f32>()?;
+/// The diagonal along which a quad face gets split into two triangles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuadSplit {
+    /// Always split along the diagonal between the first and third vertex.
+    #[default]
+    Fixed02,
+    /// Split along the shorter of the two diagonals.
+    ShortestDiagonal,
+    /// Split along the diagonal that minimizes the dihedral angle between the two resulting triangles.
+    MostPlanar,
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let z = str.parse::<f32>()?;
+/// Decides whether a quad should be split along the 1-3 diagonal, instead of 0-2.
+///
+/// Falls back to 0-2 if any of the positions is not known (yet).
+fn split_along_13(mode: QuadSplit, index: [u32; 4], positions: &[[f32; 3]]) -> bool {
+    if mode == QuadSplit::Fixed02 {
+        return false;
+    }
 
-        if let Some(str) = split.next() {
-            let a = str.parse::<f32>()?;
+    let mut quad = [Vec3::zero(); 4];
+    for (p, i) in quad.iter_mut().zip(index) {
+        // indices are 1-based
+        match positions.get((i as usize).wrapping_sub(1)) {
+            Some(position) => *p = Vec3::from(*position),
+            None => return false,
+        }
+    }
+    let [p0, p1, p2, p3] = quad;
+
+    match mode {
+        QuadSplit::Fixed02 => false,
+        QuadSplit::ShortestDiagonal => p1.distance(p3) < p0.distance(p2),
+        QuadSplit::MostPlanar => {
+            // cosine of the angle between the normals of the two triangles
+            let planarity = |a: Vec3, b: Vec3, c: Vec3, d: Vec3| {
+                let n1 = (b - a).cross(&(c - a)).normalized();
+                let n2 = (c - a).cross(&(d - a)).normalized();
+                n1.dot(&n2)
+            };
 
-            let str = split.next().ok_or(Error::NonUniformColors)?;
-            let b = str.parse::<f32>()?;
+            planarity(p1, p2, p3, p0) > planarity(p0, p1, p2, p3)
+        }
+    }
+}
 
-            let str = split.next().ok_or(Error::NonUniformColors)?;
-            let c = str.parse::<f32>()?;
+/// The object and group that are currently being filled while parsing.
+#[derive(Debug, Default)]
+pub struct ParseState<'w> {
+    current_group: GroupingData,
+    current_object: GroupingData,
+    /// The active smoothing group, `None` if smoothing is off.
+    smooth_group: Option<u32>,
+    options: ParseOptions,
+    /// The (1-based) number of the (first physical) line currently being parsed.
+    line: usize,
+    /// Collects warnings instead of failing on recoverable issues, if set.
+    warnings: Option<&'w mut Vec<ParseWarning>>,
+}
 
-            return Ok(VertexData {
-                position: (x, y, z),
-                color: Some((a, b, c)),
-            });
-        }
+impl ParseState<'_> {
+    /// Whether recoverable issues are skipped, instead of returning an error.
+    #[inline]
+    const fn lenient(&self) -> bool {
+        !self.options.strict || self.warnings.is_some()
+    }
 
-        Ok(VertexData {
-            position: (x, y, z),
-            color: None,
-        })
+    #[inline]
+    fn warn(&mut self, warning: ParseWarning) {
+        if let Some(warnings) = &mut self.warnings {
+            warnings.push(warning);
+        }
     }
+}
 
-    fn parse_normal(data: &str) -> Result<(f32, f32, f32), Error> {
-        let mut split = data.split_whitespace();
+/// A non-fatal issue found by [`ObjObject::parse_with_warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// A vertex normal (vn) with a length of zero.
+    ZeroNormal { line: usize },
+    /// A line that could not be interpreted and was skipped.
+    IgnoredLine { line: usize, data: String },
+    /// A free-form geometry statement (e.g. `curv` or `surf`), which was skipped.
+    SkippedFreeForm { line: usize, statement: String },
+    /// A vertex position (v), normal (vn) or texture coord (vt) containing NaN or an infinite value,
+    /// see [`ParseOptions::reject_non_finite`].
+    ///
+    /// The non-finite values were replaced, so indices referencing the statement or elements after it stay valid.
+    /// Like for [`Error::NonFiniteValue`], the line is `0` for data decoded from other formats.
+    NonFiniteValue { line: usize, statement: String },
+    /// A display or render attribute (e.g. `lod` or `bevel`) with a missing or malformed value, which was skipped.
+    InvalidDisplayAttribute { line: usize, statement: String },
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let x = str.parse::<f32>()?;
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroNormal { line } => writeln!(f, "on line {line}: Zero length vertex normal"),
+            Self::IgnoredLine { line, data } => {
+                writeln!(f, "on line {line}: Ignored unknown line: [{data}]")
+            }
+            Self::SkippedFreeForm { line, statement } => writeln!(
+                f,
+                "on line {line}: Skipped free-form geometry statement [{statement}]"
+            ),
+            Self::NonFiniteValue { line, statement } => writeln!(
+                f,
+                "on line {line}: Replaced non-finite value of statement [{statement}]"
+            ),
+            Self::InvalidDisplayAttribute { line, statement } => writeln!(
+                f,
+                "on line {line}: Skipped invalid display attribute [{statement}]"
+            ),
+        }
+    }
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let y = str.parse::<f32>()?;
+/// Parses a .obj file statement by statement, without building an [`ObjObject`].
+///
+/// Useful to build custom data structures from very large files. Faces are triangulated, and relative (negative)
+/// indices resolved, exactly like [`ObjObject::parse_with`] does: the number of vertex positions, texture coords and
+/// normals is counted internally, so all returned indices are already absolute.
+///
+/// Empty lines and comments are skipped. After a parse error, the next call continues with the following line.
+///
+/// ## Example
+/// ```rust
+/// use polypath::{Line, LineParser, ParseOptions};
+///
+/// let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf -4 -3 -1 -2\n";
+/// let mut parser = LineParser::new(std::io::Cursor::new(data), &ParseOptions::default());
+///
+/// let mut triangles = Vec::new();
+/// while let Some(line) = parser.next_line().unwrap() {
+///     if let Line::Faces(faces, _) = line {
+///         triangles.extend(faces.iter().map(|face| face.indices()));
+///     }
+/// }
+/// assert_eq!(triangles, [[1, 2, 4], [1, 4, 3]]);
+/// ```
+pub struct LineParser<R> {
+    reader: LineReader<R>,
+    statements: StatementParser,
+    line: usize,
+    /// Only kept if quads are not split along a fixed diagonal.
+    positions: Vec<[f32; 3]>,
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let z = str.parse::<f32>()?;
+impl<R: std::io::BufRead> LineParser<R> {
+    /// Creates a new parser, reading from some sort of input reader.
+    #[inline]
+    pub fn new(reader: R, options: &ParseOptions) -> Self {
+        Self {
+            reader: LineReader::new(reader, options.limits.max_line_len).lossy(options.lossy_utf8),
+            statements: StatementParser::new(options),
+            line: 0,
+            positions: Vec::new(),
+        }
+    }
+
+    /// Parses the next statement.
+    ///
+    /// Returns `None` once the end of the input is reached.
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, or the statement is not as structured as expected.
+    pub fn next_line(&mut self) -> Result<Option<Line<'_>>, Error> {
+        let Some((line_number, line)) = self.reader.next_line()? else {
+            return Ok(None);
+        };
+        self.line = line_number;
+
+        let line = self
+            .statements
+            .parse(line, line_number, &self.positions)
+            .map_err(|err| match err {
+                err @ Error::NonFiniteValue { .. } => err,
+                err => err.at_line(line_number),
+            })?;
+
+        if let Line::Vertex(vertex) = &line
+            && self.statements.options.quad_split != QuadSplit::Fixed02
+        {
+            self.positions.push(vertex.position);
+        }
 
-        Ok((x, y, z))
+        Ok(Some(line))
     }
 
-    fn parse_texture_coord(data: &str) -> Result<(f32, f32), Error> {
-        let mut split = data.split_whitespace();
+    #[inline]
+    #[must_use]
+    /// Returns the (1-based) line number of the last parsed statement, `0` if nothing was parsed yet.
+    pub const fn line_number(&self) -> usize {
+        self.line
+    }
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let x = str.parse::<f32>()?;
+    #[inline]
+    #[must_use]
+    /// Returns the number of bytes read so far, e.g. to report the progress of parsing a large file.
+    pub fn bytes_read(&self) -> u64 {
+        self.reader.bytes_read()
+    }
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let y = str.parse::<f32>()?;
+/// Parses single statements, counting the vertex positions, texture coords and normals,
+/// so relative (negative) indices can be resolved.
+pub struct StatementParser {
+    options: ParseOptions,
+    positions: u32,
+    texture_coords: u32,
+    normals: u32,
+    faces: usize,
+}
 
-        Ok((x, y))
+impl StatementParser {
+    #[inline]
+    pub const fn new(options: &ParseOptions) -> Self {
+        Self {
+            options: *options,
+            positions: 0,
+            texture_coords: 0,
+            normals: 0,
+            faces: 0,
+        }
     }
 
-    fn parse_face(
-        data: &str,
-        v_count: u32,
-        t_count: u32,
-        n_count: u32,
-    ) -> Result<(FaceData, Option<FaceData>), Error> {
-        // i t n
-        fn parse_single(
-            data: &str,
-            v_count: u32,
-            t_count: u32,
-            n_count: u32,
-        ) -> Result<(u32, Option<u32>, Option<u32>), Error> {
-            let mut split = data.split('/');
+    /// Starts counting after the given number of vertex positions, texture coords and normals,
+    /// to parse the statements of a file from the middle.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub const fn starting_after(
+        mut self,
+        positions: u32,
+        texture_coords: u32,
+        normals: u32,
+    ) -> Self {
+        self.positions = positions;
+        self.texture_coords = texture_coords;
+        self.normals = normals;
+        self
+    }
 
-            // vertex index
-            let str = split.next().ok_or(Error::UnexpectedEoL)?;
-            let i = str.parse::<i32>()?;
-            let i = if i < 0 {
-                // negativ index, meaning
-                // => -1 = v_count
-                // => -2 = v_count - 1
-                (v_count as i32 + (i + 1)) as u32
-            } else {
-                i as u32
-            };
+    /// Parses a single (non-empty) line.
+    ///
+    /// `positions` are only used to decide how quads get split, see [`QuadSplit`].
+    /// `line_number` is only used for [`Error::NonFiniteValue`].
+    pub fn parse<'a>(
+        &mut self,
+        data: &'a str,
+        line_number: usize,
+        positions: &[[f32; 3]],
+    ) -> Result<Line<'a>, Error> {
+        let line = ObjObject::parse_line(
+            data,
+            self.positions,
+            self.texture_coords,
+            self.normals,
+            positions,
+            &self.options,
+        )?;
+
+        self.count(&line)?;
+
+        // counted anyways, as the statement may still be kept with a placeholder
+        if self.options.reject_non_finite && !is_finite(&line) {
+            return Err(Error::NonFiniteValue {
+                statement: data.trim().to_string(),
+                line: line_number,
+            });
+        }
 
-            // texture index
-            let t = match split.next() {
-                None => return Ok((i, None, None)),
+        Ok(line)
+    }
 
-                // ....//0980
-                Some("") => None,
+    /// Counts a parsed statement, after checking it against the limits.
+    fn count(&mut self, line: &Line<'_>) -> Result<(), Error> {
+        // checked before the statement gets stored, so the limits bound the memory used
+        let limits = &self.options.limits;
+        match line {
+            Line::Vertex(_) if self.positions as usize >= limits.max_vertices => {
+                return Err(Error::TooManyVertices {
+                    limit: limits.max_vertices,
+                });
+            }
+            Line::Faces(faces, _) if self.faces.saturating_add(faces.len()) > limits.max_faces => {
+                return Err(Error::TooManyFaces {
+                    limit: limits.max_faces,
+                });
+            }
+            _ => {}
+        }
 
-                // 986/0980...
-                Some(str) => {
-                    let t = str.parse::<i32>()?;
-                    let t = if t < 0 {
-                        // negativ index
-                        (t_count as i32 + (t + 1)) as u32
-                    } else {
-                        t as u32
-                    };
-
-                    Some(t)
-                }
-            };
+        match line {
+            Line::Vertex(_) => self.positions = self.positions.saturating_add(1),
+            Line::Faces(faces, _) => self.faces += faces.len(),
+            Line::TextureCoord(_) => self.texture_coords = self.texture_coords.saturating_add(1),
+            Line::Normal(_) => self.normals = self.normals.saturating_add(1),
+            _ => {}
+        }
 
-            // normal index
-            let n = match split.next() {
-                None => return Ok((i, t, None)),
+        Ok(())
+    }
 
-                // .../.../1231
-                Some(str) => {
-                    let n = str.parse::<i32>()?;
-                    let n = if n < 0 {
-                        // negativ index
-                        (n_count as i32 + (n + 1)) as u32
-                    } else {
-                        n as u32
-                    };
-
-                    Some(n)
+    /// Counts a statement parsed by another `StatementParser` (see [`StatementParser::starting_after`]),
+    /// as if this parser parsed it, so following statements resolve their relative indices
+    /// and are checked against the limits across the whole file.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn count_parsed<'a>(
+        &mut self,
+        line: Result<Line<'a>, Error>,
+    ) -> Result<Line<'a>, Error> {
+        match line {
+            Ok(line) => self.count(&line).map(|()| line),
+            Err(Error::NonFiniteValue { statement, line }) => {
+                // non-finite values are counted anyways, see `parse`
+                if let Ok(parsed) = ObjObject::parse_line(&statement, 0, 0, 0, &[], &self.options) {
+                    self.count(&parsed)?;
                 }
-            };
-
-            Ok((i, t, n))
+                Err(Error::NonFiniteValue { statement, line })
+            }
+            Err(err) => Err(err),
         }
+    }
+}
 
-        let mut split = data.split_whitespace();
+/// Whether all values of a vertex position (v), normal (vn) or texture coord (vt) are finite.
+fn is_finite(line: &Line) -> bool {
+    let all_finite = |values: &[f32]| values.iter().all(|value| value.is_finite());
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let (i1, t1, n1) = parse_single(str, v_count, t_count, n_count)?;
+    match line {
+        Line::Vertex(vertex) => {
+            all_finite(&vertex.position)
+                && vertex.w.is_finite()
+                && vertex.color.is_none_or(|color| all_finite(&color))
+                && vertex.alpha.is_none_or(f32::is_finite)
+        }
+        Line::Normal(values) | Line::TextureCoord(values) => all_finite(values),
+        _ => true,
+    }
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let (i2, t2, n2) = parse_single(str, v_count, t_count, n_count)?;
+/// Adds a warning, if warnings are collected.
+#[inline]
+fn warn(warnings: &mut Option<&mut Vec<ParseWarning>>, warning: impl FnOnce() -> ParseWarning) {
+    if let Some(warnings) = warnings {
+        warnings.push(warning());
+    }
+}
 
-        let str = split.next().ok_or(Error::UnexpectedEoL)?;
-        let (i3, t3, n3) = parse_single(str, v_count, t_count, n_count)?;
+/// Formats a decoded vertex position (v), normal (vn) or texture coord (vt) like its .obj statement, see [`ObjObject::push_decoded`].
+#[cfg(any(feature = "ply", feature = "stl"))]
+fn non_finite_statement(line: &Line) -> String {
+    let join = |values: &[f32]| {
+        values
+            .iter()
+            .map(f32::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    match line {
+        Line::Vertex(vertex) => {
+            let color = vertex.color.map(|color| format!(" {}", join(&color)));
+            format!("v {}{}", join(&vertex.position), color.unwrap_or_default())
+        }
+        Line::Normal(values) => format!("vn {}", join(values)),
+        Line::TextureCoord(values) => format!("vt {}", join(values)),
+        _ => String::new(),
+    }
+}
 
-        let normal = match (n1, n2, n3) {
-            (None, None, None) => None,
-            (Some(n1), Some(n2), Some(n3)) => Some((n1, n2, n3)),
-            _ => unreachable!(""),
-        };
+/// Replaces the non-finite values of a vertex position (v), normal (vn) or texture coord (vt) by a placeholder.
+fn replace_non_finite(line: Line) -> Line {
+    let finite = |value: f32, placeholder: f32| {
+        if value.is_finite() {
+            value
+        } else {
+            placeholder
+        }
+    };
+    let replace = |values: [f32; 3]| values.map(|value| finite(value, 0.0));
+
+    match line {
+        Line::Vertex(vertex) => Line::Vertex(ParsedVertex {
+            position: replace(vertex.position),
+            w: finite(vertex.w, 1.0),
+            color: vertex.color.map(replace),
+            alpha: vertex.alpha.map(|alpha| finite(alpha, 0.0)),
+            position_f64: vertex
+                .position_f64
+                .map(|position| position.map(|value| if value.is_finite() { value } else { 0.0 })),
+        }),
+        Line::Normal(values) => Line::Normal(replace(values)),
+        Line::TextureCoord(values) => Line::TextureCoord(replace(values)),
+        line => line,
+    }
+}
 
-        let texture = match (t1, t2, t3) {
-            (None, None, None) => None,
-            (Some(t1), Some(t2), Some(t3)) => Some((t1, t2, t3)),
-            _ => unreachable!(""),
-        };
+/// The progress of parsing a .obj file, see [`ObjObject::parse_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseProgress {
+    /// The number of bytes read so far.
+    pub bytes_read: u64,
+    /// The size of the whole input in bytes, if known (e.g. from the metadata of a file).
+    pub total_bytes: Option<u64>,
+    /// The number of vertex positions (v) parsed so far.
+    pub vertices: usize,
+    /// The number of faces parsed so far, counting the triangles faces get split into.
+    pub faces: usize,
+}
 
-        // check for 4th vertex
-        if let Some(str) = split.next() {
-            let (i4, t4, n4) = parse_single(str, v_count, t_count, n_count)?;
+/// Reports the progress of parsing to a callback, see [`ObjObject::parse_with_progress`].
+pub struct Progress<'p> {
+    callback: &'p mut dyn FnMut(ParseProgress) -> ControlFlow<()>,
+    total_bytes: Option<u64>,
+    interval: usize,
+    statements: usize,
+}
 
-            let normals = normal.map(|(n1, n2, n3)| [n1, n2, n3, n4.unwrap()]);
-            let texture = texture.map(|(t1, t2, t3)| [t1, t2, t3, t4.unwrap()]);
-            let [f1, f2] = Self::triangulate([i1, i2, i3, i4], normals, texture);
+impl<'p> Progress<'p> {
+    #[inline]
+    pub fn new(
+        callback: &'p mut dyn FnMut(ParseProgress) -> ControlFlow<()>,
+        total_bytes: Option<u64>,
+        interval: usize,
+    ) -> Self {
+        Self {
+            callback,
+            total_bytes,
+            interval,
+            statements: 0,
+        }
+    }
 
-            return Ok((f1, Some(f2)));
+    /// Counts a parsed statement, reporting the progress every [`ParseOptions::progress_interval`] statements.
+    ///
+    /// Returns [`Error::Cancelled`], if the callback requested it.
+    #[inline]
+    fn statement(&mut self, bytes_read: u64, obj: &ObjObject) -> Result<(), Error> {
+        self.statements += 1;
+        if !self.statements.is_multiple_of(self.interval) {
+            return Ok(());
         }
 
-        Ok((
-            FaceData {
-                indicies: (i1, i2, i3),
-                normal_indicies: normal,
-                texture_indcicies: texture,
-            },
-            None,
-        ))
+        match self.report(bytes_read, obj) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(Error::Cancelled),
+        }
     }
 
-    fn parse_grouping(data: &str) -> String {
-        let trimmed = data.trim();
-        trimmed.to_string()
+    /// Reports the final progress, after the whole input was read. Parsing is already done, so it can not be cancelled anymore.
+    fn finish(&mut self, bytes_read: u64, obj: &ObjObject) {
+        let _ = self.report(bytes_read, obj);
     }
 
-    fn parse_mtl(data: &str) -> Result<String, Error> {
-        let str = data.trim();
-        Ok(str.to_owned())
+    fn report(&mut self, bytes_read: u64, obj: &ObjObject) -> ControlFlow<()> {
+        (self.callback)(ParseProgress {
+            bytes_read,
+            total_bytes: self.total_bytes,
+            vertices: obj.vertices.len(),
+            faces: obj.faces.len(),
+        })
     }
+}
 
-    const fn triangulate(
-        index: [u32; 4],
-        normals: Option<[u32; 4]>,
-        texture: Option<[u32; 4]>,
-    ) -> [FaceData; 2] {
-        let i1 = (index[0], index[1], index[2]);
-        let i2 = (index[0], index[2], index[3]);
+/// An [`ObjObject`] together with the warnings collected while parsing it.
+#[derive(Debug)]
+pub struct ParseResult {
+    pub obj: ObjObject,
+    pub warnings: Vec<ParseWarning>,
+}
 
-        let (n1, n2) = match normals {
-            Some(normals) => {
-                let n1 = (normals[0], normals[1], normals[2]);
-                let n2 = (normals[0], normals[2], normals[3]);
+/// Removes the normal and texture indices of a face, if they are not kept, see [`ParseOptions::keep_normals`].
+fn discard_attributes(faces: &mut Triangles, corners: &mut Corners, options: &ParseOptions) {
+    if options.keep_normals && options.keep_texture_coords {
+        return;
+    }
 
-                (Some(n1), Some(n2))
-            }
-            None => (None, None),
-        };
+    for face in faces {
+        if !options.keep_normals {
+            face.normal_indicies = None;
+        }
+        if !options.keep_texture_coords {
+            face.texture_indcicies = None;
+        }
+    }
+    for (_, texture, normal) in corners {
+        if !options.keep_normals {
+            *normal = None;
+        }
+        if !options.keep_texture_coords {
+            *texture = None;
+        }
+    }
+}
 
-        let (t1, t2) = match texture {
-            Some(texture) => {
-                let n1 = (texture[0], texture[1], texture[2]);
-                let n2 = (texture[0], texture[2], texture[3]);
+/// Replaces the content of a `String`, reusing its allocation.
+#[inline]
+fn assign(target: &mut String, data: &str) {
+    target.clear();
+    target.push_str(data);
+}
 
-                (Some(n1), Some(n2))
-            }
-            None => (None, None),
-        };
+/// Returns `true` for statements of free-form geometry (curves and surfaces), which are not supported,
+/// but get skipped, so the polygonal part of a file still loads.
+#[inline]
+pub fn is_free_form(statement: &str) -> bool {
+    matches!(
+        statement,
+        "vp" | "cstype"
+            | "deg"
+            | "bmat"
+            | "step"
+            | "curv"
+            | "curv2"
+            | "surf"
+            | "parm"
+            | "trim"
+            | "hole"
+            | "scrv"
+            | "sp"
+            | "end"
+            | "con"
+            | "ctech"
+            | "stech"
+    )
+}
 
-        [
-            FaceData {
-                indicies: i1,
-                texture_indcicies: t1,
-                normal_indicies: n1,
-            },
-            FaceData {
-                indicies: i2,
-                texture_indcicies: t2,
-                normal_indicies: n2,
-            },
-        ]
+#[inline]
+pub fn index_count(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+/// Parses and resolves a single (1-based) index of a face, line or point element.
+#[inline]
+fn parse_index(str: &str, count: u32, corner: usize, kind: IndexKind) -> Result<u32, Error> {
+    let index = str.parse::<i32>()?;
+
+    resolve_index(index, count).ok_or(if index == 0 {
+        Error::ZeroIndex { corner, kind }
+    } else {
+        Error::RelativeIndexOutOfRange {
+            index,
+            count,
+            corner,
+            kind,
+        }
+    })
+}
+
+/// Resolves a relative (negative) index, returns `None` if the index does not resolve to a positive one.
+#[inline]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const fn resolve_index(index: i32, count: u32) -> Option<u32> {
+    if index < 0 {
+        // negativ index, meaning
+        // => -1 = count
+        // => -2 = count - 1
+        let index = count as i64 + (index as i64 + 1);
+        if index <= 0 { None } else { Some(index as u32) }
+    } else if index == 0 {
+        None
+    } else {
+        Some(index as u32)
     }
 }
 
+/// A single statement of a .obj file, as returned by [`LineParser::next_line`].
+///
+/// All indices are absolute (1-based), relative (negative) indices are already resolved.
 #[derive(Debug, Clone)]
-pub enum Line {
-    Empty,
-    Comment,
-    Vertex(VertexData),
-    Normal((f32, f32, f32)),
-    TextureCoord((f32, f32)),
-    Face(FaceData),
-    DoubleFace(FaceData, FaceData),
-    MaterialLib(String),
-    MaterialUse(String),
-    Group(String),
-    Object(String),
+pub enum Line<'a> {
+    /// A vertex position (v), with its optional w component or color.
+    Vertex(ParsedVertex),
+    /// A vertex normal (vn).
+    Normal([f32; 3]),
+    /// A texture coord (vt) with u, v and w, missing components are `0.0`.
+    TextureCoord([f32; 3]),
+    /// The triangles of a single face (f), and its corners as written in the file.
+    Faces(Triangles, Corners),
+    /// The resolved vertex and (optional) texture indices of a line (l) element.
+    #[allow(clippy::enum_variant_names)]
+    PolyLine(Vec<u32>, Option<Vec<u32>>),
+    /// The resolved vertex indices of a point (p) element.
+    PointElement(Vec<u32>),
+    /// A smoothing group (s), `None` if turned off.
+    SmoothGroup(Option<u32>),
+    /// A material lib (mtllib).
+    MaterialLib(&'a str),
+    /// A material use (usemtl).
+    MaterialUse(&'a str),
+    /// A texture map library (maplib).
+    TextureMapLib(&'a str),
+    /// A texture map use (usemap), `None` if turned off.
+    TextureMapUse(Option<&'a str>),
+    /// A display or render attribute (lod, `shadow_obj`, `trace_obj`, bevel, `c_interp`, `d_interp`).
+    DisplayAttribute(DisplayAttribute<'a>),
+    /// A group (g), with all of its names. Empty for a group statement without names.
+    Group(&'a str),
+    /// An object (o).
+    Object(&'a str),
+    /// A skipped free-form geometry statement.
+    FreeForm(&'a str),
+}
+
+/// A display or render attribute statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayAttribute<'a> {
+    /// Level of detail (lod), from `1` to `100`, `0` turns it off.
+    Lod(u8),
+    /// File of the object casting shadows (`shadow_obj`).
+    ShadowObj(&'a str),
+    /// File of the object used for ray tracing (`trace_obj`).
+    TraceObj(&'a str),
+    /// Bevel interpolation (bevel).
+    Bevel(bool),
+    /// Color interpolation (`c_interp`).
+    ColorInterpolation(bool),
+    /// Dissolve interpolation (`d_interp`).
+    DissolveInterpolation(bool),
+    /// A display attribute with a missing or malformed value, containing the whole statement.
+    ///
+    /// It is skipped with a [`ParseWarning::InvalidDisplayAttribute`], instead of failing the parse.
+    Invalid(&'a str),
+}
+
+/// The display and render attributes of an object, `None` for every attribute not specified inside of it.
+///
+/// If an object contains multiple statements of the same attribute, the last one is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayAttributes {
+    /// Level of detail (lod), from `1` to `100`, `0` turns it off.
+    pub lod: Option<u8>,
+    /// File of the object casting shadows (`shadow_obj`).
+    pub shadow_obj: Option<String>,
+    /// File of the object used for ray tracing (`trace_obj`).
+    pub trace_obj: Option<String>,
+    /// Bevel interpolation (bevel).
+    pub bevel: Option<bool>,
+    /// Color interpolation (`c_interp`).
+    pub color_interpolation: Option<bool>,
+    /// Dissolve interpolation (`d_interp`).
+    pub dissolve_interpolation: Option<bool>,
 }
 
+/// A vertex position (v) statement.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct VertexData {
-    pub position: (f32, f32, f32),
-    pub color: Option<(f32, f32, f32)>,
+pub struct ParsedVertex {
+    pub position: [f32; 3],
+    /// The homogeneous w component, `1.0` if not specified.
+    pub w: f32,
+    pub color: Option<[f32; 3]>,
+    pub alpha: Option<f32>,
+    /// The position with double precision, only parsed if [`ParseOptions::keep_f64_positions`] is set.
+    pub position_f64: Option<[f64; 3]>,
 }
 
+/// A single triangle of a face (f).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FaceData {
     // for vertex & color
     pub(crate) indicies: (u32, u32, u32),
     pub(crate) texture_indcicies: Option<(u32, u32, u32)>,
     pub(crate) normal_indicies: Option<(u32, u32, u32)>,
+    pub(crate) smooth_group: Option<u32>,
+}
+
+impl FaceData {
+    #[inline]
+    #[must_use]
+    /// Returns the (1-based) position indices of the triangle corners.
+    pub fn indices(&self) -> [u32; 3] {
+        self.indicies.into()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the (1-based) texture coord indices of the triangle corners, if the face has texture coords.
+    pub fn texture_indices(&self) -> Option<[u32; 3]> {
+        self.texture_indcicies.map(<[u32; 3]>::from)
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the (1-based) normal indices of the triangle corners, if the face has normals.
+    pub fn normal_indices(&self) -> Option<[u32; 3]> {
+        self.normal_indicies.map(<[u32; 3]>::from)
+    }
+}
+
+/// A polygon face (f) as written in the .obj file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonData {
+    /// Index of the first triangle this polygon was split into.
+    pub(crate) face: usize,
+    /// Range of the corners of this polygon.
+    pub(crate) start: usize,
+    pub(crate) finish: usize,
+    pub(crate) smooth_group: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupingData {
     pub(crate) name: String,
+    /// Further names of a group (g), after the first one, not used for objects.
+    pub(crate) other_names: Vec<String>,
+    /// Material use (usemtl) of a group, not used for objects.
     pub(crate) mtl: Option<String>,
+    /// Material libraries (mtllib) of an object, in the order they are referenced, not used for groups.
+    pub(crate) mtllibs: Vec<String>,
+    /// Material uses (usemtl) after the first face of a group, with the (absolute) index of the first face they apply to,
+    /// not used for objects.
+    pub(crate) mtl_changes: Vec<(usize, String)>,
+    /// Texture map use (usemap) of a group, not used for objects.
+    pub(crate) map: Option<String>,
+    /// Texture map libraries (maplib) of an object, in the order they are referenced, not used for groups.
+    pub(crate) maplibs: Vec<String>,
+    /// Display and render attributes of an object, not used for groups.
+    pub(crate) display: DisplayAttributes,
     pub(crate) start: usize,
     pub(crate) finish: usize,
+    /// Range of the line (l) elements of a group, not used for objects.
+    pub(crate) line_start: usize,
+    pub(crate) line_finish: usize,
+    /// Range of the point (p) elements of a group, not used for objects.
+    pub(crate) point_start: usize,
+    pub(crate) point_finish: usize,
+    /// Smoothing group active at the start of a group, not used for objects.
+    pub(crate) smooth: Option<u32>,
+    /// Column-major transform of an object, not used for groups.
+    pub(crate) transform: Option<[[f32; 4]; 4]>,
+}
+
+impl GroupingData {
+    /// Returns `true` if a group contains neither faces, nor line or point elements.
+    #[inline]
+    const fn is_empty(&self) -> bool {
+        self.start == self.finish
+            && self.line_start == self.line_finish
+            && self.point_start == self.point_finish
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
-    use crate::ObjObject;
-    use crate::parse::FaceData;
+    use crate::parse::{FaceData, ParseOptions};
+    use crate::{Error, ObjObject};
 
     #[test]
     fn test_vertex_no_color() {
@@ -450,8 +2466,9 @@ mod tests {
 
         let res = ObjObject::parse_vertex(line).unwrap();
 
-        assert_eq!(res.position, (1.0, 1.2, 0.0));
+        assert_eq!(res.position, [1.0, 1.2, 0.0]);
         assert!(res.color.is_none());
+        assert!(res.alpha.is_none());
     }
 
     #[test]
@@ -460,8 +2477,44 @@ mod tests {
 
         let res = ObjObject::parse_vertex(line).unwrap();
 
-        assert_eq!(res.position, (1.0, 1.2, 0.0));
-        assert_eq!(res.color, Some((255.0, 123.0, 90.0)));
+        assert_eq!(res.position, [1.0, 1.2, 0.0]);
+        assert_eq!(res.color, Some([255.0, 123.0, 90.0]));
+        assert!(res.alpha.is_none());
+    }
+
+    #[test]
+    fn test_vertex_w() {
+        let line = "1.0  1.2  0.0  0.5";
+
+        let res = ObjObject::parse_vertex(line).unwrap();
+
+        assert_eq!(res.position, [1.0, 1.2, 0.0]);
+        assert_eq!(res.w, 0.5);
+        assert!(res.color.is_none());
+    }
+
+    #[test]
+    fn test_vertex_with_alpha() {
+        let line = "1.0  1.2  0.0  255.0 123.0 90.0 0.5";
+
+        let res = ObjObject::parse_vertex(line).unwrap();
+
+        assert_eq!(res.position, [1.0, 1.2, 0.0]);
+        assert_eq!(res.w, 1.0);
+        assert_eq!(res.color, Some([255.0, 123.0, 90.0]));
+        assert_eq!(res.alpha, Some(0.5));
+    }
+
+    #[test]
+    fn test_vertex_default_w() {
+        assert_eq!(ObjObject::parse_vertex("1.0 1.2 0.0").unwrap().w, 1.0);
+        assert_eq!(ObjObject::parse_vertex("1.0 1.2 0.0 1 0 0").unwrap().w, 1.0);
+    }
+
+    #[test]
+    fn test_vertex_incomplete_color() {
+        assert!(ObjObject::parse_vertex("1.0 1.2 0.0 1.0 0.0").is_err());
+        assert!(ObjObject::parse_vertex("1.0 1.2").is_err());
     }
 
     #[test]
@@ -470,21 +2523,87 @@ mod tests {
 
         let res = ObjObject::parse_normal(line).unwrap();
 
-        assert_eq!(res, (0.5, 0.0, -1.0));
+        assert_eq!(res, [0.5, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_texture_coord() {
+        assert_eq!(
+            ObjObject::parse_texture_coord("0.5").unwrap(),
+            [0.5, 0.0, 0.0]
+        );
+        assert_eq!(
+            ObjObject::parse_texture_coord("0.5 0.25").unwrap(),
+            [0.5, 0.25, 0.0]
+        );
+        assert_eq!(
+            ObjObject::parse_texture_coord("0.5 0.25 1.0").unwrap(),
+            [0.5, 0.25, 1.0]
+        );
+        assert_eq!(
+            ObjObject::parse_texture_coord("0.5  0.25\t1.0  \t").unwrap(),
+            [0.5, 0.25, 1.0]
+        );
+        assert!(ObjObject::parse_texture_coord("").is_err());
+        assert!(ObjObject::parse_texture_coord("0.5 x").is_err());
+    }
+
+    #[test]
+    fn test_smooth_group() {
+        assert_eq!(ObjObject::parse_smooth_group("off").unwrap(), None);
+        assert_eq!(ObjObject::parse_smooth_group("0").unwrap(), None);
+        assert_eq!(ObjObject::parse_smooth_group("3").unwrap(), Some(3));
+        assert!(ObjObject::parse_smooth_group("").is_err());
+        assert!(ObjObject::parse_smooth_group("on").is_err());
+    }
+
+    #[test]
+    fn test_polyline() {
+        assert_eq!(
+            ObjObject::parse_polyline("1 2 -1", 3, 0).unwrap(),
+            (vec![1, 2, 3], None)
+        );
+        assert_eq!(
+            ObjObject::parse_polyline("1/2 -2/-1", 3, 4).unwrap(),
+            (vec![1, 2], Some(vec![2, 4]))
+        );
+        assert!(matches!(
+            ObjObject::parse_polyline("1/2 2", 3, 4),
+            Err(Error::InconsistentFaceFormat(line)) if line == "1/2 2"
+        ));
+        assert!(ObjObject::parse_polyline("1", 3, 0).is_err());
+        assert!(ObjObject::parse_polyline("1 x", 3, 0).is_err());
+    }
+
+    #[test]
+    fn test_point_element() {
+        assert_eq!(
+            ObjObject::parse_point_element("1 2 3", 3).unwrap(),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            ObjObject::parse_point_element("1/4 -1/2", 3).unwrap(),
+            [1, 3]
+        );
+        assert_eq!(ObjObject::parse_point_element("-2", 3).unwrap(), [2]);
+        assert!(ObjObject::parse_point_element("", 3).is_err());
+        assert!(ObjObject::parse_point_element("a", 3).is_err());
     }
 
     #[test]
     fn test_face_itn() {
         let line = "123/5445/123 456/123/1231 789/113/12";
 
-        let (res, f2) = ObjObject::parse_face(line, 0, 0, 0).unwrap();
-        assert!(f2.is_none());
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 1);
         assert_eq!(
-            res,
+            faces[0],
             FaceData {
                 indicies: (123, 456, 789),
                 texture_indcicies: Some((5445, 123, 113)),
-                normal_indicies: Some((123, 1231, 12))
+                normal_indicies: Some((123, 1231, 12)),
+                smooth_group: None,
             }
         );
     }
@@ -493,14 +2612,16 @@ mod tests {
     fn test_face_it() {
         let line = "123/5445 456/123 789/113";
 
-        let (res, f2) = ObjObject::parse_face(line, 0, 0, 0).unwrap();
-        assert!(f2.is_none());
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 1);
         assert_eq!(
-            res,
+            faces[0],
             FaceData {
                 indicies: (123, 456, 789),
                 texture_indcicies: Some((5445, 123, 113)),
                 normal_indicies: None,
+                smooth_group: None,
             }
         );
     }
@@ -509,14 +2630,16 @@ mod tests {
     fn test_face_i() {
         let line = "123 456 789";
 
-        let (res, f2) = ObjObject::parse_face(line, 0, 0, 0).unwrap();
-        assert!(f2.is_none());
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 1);
         assert_eq!(
-            res,
+            faces[0],
             FaceData {
                 indicies: (123, 456, 789),
                 texture_indcicies: None,
                 normal_indicies: None,
+                smooth_group: None,
             }
         );
     }
@@ -525,14 +2648,16 @@ mod tests {
     fn test_face_in() {
         let line = "123//123 456//1231 789//12";
 
-        let (res, f2) = ObjObject::parse_face(line, 0, 0, 0).unwrap();
-        assert!(f2.is_none());
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 1);
         assert_eq!(
-            res,
+            faces[0],
             FaceData {
                 indicies: (123, 456, 789),
                 texture_indcicies: None,
                 normal_indicies: Some((123, 1231, 12)),
+                smooth_group: None,
             }
         );
     }
@@ -542,14 +2667,16 @@ mod tests {
         //                 i  t  n  i  t  n  i  t  n
         let line = "-2/-3/-1 -1/-1/-1 -5/-2/-3";
 
-        let (res, f2) = ObjObject::parse_face(line, 10, 4, 7).unwrap();
-        assert!(f2.is_none());
+        let (faces, _) =
+            ObjObject::parse_face(line, 10, 4, 7, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 1);
         assert_eq!(
-            res,
+            faces[0],
             FaceData {
                 indicies: (9, 10, 6),
                 texture_indcicies: Some((2, 4, 3)),
                 normal_indicies: Some((7, 7, 5)),
+                smooth_group: None,
             }
         );
     }
@@ -558,23 +2685,42 @@ mod tests {
     fn test_face_double() {
         let line = "123/5445/123 456/123/1231 789/113/12 509/111/576";
 
-        let (f1, f2) = ObjObject::parse_face(line, 0, 0, 0).unwrap();
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 2);
         assert_eq!(
-            f1,
+            faces[0],
             FaceData {
                 indicies: (123, 456, 789),
                 texture_indcicies: Some((5445, 123, 113)),
-                normal_indicies: Some((123, 1231, 12))
+                normal_indicies: Some((123, 1231, 12)),
+                smooth_group: None,
             }
         );
 
         assert_eq!(
-            f2,
-            Some(FaceData {
+            faces[1],
+            FaceData {
                 indicies: (123, 789, 509),
                 texture_indcicies: Some((5445, 113, 111)),
                 normal_indicies: Some((123, 12, 576)),
-            })
+                smooth_group: None,
+            }
         );
     }
+
+    #[test]
+    fn test_face_pentagon() {
+        let line = "1/1/1 2/2/2 3/3/3 4/4/4 5/5/5";
+
+        let (faces, _) =
+            ObjObject::parse_face(line, 0, 0, 0, &[], &ParseOptions::default()).unwrap();
+        assert_eq!(faces.len(), 3);
+
+        for (face, i) in faces.iter().zip(2..) {
+            assert_eq!(face.indicies, (1, i, i + 1));
+            assert_eq!(face.texture_indcicies, Some((1, i, i + 1)));
+            assert_eq!(face.normal_indicies, Some((1, i, i + 1)));
+        }
+    }
 }
@@ -0,0 +1,599 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crate::{Error, Line, ObjObject, ParseOptions, ParsedVertex, parse::Corners};
+
+/// Represents a parsed .ply (Polygon File Format) file, containing its vertices and faces.
+///
+/// Supports ASCII and binary (little and big endian) files.
+/// Only the `vertex` and `face` elements are read, all other elements are skipped.
+///
+/// Convert it into an [`ObjObject`] to use everything else of this crate, see [`ObjObject::read_ply`].
+///
+/// ## Example
+/// ```rust
+/// use polypath::{ObjObject, PlyObject};
+///
+/// let data = "ply
+/// format ascii 1.0
+/// element vertex 3
+/// property float x
+/// property float y
+/// property float z
+/// element face 1
+/// property list uchar int vertex_indices
+/// end_header
+/// 0 0 0
+/// 1 0 0
+/// 0 1 0
+/// 3 0 1 2
+/// ";
+/// let ply = PlyObject::parse(std::io::Cursor::new(data)).unwrap();
+/// assert_eq!(ply.faces, [vec![0, 1, 2]]);
+///
+/// let obj = ObjObject::try_from(ply).unwrap();
+/// assert_eq!(obj.face_count(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlyObject {
+    /// The vertex positions (x, y, z).
+    pub positions: Vec<[f32; 3]>,
+    /// The vertex normals (nx, ny, nz), empty if the vertices do not have normals.
+    pub normals: Vec<[f32; 3]>,
+    /// The texture coords (s, t or u, v), empty if the vertices do not have texture coords.
+    pub texture_coords: Vec<[f32; 2]>,
+    /// The vertex colors (red, green, blue), empty if the vertices do not have colors.
+    ///
+    /// Integer colors are scaled to `0.0..=1.0`, e.g. `255` of an `uchar` is `1.0`.
+    pub colors: Vec<[f32; 3]>,
+    /// The (0-based) vertex indices (`vertex_indices`) of every face.
+    pub faces: Vec<Vec<u32>>,
+}
+
+impl PlyObject {
+    /// Reads and parses a .ply file.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the file could not be read, or the .ply file is not as structured as expected.
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|file| Self::parse(BufReader::new(file)))
+            .map_err(|err| err.in_file(path))
+    }
+
+    /// Parses a .ply file from some sort of input reader.
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, or the .ply file is not as structured as expected.
+    /// Errors in the header or the body of an ASCII file are wrapped in [`Error::AtLine`].
+    pub fn parse(mut reader: impl BufRead) -> Result<Self, Error> {
+        let header = Header::parse(&mut reader)?;
+
+        let mut ply = Self::default();
+        let mut line_number = header.lines;
+        let mut line = String::new();
+
+        for element in &header.elements {
+            for _ in 0..element.count {
+                match header.format {
+                    Format::Ascii => {
+                        line.clear();
+                        if reader.read_line(&mut line)? == 0 {
+                            return Err(Error::InvalidPly(format!(
+                                "the file ends before all [{}] {} elements",
+                                element.count, element.name
+                            )));
+                        }
+                        line_number += 1;
+
+                        let mut values = AsciiValues(line.split_whitespace());
+                        ply.read_element(element, &mut values)
+                            .map_err(|err| err.at_line(line_number))?;
+                    }
+                    Format::BinaryLittleEndian | Format::BinaryBigEndian => {
+                        let mut values = BinaryValues {
+                            reader: &mut reader,
+                            big_endian: header.format == Format::BinaryBigEndian,
+                        };
+                        ply.read_element(element, &mut values)?;
+                    }
+                }
+            }
+        }
+
+        // faces may be listed before the vertices
+        let vertex_count = ply.positions.len();
+        if let Some(index) = ply
+            .faces
+            .iter()
+            .flatten()
+            .find(|index| **index as usize >= vertex_count)
+        {
+            return Err(Error::InvalidPly(format!(
+                "face index [{index}] is out of range, only [{vertex_count}] vertices are defined"
+            )));
+        }
+
+        Ok(ply)
+    }
+
+    /// Reads a single element, storing it if it is a vertex or face.
+    fn read_element(&mut self, element: &Element, values: &mut impl Values) -> Result<(), Error> {
+        let mut vertex = [0.0; 11];
+
+        for property in &element.properties {
+            match (property.kind, property.target) {
+                (Kind::Scalar(ty), Target::Vertex(i)) => {
+                    // integer colors are scaled to 0-1, everything else is used as is
+                    #[allow(clippy::cast_possible_truncation)]
+                    let value = (values.next(ty)? / ty.color_scale(i >= COLOR)) as f32;
+                    vertex[i] = value;
+                }
+                (Kind::List { count, item }, Target::Indices) => {
+                    let count = to_u32(values.next(count)?)?;
+                    if count < 3 {
+                        return Err(Error::InvalidPly(format!(
+                            "face with only [{count}] vertices"
+                        )));
+                    }
+
+                    let face = (0..count)
+                        .map(|_| to_u32(values.next(item)?))
+                        .collect::<Result<_, _>>()?;
+                    self.faces.push(face);
+                }
+                (Kind::Scalar(ty), _) => {
+                    values.next(ty)?;
+                }
+                (Kind::List { count, item }, _) => {
+                    for _ in 0..to_u32(values.next(count)?)? {
+                        values.next(item)?;
+                    }
+                }
+            }
+        }
+
+        if element.name == "vertex" {
+            let [px, py, pz, nx, ny, nz, tu, tv, red, green, blue] = vertex;
+            self.positions.push([px, py, pz]);
+            if element.normals {
+                self.normals.push([nx, ny, nz]);
+            }
+            if element.texture_coords {
+                self.texture_coords.push([tu, tv]);
+            }
+            if element.colors {
+                self.colors.push([red, green, blue]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ObjObject {
+    /// Reads and parses a .ply file, converting it into an `ObjObject`, see [`PlyObject`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the file could not be read, or the .ply file is not as structured as expected.
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
+    pub fn read_ply<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        PlyObject::read_from_file(path)
+            .and_then(|ply| Self::try_from(ply).map_err(|err| err.in_file(path)))
+    }
+
+    /// Converts a [`PlyObject`] into an `ObjObject`, storing its vertex data and faces like the given [`ParseOptions`] request.
+    ///
+    /// All faces belong to the default group, vertex normals and texture coords use the same indices as the positions.
+    /// Normals, texture coords and colors are only used, if there is one for every vertex.
+    ///
+    /// Only options affecting how data is stored apply (e.g. [`ParseOptions::quad_split`], [`ParseOptions::keep_polygons`],
+    /// [`ParseOptions::weld_epsilon`] or [`ParseOptions::generate_normals`]), as well as [`ParseOptions::reject_non_finite`].
+    /// Limits do not apply.
+    ///
+    /// # Errors
+    /// - Returns [`Error::InvalidPly`] if a face references a vertex that does not exist.
+    /// - Returns [`Error::NonFiniteValue`] if a vertex contains NaN or an infinite value, see [`ParseOptions::reject_non_finite`].
+    pub fn from_ply_with(ply: PlyObject, options: &ParseOptions) -> Result<Self, Error> {
+        let PlyObject {
+            positions,
+            normals,
+            texture_coords,
+            colors,
+            faces,
+        } = ply;
+
+        let vertex_count = positions.len();
+        let has_normal = normals.len() == vertex_count;
+        let has_texture = texture_coords.len() == vertex_count;
+        let has_color = colors.len() == vertex_count;
+
+        let mut obj = Self::empty();
+        let mut state = obj.start_parsing(options, None);
+
+        let vertices = positions.iter().enumerate().map(|(i, position)| {
+            Line::Vertex(ParsedVertex {
+                position: *position,
+                w: 1.0,
+                color: has_color.then(|| colors[i]),
+                alpha: None,
+                position_f64: None,
+            })
+        });
+        let normals = normals.into_iter().filter(|_| has_normal).map(Line::Normal);
+        let texture_coords = texture_coords
+            .into_iter()
+            .filter(|_| has_texture)
+            .map(|[u, v]| Line::TextureCoord([u, v, 0.0]));
+
+        let mut res = vertices
+            .chain(normals)
+            .chain(texture_coords)
+            .try_for_each(|line| obj.push_decoded(&mut state, line));
+
+        for face in faces {
+            if res.is_err() {
+                break;
+            }
+
+            if let Some(index) = face.iter().find(|index| **index as usize >= vertex_count) {
+                res = Err(Error::InvalidPly(format!(
+                    "face index [{index}] is out of range, only [{vertex_count}] vertices are defined"
+                )));
+                break;
+            }
+
+            // .ply vertices share one index for all of their attributes, .obj indices are 1-based
+            let corners = face
+                .iter()
+                .map(|index| {
+                    let index = index + 1;
+                    (
+                        index,
+                        has_texture.then_some(index),
+                        has_normal.then_some(index),
+                    )
+                })
+                .collect::<Corners>();
+            let triangles =
+                Self::triangulate_face(&corners, has_texture, has_normal, &positions, options);
+
+            res = obj.push_decoded(&mut state, Line::Faces(triangles, corners));
+        }
+
+        obj.finish_parsing(state, options, res)?;
+
+        Ok(obj)
+    }
+}
+
+impl TryFrom<PlyObject> for ObjObject {
+    type Error = Error;
+
+    /// Converts a [`PlyObject`] with the default [`ParseOptions`], see [`ObjObject::from_ply_with`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the conversion fails, see [`ObjObject::from_ply_with`].
+    #[inline]
+    fn try_from(ply: PlyObject) -> Result<Self, Error> {
+        Self::from_ply_with(ply, &ParseOptions::default())
+    }
+}
+
+/// The index of the first color component of a vertex.
+const COLOR: usize = 8;
+
+/// The header of a .ply file.
+struct Header {
+    format: Format,
+    elements: Vec<Element>,
+    /// The number of lines of the header.
+    lines: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// An element (element) of the header, and all of its properties.
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+    /// Whether all components of the vertex normals, texture coords or colors are present, only used for vertices.
+    normals: bool,
+    texture_coords: bool,
+    colors: bool,
+}
+
+/// A property (property) of an element.
+struct Property {
+    kind: Kind,
+    target: Target,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Scalar(Scalar),
+    List { count: Scalar, item: Scalar },
+}
+
+/// Where the value of a property is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// A component of a vertex, in the order x, y, z, nx, ny, nz, s, t, red, green, blue.
+    Vertex(usize),
+    /// The vertex indices of a face.
+    Indices,
+    /// Skipped.
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Header {
+    fn parse(reader: &mut impl BufRead) -> Result<Self, Error> {
+        let mut format = None;
+        let mut elements: Vec<Element> = Vec::new();
+        let mut buffer = Vec::new();
+        let mut line_number = 0;
+
+        loop {
+            buffer.clear();
+            if reader.read_until(b'\n', &mut buffer)? == 0 {
+                return Err(Error::InvalidPly(String::from(
+                    "the header does not end (end_header)",
+                )));
+            }
+            line_number += 1;
+
+            let line = std::str::from_utf8(&buffer)
+                .map_err(|_| Error::InvalidPly(String::from("the header is not valid UTF-8")))
+                .map_err(|err| err.at_line(line_number))?
+                .trim();
+
+            if line_number == 1 {
+                if line != "ply" {
+                    return Err(Error::InvalidPly(String::from(
+                        "the file does not start with ply",
+                    )));
+                }
+                continue;
+            }
+
+            let mut tokens = line.split_whitespace();
+            let res = match tokens.next() {
+                Some("end_header") => break,
+                Some("format") => Self::parse_format(tokens).map(|parsed| format = Some(parsed)),
+                Some("element") => Element::parse(tokens).map(|element| elements.push(element)),
+                Some("property") => match elements.last_mut() {
+                    Some(element) => Property::parse(tokens, &element.name)
+                        .map(|property| element.properties.push(property)),
+                    None => Err(Error::InvalidPly(String::from(
+                        "property before any element",
+                    ))),
+                },
+                Some("comment" | "obj_info") | None => Ok(()),
+                Some(_) => Err(Error::UnknownLine(String::from(line))),
+            };
+            res.map_err(|err| err.at_line(line_number))?;
+        }
+
+        let format = format.ok_or_else(|| Error::InvalidPly(String::from("missing format")))?;
+
+        for element in &mut elements {
+            element.finish()?;
+        }
+
+        Ok(Self {
+            format,
+            elements,
+            lines: line_number,
+        })
+    }
+
+    fn parse_format<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Format, Error> {
+        let format = match tokens.next().ok_or(Error::UnexpectedEoL)? {
+            "ascii" => Format::Ascii,
+            "binary_little_endian" => Format::BinaryLittleEndian,
+            "binary_big_endian" => Format::BinaryBigEndian,
+            format => {
+                return Err(Error::InvalidPly(format!("unknown format [{format}]")));
+            }
+        };
+
+        match tokens.next() {
+            Some("1.0") => Ok(format),
+            version => Err(Error::InvalidPly(format!(
+                "unsupported version [{}]",
+                version.unwrap_or_default()
+            ))),
+        }
+    }
+}
+
+impl Element {
+    fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Self, Error> {
+        let name = tokens.next().ok_or(Error::UnexpectedEoL)?;
+        let count = tokens.next().ok_or(Error::UnexpectedEoL)?.parse()?;
+
+        Ok(Self {
+            name: String::from(name),
+            count,
+            properties: Vec::new(),
+            normals: false,
+            texture_coords: false,
+            colors: false,
+        })
+    }
+
+    /// Checks which vertex data is present, after all properties were parsed.
+    fn finish(&mut self) -> Result<(), Error> {
+        if self.name != "vertex" {
+            return Ok(());
+        }
+
+        let has = |components: &[usize]| {
+            components.iter().all(|component| {
+                self.properties
+                    .iter()
+                    .any(|property| property.target == Target::Vertex(*component))
+            })
+        };
+
+        if !has(&[0, 1, 2]) {
+            return Err(Error::InvalidPly(String::from(
+                "vertices without x, y and z",
+            )));
+        }
+        self.normals = has(&[3, 4, 5]);
+        self.texture_coords = has(&[6, 7]);
+        self.colors = has(&[COLOR, COLOR + 1, COLOR + 2]);
+
+        Ok(())
+    }
+}
+
+impl Property {
+    fn parse<'a>(mut tokens: impl Iterator<Item = &'a str>, element: &str) -> Result<Self, Error> {
+        let kind = match tokens.next().ok_or(Error::UnexpectedEoL)? {
+            "list" => Kind::List {
+                count: Scalar::parse(tokens.next().ok_or(Error::UnexpectedEoL)?)?,
+                item: Scalar::parse(tokens.next().ok_or(Error::UnexpectedEoL)?)?,
+            },
+            ty => Kind::Scalar(Scalar::parse(ty)?),
+        };
+        let name = tokens.next().ok_or(Error::UnexpectedEoL)?;
+
+        let target = match (element, name, kind) {
+            ("vertex", name, Kind::Scalar(_)) => [
+                "x", "y", "z", "nx", "ny", "nz", "s", "t", "red", "green", "blue",
+            ]
+            .iter()
+            .position(|component| *component == name)
+            .or(match name {
+                "u" | "texture_u" => Some(6),
+                "v" | "texture_v" => Some(7),
+                _ => None,
+            })
+            .map_or(Target::None, Target::Vertex),
+            ("face", "vertex_indices" | "vertex_index", Kind::List { .. }) => Target::Indices,
+            _ => Target::None,
+        };
+
+        Ok(Self { kind, target })
+    }
+}
+
+impl Scalar {
+    fn parse(ty: &str) -> Result<Self, Error> {
+        match ty {
+            "char" | "int8" => Ok(Self::I8),
+            "uchar" | "uint8" => Ok(Self::U8),
+            "short" | "int16" => Ok(Self::I16),
+            "ushort" | "uint16" => Ok(Self::U16),
+            "int" | "int32" => Ok(Self::I32),
+            "uint" | "uint32" => Ok(Self::U32),
+            "float" | "float32" => Ok(Self::F32),
+            "double" | "float64" => Ok(Self::F64),
+            ty => Err(Error::InvalidPly(format!("unknown property type [{ty}]"))),
+        }
+    }
+
+    /// The size of a binary value in bytes.
+    const fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    /// The value of full intensity, if this is the type of a color component.
+    const fn color_scale(self, color: bool) -> f64 {
+        match self {
+            Self::U8 if color => 255.0,
+            Self::U16 if color => 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Converts an index or list length to an integer.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_u32(value: f64) -> Result<u32, Error> {
+    if value.fract() == 0.0 && (0.0..=f64::from(u32::MAX)).contains(&value) {
+        Ok(value as u32)
+    } else {
+        Err(Error::InvalidPly(format!(
+            "invalid index or count [{value}]"
+        )))
+    }
+}
+
+/// The values of the body of a .ply file.
+trait Values {
+    /// Returns the next value, converted to `f64`, which can represent every supported type exactly.
+    fn next(&mut self, ty: Scalar) -> Result<f64, Error>;
+}
+
+/// The values of a single line of an ASCII file.
+struct AsciiValues<'a>(std::str::SplitWhitespace<'a>);
+
+impl Values for AsciiValues<'_> {
+    fn next(&mut self, _: Scalar) -> Result<f64, Error> {
+        Ok(self.0.next().ok_or(Error::UnexpectedEoL)?.parse()?)
+    }
+}
+
+/// The values of a binary file.
+struct BinaryValues<'r, R> {
+    reader: &'r mut R,
+    big_endian: bool,
+}
+
+impl<R: Read> Values for BinaryValues<'_, R> {
+    fn next(&mut self, ty: Scalar) -> Result<f64, Error> {
+        let mut bytes = [0; 8];
+        self.reader.read_exact(&mut bytes[..ty.size()])?;
+        if self.big_endian {
+            bytes[..ty.size()].reverse();
+        }
+
+        let [b0, b1, b2, b3, ..] = bytes;
+        Ok(match ty {
+            Scalar::I8 => f64::from(i8::from_le_bytes([b0])),
+            Scalar::U8 => f64::from(b0),
+            Scalar::I16 => f64::from(i16::from_le_bytes([b0, b1])),
+            Scalar::U16 => f64::from(u16::from_le_bytes([b0, b1])),
+            Scalar::I32 => f64::from(i32::from_le_bytes([b0, b1, b2, b3])),
+            Scalar::U32 => f64::from(u32::from_le_bytes([b0, b1, b2, b3])),
+            Scalar::F32 => f64::from(f32::from_le_bytes([b0, b1, b2, b3])),
+            Scalar::F64 => f64::from_le_bytes(bytes),
+        })
+    }
+}
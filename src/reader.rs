@@ -0,0 +1,567 @@
+use std::{
+    borrow::Cow,
+    io::{BufRead, ErrorKind},
+    mem,
+};
+
+use crate::Error;
+
+/// A source of (possibly joined) lines, skipping empty lines and comments, see [`LineReader::next_line`].
+pub trait Lines {
+    /// Returns the next line, without the line terminator, together with its (1-based) line number.
+    ///
+    /// Returns `None` once the end of the input is reached.
+    fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error>;
+
+    /// Returns the number of bytes consumed from the input so far, including skipped lines and line terminators.
+    fn bytes_read(&self) -> u64;
+}
+
+/// Reads single lines from a [`BufRead`], without ever buffering more than `max_len` bytes of a line.
+///
+/// Empty lines and comments are skipped.
+///
+/// `BufRead::read_line` reads until a newline is found, which lets a single huge line allocate unbounded memory.
+/// This reader copies the input chunk by chunk and errors as soon as a line would exceed the limit.
+///
+/// Lines may end with `\n`, `\r\n` or a lone `\r`.
+pub struct LineReader<R> {
+    reader: R,
+    lines: LineBuffer,
+}
+
+impl<R: BufRead> LineReader<R> {
+    #[inline]
+    pub fn new(reader: R, max_len: usize) -> Self {
+        Self::with_buffer(reader, max_len, Vec::with_capacity(256))
+    }
+
+    /// Same as [`LineReader::new`], but reuses the allocation of an existing buffer.
+    #[inline]
+    pub fn with_buffer(reader: R, max_len: usize, buffer: Vec<u8>) -> Self {
+        Self {
+            reader,
+            lines: LineBuffer::new(max_len, buffer),
+        }
+    }
+
+    /// Sets whether invalid UTF-8 gets replaced by `U+FFFD`, instead of returning an error.
+    #[inline]
+    pub const fn lossy(mut self, lossy: bool) -> Self {
+        self.lines.lossy = lossy;
+        self
+    }
+
+    /// Returns the internal line buffer, so its allocation can be reused.
+    #[inline]
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.lines.buffer
+    }
+
+    /// Reads the next line, without the line terminator, together with its (1-based) line number.
+    ///
+    /// A trailing backslash (before any comment) continues a line on the next physical line,
+    /// these lines are joined, with the backslash (and comment) replaced by a space.
+    /// The line number of a joined line is the number of its first physical line.
+    /// Empty lines and comments are skipped.
+    ///
+    /// Returns `None` once the end of the input is reached.
+    pub fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error> {
+        loop {
+            let line_number = self.lines.start_line();
+
+            if !self.append_line()? {
+                return Ok(None);
+            }
+
+            // a backslash on the last line has nothing to continue with
+            while self.lines.join_next_line() {
+                if !self.append_line()? {
+                    break;
+                }
+            }
+
+            if !self.lines.is_blank() {
+                return self.lines.decode().map(|line| Some((line_number, line)));
+            }
+        }
+    }
+
+    /// Appends the next physical line to the buffer, without the line terminator.
+    ///
+    /// Returns `false` once the end of the input is reached.
+    fn append_line(&mut self) -> Result<bool, Error> {
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::Io(err)),
+            };
+
+            let (consumed, finished) = self.lines.append(available)?;
+            self.reader.consume(consumed);
+
+            if finished {
+                return Ok(self.lines.finish_physical_line());
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Lines for LineReader<R> {
+    #[inline]
+    fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error> {
+        Self::next_line(self)
+    }
+
+    #[inline]
+    fn bytes_read(&self) -> u64 {
+        self.lines.bytes
+    }
+}
+
+/// Same as [`LineReader`], but reading from a [`tokio::io::AsyncBufRead`].
+#[cfg(feature = "tokio")]
+pub struct AsyncLineReader<R> {
+    reader: R,
+    lines: LineBuffer,
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncBufRead + Unpin> AsyncLineReader<R> {
+    /// Same as [`LineReader::with_buffer`].
+    #[inline]
+    pub fn with_buffer(reader: R, max_len: usize, buffer: Vec<u8>) -> Self {
+        Self {
+            reader,
+            lines: LineBuffer::new(max_len, buffer),
+        }
+    }
+
+    /// Same as [`LineReader::lossy`].
+    #[inline]
+    pub const fn lossy(mut self, lossy: bool) -> Self {
+        self.lines.lossy = lossy;
+        self
+    }
+
+    /// Returns the internal line buffer, so its allocation can be reused.
+    #[inline]
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.lines.buffer
+    }
+
+    /// Same as [`LineReader::next_line`].
+    pub async fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error> {
+        loop {
+            let line_number = self.lines.start_line();
+
+            if !self.append_line().await? {
+                return Ok(None);
+            }
+
+            // a backslash on the last line has nothing to continue with
+            while self.lines.join_next_line() {
+                if !self.append_line().await? {
+                    break;
+                }
+            }
+
+            if !self.lines.is_blank() {
+                return self.lines.decode().map(|line| Some((line_number, line)));
+            }
+        }
+    }
+
+    /// Same as [`LineReader::append_line`].
+    async fn append_line(&mut self) -> Result<bool, Error> {
+        use tokio::io::AsyncBufReadExt;
+
+        loop {
+            let available = match self.reader.fill_buf().await {
+                Ok(available) => available,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::Io(err)),
+            };
+
+            let (consumed, finished) = self.lines.append(available)?;
+            self.reader.consume(consumed);
+
+            if finished {
+                return Ok(self.lines.finish_physical_line());
+            }
+        }
+    }
+}
+
+/// The line buffer of a [`LineReader`], filled chunk by chunk with the input, independent of how the input is read.
+struct LineBuffer {
+    buffer: Vec<u8>,
+    max_len: usize,
+    line: usize,
+    bytes: u64,
+    /// Whether any input was appended to the current physical line, so it exists, even if it is empty.
+    read_any: bool,
+    /// The previous line ended with a `\r` at the end of the available input, so a directly following `\n` belongs to it.
+    skip_newline: bool,
+    lossy: bool,
+    lossy_buffer: String,
+}
+
+impl LineBuffer {
+    #[inline]
+    fn new(max_len: usize, mut buffer: Vec<u8>) -> Self {
+        buffer.clear();
+
+        Self {
+            buffer,
+            max_len,
+            line: 0,
+            bytes: 0,
+            read_any: false,
+            skip_newline: false,
+            lossy: false,
+            lossy_buffer: String::new(),
+        }
+    }
+
+    /// Clears the buffer for the next (possibly joined) line, returning its line number.
+    #[inline]
+    fn start_line(&mut self) -> usize {
+        self.buffer.clear();
+        self.line + 1
+    }
+
+    /// Appends the available input up to the end of the current physical line.
+    ///
+    /// Returns the number of bytes to consume from the input, and whether the physical line is finished.
+    /// An empty input finishes the line, as the end of the input is reached.
+    fn append(&mut self, available: &[u8]) -> Result<(usize, bool), Error> {
+        if available.is_empty() {
+            return Ok((0, true));
+        }
+
+        // the second half of a `\r\n`, split between two reads
+        if mem::take(&mut self.skip_newline) && available[0] == b'\n' {
+            self.bytes += 1;
+            return Ok((1, false));
+        }
+
+        self.read_any = true;
+
+        let (data, consumed, finished) = match line_end(available) {
+            Some((i, terminator)) => {
+                // a `\r` at the end of the available input might be followed by a `\n`
+                self.skip_newline = i + terminator == available.len() && available[i] == b'\r';
+                (&available[..i], i + terminator, true)
+            }
+            None => (available, available.len(), false),
+        };
+
+        // check before copying, so the buffer never grows beyond the limit
+        if self.buffer.len() + data.len() > self.max_len {
+            return Err(Error::LineTooLong {
+                line: self.line + 1,
+                limit: self.max_len,
+            });
+        }
+
+        self.buffer.extend_from_slice(data);
+        self.bytes += consumed as u64;
+
+        Ok((consumed, finished))
+    }
+
+    /// Finishes the current physical line.
+    ///
+    /// Returns `false` if there was no line, as the end of the input is reached.
+    #[inline]
+    fn finish_physical_line(&mut self) -> bool {
+        let read_any = mem::take(&mut self.read_any);
+        if read_any {
+            self.line += 1;
+        }
+
+        read_any
+    }
+
+    /// If the buffer ends with a backslash, replaces it with a space, so the next physical line gets appended.
+    #[inline]
+    fn join_next_line(&mut self) -> bool {
+        let Some(backslash) = continuation(&self.buffer) else {
+            return false;
+        };
+
+        self.buffer.truncate(backslash);
+        self.buffer.push(b' ');
+
+        true
+    }
+
+    #[inline]
+    fn is_blank(&self) -> bool {
+        is_blank(&self.buffer)
+    }
+
+    #[inline]
+    fn decode(&mut self) -> Result<&str, Error> {
+        decode(&self.buffer, self.lossy, &mut self.lossy_buffer)
+    }
+}
+
+/// Reads single lines from an in-memory buffer.
+///
+/// Lines are returned as slices of the input, only joined lines (and lossily decoded lines) get copied.
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+    /// The length of the whole input.
+    len: usize,
+    buffer: Vec<u8>,
+    max_len: usize,
+    line: usize,
+    lossy: bool,
+    lossy_buffer: String,
+}
+
+impl<'a> SliceReader<'a> {
+    /// Same as [`LineReader::with_buffer`], but reading from a byte slice.
+    #[inline]
+    pub fn with_buffer(data: &'a [u8], max_len: usize, mut buffer: Vec<u8>) -> Self {
+        buffer.clear();
+
+        Self {
+            data,
+            len: data.len(),
+            buffer,
+            max_len,
+            line: 0,
+            lossy: false,
+            lossy_buffer: String::new(),
+        }
+    }
+
+    /// Same as [`LineReader::lossy`].
+    #[inline]
+    pub const fn lossy(mut self, lossy: bool) -> Self {
+        self.lossy = lossy;
+        self
+    }
+
+    /// Sets the number of physical lines before the input, for input that starts in the middle of a file.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub const fn after_lines(mut self, lines: usize) -> Self {
+        self.line = lines;
+        self
+    }
+
+    /// Returns the number of physical lines read so far.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub const fn lines_read(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the internal line buffer, so its allocation can be reused.
+    #[inline]
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Same as [`LineReader::next_line`].
+    pub fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error> {
+        loop {
+            let line_number = self.line + 1;
+
+            let Some(line) = self.next_physical_line()? else {
+                return Ok(None);
+            };
+
+            if continuation(line).is_none() {
+                if is_blank(line) {
+                    continue;
+                }
+
+                return decode(line, self.lossy, &mut self.lossy_buffer)
+                    .map(|l| Some((line_number, l)));
+            }
+
+            self.join_lines(line)?;
+
+            if !is_blank(&self.buffer) {
+                return decode(&self.buffer, self.lossy, &mut self.lossy_buffer)
+                    .map(|l| Some((line_number, l)));
+            }
+        }
+    }
+
+    /// Joins a line ending with a backslash with its following lines into the buffer.
+    fn join_lines(&mut self, mut line: &'a [u8]) -> Result<(), Error> {
+        // only joined lines need to be copied
+        self.buffer.clear();
+
+        while let Some(backslash) = continuation(line) {
+            self.buffer.extend_from_slice(&line[..backslash]);
+            self.buffer.push(b' ');
+
+            // a backslash on the last line has nothing to continue with
+            let Some(next) = self.next_physical_line()? else {
+                line = &[];
+                break;
+            };
+            line = next;
+
+            if self.buffer.len() + line.len() > self.max_len {
+                return Err(Error::LineTooLong {
+                    line: self.line,
+                    limit: self.max_len,
+                });
+            }
+        }
+
+        self.buffer.extend_from_slice(line);
+
+        Ok(())
+    }
+
+    /// Returns the next physical line, without the line terminator.
+    ///
+    /// Returns `None` once the end of the input is reached.
+    fn next_physical_line(&mut self) -> Result<Option<&'a [u8]>, Error> {
+        if self.data.is_empty() {
+            return Ok(None);
+        }
+
+        let (line, rest) = line_end(self.data).map_or((self.data, &[][..]), |(i, terminator)| {
+            (&self.data[..i], &self.data[i + terminator..])
+        });
+
+        self.data = rest;
+        self.line += 1;
+
+        if line.len() > self.max_len {
+            return Err(Error::LineTooLong {
+                line: self.line,
+                limit: self.max_len,
+            });
+        }
+
+        Ok(Some(line))
+    }
+}
+
+impl Lines for SliceReader<'_> {
+    #[inline]
+    fn next_line(&mut self) -> Result<Option<(usize, &str)>, Error> {
+        Self::next_line(self)
+    }
+
+    #[inline]
+    fn bytes_read(&self) -> u64 {
+        (self.len - self.data.len()) as u64
+    }
+}
+
+/// Splits the input at line boundaries into chunks of at least `min_len` bytes (except for the last one).
+///
+/// Lines continued by a trailing backslash are never split up, so every chunk can be read on its own.
+#[cfg(feature = "rayon")]
+pub fn split_lines(mut data: &[u8], min_len: usize) -> Vec<&[u8]> {
+    let mut chunks = Vec::with_capacity(data.len() / min_len.max(1) + 1);
+
+    while !data.is_empty() {
+        // the last byte of the chunk, moved back from the middle of a `\r\n`
+        let mut from = min_len.clamp(1, data.len()) - 1;
+        if from > 0 && data[from] == b'\n' && data[from - 1] == b'\r' {
+            from -= 1;
+        }
+
+        // extend the chunk up to the end of a line, which is not continued on the next one
+        let end = loop {
+            let Some((i, terminator)) = line_end(&data[from..]) else {
+                break data.len();
+            };
+            let finish = from + i;
+
+            let start = data[..finish]
+                .iter()
+                .rposition(|b| *b == b'\n' || *b == b'\r')
+                .map_or(0, |i| i + 1);
+            if continuation(&data[start..finish]).is_none() {
+                break finish + terminator;
+            }
+
+            from = finish + terminator;
+        };
+
+        let (chunk, rest) = data.split_at(end);
+        chunks.push(chunk);
+        data = rest;
+    }
+
+    chunks
+}
+
+/// Returns the position and length of the first line terminator (`\n`, `\r\n` or `\r`).
+///
+/// A `\r` at the end of `data` is returned as a lone `\r`, even if the next input starts with `\n`.
+#[inline]
+fn line_end(data: &[u8]) -> Option<(usize, usize)> {
+    let i = data.iter().position(|b| *b == b'\n' || *b == b'\r')?;
+
+    let terminator = if data[i] == b'\r' && data.get(i + 1) == Some(&b'\n') {
+        2
+    } else {
+        1
+    };
+
+    Some((i, terminator))
+}
+
+/// Returns `true` if a line is empty, only contains whitespace, or is a comment.
+fn is_blank(line: &[u8]) -> bool {
+    let line = line.trim_ascii_start();
+
+    match line.first() {
+        None | Some(b'#') => true,
+        Some(b) if b.is_ascii() => false,
+        // might start with non-ASCII whitespace
+        Some(_) => std::str::from_utf8(line).is_ok_and(|line| {
+            let line = line.trim_start();
+            line.is_empty() || line.starts_with('#')
+        }),
+    }
+}
+
+/// Converts a line to a `&str`, either replacing invalid UTF-8 (using `lossy_buffer` for the converted line)
+/// or returning an error.
+fn decode<'b>(line: &'b [u8], lossy: bool, lossy_buffer: &'b mut String) -> Result<&'b str, Error> {
+    if lossy {
+        return Ok(match String::from_utf8_lossy(line) {
+            Cow::Borrowed(line) => line,
+            Cow::Owned(line) => {
+                *lossy_buffer = line;
+                lossy_buffer
+            }
+        });
+    }
+
+    std::str::from_utf8(line).map_err(|_| {
+        Error::Io(std::io::Error::new(
+            ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        ))
+    })
+}
+
+/// Returns the position of the backslash, if a line ends with a backslash (ignoring a trailing comment).
+fn continuation(line: &[u8]) -> Option<usize> {
+    let content = line
+        .iter()
+        .position(|b| *b == b'#')
+        .map_or(line, |comment| &line[..comment]);
+
+    let end = content.trim_ascii_end().len();
+
+    (end > 0 && content[end - 1] == b'\\').then(|| end - 1)
+}
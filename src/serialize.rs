@@ -0,0 +1,329 @@
+//! (De)serialization of foreign error types wrapped by [`Error`][crate::Error], which do not implement serde themselves,
+//! and the validated format of an [`ObjObject`].
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
+
+use crate::{
+    ObjObject, PolygonCorner,
+    parse::{FaceData, GroupingData, PolygonData},
+};
+
+pub mod io_error {
+    use super::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        error: &std::io::Error,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<std::io::Error, D::Error> {
+        let message = String::deserialize(deserializer)?;
+        Ok(std::io::Error::other(message))
+    }
+}
+
+pub mod parse_float_error {
+    use std::num::ParseFloatError;
+
+    use super::{Deserialize, Deserializer, Serializer, reconstruct};
+
+    pub fn serialize<S: Serializer>(
+        error: &ParseFloatError,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ParseFloatError, D::Error> {
+        let message = String::deserialize(deserializer)?;
+        Ok(reconstruct(
+            &message,
+            ["x", ""].map(|s| s.parse::<f32>().unwrap_err()),
+        ))
+    }
+}
+
+pub mod parse_int_error {
+    use std::num::{NonZeroU32, ParseIntError};
+
+    use super::{Deserialize, Deserializer, Serializer, reconstruct};
+
+    pub fn serialize<S: Serializer>(
+        error: &ParseIntError,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(error)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ParseIntError, D::Error> {
+        let message = String::deserialize(deserializer)?;
+
+        let candidates = [
+            "x".parse::<i32>().unwrap_err(),
+            "".parse::<i32>().unwrap_err(),
+            "4294967296".parse::<i32>().unwrap_err(),
+            "-4294967296".parse::<i32>().unwrap_err(),
+            "0".parse::<NonZeroU32>().unwrap_err(),
+        ];
+
+        Ok(reconstruct(&message, candidates))
+    }
+}
+
+/// Parse errors can not be constructed directly, so the first candidate with a matching message is used.
+///
+/// Falls back to the first candidate, if no message matches.
+fn reconstruct<E: std::fmt::Display, const N: usize>(message: &str, candidates: [E; N]) -> E {
+    let mut candidates = candidates.into_iter();
+    let first = candidates.next().expect("At least one candidate");
+
+    if first.to_string() == message {
+        return first;
+    }
+
+    candidates
+        .find(|candidate| candidate.to_string() == message)
+        .unwrap_or(first)
+}
+
+/// The serialized form of an [`ObjObject`].
+///
+/// Kept apart from the fields of [`ObjObject`], so the format only changes on purpose.
+/// Indices are 1-based like in the .obj file, and groups and objects reference ranges of faces, elements and groups.
+/// All of them are checked when deserializing, see [`ObjObjectData::validate`].
+#[derive(Serialize, Deserialize)]
+struct ObjObjectData<'a> {
+    vertices: Cow<'a, [[f32; 3]]>,
+    vertices_w: Cow<'a, [f32]>,
+    vertices_f64: Cow<'a, [[f64; 3]]>,
+    vertex_colors: Cow<'a, [[f32; 3]]>,
+    vertex_alphas: Cow<'a, [f32]>,
+    vertex_normals: Cow<'a, [[f32; 3]]>,
+    texture_coords: Cow<'a, [[f32; 2]]>,
+    texture_coords_w: Cow<'a, [f32]>,
+    vertex_tangents: Cow<'a, [[f32; 4]]>,
+
+    faces: Cow<'a, [FaceData]>,
+    line_elements: Cow<'a, [Vec<u32>]>,
+    line_texture_indices: Cow<'a, [Option<Vec<u32>>]>,
+    point_elements: Cow<'a, [Vec<u32>]>,
+    polygons: Cow<'a, [PolygonData]>,
+    polygon_corners: Cow<'a, [PolygonCorner]>,
+
+    groups: Cow<'a, [GroupingData]>,
+    objects: Cow<'a, [GroupingData]>,
+}
+
+impl ObjObjectData<'_> {
+    /// Checks that every index and range references existing data, so none of the accessors of [`ObjObject`] panic.
+    fn validate(&self) -> Result<(), String> {
+        let positions = self.vertices.len();
+        let texture_coords = self.texture_coords.len();
+        let normals = self.vertex_normals.len();
+
+        // optional data is either missing, or there for every element
+        for (name, len, expected) in [
+            ("vertices_w", self.vertices_w.len(), positions),
+            ("vertices_f64", self.vertices_f64.len(), positions),
+            ("vertex_colors", self.vertex_colors.len(), positions),
+            ("vertex_alphas", self.vertex_alphas.len(), positions),
+            (
+                "texture_coords_w",
+                self.texture_coords_w.len(),
+                texture_coords,
+            ),
+            (
+                "vertex_tangents",
+                self.vertex_tangents.len(),
+                self.faces.len() * 3,
+            ),
+        ] {
+            if len != 0 && len != expected {
+                return Err(format!("{name} has {len} entries instead of {expected}"));
+            }
+        }
+
+        for face in self.faces.iter() {
+            check_indices("position", &face.indices(), positions)?;
+            if let Some(indices) = face.texture_indices() {
+                check_indices("texture coord", &indices, texture_coords)?;
+            }
+            if let Some(indices) = face.normal_indices() {
+                check_indices("normal", &indices, normals)?;
+            }
+        }
+
+        self.validate_elements()?;
+
+        let mut previous = 0;
+        for polygon in self.polygons.iter() {
+            if polygon.face < previous || polygon.face >= self.faces.len() {
+                return Err(format!("polygon of face {} is out of order", polygon.face));
+            }
+            check_range(
+                "polygon",
+                polygon.start,
+                polygon.finish,
+                self.polygon_corners.len(),
+            )?;
+            previous = polygon.face;
+        }
+        for corner in self.polygon_corners.iter() {
+            check_indices("position", &[corner.position], positions)?;
+            check_indices(
+                "texture coord",
+                corner.texture_coord.as_slice(),
+                texture_coords,
+            )?;
+            check_indices("normal", corner.normal.as_slice(), normals)?;
+        }
+
+        for group in self.groups.iter() {
+            check_range("group", group.start, group.finish, self.faces.len())?;
+            check_range(
+                "group",
+                group.line_start,
+                group.line_finish,
+                self.line_elements.len(),
+            )?;
+            check_range(
+                "group",
+                group.point_start,
+                group.point_finish,
+                self.point_elements.len(),
+            )?;
+
+            if group
+                .mtl_changes
+                .iter()
+                .any(|(face, _)| !(group.start..=group.finish).contains(face))
+            {
+                return Err(format!("material change outside of group {}", group.name));
+            }
+        }
+        for object in self.objects.iter() {
+            check_range("object", object.start, object.finish, self.groups.len())?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the line and point elements, line elements need at least 2 vertices and point elements at least 1.
+    fn validate_elements(&self) -> Result<(), String> {
+        let positions = self.vertices.len();
+        let texture_coords = self.texture_coords.len();
+
+        if self.line_texture_indices.len() != self.line_elements.len() {
+            return Err(String::from("not every line element has texture indices"));
+        }
+        for (element, texture) in self
+            .line_elements
+            .iter()
+            .zip(self.line_texture_indices.iter())
+        {
+            if element.len() < 2 {
+                return Err(String::from("line element with less than 2 vertices"));
+            }
+            check_indices("position", element, positions)?;
+            if let Some(texture) = texture {
+                if texture.len() != element.len() {
+                    return Err(String::from("not every line corner has a texture index"));
+                }
+                check_indices("texture coord", texture, texture_coords)?;
+            }
+        }
+        for element in self.point_elements.iter() {
+            if element.is_empty() {
+                return Err(String::from("point element without vertices"));
+            }
+            check_indices("position", element, positions)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_indices(kind: &str, indices: &[u32], count: usize) -> Result<(), String> {
+    indices
+        .iter()
+        .find(|&&index| index == 0 || index as usize > count)
+        .map_or(Ok(()), |index| {
+            Err(format!(
+                "{kind} index {index} is out of range, there are {count}"
+            ))
+        })
+}
+
+fn check_range(kind: &str, start: usize, finish: usize, len: usize) -> Result<(), String> {
+    if start <= finish && finish <= len {
+        Ok(())
+    } else {
+        Err(format!(
+            "{kind} range {start}..{finish} is out of range, there are {len}"
+        ))
+    }
+}
+
+impl Serialize for ObjObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ObjObjectData {
+            vertices: Cow::Borrowed(&self.vertices),
+            vertices_w: Cow::Borrowed(&self.vertices_w),
+            vertices_f64: Cow::Borrowed(&self.vertices_f64),
+            vertex_colors: Cow::Borrowed(&self.vertex_colors),
+            vertex_alphas: Cow::Borrowed(&self.vertex_alphas),
+            vertex_normals: Cow::Borrowed(&self.vertex_normals),
+            texture_coords: Cow::Borrowed(&self.texture_coords),
+            texture_coords_w: Cow::Borrowed(&self.texture_coords_w),
+            vertex_tangents: Cow::Borrowed(&self.vertex_tangents),
+            faces: Cow::Borrowed(&self.faces),
+            line_elements: Cow::Borrowed(&self.line_elements),
+            line_texture_indices: Cow::Borrowed(&self.line_texture_indices),
+            point_elements: Cow::Borrowed(&self.point_elements),
+            polygons: Cow::Borrowed(&self.polygons),
+            polygon_corners: Cow::Borrowed(&self.polygon_corners),
+            groups: Cow::Borrowed(&self.groups),
+            objects: Cow::Borrowed(&self.objects),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ObjObjectData::deserialize(deserializer)?;
+        data.validate().map_err(D::Error::custom)?;
+
+        Ok(Self {
+            vertices: data.vertices.into_owned(),
+            vertices_w: data.vertices_w.into_owned(),
+            vertices_f64: data.vertices_f64.into_owned(),
+            vertex_colors: data.vertex_colors.into_owned(),
+            vertex_alphas: data.vertex_alphas.into_owned(),
+            vertex_normals: data.vertex_normals.into_owned(),
+            texture_coords: data.texture_coords.into_owned(),
+            texture_coords_w: data.texture_coords_w.into_owned(),
+            vertex_tangents: data.vertex_tangents.into_owned(),
+            faces: data.faces.into_owned(),
+            line_elements: data.line_elements.into_owned(),
+            line_texture_indices: data.line_texture_indices.into_owned(),
+            point_elements: data.point_elements.into_owned(),
+            polygons: data.polygons.into_owned(),
+            polygon_corners: data.polygon_corners.into_owned(),
+            groups: data.groups.into_owned(),
+            objects: data.objects.into_owned(),
+            line_buffer: Vec::new(),
+            string_pool: Vec::new(),
+        })
+    }
+}
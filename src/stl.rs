@@ -0,0 +1,409 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{
+    Error, Line, ObjObject, ParseLimits, ParseOptions, ParsedVertex, Vec3,
+    geometry::face_normal,
+    parse::{Corners, ParseState},
+};
+
+/// The size of the header of a binary .stl file, before the number of triangles.
+const HEADER_LEN: usize = 80;
+/// The size of a single triangle of a binary .stl file: the normal, three vertices and the attribute byte count.
+const TRIANGLE_LEN: usize = 50;
+
+/// A single triangle (facet) of a .stl file.
+struct Facet {
+    normal: [f32; 3],
+    vertices: [[f32; 3]; 3],
+}
+
+/// A named solid of an ASCII .stl file, binary files only contain a single unnamed one.
+struct Solid {
+    name: String,
+    facets: Vec<Facet>,
+}
+
+impl ObjObject {
+    /// Reads and parses a .stl file, see [`ObjObject::parse_stl`].
+    ///
+    /// # Errors
+    /// Returns an `Error` if the file could not be read, or the .stl file is not as structured as expected.
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    #[inline]
+    pub fn read_stl<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::read_stl_with(path, &ParseOptions::default())
+    }
+
+    /// Same as [`ObjObject::read_stl`], but using the given [`ParseOptions`], see [`ObjObject::parse_stl_with`].
+    ///
+    /// Files larger than [`ParseLimits::max_file_size`][crate::ParseLimits::max_file_size] are rejected before reading them.
+    ///
+    /// # Errors
+    /// Returns an `Error` if the file could not be read, or the .stl file is not as structured as expected.
+    /// All errors are wrapped in [`Error::File`], containing the path of the file.
+    pub fn read_stl_with<P: AsRef<Path>>(path: P, options: &ParseOptions) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        File::open(path)
+            .map_err(Error::from)
+            .and_then(|file| {
+                let size = file.metadata()?.len();
+                let limit = options.limits.max_file_size;
+                if size > limit {
+                    return Err(Error::FileTooLarge { size, limit });
+                }
+
+                Self::parse_stl_with(file, options)
+            })
+            .map_err(|err| err.in_file(path))
+    }
+
+    /// Parses an ASCII or binary .stl file from some sort of input reader, using the default [`ParseOptions`].
+    ///
+    /// Files starting with `solid` are ASCII, unless their size matches the number of triangles of a binary file,
+    /// as some exporters also start the header of binary files with `solid`.
+    ///
+    /// Every triangle (facet) is stored with its own three vertex positions, and its normal for all three corners.
+    /// Set [`ParseOptions::weld_epsilon`] via [`ObjObject::parse_stl_with`] to share the positions between triangles.
+    /// Every named solid of an ASCII file becomes an object (o).
+    ///
+    /// ## Example
+    /// ```rust
+    /// use polypath::ObjObject;
+    ///
+    /// let data = "solid triangle
+    ///   facet normal 0 0 1
+    ///     outer loop
+    ///       vertex 0 0 0
+    ///       vertex 1 0 0
+    ///       vertex 0 1 0
+    ///     endloop
+    ///   endfacet
+    /// endsolid triangle
+    /// ";
+    /// let obj = ObjObject::parse_stl(data.as_bytes()).unwrap();
+    /// assert_eq!(obj.face_count(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, or the .stl file is not as structured as expected.
+    /// Errors of an ASCII file are wrapped in [`Error::AtLine`].
+    #[inline]
+    pub fn parse_stl(reader: impl Read) -> Result<Self, Error> {
+        Self::parse_stl_with(reader, &ParseOptions::default())
+    }
+
+    /// Same as [`ObjObject::parse_stl`], but using the given [`ParseOptions`].
+    ///
+    /// Options affecting how data is stored apply (e.g. [`ParseOptions::keep_normals`], [`ParseOptions::weld_epsilon`]),
+    /// as well as [`ParseOptions::reject_non_finite`] and the [`ParseLimits`].
+    /// Reading stops as soon as more than [`ParseLimits::max_file_size`] bytes were read,
+    /// and the triangle count of a binary file is checked against the other limits before reading its triangles.
+    ///
+    /// # Errors
+    /// Returns an `Error` if reading fails, the .stl file is not as structured as expected, or any limit is exceeded.
+    /// Errors of an ASCII file are wrapped in [`Error::AtLine`].
+    pub fn parse_stl_with(reader: impl Read, options: &ParseOptions) -> Result<Self, Error> {
+        let limits = &options.limits;
+        let mut reader = reader.take(limits.max_file_size.saturating_add(1));
+
+        let mut data = Vec::new();
+        (&mut reader)
+            .take((HEADER_LEN + 4) as u64)
+            .read_to_end(&mut data)?;
+
+        // a header not starting with `solid` always belongs to a binary file
+        let header = data.trim_ascii_start();
+        if header.len() >= 5
+            && !header.starts_with(b"solid")
+            && let Some(count) = triangle_count(&data)
+        {
+            check_limits(count as usize, limits)?;
+        }
+
+        reader.read_to_end(&mut data)?;
+        let size = data.len() as u64;
+        if size > limits.max_file_size {
+            return Err(Error::FileTooLarge {
+                size,
+                limit: limits.max_file_size,
+            });
+        }
+
+        let solids = if is_binary(&data) {
+            parse_binary(&data)?
+        } else {
+            parse_ascii(&data)?
+        };
+
+        let facets = solids.iter().map(|solid| solid.facets.len()).sum::<usize>();
+        check_limits(facets, limits)?;
+
+        let mut obj = Self::empty();
+        let mut state = obj.start_parsing(options, None);
+        let res = solids.iter().try_for_each(|solid| {
+            if !solid.name.is_empty() {
+                obj.push_decoded(&mut state, Line::Object(&solid.name))?;
+            }
+
+            solid
+                .facets
+                .iter()
+                .try_for_each(|facet| obj.push_facet(&mut state, facet, options))
+        });
+        obj.finish_parsing(state, options, res)?;
+
+        Ok(obj)
+    }
+
+    /// Adds the vertices, normal and face of a single triangle.
+    fn push_facet(
+        &mut self,
+        state: &mut ParseState,
+        facet: &Facet,
+        options: &ParseOptions,
+    ) -> Result<(), Error> {
+        for position in facet.vertices {
+            let vertex = ParsedVertex {
+                position,
+                w: 1.0,
+                color: None,
+                alpha: None,
+                position_f64: None,
+            };
+            self.push_decoded(state, Line::Vertex(vertex))?;
+        }
+        self.push_decoded(state, Line::Normal(facet.normal))?;
+
+        // indices are 1-based, the vertices and normal were just added
+        let vertex = u32::try_from(self.vertices.len()).map_err(|_| Error::IndexOverflow {
+            count: self.vertices.len(),
+            limit: u32::MAX as usize,
+        })?;
+        let normal = u32::try_from(self.vertex_normals.len())
+            .ok()
+            .filter(|_| options.keep_normals);
+        let corners = (0..3)
+            .map(|i| (vertex - 2 + i, None, normal))
+            .collect::<Corners>();
+        let triangles =
+            Self::triangulate_face(&corners, false, normal.is_some(), &self.vertices, options);
+
+        self.push_decoded(state, Line::Faces(triangles, corners))
+    }
+
+    /// Writes this `ObjObject` as a binary .stl file to a file, see [`ObjObject::write_stl_binary_to_writer`].
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error] if creating or writing the file fails.
+    pub fn write_stl_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.write_stl_binary_to_writer(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the faces of all objects and groups as a binary .stl file to some sort of writer.
+    ///
+    /// The normal of a triangle is the normal of its corners, if all three corners have the same normal.
+    /// Otherwise it is calculated from the positions, following the winding order.
+    /// Everything else (e.g. colors, texture coords, groups or materials) can not be stored.
+    ///
+    /// The writer is not buffered, consider wrapping it in a [`BufWriter`].
+    ///
+    /// # Errors
+    /// - Returns an [Error][std::io::Error] if writing fails
+    /// - Returns [`Error::IndexOverflow`] if there are more triangles than a .stl file can hold
+    pub fn write_stl_binary_to_writer(&self, mut writer: impl Write) -> Result<(), Error> {
+        let count = u32::try_from(self.faces.len()).map_err(|_| Error::IndexOverflow {
+            count: self.faces.len(),
+            limit: u32::MAX as usize,
+        })?;
+
+        // a binary header must not start with `solid`, or it could be mistaken for an ASCII file
+        let mut header = [0; HEADER_LEN];
+        let comment = b"binary stl written by polypath";
+        header[..comment.len()].copy_from_slice(comment);
+        writer.write_all(&header)?;
+        writer.write_all(&count.to_le_bytes())?;
+
+        for face in self.faces_iter() {
+            #[allow(clippy::float_cmp)]
+            let normal = match face.vert_normals {
+                Some([n0, n1, n2]) if n0 == n1 && n1 == n2 => n0,
+                _ => {
+                    let [p0, p1, p2] = face.vert_positions.map(Vec3::from);
+                    face_normal(p0, p1, p2).to_array()
+                }
+            };
+
+            let mut triangle = [0; TRIANGLE_LEN];
+            let values = normal.iter().chain(face.vert_positions.as_flattened());
+            for (bytes, value) in triangle.chunks_exact_mut(4).zip(values) {
+                bytes.copy_from_slice(&value.to_le_bytes());
+            }
+            // the attribute byte count stays 0
+            writer.write_all(&triangle)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the number of triangles (facets) against [`ParseLimits::max_faces`] and [`ParseLimits::max_vertices`],
+/// as every triangle has its own three vertices.
+const fn check_limits(facets: usize, limits: &ParseLimits) -> Result<(), Error> {
+    if facets > limits.max_faces {
+        return Err(Error::TooManyFaces {
+            limit: limits.max_faces,
+        });
+    }
+    if facets.saturating_mul(3) > limits.max_vertices {
+        return Err(Error::TooManyVertices {
+            limit: limits.max_vertices,
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether a .stl file is binary.
+///
+/// Binary files may start with `solid` as well, so the size of the file is checked first.
+fn is_binary(data: &[u8]) -> bool {
+    if let Some(count) = triangle_count(data)
+        && (count as usize)
+            .checked_mul(TRIANGLE_LEN)
+            .and_then(|len| len.checked_add(HEADER_LEN + 4))
+            == Some(data.len())
+    {
+        return true;
+    }
+
+    !data.trim_ascii_start().starts_with(b"solid")
+}
+
+/// The number of triangles of a binary .stl file, after the header.
+fn triangle_count(data: &[u8]) -> Option<u32> {
+    let bytes = data.get(HEADER_LEN..HEADER_LEN + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn parse_binary(data: &[u8]) -> Result<Vec<Solid>, Error> {
+    let count = triangle_count(data)
+        .ok_or_else(|| Error::InvalidStl(String::from("the file ends inside of the header")))?;
+
+    let triangles = &data[HEADER_LEN + 4..];
+    if triangles.len() / TRIANGLE_LEN < count as usize {
+        return Err(Error::InvalidStl(format!(
+            "the file ends before all [{count}] triangles"
+        )));
+    }
+
+    let facets = triangles
+        .chunks_exact(TRIANGLE_LEN)
+        .take(count as usize)
+        .map(|triangle| {
+            let mut values = triangle
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+            let mut vector = || std::array::from_fn(|_| values.next().unwrap_or_default());
+
+            Facet {
+                normal: vector(),
+                vertices: [vector(), vector(), vector()],
+            }
+        })
+        .collect();
+
+    Ok(vec![Solid {
+        name: String::new(),
+        facets,
+    }])
+}
+
+fn parse_ascii(data: &[u8]) -> Result<Vec<Solid>, Error> {
+    // only the keywords and numbers matter, names may use any encoding
+    let data = String::from_utf8_lossy(data);
+
+    let mut solids = Vec::new();
+    let mut solid: Option<Solid> = None;
+    let mut facet: Option<(Facet, usize)> = None;
+
+    for (i, line) in data.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+
+        let res = match (tokens.next(), &mut solid, &mut facet) {
+            // the loop of a facet does not carry any information
+            (None, ..) | (Some("endloop"), Some(_), Some(_)) => Ok(()),
+            (Some("solid"), None, _) => {
+                solid = Some(Solid {
+                    name: tokens.collect::<Vec<_>>().join(" "),
+                    facets: Vec::new(),
+                });
+                Ok(())
+            }
+            (Some("facet"), Some(_), None) => match tokens.next() {
+                Some("normal") => parse_vector(tokens).map(|normal| {
+                    let vertices = [[0.0; 3]; 3];
+                    facet = Some((Facet { normal, vertices }, 0));
+                }),
+                _ => Err(Error::UnknownLine(String::from(line.trim()))),
+            },
+            (Some("outer"), Some(_), Some(_)) if tokens.next() == Some("loop") => Ok(()),
+            (Some("vertex"), Some(_), Some((facet, count))) => {
+                let index = *count;
+                *count += 1;
+
+                facet.vertices.get_mut(index).map_or_else(
+                    || {
+                        Err(Error::InvalidStl(String::from(
+                            "facet with more than 3 vertices",
+                        )))
+                    },
+                    |vertex| parse_vector(tokens).map(|parsed| *vertex = parsed),
+                )
+            }
+            (Some("endfacet"), Some(solid), Some((_, count))) => {
+                if *count == 3 {
+                    solid.facets.extend(facet.take().map(|(facet, _)| facet));
+                    Ok(())
+                } else {
+                    Err(Error::InvalidStl(format!(
+                        "facet with only [{count}] vertices"
+                    )))
+                }
+            }
+            (Some("endsolid"), Some(_), None) => {
+                solids.extend(solid.take());
+                Ok(())
+            }
+            (Some(_), ..) => Err(Error::UnknownLine(String::from(line.trim()))),
+        };
+        res.map_err(|err| err.at_line(i + 1))?;
+    }
+
+    // some exporters do not end the last solid
+    if facet.is_some() {
+        return Err(Error::InvalidStl(String::from(
+            "the file ends inside of a facet",
+        )));
+    }
+    solids.extend(solid);
+
+    Ok(solids)
+}
+
+fn parse_vector<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<[f32; 3], Error> {
+    let mut vector = [0.0; 3];
+    for value in &mut vector {
+        *value = tokens.next().ok_or(Error::UnexpectedEoL)?.parse()?;
+    }
+    Ok(vector)
+}
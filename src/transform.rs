@@ -0,0 +1,374 @@
+use std::{mem, ops::Range};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    ObjObject,
+    parse::{FaceData, GroupingData, index_count},
+    vec3::Vec3,
+};
+
+/// Identifies an object (o) inside an [`ObjObject`], either by its index or by its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectId<'a> {
+    Index(usize),
+    Name(&'a str),
+}
+
+impl From<usize> for ObjectId<'_> {
+    #[inline]
+    fn from(value: usize) -> Self {
+        Self::Index(value)
+    }
+}
+
+impl<'a> From<&'a str> for ObjectId<'a> {
+    #[inline]
+    fn from(value: &'a str) -> Self {
+        Self::Name(value)
+    }
+}
+
+/// Marks a position or normal as not yet claimed by any transformed object.
+const UNCLAIMED: usize = usize::MAX;
+/// Marks a position or normal as referenced by an object without a transform.
+const PINNED: usize = usize::MAX - 1;
+
+impl ObjObject {
+    /// Sets the column-major transform of an object.
+    ///
+    /// The transform is only stored, vertex data is not changed until [`ObjObject::apply_object_transforms`] is called.
+    /// When writing, [`WriteConfig::bake_object_transforms`][crate::WriteConfig::bake_object_transforms] applies it to the written data.
+    /// If multiple objects share the same name, the first one is used.
+    ///
+    /// Returns `false` if no such object exists.
+    pub fn set_object_transform<'a>(
+        &mut self,
+        object: impl Into<ObjectId<'a>>,
+        transform: [[f32; 4]; 4],
+    ) -> bool {
+        let object = match object.into() {
+            ObjectId::Index(index) => self.objects.get_mut(index),
+            ObjectId::Name(name) => self.objects.iter_mut().find(|o| o.name == name),
+        };
+
+        object.map(|o| o.transform = Some(transform)).is_some()
+    }
+
+    /// Bakes the transform of every object into its vertex positions and normals, and clears the transforms.
+    ///
+    /// Transforms are expected to be affine, the last row is ignored.
+    /// Normals are transformed by the inverse transpose of the upper 3x3 part and renormalized.
+    ///
+    /// Objects share the same attribute arrays, so positions and normals that are also referenced by another object
+    /// (with or without a transform) are duplicated, instead of being transformed in place.
+    pub fn apply_object_transforms(&mut self) {
+        if self.objects.iter().all(|o| o.transform.is_none()) {
+            return;
+        }
+
+        let (position_owner, normal_owner) = self.claim_attributes();
+
+        // attributes referenced by multiple objects get duplicated, while all originals are still untransformed
+        for object_index in 0..self.objects.len() {
+            let Some(transform) = self.objects[object_index].transform else {
+                continue;
+            };
+
+            let mut positions = FxHashMap::default();
+            let mut normals = FxHashMap::default();
+
+            let groups = self.objects[object_index].start..self.objects[object_index].finish;
+            let faces = self.groups[groups.clone()]
+                .first()
+                .zip(self.groups[groups].last())
+                .map_or(0..0, |(first, last)| first.start..last.finish);
+
+            for face_index in faces.clone() {
+                let mut face = self.faces[face_index];
+
+                face.indicies = <[u32; 3]>::from(face.indicies)
+                    .map(|i| {
+                        self.remap_position(
+                            i,
+                            object_index,
+                            &position_owner,
+                            &mut positions,
+                            &transform,
+                        )
+                    })
+                    .into();
+
+                face.normal_indicies = face.normal_indicies.map(|normal| {
+                    <[u32; 3]>::from(normal)
+                        .map(|n| {
+                            self.remap_normal(
+                                n,
+                                object_index,
+                                &normal_owner,
+                                &mut normals,
+                                &transform,
+                            )
+                        })
+                        .into()
+                });
+
+                self.faces[face_index] = face;
+            }
+
+            // kept polygons reference the same attributes as their triangles
+            let polygons = self.polygons.partition_point(|p| p.face < faces.start)
+                ..self.polygons.partition_point(|p| p.face < faces.end);
+            for polygon_index in polygons {
+                let polygon = self.polygons[polygon_index];
+
+                for corner_index in polygon.start..polygon.finish {
+                    let mut corner = self.polygon_corners[corner_index];
+
+                    corner.position = self.remap_position(
+                        corner.position,
+                        object_index,
+                        &position_owner,
+                        &mut positions,
+                        &transform,
+                    );
+                    corner.normal = corner.normal.map(|n| {
+                        self.remap_normal(n, object_index, &normal_owner, &mut normals, &transform)
+                    });
+
+                    self.polygon_corners[corner_index] = corner;
+                }
+            }
+
+            self.remap_elements(
+                object_index,
+                |group| group.line_start..group.line_finish,
+                |obj| &mut obj.line_elements,
+                &position_owner,
+                &mut positions,
+                &transform,
+            );
+            self.remap_elements(
+                object_index,
+                |group| group.point_start..group.point_finish,
+                |obj| &mut obj.point_elements,
+                &position_owner,
+                &mut positions,
+                &transform,
+            );
+        }
+
+        // finally transform the claimed attributes in place
+        for (position, owner) in self.vertices.iter_mut().zip(position_owner) {
+            if let Some(transform) = self.objects.get(owner).and_then(|o| o.transform) {
+                *position = transform_point(&transform, *position);
+            }
+        }
+
+        for (normal, owner) in self.vertex_normals.iter_mut().zip(normal_owner) {
+            if let Some(transform) = self.objects.get(owner).and_then(|o| o.transform) {
+                *normal = transform_normal(&transform, *normal);
+            }
+        }
+
+        for object in &mut self.objects {
+            object.transform = None;
+        }
+    }
+
+    /// Returns for every position and normal, the index of the transformed object it gets transformed with.
+    ///
+    /// Attributes of objects without a transform must stay untouched,
+    /// every other attribute is claimed by the first transformed object referencing it.
+    fn claim_attributes(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut position_owner = vec![UNCLAIMED; self.vertices.len()];
+        let mut normal_owner = vec![UNCLAIMED; self.vertex_normals.len()];
+
+        for pinned in [true, false] {
+            for object_index in 0..self.objects.len() {
+                if self.objects[object_index].transform.is_none() != pinned {
+                    continue;
+                }
+
+                let marker = if pinned { PINNED } else { object_index };
+
+                for face in self.object_faces(object_index) {
+                    for i in <[u32; 3]>::from(face.indicies) {
+                        claim_attribute(&mut position_owner, i, marker);
+                    }
+
+                    if let Some(normal) = face.normal_indicies {
+                        for n in <[u32; 3]>::from(normal) {
+                            claim_attribute(&mut normal_owner, n, marker);
+                        }
+                    }
+                }
+
+                for i in self.object_elements(object_index) {
+                    claim_attribute(&mut position_owner, i, marker);
+                }
+            }
+        }
+
+        (position_owner, normal_owner)
+    }
+
+    fn object_faces(&self, object: usize) -> impl Iterator<Item = &FaceData> {
+        let object = &self.objects[object];
+
+        self.groups[object.start..object.finish]
+            .iter()
+            .flat_map(|group| &self.faces[group.start..group.finish])
+    }
+
+    /// Returns the position indices of all line and point elements of an object.
+    fn object_elements(&self, object: usize) -> impl Iterator<Item = u32> {
+        let object = &self.objects[object];
+
+        self.groups[object.start..object.finish]
+            .iter()
+            .flat_map(|group| {
+                self.line_elements[group.line_start..group.line_finish]
+                    .iter()
+                    .chain(&self.point_elements[group.point_start..group.point_finish])
+            })
+            .flatten()
+            .copied()
+    }
+
+    /// Remaps the positions of the elements (of one kind) of every group of an object, see [`ObjObject::remap_position`].
+    fn remap_elements(
+        &mut self,
+        object: usize,
+        range: fn(&GroupingData) -> Range<usize>,
+        elements: fn(&mut Self) -> &mut [Vec<u32>],
+        owner: &[usize],
+        remap: &mut FxHashMap<u32, u32>,
+        transform: &[[f32; 4]; 4],
+    ) {
+        for group_index in self.objects[object].start..self.objects[object].finish {
+            for element_index in range(&self.groups[group_index]) {
+                let mut element = mem::take(&mut elements(self)[element_index]);
+
+                for i in &mut element {
+                    *i = self.remap_position(*i, object, owner, remap, transform);
+                }
+
+                elements(self)[element_index] = element;
+            }
+        }
+    }
+
+    /// Returns the index of the position an object should reference after its transform is applied.
+    ///
+    /// Positions owned by another object are duplicated (once per object), using `remap` to track the copies.
+    fn remap_position(
+        &mut self,
+        index: u32,
+        object: usize,
+        owner: &[usize],
+        remap: &mut FxHashMap<u32, u32>,
+        transform: &[[f32; 4]; 4],
+    ) -> u32 {
+        if owner[index as usize - 1] == object {
+            return index;
+        }
+
+        *remap
+            .entry(index)
+            .or_insert_with(|| self.duplicate_position(index, transform))
+    }
+
+    /// Returns the index of the normal an object should reference after its transform is applied.
+    ///
+    /// Normals owned by another object are duplicated (once per object), using `remap` to track the copies.
+    fn remap_normal(
+        &mut self,
+        index: u32,
+        object: usize,
+        owner: &[usize],
+        remap: &mut FxHashMap<u32, u32>,
+        transform: &[[f32; 4]; 4],
+    ) -> u32 {
+        if owner[index as usize - 1] == object {
+            return index;
+        }
+
+        *remap
+            .entry(index)
+            .or_insert_with(|| self.duplicate_normal(index, transform))
+    }
+
+    /// Appends a transformed copy of a position (and its color, alpha and w), returning the (1-based) index of the copy.
+    fn duplicate_position(&mut self, index: u32, transform: &[[f32; 4]; 4]) -> u32 {
+        let i = index as usize - 1;
+
+        self.vertices
+            .push(transform_point(transform, self.vertices[i]));
+        if let Some(color) = self.vertex_colors.get(i).copied() {
+            self.vertex_colors.push(color);
+        }
+        if let Some(alpha) = self.vertex_alphas.get(i).copied() {
+            self.vertex_alphas.push(alpha);
+        }
+        if let Some(w) = self.vertices_w.get(i).copied() {
+            self.vertices_w.push(w);
+        }
+
+        index_count(self.vertices.len())
+    }
+
+    /// Appends a transformed copy of a normal, returning the (1-based) index of the copy.
+    fn duplicate_normal(&mut self, index: u32, transform: &[[f32; 4]; 4]) -> u32 {
+        let i = index as usize - 1;
+
+        self.vertex_normals
+            .push(transform_normal(transform, self.vertex_normals[i]));
+
+        index_count(self.vertex_normals.len())
+    }
+}
+
+#[inline]
+fn claim_attribute(owner: &mut [usize], index: u32, marker: usize) {
+    let owner = &mut owner[index as usize - 1];
+    if *owner == UNCLAIMED {
+        *owner = marker;
+    }
+}
+
+#[inline]
+const fn column(transform: &[[f32; 4]; 4], index: usize) -> Vec3 {
+    let [x, y, z, _] = transform[index];
+    Vec3::new(x, y, z)
+}
+
+fn transform_point(transform: &[[f32; 4]; 4], [x, y, z]: [f32; 3]) -> [f32; 3] {
+    let [c0, c1, c2, c3] = [0, 1, 2, 3].map(|i| column(transform, i));
+
+    [
+        c2.x.mul_add(z, c1.x.mul_add(y, c0.x.mul_add(x, c3.x))),
+        c2.y.mul_add(z, c1.y.mul_add(y, c0.y.mul_add(x, c3.y))),
+        c2.z.mul_add(z, c1.z.mul_add(y, c0.z.mul_add(x, c3.z))),
+    ]
+}
+
+fn transform_normal(transform: &[[f32; 4]; 4], [x, y, z]: [f32; 3]) -> [f32; 3] {
+    let [c0, c1, c2] = [0, 1, 2].map(|i| column(transform, i));
+
+    // the columns of the inverse transpose are the cross products of the columns, divided by the determinant
+    // only the sign of the determinant matters, as the normal gets renormalized
+    let r0 = c1.cross(&c2);
+    let r1 = c2.cross(&c0);
+    let r2 = c0.cross(&c1);
+    let sign = c0.dot(&r0).signum();
+
+    let normal = Vec3::new(
+        sign * r2.x.mul_add(z, r1.x.mul_add(y, r0.x * x)),
+        sign * r2.y.mul_add(z, r1.y.mul_add(y, r0.y * x)),
+        sign * r2.z.mul_add(z, r1.z.mul_add(y, r0.z * x)),
+    )
+    .normalized();
+
+    [normal.x, normal.y, normal.z]
+}
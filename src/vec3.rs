@@ -1,6 +1,9 @@
-use core::ops::Sub;
+use core::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::ops::AddAssign;
 
+/// A minimal 3D vector, used for the geometric computations of this crate.
+///
+/// All public functions take and return plain `[f32; 3]` arrays, see [`Vec3::from_array`] and [`Vec3::to_array`].
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Vec3 {
     pub x: f32,
@@ -15,10 +18,30 @@ impl PartialEq for Vec3 {
     }
 }
 
-impl From<(f32, f32, f32)> for Vec3 {
+impl From<[f32; 3]> for Vec3 {
     #[inline]
-    fn from(value: (f32, f32, f32)) -> Self {
-        Self::new(value.0, value.1, value.2)
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Vec3> for [f32; 3] {
+    #[inline]
+    fn from(value: Vec3) -> Self {
+        value.to_array()
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
     }
 }
 
@@ -44,8 +67,75 @@ impl Sub for Vec3 {
     }
 }
 
+impl SubAssign for Vec3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+        self.z = self.z - rhs.z;
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl MulAssign<f32> for Vec3 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.x = self.x * rhs;
+        self.y = self.y * rhs;
+        self.z = self.z * rhs;
+    }
+}
+
+impl Div<f32> for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        }
+    }
+}
+
+impl DivAssign<f32> for Vec3 {
+    #[inline]
+    fn div_assign(&mut self, rhs: f32) {
+        self.x = self.x / rhs;
+        self.y = self.y / rhs;
+        self.z = self.z / rhs;
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
 impl Vec3 {
     #[inline]
+    #[must_use]
     pub const fn new(x: f32, y: f32, z: f32) -> Self {
         Self { x, y, z }
     }
@@ -74,26 +164,60 @@ impl Vec3 {
 
     #[inline]
     #[must_use]
-    pub fn lenght(&self) -> f32 {
-        self.z
-            .mul_add(self.z, self.x.mul_add(self.x, self.y * self.y))
-            .sqrt()
+    pub fn length(&self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns the squared length, which avoids the square root when only comparing lengths.
+    pub fn length_squared(&self) -> f32 {
+        self.dot(self)
     }
 
     #[inline]
     #[must_use]
     pub fn distance(self, rhs: Self) -> f32 {
-        (self - rhs).lenght()
+        (self - rhs).length()
     }
 
     #[inline]
     #[must_use]
     pub fn normalized(&self) -> Self {
-        let len = self.lenght();
-        Self {
-            x: self.x / len,
-            y: self.y / len,
-            z: self.z / len,
-        }
+        *self / self.length()
+    }
+
+    #[inline]
+    #[must_use]
+    /// Linearly interpolates between `self` (at `t = 0.0`) and `other` (at `t = 1.0`).
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    #[inline]
+    #[must_use]
+    /// Reflects this vector at a plane with the given (normalized) normal.
+    pub fn reflect(self, normal: Self) -> Self {
+        self - normal * (2.0 * self.dot(&normal))
+    }
+
+    #[inline]
+    #[must_use]
+    /// Scales this vector down to a length of `max`, if it is longer.
+    pub fn clamp_length(self, max: f32) -> Self {
+        let len = self.length();
+        if len > max { self * (max / len) } else { self }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn to_array(self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn from_array([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
     }
 }
@@ -0,0 +1,572 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Write},
+    iter::Peekable,
+    path::Path,
+};
+
+use crate::{
+    Error, MaterialIdent, ObjObject, VertexData, VertexTextureData,
+    parse::{DEFAULT_GROUP_NAME, DisplayAttributes, GroupingData},
+};
+
+impl ObjObject {
+    /// Writes this `ObjObject` as a .obj file to a file, using the default [`WriteConfig`].
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error] if creating or writing the file fails.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        self.write_to_writer(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes this `ObjObject` as a .obj file to some sort of writer.
+    ///
+    /// All vertex data (v, vt, vn) is written first, followed by the objects (o) and groups (g) with their elements,
+    /// so vertex data shared between groups is only written once.
+    /// Faces are written as triangles, unless the polygons were kept while parsing,
+    /// see [`ParseOptions::keep_polygons`][crate::ParseOptions::keep_polygons].
+    ///
+    /// The writer is not buffered, consider wrapping it in a [`BufWriter`].
+    /// Uses the default [`WriteConfig`].
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error] if writing fails.
+    #[inline]
+    pub fn write_to_writer(&self, writer: impl Write) -> Result<(), Error> {
+        self.write_to_writer_with_config(writer, &WriteConfig::default())
+    }
+
+    /// Writes this `ObjObject` as a .obj file to some sort of writer, same as [`ObjObject::write_to_writer`].
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error] if writing fails.
+    #[inline]
+    pub fn write_to(&self, writer: impl Write) -> Result<(), Error> {
+        self.write_to_writer(writer)
+    }
+
+    /// Same as [`ObjObject::write_to_writer`], but using the given [`WriteConfig`].
+    ///
+    /// # Errors
+    /// Returns an [Error][std::io::Error] if writing fails.
+    pub fn write_to_writer_with_config(
+        &self,
+        mut writer: impl Write,
+        config: &WriteConfig,
+    ) -> Result<(), Error> {
+        if config.bake_object_transforms && self.objects.iter().any(|o| o.transform.is_some()) {
+            let mut baked = self.clone();
+            baked.apply_object_transforms();
+
+            return baked.write_to_writer_with_config(writer, config);
+        }
+
+        // vertex data that is the same for every vertex does not carry any information
+        let texture = !(config.compact && all_equal(&self.texture_coords));
+        let normal = !(config.compact && all_equal(&self.vertex_normals));
+
+        if config.include_comments {
+            self.write_header(&mut writer, texture, normal)?;
+        }
+
+        let options = WriteOptions {
+            precision: config.float_precision,
+            texture,
+            normal,
+        };
+
+        self.write_vertex_data(&mut writer, &options)?;
+
+        let mut smooth_group = None;
+        let mut usemap = None;
+
+        for object in &self.objects {
+            // the implicit object of a file has no name
+            if !object.name.is_empty() {
+                writeln!(writer, "o {}", object.name)?;
+            }
+            if !object.mtllibs.is_empty() {
+                writeln!(writer, "mtllib {}", object.mtllibs.join(" "))?;
+            }
+            if !object.maplibs.is_empty() {
+                writeln!(writer, "maplib {}", object.maplibs.join(" "))?;
+            }
+            write_display_attributes(&mut writer, &object.display)?;
+
+            for (index, group) in self.groups[object.start..object.finish].iter().enumerate() {
+                // every object starts in the default group
+                let implicit =
+                    index == 0 && group.name == DEFAULT_GROUP_NAME && group.other_names.is_empty();
+                if !implicit {
+                    write!(writer, "g {}", group.name)?;
+                    for name in &group.other_names {
+                        write!(writer, " {name}")?;
+                    }
+                    writeln!(writer)?;
+                }
+
+                if usemap != group.map.as_deref() {
+                    usemap = group.map.as_deref();
+                    writeln!(writer, "usemap {}", usemap.unwrap_or("off"))?;
+                }
+
+                self.write_group(&mut writer, group, &options, &mut smooth_group)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_header(
+        &self,
+        writer: &mut impl Write,
+        texture: bool,
+        normal: bool,
+    ) -> Result<(), Error> {
+        let texture_coords = if texture {
+            self.texture_coords.len()
+        } else {
+            0
+        };
+        let normals = if normal { self.vertex_normals.len() } else { 0 };
+
+        writeln!(writer, "# vertices: {}", self.vertices.len())?;
+        writeln!(writer, "# texture coords: {texture_coords}")?;
+        writeln!(writer, "# normals: {normals}")?;
+        writeln!(writer, "# faces: {}", self.faces.len())?;
+
+        Ok(())
+    }
+
+    fn write_vertex_data(
+        &self,
+        writer: &mut impl Write,
+        options: &WriteOptions,
+    ) -> Result<(), Error> {
+        let precision = options.precision;
+        let colors =
+            !self.vertex_colors.is_empty() && self.vertex_colors.len() == self.vertices.len();
+
+        let alphas = colors && self.vertex_alphas.len() == self.vertices.len();
+        // a position can either have a w or a color
+        let w = !colors && self.vertices_w.len() == self.vertices.len();
+
+        for (i, position) in self.vertices.iter().enumerate() {
+            writer.write_all(b"v")?;
+            write_floats(writer, position, precision)?;
+            if w {
+                write_floats(writer, &[self.vertices_w[i]], precision)?;
+            }
+            if colors {
+                write_floats(writer, &self.vertex_colors[i], precision)?;
+            }
+            if alphas {
+                write_floats(writer, &[self.vertex_alphas[i]], precision)?;
+            }
+            writeln!(writer)?;
+        }
+
+        if options.texture {
+            let w = self.texture_coords_w.len() == self.texture_coords.len();
+
+            for (i, texture_coord) in self.texture_coords.iter().enumerate() {
+                writer.write_all(b"vt")?;
+                write_floats(writer, texture_coord, precision)?;
+                if w {
+                    write_floats(writer, &[self.texture_coords_w[i]], precision)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        if options.normal {
+            for normal in &self.vertex_normals {
+                writer.write_all(b"vn")?;
+                write_floats(writer, normal, precision)?;
+                writeln!(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_group(
+        &self,
+        writer: &mut impl Write,
+        group: &GroupingData,
+        options: &WriteOptions,
+        smooth_group: &mut Option<u32>,
+    ) -> Result<(), Error> {
+        if let Some(mtl) = &group.mtl {
+            writeln!(writer, "usemtl {mtl}")?;
+        }
+
+        let polygons = &self.polygons[self.polygons.partition_point(|p| p.face < group.start)
+            ..self.polygons.partition_point(|p| p.face < group.finish)];
+
+        let mut mtl_changes = group.mtl_changes.iter().peekable();
+
+        if polygons.is_empty() {
+            for (index, face) in self.faces[group.start..group.finish].iter().enumerate() {
+                write_mtl_change(writer, &mut mtl_changes, group.start + index)?;
+                write_smooth_group(writer, smooth_group, face.smooth_group)?;
+
+                let indices = <[u32; 3]>::from(face.indicies);
+                let texture_indices = face
+                    .texture_indcicies
+                    .filter(|_| options.texture)
+                    .map(<[u32; 3]>::from);
+                let normal_indices = face
+                    .normal_indicies
+                    .filter(|_| options.normal)
+                    .map(<[u32; 3]>::from);
+
+                writer.write_all(b"f")?;
+                for i in 0..3 {
+                    write_corner(
+                        writer,
+                        indices[i],
+                        texture_indices.map(|t| t[i]),
+                        normal_indices.map(|n| n[i]),
+                    )?;
+                }
+                writeln!(writer)?;
+            }
+        } else {
+            for polygon in polygons {
+                write_mtl_change(writer, &mut mtl_changes, polygon.face)?;
+                write_smooth_group(writer, smooth_group, polygon.smooth_group)?;
+
+                writer.write_all(b"f")?;
+                for corner in &self.polygon_corners[polygon.start..polygon.finish] {
+                    write_corner(
+                        writer,
+                        corner.position,
+                        corner.texture_coord.filter(|_| options.texture),
+                        corner.normal.filter(|_| options.normal),
+                    )?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        for line in group.line_start..group.line_finish {
+            let texture_indices = self.line_texture_indices[line]
+                .as_deref()
+                .filter(|_| options.texture);
+
+            writer.write_all(b"l")?;
+            for (i, index) in self.line_elements[line].iter().enumerate() {
+                write_corner(writer, *index, texture_indices.map(|t| t[i]), None)?;
+            }
+            writeln!(writer)?;
+        }
+
+        for point in &self.point_elements[group.point_start..group.point_finish] {
+            writer.write_all(b"p")?;
+            for index in point {
+                write!(writer, " {index}")?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes indexed vertices as a .obj file to some sort of writer, using the default [`WriteConfig`].
+///
+/// Meant for meshes from [`opt::indexed_vertices`][crate::opt::indexed_vertices] or [`ObjObject::vertices_indexed`],
+/// where every 3 indices build a triangle.
+/// Each vertex is written once, with its color on the position (v), and its texture coord (vt) and normal (vn)
+/// at the same index, so faces are written as `i/i/i`.
+/// Colors, texture coords and normals are only written, if every vertex has them.
+///
+/// All libraries of the `materials` are written as one `mtllib`, and a `usemtl` is written
+/// whenever the `material_index` changes between consecutive triangles.
+/// There is no statement to stop using a material, so triangles without a `mtluse` following a material keep using it.
+///
+/// The writer is not buffered, consider wrapping it in a [`BufWriter`].
+///
+/// # Errors
+/// Returns an [Error][std::io::Error] if writing fails.
+///
+/// # Panics
+/// Panics if the number of indices is not a multiple of 3, an index is out of bounds of `vertices`,
+/// or a `material_index` is out of bounds of `materials`.
+#[inline]
+pub fn write_indexed(
+    writer: impl Write,
+    vertices: &[VertexTextureData],
+    indices: &[usize],
+    materials: &[MaterialIdent],
+) -> Result<(), Error> {
+    write_indexed_with_config(
+        writer,
+        vertices,
+        indices,
+        materials,
+        &WriteConfig::default(),
+    )
+}
+
+/// Same as [`write_indexed`], but using the given [`WriteConfig`].
+///
+/// # Errors
+/// Returns an [Error][std::io::Error] if writing fails.
+///
+/// # Panics
+/// Panics if the number of indices is not a multiple of 3, an index is out of bounds of `vertices`,
+/// or a `material_index` is out of bounds of `materials`.
+pub fn write_indexed_with_config(
+    mut writer: impl Write,
+    vertices: &[VertexTextureData],
+    indices: &[usize],
+    materials: &[MaterialIdent],
+    config: &WriteConfig,
+) -> Result<(), Error> {
+    assert!(
+        indices.len().is_multiple_of(3),
+        "the number of indices is not a multiple of 3"
+    );
+
+    let precision = config.float_precision;
+    let colors = has_attribute(vertices, false, |v| v.color);
+    let alphas = colors && has_attribute(vertices, false, |v| v.alpha);
+    let texture = has_attribute(vertices, config.compact, |v| v.texture_coord);
+    let normal = has_attribute(vertices, config.compact, |v| v.normal);
+
+    if config.include_comments {
+        let count = |written: bool| if written { vertices.len() } else { 0 };
+
+        writeln!(writer, "# vertices: {}", vertices.len())?;
+        writeln!(writer, "# texture coords: {}", count(texture))?;
+        writeln!(writer, "# normals: {}", count(normal))?;
+        writeln!(writer, "# faces: {}", indices.len() / 3)?;
+    }
+
+    let mut mtllibs = Vec::new();
+    for mtllib in materials.iter().filter_map(|material| material.mtllib) {
+        if !mtllibs.contains(&mtllib) {
+            mtllibs.push(mtllib);
+        }
+    }
+    if !mtllibs.is_empty() {
+        writeln!(writer, "mtllib {}", mtllibs.join(" "))?;
+    }
+
+    for vertex in vertices.iter().map(|v| &v.vertex) {
+        writer.write_all(b"v")?;
+        write_floats(&mut writer, &vertex.position, precision)?;
+        if let (true, Some(color)) = (colors, vertex.color) {
+            write_floats(&mut writer, &color, precision)?;
+        }
+        if let (true, Some(alpha)) = (alphas, vertex.alpha) {
+            write_floats(&mut writer, &[alpha], precision)?;
+        }
+        writeln!(writer)?;
+    }
+
+    if texture {
+        for texture_coord in vertices.iter().filter_map(|v| v.vertex.texture_coord) {
+            writer.write_all(b"vt")?;
+            write_floats(&mut writer, &texture_coord, precision)?;
+            writeln!(writer)?;
+        }
+    }
+
+    if normal {
+        for normal in vertices.iter().filter_map(|v| v.vertex.normal) {
+            writer.write_all(b"vn")?;
+            write_floats(&mut writer, &normal, precision)?;
+            writeln!(writer)?;
+        }
+    }
+
+    let mut mtluse = None;
+    for triangle in indices.chunks_exact(3) {
+        // deduplicated vertices are never shared between materials, so every corner has the same material
+        let material = &materials[vertices[triangle[0]].material_index];
+        if let Some(name) = material.mtluse
+            && mtluse != Some(name)
+        {
+            mtluse = Some(name);
+            writeln!(writer, "usemtl {name}")?;
+        }
+
+        writer.write_all(b"f")?;
+        for &index in triangle {
+            // .obj indices start at 1
+            let index = index + 1;
+            write_corner(
+                &mut writer,
+                index,
+                texture.then_some(index),
+                normal.then_some(index),
+            )?;
+        }
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Configures how an [`ObjObject`] is written as a .obj file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteConfig {
+    /// The number of decimal places written for each float.
+    pub float_precision: usize,
+    /// Omits all texture coords (vt) or normals (vn), if they are the same for every vertex.
+    pub compact: bool,
+    /// Writes the number of vertices, texture coords, normals and faces as a header comment.
+    pub include_comments: bool,
+    /// Writes the vertex data of every object with its transform applied, see [`ObjObject::apply_object_transforms`].
+    ///
+    /// Otherwise the vertex data is written as stored, and the transforms are lost, as a .obj file can not contain them.
+    pub bake_object_transforms: bool,
+}
+
+impl Default for WriteConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            float_precision: 6,
+            compact: false,
+            include_comments: false,
+            bake_object_transforms: false,
+        }
+    }
+}
+
+/// The resolved [`WriteConfig`] used while writing.
+struct WriteOptions {
+    precision: usize,
+    texture: bool,
+    normal: bool,
+}
+
+#[inline]
+fn write_floats(writer: &mut impl Write, values: &[f32], precision: usize) -> Result<(), Error> {
+    for value in values {
+        write!(writer, " {value:.precision$}")?;
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if every vertex has the attribute,
+/// unless it is the same for every vertex and `compact` is set, see [`WriteConfig::compact`].
+fn has_attribute<T: PartialEq>(
+    vertices: &[VertexTextureData],
+    compact: bool,
+    attribute: impl Fn(&VertexData) -> Option<T>,
+) -> bool {
+    let mut values = vertices.iter().map(|v| attribute(&v.vertex));
+    let Some(Some(first)) = values.next() else {
+        return false;
+    };
+
+    let mut same = true;
+    for value in values {
+        match value {
+            Some(value) => same &= value == first,
+            None => return false,
+        }
+    }
+
+    !(compact && same)
+}
+
+/// Returns `true` if there are values, and all of them are the same.
+#[inline]
+fn all_equal<T: PartialEq>(values: &[T]) -> bool {
+    values
+        .first()
+        .is_some_and(|first| values.iter().all(|v| v == first))
+}
+
+/// Writes a smoothing group (s) statement, if the smoothing group changes.
+#[inline]
+fn write_smooth_group(
+    writer: &mut impl Write,
+    current: &mut Option<u32>,
+    smooth_group: Option<u32>,
+) -> Result<(), Error> {
+    if *current != smooth_group {
+        *current = smooth_group;
+
+        match smooth_group {
+            Some(group) => writeln!(writer, "s {group}")?,
+            None => writeln!(writer, "s off")?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the display and render attributes of an object, that were specified.
+fn write_display_attributes(
+    writer: &mut impl Write,
+    display: &DisplayAttributes,
+) -> Result<(), Error> {
+    let switch = |on: bool| if on { "on" } else { "off" };
+
+    if let Some(lod) = display.lod {
+        writeln!(writer, "lod {lod}")?;
+    }
+    if let Some(file) = &display.shadow_obj {
+        writeln!(writer, "shadow_obj {file}")?;
+    }
+    if let Some(file) = &display.trace_obj {
+        writeln!(writer, "trace_obj {file}")?;
+    }
+    if let Some(on) = display.bevel {
+        writeln!(writer, "bevel {}", switch(on))?;
+    }
+    if let Some(on) = display.color_interpolation {
+        writeln!(writer, "c_interp {}", switch(on))?;
+    }
+    if let Some(on) = display.dissolve_interpolation {
+        writeln!(writer, "d_interp {}", switch(on))?;
+    }
+
+    Ok(())
+}
+
+/// Writes a material use (usemtl) statement, if the material changes at the given (absolute) face.
+#[inline]
+fn write_mtl_change<'a>(
+    writer: &mut impl Write,
+    mtl_changes: &mut Peekable<impl Iterator<Item = &'a (usize, String)>>,
+    face: usize,
+) -> Result<(), Error> {
+    if let Some((_, mtl)) = mtl_changes.next_if(|(start, _)| *start <= face) {
+        writeln!(writer, "usemtl {mtl}")?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single corner of a face (f) or line (l), in the `v/vt/vn` format matching the available indices.
+#[inline]
+fn write_corner<I: Display>(
+    writer: &mut impl Write,
+    index: I,
+    texture_index: Option<I>,
+    normal_index: Option<I>,
+) -> Result<(), Error> {
+    match (texture_index, normal_index) {
+        (None, None) => write!(writer, " {index}")?,
+        (Some(t), None) => write!(writer, " {index}/{t}")?,
+        (None, Some(n)) => write!(writer, " {index}//{n}")?,
+        (Some(t), Some(n)) => write!(writer, " {index}/{t}/{n}")?,
+    }
+
+    Ok(())
+}
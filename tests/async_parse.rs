@@ -0,0 +1,54 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::assert_same;
+use polypath::{Error, ObjObject, ParseLimits, ParseOptions};
+use tokio::io::BufReader;
+
+#[tokio::test]
+async fn test_parse_async() {
+    for mesh in [
+        "./meshes/armadillo.obj",
+        "./meshes/cheburashka.obj",
+        "./meshes/cubes.obj",
+    ] {
+        let file = tokio::fs::File::open(mesh).await.unwrap();
+        let obj = ObjObject::parse_async(BufReader::new(file)).await.unwrap();
+
+        assert_same(&obj, &ObjObject::read_from_file(mesh).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_parse_async_small_reads() {
+    let data = "v 0 0 0\r\nv 1 0 0\rv 0 1 \\\n 0\n\n# comment\nf 1 2 3\r\n";
+
+    // a single byte per read, splitting every line ending and continuation
+    let reader = BufReader::with_capacity(1, data.as_bytes());
+    let obj = ObjObject::parse_async(reader).await.unwrap();
+
+    assert_same(&obj, &ObjObject::parse_str(data).unwrap());
+    assert_eq!(obj.face_count(), 1);
+}
+
+#[tokio::test]
+async fn test_parse_async_with() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+
+    let options = ParseOptions::new().generate_normals(true);
+    let obj = ObjObject::parse_async_with(data.as_bytes(), &options)
+        .await
+        .unwrap();
+    assert!(obj.faces_iter().all(|face| face.vert_normals.is_some()));
+
+    let options = ParseOptions::new().limits(ParseLimits {
+        max_line_len: 4,
+        ..Default::default()
+    });
+    let res = ObjObject::parse_async_with(data.as_bytes(), &options).await;
+    assert!(
+        matches!(res, Err(Error::LineTooLong { line: 1, limit: 4 })),
+        "{res:?}"
+    );
+}
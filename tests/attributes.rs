@@ -0,0 +1,233 @@
+use std::io::Cursor;
+
+use polypath::{Error, ObjObject, ParseOptions};
+
+const MATCHING: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+vn 0 0 1
+vn 0 0.5 0.5
+vn 0.5 0 0.5
+vt 0 0
+vt 1 0
+vt 0 1
+f 1 2 3
+";
+
+const MISMATCHED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vn 0 0 1
+vt 0 0
+vt 1 0
+vt 0 1
+f 1 2 3
+f 2 4 3
+";
+
+#[test]
+fn test_infer_attributes() {
+    let mut obj = ObjObject::parse(Cursor::new(MATCHING)).unwrap();
+
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert!(face.vert_normals.is_none());
+    assert!(face.vert_uv_coords.is_none());
+
+    assert!(obj.infer_attribute_indices());
+
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert_eq!(
+        face.vert_normals,
+        Some([[0.0, 0.0, 1.0], [0.0, 0.5, 0.5], [0.5, 0.0, 0.5]])
+    );
+    assert_eq!(
+        face.vert_uv_coords,
+        Some([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]])
+    );
+}
+
+#[test]
+fn test_infer_attributes_refused() {
+    let mut obj = ObjObject::parse(Cursor::new(MISMATCHED)).unwrap();
+
+    // neither 1 normal nor 3 texture coords match the 4 vertex positions
+    assert!(!obj.infer_attribute_indices());
+
+    for o in obj.objects_iter() {
+        for g in o.group_iter() {
+            for f in g.faces_iter() {
+                assert!(f.vert_normals.is_none());
+                assert!(f.vert_uv_coords.is_none());
+            }
+        }
+    }
+}
+
+const TEXTURE_ARITIES: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+vt 0.5
+vt 0.25 0.75
+vt 0.1 0.2 0.3\t  
+f 1/1 2/2 3/3
+";
+
+#[test]
+fn test_texture_coord_arities() {
+    let obj = ObjObject::parse(Cursor::new(TEXTURE_ARITIES)).unwrap();
+
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert_eq!(
+        face.vert_uv_coords,
+        Some([[0.5, 0.0], [0.25, 0.75], [0.1, 0.2]])
+    );
+
+    // w is dropped by default
+    assert_eq!(obj.texture_coords_3d(), None);
+}
+
+#[test]
+fn test_texture_coords_3d() {
+    let options = ParseOptions {
+        keep_texture_w: true,
+        ..Default::default()
+    };
+    let obj = ObjObject::parse_with(Cursor::new(TEXTURE_ARITIES), &options).unwrap();
+
+    assert_eq!(
+        obj.texture_coords_3d(),
+        Some(vec![[0.5, 0.0, 0.0], [0.25, 0.75, 0.0], [0.1, 0.2, 0.3]])
+    );
+}
+
+// mixes positions with and without w
+const HOMOGENEOUS: &str = "
+v 0 0 0
+v 1 0 0 1.0
+v 0 1 0 0.5
+v 1 1 0
+f 1 2 3
+f 2 4 3
+";
+
+#[test]
+fn test_homogeneous_vertices() {
+    let obj = ObjObject::parse(Cursor::new(HOMOGENEOUS)).unwrap();
+    assert_eq!(obj.face_count(), 2);
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let face = group.faces_iter().next().unwrap();
+    assert_eq!(face.vert_positions[2], [0.0, 1.0, 0.0]);
+    assert_eq!(obj.positions_4d(), None);
+
+    let options = ParseOptions {
+        keep_vertex_w: true,
+        ..Default::default()
+    };
+    let obj = ObjObject::parse_with(Cursor::new(HOMOGENEOUS), &options).unwrap();
+
+    assert_eq!(
+        obj.positions_4d(),
+        Some(vec![
+            [0.0, 0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 0.5],
+            [1.0, 1.0, 0.0, 1.0],
+        ])
+    );
+}
+
+#[test]
+fn test_rgba_colors() {
+    let data = "
+v 0 0 0 1 0 0 0.25
+v 1 0 0 0 1 0 0.5
+v 0 1 0 0 0 1 1.0
+f 1 2 3
+";
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let face = group.faces_iter().next().unwrap();
+    assert_eq!(
+        face.vert_colors,
+        Some([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    );
+    assert_eq!(face.vert_alphas, Some([0.25, 0.5, 1.0]));
+    assert_eq!(face.vertices()[1].alpha, Some(0.5));
+
+    let rgb = ObjObject::parse(Cursor::new(
+        "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nf 1 2 3\n",
+    ))
+    .unwrap();
+    let group = rgb
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let face = group.faces_iter().next().unwrap();
+    assert!(face.vert_colors.is_some());
+    assert_eq!(face.vert_alphas, None);
+}
+
+#[test]
+fn test_non_uniform_colors() {
+    for data in [
+        // RGB and RGBA mixed
+        "v 0 0 0 1 0 0\nv 1 0 0 0 1 0 0.5\n",
+        "v 0 0 0 1 0 0 0.5\nv 1 0 0 0 1 0\n",
+        // colored and uncolored mixed
+        "v 0 0 0\nv 1 0 0 0 1 0\n",
+        "v 0 0 0 1 0 0\nv 1 0 0\n",
+    ] {
+        let err = ObjObject::parse(Cursor::new(data)).unwrap_err();
+        assert!(
+            matches!(&err, Error::AtLine { line: 2, source } if matches!(**source, Error::NonUniformColors)),
+            "{err:?}"
+        );
+    }
+}
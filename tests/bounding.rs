@@ -0,0 +1,185 @@
+use polypath::{
+    ObjObject,
+    bounding::{Aabb, Sphere, build_aabb, build_bounding_sphere},
+};
+
+const TWO_OBJECTS: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 5 5 5
+v 6 5 5
+v 5 6 7
+v -9 -9 -9
+o a
+f 1 2 3
+o b
+g faces
+f 4 5 6
+g points
+p 1
+";
+
+#[test]
+fn test_bounding_sphere_off_origin() {
+    let points = [[10.0, 10.0, 10.0], [12.0, 10.0, 10.0], [11.0, 11.0, 10.0]];
+    let sphere = build_bounding_sphere(points.into_iter());
+
+    // the triangle has a right angle at its apex, so the sphere is centered on its longest side
+    assert_eq!(sphere.center, [11.0, 10.0, 10.0]);
+    assert!(points.iter().all(|p| {
+        let d = [0, 1, 2].map(|i| p[i] - sphere.center[i]);
+        d[0].hypot(d[1]).hypot(d[2]) <= sphere.radius
+    }));
+    assert!(sphere.radius <= 1.0);
+}
+
+#[test]
+fn test_bounding_sphere_minimum() {
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+    let points = obj
+        .vertices()
+        .0
+        .into_iter()
+        .map(|v| v.vertex.position)
+        .collect::<Vec<_>>();
+    let sphere = build_bounding_sphere(points.iter().copied());
+
+    // the sphere around the center of the bounds, as built before
+    let aabb = build_aabb(points.iter().copied());
+    let center = [0, 1, 2].map(|i| f32::midpoint(aabb.min[i], aabb.max[i]));
+    let distance = |p: &[f32; 3], c: [f32; 3]| {
+        let d = [0, 1, 2].map(|i| p[i] - c[i]);
+        d[0].hypot(d[1]).hypot(d[2])
+    };
+    let radius = points
+        .iter()
+        .map(|p| distance(p, center))
+        .fold(0.0, f32::max);
+
+    assert!(sphere.radius <= radius, "{} > {radius}", sphere.radius);
+    assert!(
+        points
+            .iter()
+            .all(|p| distance(p, sphere.center) <= sphere.radius)
+    );
+
+    // a regular tetrahedron around the origin, with the minimum sphere touching every corner
+    let corners = [
+        [1.0, 1.0, 1.0],
+        [1.0, -1.0, -1.0],
+        [-1.0, 1.0, -1.0],
+        [-1.0, -1.0, 1.0],
+    ];
+    let sphere = build_bounding_sphere(corners.into_iter().chain([[0.5, 0.0, 0.0]]));
+    assert!(sphere.center.iter().all(|c| c.abs() < 1e-6), "{sphere:?}");
+    assert!((sphere.radius - 3.0_f32.sqrt()).abs() < 1e-6, "{sphere:?}");
+}
+
+#[test]
+fn test_bounding_sphere_right_triangle() {
+    // the center of the bounds of a right triangle is the midpoint of its hypotenuse, which is its circumcenter
+    let [a, b, c] = [[-4.0, 3.0, 7.0], [2.0, 3.0, 7.0], [-4.0, 11.0, 7.0]];
+    let sphere = build_bounding_sphere([a, b, c].into_iter());
+
+    assert_eq!(sphere.center, [-1.0, 7.0, 7.0]);
+    // the circumradius is half of the hypotenuse
+    assert_eq!(sphere.radius, 5.0);
+
+    // the center lies on the triangle: u * (b - a) + v * (c - a), with u, v >= 0 and u + v <= 1
+    let u = (sphere.center[0] - a[0]) / (b[0] - a[0]);
+    let v = (sphere.center[1] - a[1]) / (c[1] - a[1]);
+    assert!(u >= 0.0 && v >= 0.0 && u + v <= 1.0);
+    assert_eq!(sphere.center[2], a[2]);
+}
+
+#[test]
+fn test_aabb() {
+    let a = build_aabb([[0.0, 0.0, 0.0], [2.0, 1.0, 3.0]].into_iter());
+    assert_eq!(a.min, [0.0, 0.0, 0.0]);
+    assert_eq!(a.max, [2.0, 1.0, 3.0]);
+    assert_eq!(a.surface_area(), 2.0 * (2.0 + 3.0 + 6.0));
+
+    let b = Aabb {
+        min: [1.0, -1.0, 1.0],
+        max: [4.0, 0.5, 2.0],
+    };
+    assert_eq!(
+        a.union(&b),
+        Aabb {
+            min: [0.0, -1.0, 0.0],
+            max: [4.0, 1.0, 3.0],
+        }
+    );
+    assert_eq!(
+        a.intersection(&b),
+        Some(Aabb {
+            min: [1.0, 0.0, 1.0],
+            max: [2.0, 0.5, 2.0],
+        })
+    );
+    assert_eq!(
+        a.intersection(&Aabb {
+            min: [5.0; 3],
+            max: [6.0; 3]
+        }),
+        None
+    );
+
+    assert!(a.contains_point([2.0, 0.5, 0.0]));
+    assert!(!a.contains_point([2.5, 0.5, 0.0]));
+
+    let sphere = Sphere {
+        center: [1.0, 0.5, 1.5],
+        radius: 0.5,
+    };
+    assert!(a.contains_sphere(&sphere));
+    assert!(!a.contains_sphere(&Sphere {
+        radius: 1.0,
+        ..sphere
+    }));
+}
+
+#[test]
+fn test_aabb_empty() {
+    let empty = build_aabb(std::iter::empty());
+    assert_eq!(empty, Aabb::EMPTY);
+    assert_eq!(empty, Aabb::default());
+    assert!(empty.is_empty());
+    assert_eq!(empty.surface_area(), 0.0);
+    assert!(!empty.contains_point([0.0; 3]));
+
+    let point = build_aabb(std::iter::once([1.0, 2.0, 3.0]));
+    assert!(!point.is_empty());
+    assert_eq!(empty.union(&point), point);
+}
+
+#[test]
+fn test_obj_aabb() {
+    let obj = ObjObject::parse_str(TWO_OBJECTS).unwrap();
+
+    // all vertices, even unused ones
+    assert_eq!(obj.aabb().min, [-9.0; 3]);
+    assert_eq!(obj.aabb().max, [6.0, 6.0, 7.0]);
+
+    let objects = obj.objects_iter().collect::<Vec<_>>();
+    assert_eq!(
+        objects[0].aabb(),
+        Aabb {
+            min: [0.0; 3],
+            max: [1.0, 1.0, 0.0],
+        }
+    );
+    // point elements count as well
+    assert_eq!(
+        objects[1].aabb(),
+        Aabb {
+            min: [0.0; 3],
+            max: [6.0, 6.0, 7.0],
+        }
+    );
+
+    let groups = objects[1].group_iter().collect::<Vec<_>>();
+    assert_eq!(groups[0].aabb().min, [5.0; 3]);
+    assert_eq!(groups[1].aabb(), build_aabb(std::iter::once([0.0; 3])));
+}
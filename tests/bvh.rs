@@ -0,0 +1,86 @@
+use polypath::{ObjObject, bounding::Aabb, bvh::Bvh};
+
+/// Rays from points around the mesh towards points inside of its bounding box.
+#[allow(clippy::cast_precision_loss)]
+fn rays(aabb: &Aabb, count: usize) -> Vec<([f32; 3], [f32; 3])> {
+    let center = [0, 1, 2].map(|i| f32::midpoint(aabb.min[i], aabb.max[i]));
+    let size = [0, 1, 2].map(|i| aabb.max[i] - aabb.min[i]);
+    let radius = size.iter().copied().fold(0.0, f32::max) * 2.0;
+
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / count as f32;
+            let (theta, phi) = (t * 37.0, t * 11.0);
+
+            let origin = [
+                center[0] + radius * theta.cos() * phi.sin(),
+                center[1] + radius * phi.cos(),
+                center[2] + radius * theta.sin() * phi.sin(),
+            ];
+            // aim at different points of the box, so rays also miss
+            let target = [0, 1, 2].map(|a| {
+                aabb.min[a] + size[a] * (t * [7.0, 13.0, 17.0][a]).fract() * 1.2 - size[a] * 0.1
+            });
+
+            (origin, [0, 1, 2].map(|a| target[a] - origin[a]))
+        })
+        .collect()
+}
+
+#[test]
+fn test_bvh_matches_ray_cast() {
+    for mesh in ["./meshes/cheburashka.obj", "./meshes/cubes.obj"] {
+        let obj = ObjObject::read_from_file(mesh).unwrap();
+        let bvh = obj.build_bvh();
+        assert_eq!(bvh.face_count(), obj.face_count());
+        assert_eq!(bvh.aabb(), obj.aabb());
+
+        let mut hits = 0;
+        for (origin, dir) in rays(&obj.aabb(), 500) {
+            let expected = obj.ray_cast(origin, dir);
+            let hit = bvh.ray_cast(origin, dir);
+
+            assert_eq!(hit.is_some(), expected.is_some(), "{origin:?} {dir:?}");
+            let (Some(hit), Some(expected)) = (hit, expected) else {
+                continue;
+            };
+            hits += 1;
+
+            // rays through shared edges or overlapping faces may report any of them, at the same distance
+            assert_eq!(hit.distance, expected.distance);
+        }
+        assert!(hits > 100, "{mesh}: {hits}");
+    }
+}
+
+#[test]
+fn test_bvh_empty() {
+    let bvh = Bvh::build(&[]);
+
+    assert_eq!(bvh.face_count(), 0);
+    assert!(bvh.aabb().is_empty());
+    assert!(bvh.ray_cast([0.0; 3], [0.0, 0.0, 1.0]).is_none());
+}
+
+#[test]
+fn test_bvh_nearest() {
+    // a stack of parallel triangles, hit from both sides
+    let faces = (0..100u8)
+        .map(|z| {
+            let z = f32::from(z);
+            [[0.0, 0.0, z], [1.0, 0.0, z], [0.0, 1.0, z]]
+        })
+        .collect::<Vec<_>>();
+    let bvh = Bvh::build(&faces);
+
+    let hit = bvh.ray_cast([0.25, 0.25, 50.5], [0.0, 0.0, 1.0]).unwrap();
+    assert_eq!((hit.face, hit.distance), (51, 0.5));
+    assert_eq!((hit.u, hit.v), (0.25, 0.25));
+
+    let hit = bvh.ray_cast([0.25, 0.25, 150.0], [0.0, 0.0, -2.0]).unwrap();
+    assert_eq!((hit.face, hit.distance), (99, 25.5));
+
+    // next to the triangles, or pointing away from them
+    assert!(bvh.ray_cast([0.75, 0.75, -1.0], [0.0, 0.0, 1.0]).is_none());
+    assert!(bvh.ray_cast([0.25, 0.25, -1.0], [0.0, 0.0, -1.0]).is_none());
+}
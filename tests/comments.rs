@@ -0,0 +1,46 @@
+use polypath::ObjObject;
+
+const COMMENTED: &str = "
+# a full line comment
+v 0 0 0  # exported by tool
+v 1 0 0 0.5 # with w
+v 1 1 0#no space
+v 0 1 0 # trailing whitespace	 
+vt 0 0 # uv
+vt 1 0 #uv
+vt 1 1 0 # uvw
+vn 0 0 1 # up
+o quad # the only object
+g front#first group
+usemtl red # material
+s 1 # smoothing
+f 1/1/1 2/2/1 3/3/1 # first
+f 1/1/1 3/3/1 4/1/1# second
+l 1 2 # line
+p 3 # point
+";
+
+#[test]
+fn test_trailing_comments() {
+    let obj = ObjObject::parse_str(COMMENTED).unwrap();
+    assert_eq!(obj.face_count(), 2);
+
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.name(), "quad");
+
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(group.name(), "front");
+    assert_eq!(group.mtluse(), Some("red"));
+    assert_eq!(group.smooth_group(), Some(1));
+
+    let face = group.faces_iter().nth(1).unwrap();
+    assert_eq!(
+        face.vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    assert_eq!(face.vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+    assert_eq!(face.vert_uv_coords.unwrap()[1], [1.0, 1.0]);
+
+    assert_eq!(obj.line_elements(), [vec![1, 2]]);
+    assert_eq!(obj.point_elements(), [vec![3]]);
+}
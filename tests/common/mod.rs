@@ -0,0 +1,16 @@
+use polypath::ObjObject;
+
+/// Asserts that two parses of the same data, through different readers, produced the same objects, groups and vertices.
+pub fn assert_same(a: &ObjObject, b: &ObjObject) {
+    assert_eq!(
+        a.object_names().collect::<Vec<_>>(),
+        b.object_names().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        a.group_names().collect::<Vec<_>>(),
+        b.group_names().collect::<Vec<_>>()
+    );
+    assert_eq!(a.face_count(), b.face_count());
+    assert_eq!(a.unique_vertex_count(), b.unique_vertex_count());
+    assert_eq!(a.vertices(), b.vertices());
+}
@@ -0,0 +1,54 @@
+use polypath::{Error, ObjObject};
+
+const VERTICES: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n";
+
+fn positions(obj: &ObjObject) -> Vec<[[f32; 3]; 3]> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter().map(|face| face.vert_positions));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_face_across_three_lines() {
+    let joined = ObjObject::parse_str(&format!("{VERTICES}f 1 2 3 4\n")).unwrap();
+    let continued = ObjObject::parse_str(&format!("{VERTICES}f 1 \\\n  2 3\\\n 4\n")).unwrap();
+
+    assert_eq!(continued.face_count(), 2);
+    assert_eq!(positions(&continued), positions(&joined));
+}
+
+#[test]
+fn test_continuation_edge_cases() {
+    // consecutive continuations, including a line that only continues
+    let obj = ObjObject::parse_str(&format!("{VERTICES}f 1 \\\n\\\n2 \\\n3\n")).unwrap();
+    assert_eq!(obj.face_count(), 1);
+
+    // comments after the backslash, and crlf line endings
+    let obj = ObjObject::parse_str(&format!("{VERTICES}f 1 2 \\ # first half\r\n3\r\n")).unwrap();
+    assert_eq!(obj.face_count(), 1);
+
+    // a backslash inside a comment does not continue
+    let obj = ObjObject::parse_str(&format!("{VERTICES}# comment \\\nf 1 2 3\n")).unwrap();
+    assert_eq!(obj.face_count(), 1);
+
+    // a backslash at the end of the file
+    let obj = ObjObject::parse_str(&format!("{VERTICES}f 1 2 3 \\")).unwrap();
+    assert_eq!(obj.face_count(), 1);
+    let obj = ObjObject::parse_str(&format!("{VERTICES}f 1 2 3 \\\n")).unwrap();
+    assert_eq!(obj.face_count(), 1);
+}
+
+#[test]
+fn test_continuation_line_numbers() {
+    // errors report the first physical line of a statement
+    let err = ObjObject::parse_str("v 0 0 0\nv 1 \\\n0 x\n").unwrap_err();
+    assert!(matches!(err, Error::AtLine { line: 2, .. }), "{err:?}");
+
+    // and lines after a continued statement keep their number
+    let err = ObjObject::parse_str("v 0 \\\n0 \\\n0\nv 1 0 x\n").unwrap_err();
+    assert!(matches!(err, Error::AtLine { line: 4, .. }), "{err:?}");
+}
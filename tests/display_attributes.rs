@@ -0,0 +1,104 @@
+use polypath::{DisplayAttributes, ObjObject, ParseWarning};
+
+const ATTRIBUTES: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+lod 2
+shadow_obj shadow.obj
+trace_obj trace.obj
+bevel on
+c_interp on
+d_interp off
+f 1 2 3 4
+o detailed
+lod 50
+lod 75
+bevel off
+f 1 2 3
+";
+
+#[test]
+fn test_display_attributes() {
+    let obj = ObjObject::parse_str(ATTRIBUTES).unwrap();
+
+    // the geometry is not affected
+    assert_eq!(obj.face_count(), 3);
+    assert_eq!(obj.object_count(), 2);
+
+    let objects = obj.objects_iter().collect::<Vec<_>>();
+    assert_eq!(objects[0].lod(), Some(2));
+    assert_eq!(
+        objects[0].display_attributes(),
+        &DisplayAttributes {
+            lod: Some(2),
+            shadow_obj: Some(String::from("shadow.obj")),
+            trace_obj: Some(String::from("trace.obj")),
+            bevel: Some(true),
+            color_interpolation: Some(true),
+            dissolve_interpolation: Some(false),
+        }
+    );
+
+    // the last statement of an attribute is kept
+    assert_eq!(objects[1].lod(), Some(75));
+    assert_eq!(
+        objects[1].display_attributes(),
+        &DisplayAttributes {
+            lod: Some(75),
+            bevel: Some(false),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_display_attributes_write_back() {
+    let obj = ObjObject::parse_str(ATTRIBUTES).unwrap();
+
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let reread = ObjObject::parse_str(&String::from_utf8(written).unwrap()).unwrap();
+
+    for (a, b) in obj.objects_iter().zip(reread.objects_iter()) {
+        assert_eq!(a.display_attributes(), b.display_attributes());
+    }
+}
+
+#[test]
+fn test_invalid_display_attributes() {
+    for data in [
+        "lod",
+        "lod high",
+        "bevel maybe",
+        "shadow_obj",
+        "c_interp",
+        "d_interp 1",
+    ] {
+        let file = format!("v 0 0 0\nv 1 0 0\nv 0 1 0\nlod 3\n{data}\nf 1 2 3\n");
+
+        // skipped, keeping the geometry and the valid attributes
+        let obj = ObjObject::parse_str(&file).unwrap();
+        assert_eq!(obj.face_count(), 1, "{data}");
+        let object = obj.objects_iter().next().unwrap();
+        assert_eq!(
+            object.display_attributes(),
+            &DisplayAttributes {
+                lod: Some(3),
+                ..Default::default()
+            },
+            "{data}"
+        );
+
+        let result = ObjObject::parse_with_warnings(std::io::Cursor::new(&file)).unwrap();
+        assert_eq!(
+            result.warnings,
+            [ParseWarning::InvalidDisplayAttribute {
+                line: 5,
+                statement: String::from(data),
+            }],
+            "{data}"
+        );
+    }
+}
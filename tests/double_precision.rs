@@ -0,0 +1,82 @@
+use polypath::{ObjObject, ParseOptions};
+
+// differ only in the 9th significant digit, f32 has a spacing of 0.03125 at this magnitude
+const SURVEY: &str = "
+v 435812.032 5621934.5 12.0
+v 435812.033 5621934.5 12.0
+v 435812.5 5621935.0 12.0
+f 1 2 3
+";
+
+fn f64_options() -> ParseOptions {
+    ParseOptions::new().keep_f64_positions(true)
+}
+
+#[test]
+fn test_f64_stays_distinct() {
+    let obj = ObjObject::parse_str(SURVEY).unwrap();
+    assert!(obj.positions_f64().is_none());
+
+    let face = obj.faces_iter().next().unwrap();
+    assert_eq!(face.vert_positions[0], face.vert_positions[1]);
+
+    let obj = ObjObject::parse_bytes_with(SURVEY.as_bytes(), &f64_options()).unwrap();
+    let positions = obj.positions_f64().unwrap();
+    assert_eq!(positions[0], [435812.032, 5621934.5, 12.0]);
+    assert_eq!(positions[1], [435812.033, 5621934.5, 12.0]);
+    assert_ne!(positions[0], positions[1]);
+
+    // the single precision positions are the same as without the option
+    let plain = ObjObject::parse_str(SURVEY).unwrap();
+    assert_eq!(obj.vertices(), plain.vertices());
+}
+
+#[test]
+fn test_origin_rebase() {
+    let mut obj = ObjObject::parse_bytes_with(SURVEY.as_bytes(), &f64_options()).unwrap();
+
+    let (min, max) = obj.aabb_f64().unwrap();
+    assert_eq!(min, [435812.032, 5621934.5, 12.0]);
+    assert_eq!(max, [435812.5, 5621935.0, 12.0]);
+
+    assert!(obj.to_f32_with_origin_rebase(min));
+
+    let face = obj.faces_iter().next().unwrap();
+    assert_eq!(face.vert_positions[0], [0.0, 0.0, 0.0]);
+    assert!((face.vert_positions[1][0] - 0.001).abs() < 1e-6);
+    assert_ne!(face.vert_positions[0], face.vert_positions[1]);
+
+    // the double precision positions stay as parsed
+    assert_eq!(obj.positions_f64().unwrap()[0], min);
+}
+
+#[test]
+fn test_origin_rebase_without_f64() {
+    let mut obj = ObjObject::parse_str(SURVEY).unwrap();
+
+    assert!(obj.aabb_f64().is_none());
+    assert!(!obj.to_f32_with_origin_rebase([435812.0, 5621934.0, 0.0]));
+    assert_eq!(obj.faces_iter().next().unwrap().vert_positions[2][2], 12.0);
+}
+
+#[test]
+fn test_read_from_file_f64() {
+    let obj = ObjObject::read_from_file_f64("./meshes/cheburashka.obj").unwrap();
+    let plain = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+
+    let positions = obj.positions_f64().unwrap();
+    assert_eq!(positions.len(), obj.unique_vertex_count());
+    assert_eq!(obj.vertices(), plain.vertices());
+}
+
+#[test]
+fn test_remove_isolated_keeps_f64() {
+    let data = "v 1.5 0 0\nv 435812.032 5621934.5 12.0\nv 0 1 0\nv 1 1 0\nf 2 3 4\n";
+    let mut obj = ObjObject::parse_bytes_with(data.as_bytes(), &f64_options()).unwrap();
+
+    polypath::geometry::remove_isolated_vertices(&mut obj);
+
+    let positions = obj.positions_f64().unwrap();
+    assert_eq!(positions.len(), 3);
+    assert_eq!(positions[0], [435812.032, 5621934.5, 12.0]);
+}
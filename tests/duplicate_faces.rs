@@ -0,0 +1,57 @@
+use polypath::{ObjObject, opt};
+
+// a quad, where the second triangle appears again in reverse winding and with different normals,
+// followed by a triangle sharing only two of its positions
+const OVERLAPPING: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+vn 0 0 1
+vn 0 0 -1
+f 1//1 2//1 3//1
+f 1//1 3//1 4//1
+f 4//2 3//2 1//2
+f 3//1 1//1 4//1
+f 1//1 3//1 5//1
+";
+
+#[test]
+fn test_remove_duplicate_faces() {
+    let obj = ObjObject::parse_str(OVERLAPPING).unwrap();
+    let (vertices, _) = obj.vertices();
+    assert_eq!(vertices.len(), 15);
+
+    let unique = opt::remove_duplicate_faces(&vertices);
+    assert_eq!(unique.len(), 9);
+
+    // the first occurrence is kept, with its winding and attributes
+    assert_eq!(unique[..6], vertices[..6]);
+    assert_eq!(unique[6..], vertices[12..]);
+
+    // removing again does not change anything
+    assert_eq!(opt::remove_duplicate_faces(&unique), unique);
+}
+
+#[test]
+fn test_remove_duplicate_faces_indexed() {
+    let obj = ObjObject::parse_str(OVERLAPPING).unwrap();
+    let (vertices, _) = obj.vertices();
+
+    let (indices, unique) = opt::indexed_vertices(&vertices);
+    let indices = indices
+        .into_iter()
+        .map(|i| u32::try_from(i).unwrap())
+        .collect::<Vec<_>>();
+
+    // the duplicate with different normals references different vertices
+    assert_ne!(indices[6..9], indices[3..6]);
+
+    let remaining = opt::remove_duplicate_faces_indexed(&indices, &unique);
+    assert_eq!(remaining.len(), 9);
+    assert_eq!(remaining[..6], indices[..6]);
+    assert_eq!(remaining[6..], indices[12..]);
+
+    assert!(opt::remove_duplicate_faces_indexed(&[], &unique).is_empty());
+}
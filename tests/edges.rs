@@ -0,0 +1,90 @@
+use polypath::{FaceData, ObjObject, geometry::compute_edge_map};
+
+const CUBE: &str = "
+v 0 0 0
+v 0 0 1
+v 0 1 0
+v 0 1 1
+v 1 0 0
+v 1 0 1
+v 1 1 0
+v 1 1 1
+f 1 3 7 5
+f 2 6 8 4
+f 1 2 4 3
+f 5 7 8 6
+f 3 4 8 7
+f 1 5 6 2
+";
+
+fn all_faces(obj: &ObjObject) -> Vec<FaceData> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for faces in object.faces() {
+            out.extend_from_slice(faces);
+        }
+    }
+    out
+}
+
+fn sorted(edges: impl Iterator<Item = (u32, u32)>) -> Vec<(u32, u32)> {
+    let mut edges = edges.collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges
+}
+
+#[test]
+fn test_closed_cube() {
+    let obj = ObjObject::parse_str(CUBE).unwrap();
+    assert!(obj.is_manifold());
+    assert!(obj.is_closed());
+
+    let edges = compute_edge_map(&all_faces(&obj));
+    // 12 edges of the cube, and one diagonal per side
+    assert_eq!(edges.edge_count(), 18);
+    assert_eq!(edges.boundary_edges().count(), 0);
+    assert_eq!(edges.faces(3, 1), [0, 5]);
+    assert_eq!(edges.faces(1, 8), [] as [usize; 0]);
+}
+
+#[test]
+fn test_open_cube() {
+    // without its top side
+    let data = CUBE.replace("f 3 4 8 7\n", "");
+    let obj = ObjObject::parse_str(&data).unwrap();
+    assert!(obj.is_manifold());
+    assert!(!obj.is_closed());
+
+    let edges = compute_edge_map(&all_faces(&obj));
+    assert_eq!(
+        sorted(edges.boundary_edges()),
+        [(3, 4), (3, 7), (4, 8), (7, 8)]
+    );
+}
+
+#[test]
+fn test_non_manifold_fin() {
+    // three triangles sharing the edge 1-2, and one degenerate triangle
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 0 -1 0
+v 0 0 1
+f 1 2 3
+f 2 1 4
+f 1 2 5
+f 3 3 5
+";
+    let obj = ObjObject::parse_str(data).unwrap();
+    assert!(!obj.is_manifold());
+    assert!(!obj.is_closed());
+
+    let edges = compute_edge_map(&all_faces(&obj));
+    assert_eq!(sorted(edges.non_manifold_edges()), [(1, 2)]);
+    assert_eq!(edges.faces(2, 1), [0, 1, 2]);
+
+    // the degenerate triangle only adds a single edge, used once
+    assert_eq!(edges.faces(3, 5), [3]);
+    assert!(edges.faces(3, 3).is_empty());
+}
@@ -0,0 +1,181 @@
+use std::io::Cursor;
+
+use polypath::ObjObject;
+
+const ELEMENTS: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vt 0 0
+l 1 2
+l 1/1 2/1 -1/1
+p 3
+f 1 2 3
+v 2 2 0
+p 1 -1 -2
+l -3 -1
+";
+
+#[test]
+fn test_line_and_point_elements() {
+    let obj = ObjObject::parse(Cursor::new(ELEMENTS)).unwrap();
+
+    assert_eq!(obj.line_elements(), [vec![1, 2], vec![1, 2, 4], vec![3, 5]]);
+    assert_eq!(obj.point_elements(), [vec![3], vec![1, 5, 4]]);
+
+    // elements do not create any faces
+    assert_eq!(obj.face_count(), 1);
+}
+
+#[test]
+fn test_no_elements() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    assert!(obj.line_elements().is_empty());
+    assert!(obj.point_elements().is_empty());
+}
+
+#[test]
+fn test_invalid_elements() {
+    assert!(ObjObject::parse(Cursor::new("v 0 0 0\nl 1\n")).is_err());
+    assert!(ObjObject::parse(Cursor::new("v 0 0 0\np\n")).is_err());
+    assert!(ObjObject::parse(Cursor::new("v 0 0 0\np x\n")).is_err());
+}
+
+#[test]
+fn test_reparse_clears_elements() {
+    let mut obj = ObjObject::parse(Cursor::new(ELEMENTS)).unwrap();
+    obj.reparse(Cursor::new("v 0 0 0\nv 1 0 0\nl 2 1\n"))
+        .unwrap();
+
+    assert_eq!(obj.line_elements(), [vec![2, 1]]);
+    assert!(obj.point_elements().is_empty());
+}
+
+const POLYLINES: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+g first
+l 1 2 3
+f 1 2 3
+g second
+l 1/1 -1/-1
+";
+
+#[test]
+fn test_lines_iter() {
+    let obj = ObjObject::parse(Cursor::new(POLYLINES)).unwrap();
+
+    let object = obj.objects_iter().next().unwrap();
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].line_count(), 1);
+    let segments = groups[0].lines_iter().collect::<Vec<_>>();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(
+        segments[0].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]]
+    );
+    assert_eq!(
+        segments[1].vert_positions,
+        [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]
+    );
+    assert_eq!(segments[0].vert_uv_coords, None);
+
+    assert_eq!(groups[1].line_count(), 1);
+    let segments = groups[1].lines_iter().collect::<Vec<_>>();
+    assert_eq!(
+        segments[0].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0]]
+    );
+    assert_eq!(segments[0].vert_uv_coords, Some([[0.0, 0.0], [1.0, 1.0]]));
+}
+
+#[test]
+fn test_lines_only() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+o wire
+g a
+l 1 2
+g b
+l 2 3
+";
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    assert_eq!(obj.object_count(), 1);
+    assert_eq!(obj.face_count(), 0);
+
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.name(), "wire");
+
+    // groups containing only lines are kept apart
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name(), "a");
+    assert_eq!(groups[1].name(), "b");
+    assert_eq!(
+        groups[1].lines_iter().next().unwrap().vert_positions,
+        [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]
+    );
+
+    assert!(obj.vertices().0.is_empty());
+}
+
+#[test]
+fn test_points_iter() {
+    let data = "
+v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 0 1 0 0 0 1
+g cloud
+p 1 -1
+p 2
+g rest
+p -2
+";
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    let object = obj.objects_iter().next().unwrap();
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].point_element_count(), 2);
+    let points = groups[0].points_iter().collect::<Vec<_>>();
+    assert_eq!(points.len(), 3);
+    assert_eq!(points[0].position, [0.0, 0.0, 0.0]);
+    assert_eq!(points[0].color, Some([1.0, 0.0, 0.0]));
+    assert_eq!(points[1].position, [0.0, 1.0, 0.0]);
+    assert_eq!(points[2].position, [1.0, 0.0, 0.0]);
+
+    let points = groups[1].points_iter().collect::<Vec<_>>();
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].position, [1.0, 0.0, 0.0]);
+    assert_eq!(points[0].color, Some([0.0, 1.0, 0.0]));
+}
+
+#[test]
+fn test_points_only() {
+    let obj = ObjObject::parse(Cursor::new("v 0 0 0\nv 1 0 0\no cloud\np 1 2\n")).unwrap();
+
+    assert_eq!(obj.object_count(), 1);
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.name(), "cloud");
+
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(group.points_iter().count(), 2);
+    assert_eq!(group.points_iter().next().unwrap().color, None);
+
+    let (vertices, materials) = obj.vertices();
+    assert!(vertices.is_empty());
+    assert_eq!(materials.len(), 1);
+    assert!(obj.vertices_indexed().0.is_empty());
+}
@@ -0,0 +1,163 @@
+use std::io::Cursor;
+
+use polypath::{
+    ObjObject, VertexTextureData,
+    bounding::build_bounding_sphere,
+    external::{self, ExternalConfig},
+    meshlet, opt,
+};
+
+const INPUTS: &[(&str, &str)] = &[
+    ("empty", ""),
+    ("only comments", "# nothing here\n\n"),
+    ("only vertices", "v 0 0 0\nv 1 0 0\nv 0 1 0\n"),
+    ("only normals", "vn 0 0 1\nvn 0 1 0\n"),
+    ("attributes without faces", "v 0 0 0\nvn 0 0 1\nvt 0 0\n"),
+    ("groupings without vertices", "o a\ng b\nusemtl c\n"),
+    (
+        "empty objects",
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\no a\no b\ng c\n",
+    ),
+];
+
+fn check_no_faces(name: &str, data: &str) {
+    let mut obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    // the hierarchy always exists
+    assert!(obj.object_count() >= 1, "{name}");
+    assert!(obj.group_count() >= 1, "{name}");
+    for object in obj.objects_iter() {
+        assert!(object.group_count() >= 1, "{name}");
+        for group in object.group_iter() {
+            assert_eq!(group.face_count(), 0, "{name}");
+            assert_eq!(group.faces_iter().count(), 0, "{name}");
+        }
+    }
+
+    assert_eq!(obj.face_count(), 0, "{name}");
+    assert_eq!(obj.referenced_vertex_count(), 0, "{name}");
+
+    // extraction returns empty, but valid results
+    let (vertices, materials) = obj.vertices();
+    assert!(vertices.is_empty(), "{name}");
+    assert_eq!(materials.len(), obj.material_table().len(), "{name}");
+
+    let (indicies, unique, _) = obj.vertices_indexed();
+    assert!(indicies.is_empty() && unique.is_empty(), "{name}");
+    assert!(obj.face_materials().is_empty(), "{name}");
+
+    // optimizers accept empty input
+    assert!(opt::optimize_vertex_order(Vec::new()).is_empty(), "{name}");
+    let (indicies, unique) = opt::indexed_vertices(&vertices);
+    assert!(indicies.is_empty() && unique.is_empty(), "{name}");
+    let (indicies, unique) = opt::weld_preserving_seams(&vertices, 0.01, 30.0, None);
+    assert!(indicies.is_empty() && unique.is_empty(), "{name}");
+
+    let meshlets = meshlet::build_meshlets::<64, 124, VertexTextureData>(&[], &unique, 0.5);
+    assert!(meshlets.is_empty(), "{name}");
+
+    let sphere = build_bounding_sphere(std::iter::empty());
+    assert!(sphere.radius.is_finite(), "{name}");
+
+    // mutating entry points
+    assert!(
+        !obj.infer_attribute_indices() || obj.face_count() == 0,
+        "{name}"
+    );
+    obj.set_object_transform(0, [[0.0; 4]; 4]);
+    obj.apply_object_transforms();
+    obj.reparse(Cursor::new(data)).unwrap();
+    assert!(obj.object_count() >= 1, "{name}");
+
+    let mut out = Vec::new();
+    let stats =
+        external::index_out_of_core(Cursor::new(data), &mut out, &ExternalConfig::default())
+            .unwrap();
+    assert_eq!(stats.faces, 0, "{name}");
+    assert_eq!(stats.vertices, 0, "{name}");
+}
+
+#[test]
+fn test_no_faces() {
+    for (name, data) in INPUTS {
+        check_no_faces(name, data);
+    }
+}
+
+#[test]
+fn test_hierarchy_without_faces() {
+    let obj = ObjObject::parse(Cursor::new("")).unwrap();
+    assert_eq!(obj.object_count(), 1);
+    assert_eq!(obj.group_count(), 1);
+
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.name(), "");
+    assert_eq!(
+        object.group_iter().next().unwrap().name(),
+        polypath::DEFAULT_GROUP_NAME
+    );
+
+    let obj = ObjObject::parse(Cursor::new("o a\ng b\nusemtl c\n")).unwrap();
+    let object = obj.objects_iter().next().unwrap();
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(object.name(), "a");
+    assert_eq!(group.name(), "b");
+    assert_eq!(group.mtluse(), Some("c"));
+}
+
+#[test]
+fn test_empty_named_objects() {
+    let obj = ObjObject::parse(Cursor::new("o a\no b\n")).unwrap();
+
+    let names = obj
+        .objects_iter()
+        .map(|o| o.name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b"]);
+    assert!(obj.objects_iter().all(|o| o.group_count() == 1));
+
+    // only the implicit object is replaced
+    let obj = ObjObject::parse(Cursor::new("mtllib a.mtl\no a\no b\nv 0 0 0\n")).unwrap();
+    let names = obj
+        .objects_iter()
+        .map(|o| o.name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b"]);
+}
+
+#[test]
+fn test_empty_objects_between_faces() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+o a
+f 1 2 3
+o b
+o c
+f 1 2 3
+o d
+";
+
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    let objects = obj
+        .objects_iter()
+        .map(|o| {
+            let faces = o.group_iter().map(|g| g.face_count()).sum::<usize>();
+            (o.name().to_owned(), o.group_count(), faces)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        objects,
+        [
+            (String::from("a"), 1, 1),
+            (String::from("b"), 1, 0),
+            (String::from("c"), 1, 1),
+            (String::from("d"), 1, 0),
+        ]
+    );
+
+    let (vertices, _) = obj.vertices();
+    assert_eq!(vertices.len(), 6);
+}
@@ -0,0 +1,136 @@
+use polypath::{Error, ObjObject};
+
+#[test]
+fn test_error_line_numbers() {
+    let cases: [(&str, usize); 5] = [
+        ("v 0 0 0\nv 1 0 0\nunknown\n", 3),
+        ("v 0 0 0\n\n# comment\nv 1 0\n", 4),
+        ("v 0 0 x\n", 1),
+        ("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 x\n", 4),
+        ("v 0 0 0\r\nusemtl a\r\nf 1 1 0\r\n", 3),
+    ];
+
+    for (data, expected) in cases {
+        match ObjObject::parse_str(data) {
+            Err(Error::AtLine { line, .. }) => assert_eq!(line, expected, "{data:?}"),
+            other => panic!("expected an error with line number for {data:?}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_error_keeps_source() {
+    let Err(Error::AtLine { line, source }) = ObjObject::parse_str("v 0 0 0\nbad line\n") else {
+        panic!("expected an error with line number");
+    };
+
+    assert_eq!(line, 2);
+    assert!(matches!(*source, Error::UnknownLine(ref data) if data == "bad line"));
+}
+
+#[test]
+fn test_error_display() {
+    let err = ObjObject::parse_str("v 0 0 0\nv 1 0\n").unwrap_err();
+
+    assert_eq!(err.to_string(), "on line 2: Unexpected end-of-line\n");
+}
+
+#[test]
+fn test_error_missing_file() {
+    let path = std::path::Path::new("./meshes/missing.obj");
+
+    let Err(Error::File {
+        path: err_path,
+        source,
+    }) = ObjObject::read_from_file(path)
+    else {
+        panic!("expected an error with file path");
+    };
+
+    assert_eq!(err_path, path);
+    assert!(matches!(*source, Error::Io(ref err) if err.kind() == std::io::ErrorKind::NotFound));
+
+    let err = ObjObject::read_from_file(path).unwrap_err().to_string();
+    assert!(err.starts_with("in file [./meshes/missing.obj]: "), "{err}");
+}
+
+#[test]
+fn test_error_malformed_file() {
+    let path = std::env::temp_dir().join(format!("polypath_malformed_{}.obj", std::process::id()));
+    std::fs::write(&path, "v 0 0 0\nv 1 0\n").unwrap();
+    let res = ObjObject::read_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let err = res.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "in file [{}]: on line 2: Unexpected end-of-line\n",
+            path.display()
+        )
+    );
+
+    let Error::File {
+        path: err_path,
+        source,
+    } = err
+    else {
+        panic!("expected an error with file path");
+    };
+    assert_eq!(err_path, path);
+    assert!(matches!(*source, Error::AtLine { line: 2, .. }));
+}
+
+#[test]
+fn test_error_source() {
+    use std::error::Error as _;
+
+    let err = ObjObject::parse_str("v 0 0 x\n").unwrap_err();
+    assert_eq!(err.to_string(), "on line 1: Invalid float\n");
+
+    let source = err.source().unwrap();
+    assert!(source.is::<std::num::ParseFloatError>());
+    assert_eq!(source.to_string(), "invalid float literal");
+
+    let err = ObjObject::read_from_file("./meshes/missing.obj").unwrap_err();
+    let source = err.source().unwrap().downcast_ref::<std::io::Error>();
+    assert_eq!(source.unwrap().kind(), std::io::ErrorKind::NotFound);
+
+    // errors without a wrapped error have no source
+    let err = ObjObject::parse_str("v 0 0 0\nv 1 0\n").unwrap_err();
+    assert!(err.source().is_none());
+
+    let boxed: Box<dyn std::error::Error> = Box::new(err);
+    assert_eq!(boxed.to_string(), "on line 2: Unexpected end-of-line\n");
+}
+
+#[test]
+fn test_inconsistent_face_format() {
+    let faces = [
+        "f 1 2/1 3//1",
+        "f 1/1 2 3",
+        "f 1//1 2//1 3",
+        "f 1/1/1 2/1 3/1/1",
+        "f 1/1 2/1 3/1 4",
+        "f 1//1 2//1 3//1 4/1/1",
+        "f 1 2 3 4//1",
+        "f 1 2 3 4 1/1",
+        // line elements follow the same rule
+        "l 1/1 2",
+        "l 1 2 3/1",
+    ];
+
+    for face in faces {
+        let data = format!("v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nvt 0 0\nvn 0 0 1\n{face}\n");
+
+        match ObjObject::parse_str(&data) {
+            Err(Error::AtLine { line: 7, source }) => {
+                assert!(
+                    matches!(*source, Error::InconsistentFaceFormat(ref f) if f == &face[2..]),
+                    "{face:?}: {source:?}"
+                );
+            }
+            other => panic!("expected an inconsistent face format for {face:?}, got {other:?}"),
+        }
+    }
+}
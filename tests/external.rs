@@ -0,0 +1,108 @@
+use std::io::{BufReader, Cursor};
+
+use polypath::{
+    Error, ObjObject,
+    external::{self, ExternalConfig},
+    opt,
+};
+
+#[test]
+fn test_out_of_core_matches_in_memory() {
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+    let (vertices, _) = obj.vertices();
+    let (_, unique) = opt::indexed_vertices(&vertices);
+
+    // artificially tiny budget, so every pass has to work on many chunks/partitions
+    let config = ExternalConfig {
+        memory_budget: 16 * 1024,
+        ..Default::default()
+    };
+
+    let file = std::fs::File::open("./meshes/cheburashka.obj").unwrap();
+    let mut out = Vec::new();
+    let stats = external::index_out_of_core(BufReader::new(file), &mut out, &config).unwrap();
+
+    assert_eq!(stats.faces, obj.face_count());
+    assert_eq!(stats.vertices, unique.len());
+
+    let indexed = ObjObject::parse(Cursor::new(out)).unwrap();
+    assert_eq!(indexed.face_count(), obj.face_count());
+
+    let (indexed_vertices, _) = indexed.vertices();
+    assert_eq!(indexed_vertices.len(), vertices.len());
+    for (a, b) in indexed_vertices.iter().zip(&vertices) {
+        assert_eq!(a.vertex, b.vertex);
+    }
+}
+
+#[test]
+fn test_out_of_core_texture_coords() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vt 0 0
+vt 1 0
+vt 0 1
+vt 1 1
+f 1/1 2/2 4/4 3/3
+f 1/1 2/2 3/3
+";
+
+    let mut out = Vec::new();
+    let stats =
+        external::index_out_of_core(Cursor::new(data), &mut out, &ExternalConfig::default())
+            .unwrap();
+
+    assert_eq!(stats.faces, 3);
+    assert_eq!(stats.vertices, 4);
+
+    let indexed = ObjObject::parse(Cursor::new(out)).unwrap();
+    let original = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    let (a, _) = indexed.vertices();
+    let (b, _) = original.vertices();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_out_of_core_missing_attribute() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//2\n";
+
+    let mut out = Vec::new();
+    let result =
+        external::index_out_of_core(Cursor::new(data), &mut out, &ExternalConfig::default());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_out_of_core_budget_too_small() {
+    let mut data = String::from("v 0 0 0\nv 1 0 0\nv 0 1 0\n");
+    for _ in 0..20_000 {
+        data.push_str("f 1 2 3\n");
+    }
+
+    // 60000 corners can not be split into at most 1024 partitions of 4 KiB each
+    let config = ExternalConfig {
+        memory_budget: 4096,
+        ..Default::default()
+    };
+
+    let mut out = Vec::new();
+    let result = external::index_out_of_core(Cursor::new(&data), &mut out, &config);
+    let Err(Error::MemoryBudgetTooSmall { budget, required }) = result else {
+        panic!("expected the budget to be too small");
+    };
+    assert_eq!(budget, 4096);
+    assert!(required > 4096);
+
+    // but fits into a larger budget
+    let config = ExternalConfig {
+        memory_budget: 64 * 1024,
+        ..Default::default()
+    };
+    let stats = external::index_out_of_core(Cursor::new(&data), &mut out, &config).unwrap();
+    assert_eq!(stats.vertices, 3);
+    assert_eq!(stats.faces, 20_000);
+}
@@ -0,0 +1,90 @@
+use polypath::{FaceContext, ObjObject};
+
+const CONTEXT: &str = "
+mtllib scene.mtl
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+o first
+g a
+usemtl red
+f 1 2 3
+usemtl blue
+f 2 4 3
+g b
+f 1 2 4
+o second
+mtllib other.mtl
+g c
+usemtl green
+f 1 3 4
+";
+
+#[test]
+fn test_faces_iter_flat() {
+    for mesh in ["./meshes/cubes.obj", "./meshes/cheburashka.obj"] {
+        let obj = ObjObject::read_from_file(mesh).unwrap();
+
+        let mut nested = Vec::new();
+        for object in obj.objects_iter() {
+            for group in object.group_iter() {
+                nested.extend(group.faces_iter().map(|face| face.vertices()));
+            }
+        }
+
+        let flat = obj
+            .faces_iter()
+            .map(|face| face.vertices())
+            .collect::<Vec<_>>();
+        assert_eq!(flat.len(), obj.face_count(), "{mesh}");
+        assert_eq!(flat, nested, "{mesh}");
+    }
+}
+
+#[test]
+fn test_faces_with_context_iter() {
+    let obj = ObjObject::parse_str(CONTEXT).unwrap();
+
+    let contexts = obj
+        .faces_with_context_iter()
+        .map(|(context, _)| context)
+        .collect::<Vec<_>>();
+
+    let context = |object, group, mtllib, mtluse| FaceContext {
+        object,
+        group,
+        mtllib: Some(mtllib),
+        mtluse: Some(mtluse),
+    };
+    assert_eq!(
+        contexts,
+        [
+            context("first", "a", "scene.mtl", "red"),
+            context("first", "a", "scene.mtl", "blue"),
+            context("first", "b", "scene.mtl", "blue"),
+            context("second", "c", "scene.mtl", "green"),
+        ]
+    );
+
+    let faces = obj
+        .faces_with_context_iter()
+        .map(|(_, face)| face.vertices())
+        .collect::<Vec<_>>();
+    let expected = obj
+        .faces_iter()
+        .map(|face| face.vertices())
+        .collect::<Vec<_>>();
+    assert_eq!(faces, expected);
+}
+
+#[test]
+fn test_faces_with_context_iter_no_material() {
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+
+    assert_eq!(obj.faces_with_context_iter().count(), obj.face_count());
+    for (context, _) in obj.faces_with_context_iter() {
+        assert_eq!(context.mtllib, None);
+        assert_eq!(context.mtluse, None);
+    }
+}
@@ -0,0 +1,82 @@
+use polypath::ObjObject;
+
+const LAYERS: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+o LOD0
+g body
+f 1 2 3
+g wheels trim
+f 2 4 3
+o LOD1
+g body
+f 1 2 4
+o collision
+g hull
+f 1 2 3
+";
+
+#[test]
+fn test_find_object_by_name() {
+    let obj = ObjObject::parse_str(LAYERS).unwrap();
+
+    let lod1 = obj.find_object_by_name("LOD1").unwrap();
+    assert_eq!(lod1.name(), "LOD1");
+    assert_eq!(lod1.group_count(), 1);
+
+    let collision = obj.find_object_by_name("collision").unwrap();
+    assert_eq!(collision.group_iter().next().unwrap().name(), "hull");
+
+    assert!(obj.find_object_by_name("LOD2").is_none());
+    assert!(obj.find_object_by_name("lod0").is_none());
+}
+
+#[test]
+fn test_find_group_by_name() {
+    let obj = ObjObject::parse_str(LAYERS).unwrap();
+
+    // the first match across all objects
+    let body = obj.find_group_by_name("body").unwrap();
+    let face = body.faces_iter().next().unwrap();
+    assert_eq!(face.vert_positions[2], [0.0, 1.0, 0.0]);
+
+    // other names of a group match as well
+    let trim = obj.find_group_by_name("trim").unwrap();
+    assert_eq!(trim.name(), "wheels");
+
+    assert_eq!(obj.find_group_by_name("hull").unwrap().face_count(), 1);
+    assert!(obj.find_group_by_name("missing").is_none());
+}
+
+#[test]
+fn test_names() {
+    let obj = ObjObject::parse_str(LAYERS).unwrap();
+
+    assert_eq!(
+        obj.object_names().collect::<Vec<_>>(),
+        ["LOD0", "LOD1", "collision"]
+    );
+    assert_eq!(
+        obj.group_names().collect::<Vec<_>>(),
+        ["body", "wheels", "body", "hull"]
+    );
+}
+
+#[test]
+fn test_names_empty() {
+    let obj = ObjObject::parse_str("").unwrap();
+
+    // an empty file still has an unnamed object, with the default group
+    assert_eq!(obj.find_object_by_name("").unwrap().group_count(), 1);
+    assert!(
+        obj.find_group_by_name(polypath::DEFAULT_GROUP_NAME)
+            .is_some()
+    );
+    assert_eq!(obj.object_names().collect::<Vec<_>>(), [""]);
+    assert_eq!(
+        obj.group_names().collect::<Vec<_>>(),
+        [polypath::DEFAULT_GROUP_NAME]
+    );
+}
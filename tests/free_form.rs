@@ -0,0 +1,93 @@
+use std::io::Cursor;
+
+use polypath::{
+    ObjObject, ParseWarning,
+    external::{ExternalConfig, index_out_of_core},
+};
+
+// a NURBS surface between regular triangles, as exported by Rhino
+const MIXED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+f 1 2 3
+g surface
+cstype bspline
+deg 1 1
+v 5 5 5
+v 6 5 5
+vp 0.0 0.0
+vp 1.0 0.0
+surf 0.0 1.0 0.0 1.0 4 5 \\
+    -2 -1
+parm u 0.0 0.0 1.0 1.0
+parm v 0.0 0.0 1.0 1.0
+trim 0.0 1.0 1
+end
+g mesh
+v 1 1 0
+f 2 -1 3
+";
+
+#[test]
+fn test_free_form_skipped() {
+    let obj = ObjObject::parse(Cursor::new(MIXED)).unwrap();
+    assert_eq!(obj.face_count(), 2);
+
+    let mut faces = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            faces.extend(group.faces_iter());
+        }
+    }
+
+    assert_eq!(
+        faces[0].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    // vertices used by the surface still count towards the indices
+    assert_eq!(
+        faces[1].vert_positions,
+        [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+}
+
+#[test]
+fn test_free_form_warnings() {
+    let result = ObjObject::parse_with_warnings(Cursor::new(MIXED)).unwrap();
+    assert_eq!(result.obj.face_count(), 2);
+
+    let skipped = result
+        .warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            ParseWarning::SkippedFreeForm { line, statement } => Some((*line, statement.as_str())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        skipped,
+        [
+            (7, "cstype"),
+            (8, "deg"),
+            (11, "vp"),
+            (12, "vp"),
+            (13, "surf"),
+            (15, "parm"),
+            (16, "parm"),
+            (17, "trim"),
+            (18, "end"),
+        ]
+    );
+    assert_eq!(skipped.len(), result.warnings.len());
+}
+
+#[test]
+fn test_free_form_external() {
+    let mut written = Vec::new();
+    let stats =
+        index_out_of_core(Cursor::new(MIXED), &mut written, &ExternalConfig::default()).unwrap();
+
+    assert_eq!(stats.faces, 2);
+}
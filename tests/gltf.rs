@@ -0,0 +1,253 @@
+#![cfg(feature = "gltf")]
+
+use polypath::{ObjObject, ParseOptions};
+use serde_json::Value;
+
+/// Splits a .glb file into its JSON and binary chunk.
+fn split_glb(data: &[u8]) -> (Value, Vec<u8>) {
+    let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+    assert_eq!(&data[..4], b"glTF");
+    assert_eq!(u32_at(4), 2);
+    assert_eq!(u32_at(8) as usize, data.len());
+
+    let json_len = u32_at(12) as usize;
+    assert_eq!(&data[16..20], b"JSON");
+    assert_eq!(json_len % 4, 0);
+    let json = serde_json::from_slice(&data[20..20 + json_len]).unwrap();
+
+    let bin = &data[20 + json_len..];
+    if bin.is_empty() {
+        return (json, Vec::new());
+    }
+    let bin_len = u32_at(20 + json_len) as usize;
+    assert_eq!(&bin[4..8], b"BIN\0");
+    assert_eq!(bin_len % 4, 0);
+    assert_eq!(bin.len(), 8 + bin_len);
+
+    (json, bin[8..].to_vec())
+}
+
+/// The numbers of a JSON array.
+fn floats(value: &Value) -> Vec<f64> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect()
+}
+
+/// Reads the values of an accessor, as `f32` for float and `u32` for integer components.
+fn read_accessor(json: &Value, buffer: &[u8], accessor: &Value) -> Vec<f64> {
+    let accessor = &json["accessors"][accessor.as_u64().unwrap() as usize];
+    let view = &json["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+
+    let offset = view["byteOffset"].as_u64().unwrap() as usize;
+    let len = view["byteLength"].as_u64().unwrap() as usize;
+    let components = match accessor["type"].as_str().unwrap() {
+        "VEC3" => 3,
+        "VEC2" => 2,
+        _ => 1,
+    };
+    assert_eq!(
+        accessor["count"].as_u64().unwrap() as usize * components * 4,
+        len
+    );
+
+    buffer[offset..offset + len]
+        .chunks_exact(4)
+        .map(|bytes| {
+            let bytes = bytes.try_into().unwrap();
+            match accessor["componentType"].as_u64().unwrap() {
+                5126 => f64::from(f32::from_le_bytes(bytes)),
+                5125 => f64::from(u32::from_le_bytes(bytes)),
+                other => panic!("unexpected component type {other}"),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_write_glb() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let mut data = Vec::new();
+    obj.write_glb_to_writer(&mut data).unwrap();
+
+    let (json, buffer) = split_glb(&data);
+    assert_eq!(json["asset"]["version"], "2.0");
+    assert_eq!(json["scenes"][0]["nodes"], serde_json::json!([0, 1, 2]));
+    assert_eq!(
+        json["buffers"][0]["byteLength"].as_u64().unwrap() as usize,
+        buffer.len()
+    );
+    assert!(json["buffers"][0].get("uri").is_none());
+    assert!(json.get("materials").is_none());
+
+    let meshes = json["meshes"].as_array().unwrap();
+    let names = meshes
+        .iter()
+        .map(|mesh| mesh["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["cube1", "cube2", "cube3"]);
+
+    // the triangles of every mesh are the faces of its object
+    for (mesh, object) in meshes.iter().zip(obj.objects_iter()) {
+        let primitive = &mesh["primitives"][0];
+        let attributes = &primitive["attributes"];
+        let positions = read_accessor(&json, &buffer, &attributes["POSITION"]);
+        let normals = read_accessor(&json, &buffer, &attributes["NORMAL"]);
+        let indices = read_accessor(&json, &buffer, &primitive["indices"]);
+        assert!(attributes.get("TEXCOORD_0").is_none());
+
+        let position_accessor =
+            &json["accessors"][attributes["POSITION"].as_u64().unwrap() as usize];
+        assert_eq!(floats(&position_accessor["min"]), [0.0; 3]);
+        assert_eq!(floats(&position_accessor["max"]), [1.0; 3]);
+
+        let faces = object
+            .group_iter()
+            .flat_map(|group| group.faces_iter())
+            .collect::<Vec<_>>();
+        assert_eq!(indices.len(), faces.len() * 3);
+        // every corner has a single normal, so only the 8 corners of the cube are shared
+        assert!(positions.len() < indices.len() * 3);
+
+        for (corners, face) in indices.chunks_exact(3).zip(&faces) {
+            for (corner, index) in corners.iter().enumerate() {
+                let index = *index as usize * 3;
+                let position = positions[index..index + 3].iter().map(|v| *v as f32);
+                let normal = normals[index..index + 3].iter().map(|v| *v as f32);
+                assert!(position.eq(face.vert_positions[corner]));
+                assert!(normal.eq(face.vert_normals.unwrap()[corner]));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_write_gltf_materials() {
+    let data = "v 0 0 0 1 0 0
+v 1 0 0 1 0 0
+v 0 1 0 0 0 1
+v 1 1 0 0 0 1
+vt 0 0
+vt 1 0.25
+vt 0 1
+o \"quoted\"
+usemtl red
+f 1/1 2/2 3/3
+usemtl blue
+f 3/3 2/2 4/2
+o empty
+o second
+usemtl red
+f 1/1 2/2 3/3
+";
+    let mut obj = ObjObject::parse_str(data).unwrap();
+    obj.set_object_transform(
+        "second",
+        [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [2.0, 0.0, 0.0, 1.0],
+        ],
+    );
+
+    let dir = std::env::temp_dir().join(format!("polypath_gltf_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    obj.write_gltf(dir.join("scene.gltf")).unwrap();
+    let json = std::fs::read(dir.join("scene.gltf")).unwrap();
+    let buffer = std::fs::read(dir.join("scene.bin")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let json: Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["buffers"][0]["uri"], "scene.bin");
+    assert_eq!(
+        json["buffers"][0]["byteLength"].as_u64().unwrap() as usize,
+        buffer.len()
+    );
+
+    // the empty object does not become a mesh
+    let nodes = json["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0]["name"], "\"quoted\"");
+    assert!(nodes[0].get("matrix").is_none());
+    assert_eq!(nodes[1]["matrix"][12], 2.0);
+
+    // the base color is the average vertex color of all faces using a material
+    let materials = json["materials"].as_array().unwrap();
+    assert_eq!(materials[0]["name"], "red");
+    assert_eq!(materials[1]["name"], "blue");
+    let colors = materials
+        .iter()
+        .map(|material| floats(&material["pbrMetallicRoughness"]["baseColorFactor"]));
+    for (color, expected) in colors.zip([[2.0, 0.0, 1.0, 3.0], [1.0, 0.0, 2.0, 3.0]]) {
+        for (value, expected) in color.iter().zip(expected) {
+            assert!((value - expected / 3.0).abs() < 1e-6, "{color:?}");
+        }
+    }
+
+    let primitives = json["meshes"][0]["primitives"].as_array().unwrap();
+    assert_eq!(primitives.len(), 2);
+    assert_eq!(primitives[0]["material"], 0);
+    assert_eq!(primitives[1]["material"], 1);
+    assert_eq!(json["meshes"][1]["primitives"][0]["material"], 0);
+
+    // texture coords are flipped vertically, and there are no normals
+    let attributes = &primitives[0]["attributes"];
+    assert!(attributes.get("NORMAL").is_none());
+    let texture_coords = read_accessor(&json, &buffer, &attributes["TEXCOORD_0"]);
+    assert_eq!(texture_coords, [0.0, 1.0, 1.0, 0.75, 0.0, 0.0]);
+}
+
+#[test]
+fn test_write_glb_empty() {
+    let obj = ObjObject::parse_str("v 0 0 0\n").unwrap();
+    let mut data = Vec::new();
+    obj.write_glb_to_writer(&mut data).unwrap();
+
+    let (json, buffer) = split_glb(&data);
+    assert!(buffer.is_empty());
+    assert!(json.get("buffers").is_none());
+    assert!(json.get("meshes").is_none());
+    assert!(json["scenes"][0].get("nodes").is_none());
+}
+
+#[test]
+fn test_write_glb_materials_per_library() {
+    let data = "v 0 0 0
+v 1 0 0
+v 0 1 0
+mtllib a.mtl
+o first
+usemtl shared
+f 1 2 3
+mtllib b.mtl
+o second
+usemtl shared
+f 1 2 3
+o third
+mtllib a.mtl
+usemtl shared
+f 1 2 3
+";
+    let options = ParseOptions::default().reset_mtllib_per_object(true);
+    let obj = ObjObject::parse_bytes_with(data.as_bytes(), &options).unwrap();
+    let mut data = Vec::new();
+    obj.write_glb_to_writer(&mut data).unwrap();
+
+    // materials with the same name from different libraries are not merged
+    let (json, _) = split_glb(&data);
+    let materials = json["materials"].as_array().unwrap();
+    assert_eq!(materials.len(), 2);
+    assert!(
+        materials
+            .iter()
+            .all(|material| material["name"] == "shared")
+    );
+    assert_eq!(json["meshes"][0]["primitives"][0]["material"], 0);
+    assert_eq!(json["meshes"][1]["primitives"][0]["material"], 1);
+    assert_eq!(json["meshes"][2]["primitives"][0]["material"], 0);
+}
@@ -0,0 +1,117 @@
+use polypath::{GpuVertexData, GpuVertexTextureData, VertexData};
+
+#[test]
+fn test_gpu_sentinels() {
+    let vertex = GpuVertexData::from(VertexData {
+        position: [1.0, 2.0, 3.0],
+        ..Default::default()
+    });
+
+    assert_eq!(vertex.position, [1.0, 2.0, 3.0]);
+    assert_eq!(vertex.color, [1.0; 4]);
+    assert_eq!(vertex.normal, [0.0; 3]);
+    assert_eq!(vertex.texture_coord, [0.0; 2]);
+
+    let vertex = GpuVertexData::from(VertexData {
+        position: [1.0, 2.0, 3.0],
+        color: Some([0.5, 0.25, 0.0]),
+        alpha: None,
+        normal: Some([0.0, 0.0, 1.0]),
+        texture_coord: Some([0.5, 0.5]),
+        tangent: None,
+    });
+
+    assert_eq!(vertex.color, [0.5, 0.25, 0.0, 1.0]);
+    assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+    assert_eq!(vertex.texture_coord, [0.5, 0.5]);
+}
+
+#[test]
+fn test_gpu_layout() {
+    assert_eq!(std::mem::size_of::<GpuVertexData>(), 48);
+    assert_eq!(std::mem::size_of::<GpuVertexTextureData>(), 52);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_gpu_cast() {
+    let obj = polypath::ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let (vertices, _) = obj.vertices();
+
+    let gpu = vertices
+        .iter()
+        .copied()
+        .map(GpuVertexTextureData::from)
+        .collect::<Vec<_>>();
+
+    let bytes: &[u8] = bytemuck::cast_slice(&gpu);
+    assert_eq!(bytes.len(), vertices.len() * 52);
+
+    let back: &[GpuVertexTextureData] = bytemuck::cast_slice(bytes);
+    assert_eq!(back, gpu.as_slice());
+    assert_eq!(back[0].vertex.position, vertices[0].vertex.position);
+
+    let split = polypath::split_buffers(&vertices);
+    let positions: &[u8] = bytemuck::cast_slice(&split.positions);
+    assert_eq!(positions.len(), vertices.len() * 12);
+    let colors: &[u8] = bytemuck::cast_slice(&split.colors);
+    assert_eq!(colors.len(), vertices.len() * 16);
+}
+
+#[cfg(feature = "wgpu")]
+#[test]
+fn test_wgpu_vertex_buffer() {
+    let layout = polypath::wgpu_vertex_buffer_layout();
+    assert_eq!(layout.array_stride, 48);
+    assert_eq!(layout.step_mode, wgpu::VertexStepMode::Vertex);
+
+    let attributes = layout
+        .attributes
+        .iter()
+        .map(|a| (a.shader_location, a.offset, a.format))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        attributes,
+        [
+            (0, 0, wgpu::VertexFormat::Float32x3),
+            (1, 28, wgpu::VertexFormat::Float32x3),
+            (2, 40, wgpu::VertexFormat::Float32x2),
+            (3, 12, wgpu::VertexFormat::Float32x4),
+        ]
+    );
+
+    let obj = polypath::ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let (vertices, _) = obj.vertices();
+    let contents = polypath::wgpu_vertex_buffer_contents(&vertices);
+    assert_eq!(contents.len(), vertices.len() * 48);
+
+    let first: GpuVertexData = bytemuck::pod_read_unaligned(&contents[..48]);
+    assert_eq!(first, GpuVertexData::from(vertices[0].vertex));
+}
+
+#[test]
+fn test_interleaved_and_split_buffers() {
+    let obj = polypath::ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let (vertices, _) = obj.vertices();
+
+    let interleaved = polypath::interleaved_buffer(&vertices);
+    let split = polypath::split_buffers(&vertices);
+    assert_eq!(interleaved.len(), vertices.len());
+    assert_eq!(split.positions.len(), vertices.len());
+    assert_eq!(split.normals.len(), vertices.len());
+    assert_eq!(split.texture_coords.len(), vertices.len());
+    assert_eq!(split.colors.len(), vertices.len());
+
+    for (i, (vertex, gpu)) in vertices.iter().zip(&interleaved).enumerate() {
+        assert_eq!(*gpu, GpuVertexData::from(vertex.vertex));
+        assert_eq!(split.positions[i], gpu.position);
+        assert_eq!(split.normals[i], gpu.normal);
+        assert_eq!(split.texture_coords[i], gpu.texture_coord);
+        assert_eq!(split.colors[i], gpu.color);
+    }
+
+    assert_eq!(
+        polypath::split_buffers(&[]),
+        polypath::SplitBuffers::default()
+    );
+}
@@ -0,0 +1,168 @@
+use polypath::{DEFAULT_GROUP_NAME, ObjObject};
+
+const CAR: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+o car
+g body   wheels lod0
+f 1 2 3
+g wheels
+f 2 4 3
+g body
+f 1 2 4
+";
+
+#[test]
+fn test_multiple_group_names() {
+    let obj = ObjObject::parse_str(CAR).unwrap();
+    let object = obj.objects_iter().next().unwrap();
+
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[0].name(), "body");
+    assert_eq!(
+        groups[0].names().collect::<Vec<_>>(),
+        ["body", "wheels", "lod0"]
+    );
+    assert_eq!(groups[1].names().collect::<Vec<_>>(), ["wheels"]);
+    assert!(groups[0].has_name("lod0"));
+    assert!(!groups[1].has_name("lod0"));
+
+    // the first face is reachable under all of its names
+    let faces = |name: &str| {
+        let mut faces = Vec::new();
+        for group in object.groups_named(name) {
+            for face in group.faces_iter() {
+                faces.push(face.vert_positions);
+            }
+        }
+        faces
+    };
+    let first = groups[0].faces_iter().next().unwrap().vert_positions;
+
+    assert_eq!(faces("body").len(), 2);
+    assert_eq!(faces("wheels").len(), 2);
+    assert_eq!(faces("lod0"), [first]);
+    assert_eq!(faces("body")[0], first);
+    assert_eq!(faces("wheels")[0], first);
+    assert!(faces("lod1").is_empty());
+}
+
+#[test]
+fn test_multiple_group_names_write_back() {
+    let obj = ObjObject::parse_str(CAR).unwrap();
+
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(written.contains("g body wheels lod0\n"));
+
+    let reread = ObjObject::parse_str(&written).unwrap();
+    let names = |obj: &ObjObject| {
+        let mut out = Vec::new();
+        for object in obj.objects_iter() {
+            for group in object.group_iter() {
+                out.push(group.names().map(String::from).collect::<Vec<_>>());
+            }
+        }
+        out
+    };
+    assert_eq!(names(&reread), names(&obj));
+}
+
+#[test]
+fn test_merge_requires_all_names() {
+    let mut obj = ObjObject::parse_str(&format!("{CAR}g wheels\nf 1 3 4\n")).unwrap();
+    assert!(obj.merge_named_groups().is_empty());
+
+    let object = obj.objects_iter().next().unwrap();
+    let groups = object
+        .group_iter()
+        .map(|group| (group.names().count(), group.face_count()))
+        .collect::<Vec<_>>();
+    assert_eq!(groups, [(3, 1), (1, 2), (1, 1)]);
+}
+
+const UNGROUPED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+f 1 2 3
+";
+
+const MIXED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+f 1 2 3
+g wheels
+f 1 2 3
+g
+f 1 2 3
+g wheels
+f 1 2 3
+o other
+f 1 2 3
+";
+
+fn group_names(obj: &ObjObject) -> Vec<(String, usize)> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.push((group.name().to_owned(), group.face_count()));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_default_group() {
+    let obj = ObjObject::parse_str(UNGROUPED).unwrap();
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    assert_eq!(group.name(), DEFAULT_GROUP_NAME);
+    assert_eq!(group.name(), "default");
+    assert_eq!(group.face_count(), 1);
+
+    // the implicit group is not written
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    assert!(!String::from_utf8(written).unwrap().contains("g "));
+}
+
+#[test]
+fn test_mixed_default_groups() {
+    let mut obj = ObjObject::parse_str(MIXED).unwrap();
+
+    let expected = [
+        ("default", 1),
+        ("wheels", 1),
+        ("default", 1),
+        ("wheels", 1),
+        ("default", 1),
+    ];
+    assert_eq!(
+        group_names(&obj),
+        expected.map(|(name, count)| (name.to_owned(), count))
+    );
+
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let reread = ObjObject::parse_str(&String::from_utf8(written).unwrap()).unwrap();
+    assert_eq!(group_names(&reread), group_names(&obj));
+
+    // a bare group statement continues the default group
+    assert!(obj.merge_named_groups().is_empty());
+    let expected = [("default", 2), ("wheels", 2), ("default", 1)];
+    assert_eq!(
+        group_names(&obj),
+        expected.map(|(name, count)| (name.to_owned(), count))
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "flate2")]
+
+use std::io::{Cursor, Write};
+
+mod common;
+
+use common::assert_same;
+use flate2::{Compression, write::GzEncoder};
+use polypath::{Error, ObjObject};
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_parse_gzip() {
+    for mesh in ["./meshes/cheburashka.obj", "./meshes/cubes.obj"] {
+        let data = std::fs::read(mesh).unwrap();
+        let plain = ObjObject::parse_bytes(&data).unwrap();
+
+        let compressed = ObjObject::parse_gzip(Cursor::new(compress(&data))).unwrap();
+        assert_same(&plain, &compressed);
+    }
+}
+
+#[test]
+fn test_read_gzip_file() {
+    let data = std::fs::read("./meshes/cheburashka.obj").unwrap();
+    let plain = ObjObject::parse_bytes(&data).unwrap();
+    let compressed = compress(&data);
+
+    let dir = std::env::temp_dir();
+
+    // detected by the extension
+    let path = dir.join("polypath_cheburashka.obj.gz");
+    std::fs::write(&path, &compressed).unwrap();
+    let obj = ObjObject::read_from_file(&path).unwrap();
+    assert_same(&plain, &obj);
+
+    // detected by the magic bytes
+    let path = dir.join("polypath_cheburashka_gzip.obj");
+    std::fs::write(&path, &compressed).unwrap();
+    let obj = ObjObject::read_from_file(&path).unwrap();
+    assert_same(&plain, &obj);
+}
+
+#[test]
+fn test_gzip_errors() {
+    // parse errors are the same as without compression
+    let data = b"v 0 0 0\nv 1 0 0\nbogus\n";
+    let plain = ObjObject::parse_bytes(data).unwrap_err();
+    let compressed = ObjObject::parse_gzip(Cursor::new(compress(data))).unwrap_err();
+    assert_eq!(plain.to_string(), compressed.to_string());
+    assert!(matches!(
+        compressed,
+        Error::AtLine { line: 3, source } if matches!(*source, Error::UnknownLine(_))
+    ));
+
+    // not gzip at all
+    let res = ObjObject::parse_gzip(Cursor::new(b"v 0 0 0\n"));
+    assert!(matches!(res, Err(Error::Io(_))));
+}
@@ -0,0 +1,77 @@
+use polypath::{
+    Error, ObjObject, VertexData, VertexTextureData,
+    opt::{self, IndexBuffer},
+};
+
+const QUAD: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+f 1 2 3 4
+";
+
+// `count` vertices, all with a different position
+#[allow(clippy::cast_precision_loss)]
+fn unique_vertices(count: usize) -> Vec<VertexTextureData> {
+    (0..count)
+        .map(|i| VertexTextureData {
+            material_index: 0,
+            vertex: VertexData {
+                position: [i as f32, 0.0, 0.0],
+                ..Default::default()
+            },
+        })
+        .collect()
+}
+
+#[test]
+fn test_indexed_vertices_u16() {
+    let obj = ObjObject::parse_str(QUAD).unwrap();
+    let (vertices, _) = obj.vertices();
+
+    let (indices, unique) = opt::indexed_vertices(&vertices);
+    let (indices_u16, unique_u16) = opt::indexed_vertices_u16(&vertices).unwrap();
+
+    assert_eq!(unique_u16, unique);
+    assert_eq!(indices_u16, [0, 1, 2, 0, 2, 3]);
+    assert!(
+        indices
+            .iter()
+            .zip(indices_u16)
+            .all(|(a, b)| *a == usize::from(b))
+    );
+}
+
+#[test]
+fn test_indexed_vertices_u16_overflow() {
+    let vertices = unique_vertices(65_538);
+
+    assert!(matches!(
+        opt::indexed_vertices_u16(&vertices),
+        Err(Error::IndexOverflow {
+            count: 65_538,
+            limit: 65_535
+        })
+    ));
+}
+
+#[test]
+fn test_indexed_vertices_auto() {
+    let vertices = unique_vertices(65_535);
+    let IndexBuffer::U16(indices, unique) = opt::indexed_vertices_auto(&vertices) else {
+        panic!("expected u16 indices");
+    };
+    assert_eq!(indices.len(), 65_535);
+    assert_eq!(unique.len(), 65_535);
+
+    let vertices = unique_vertices(65_538);
+    let buffer = opt::indexed_vertices_auto(&vertices);
+    assert_eq!(buffer.index_count(), 65_538);
+    assert_eq!(buffer.vertices().len(), 65_538);
+
+    let IndexBuffer::U32(indices, _) = buffer else {
+        panic!("expected u32 indices");
+    };
+    assert_eq!(indices.last(), Some(&65_537));
+}
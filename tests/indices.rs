@@ -0,0 +1,127 @@
+use polypath::{Error, IndexKind, ObjObject};
+
+const VERTICES: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvn 0 0 1\n";
+
+fn zero_index(statement: &str) -> (usize, IndexKind) {
+    let data = format!("{VERTICES}{statement}\n");
+
+    match ObjObject::parse_str(&data) {
+        Err(Error::AtLine { line: 6, source }) => match *source {
+            Error::ZeroIndex { corner, kind } => (corner, kind),
+            other => panic!("expected a zero index for {statement:?}, got {other:?}"),
+        },
+        other => panic!("expected an error on line 6 for {statement:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_zero_index() {
+    assert_eq!(zero_index("f 0 1 2"), (1, IndexKind::Vertex));
+    assert_eq!(zero_index("f 1 2 3 0"), (4, IndexKind::Vertex));
+    assert_eq!(zero_index("f 1/0 2/1 3/1"), (1, IndexKind::TextureCoord));
+    assert_eq!(zero_index("f 1//1 2//0 3//1"), (2, IndexKind::Normal));
+    assert_eq!(zero_index("l 1 0"), (2, IndexKind::Vertex));
+    assert_eq!(zero_index("p 0"), (1, IndexKind::Vertex));
+}
+
+// (index, count, corner, kind)
+fn out_of_range(data: &str) -> (i32, u32, usize, IndexKind) {
+    match ObjObject::parse_str(data) {
+        Err(Error::AtLine { source, .. }) => match *source {
+            Error::RelativeIndexOutOfRange {
+                index,
+                count,
+                corner,
+                kind,
+            } => (index, count, corner, kind),
+            other => panic!("expected an out of range index for {data:?}, got {other:?}"),
+        },
+        other => panic!("expected an error with line number for {data:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_negative_index_under_run() {
+    let with = |statement: &str| format!("{VERTICES}{statement}\n");
+
+    // -4 resolves to 0 with 3 vertices
+    assert_eq!(
+        out_of_range(&with("f 1 2 -4")),
+        (-4, 3, 3, IndexKind::Vertex)
+    );
+    assert_eq!(
+        out_of_range(&with("f -1/-2 -2/-1 -3/-1")),
+        (-2, 1, 1, IndexKind::TextureCoord)
+    );
+    assert_eq!(
+        out_of_range(&with("f 1//-1 2//-1 3//-5")),
+        (-5, 1, 3, IndexKind::Normal)
+    );
+    assert_eq!(
+        out_of_range(&with("l 1 -10")),
+        (-10, 3, 2, IndexKind::Vertex)
+    );
+}
+
+#[test]
+fn test_negative_index_no_wrap_around() {
+    // these used to wrap around to huge indices
+    let mut data = String::new();
+    for i in 0..10 {
+        data += &format!("v {i} 0 0\n");
+    }
+
+    assert_eq!(
+        out_of_range(&(data.clone() + "f -50 -49 -48\n")),
+        (-50, 10, 1, IndexKind::Vertex)
+    );
+    assert_eq!(
+        out_of_range(&(data + "vt 0 0\nf -1/-1 -2/-1 -3/-2\n")),
+        (-2, 1, 3, IndexKind::TextureCoord)
+    );
+    // relative indices are resolved against the elements defined so far
+    assert_eq!(
+        out_of_range("f -1 -2 -3\nv 0 0 0\n"),
+        (-1, 0, 1, IndexKind::Vertex)
+    );
+}
+
+#[test]
+fn test_out_of_range_display() {
+    let err = ObjObject::parse_str("v 0 0 0\nf -1 -2 -1\n").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "on line 2: The relative vertex index [-2] of corner [2] is out of range, only [1] are defined\n"
+    );
+}
+
+#[test]
+fn test_negative_index() {
+    let obj = ObjObject::parse_str(&format!("{VERTICES}f -3/-1/-1 -2/-1/-1 -1/-1/-1\n")).unwrap();
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+
+    assert_eq!(
+        face.vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+}
+
+#[test]
+fn test_zero_index_display() {
+    let err = ObjObject::parse_str("v 0 0 0\nf 1/0 1 1\n").unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "on line 2: The texture coord index of corner [1] resolves to 0, but indices start at 1\n"
+    );
+}
@@ -0,0 +1,64 @@
+use polypath::{ObjObject, intersect::ray_triangle_intersect};
+
+const V0: [f32; 3] = [0.0, 0.0, 0.0];
+const V1: [f32; 3] = [1.0, 0.0, 0.0];
+const V2: [f32; 3] = [0.0, 1.0, 0.0];
+
+// two parallel quads, the second one in front of the first
+const LAYERS: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 2
+v 1 0 2
+v 1 1 2
+v 0 1 2
+o back
+f 1 2 3 4
+o front
+g glass
+f 5 6 7 8
+";
+
+#[test]
+fn test_ray_triangle_intersect() {
+    let (t, u, v) = ray_triangle_intersect([0.25, 0.5, 2.0], [0.0, 0.0, -1.0], V0, V1, V2).unwrap();
+    assert_eq!(t, 2.0);
+    assert_eq!(u, 0.25);
+    assert_eq!(v, 0.5);
+
+    // back faces are hit as well, distances scale with the direction
+    let (t, ..) = ray_triangle_intersect([0.25, 0.25, -1.0], [0.0, 0.0, 2.0], V0, V1, V2).unwrap();
+    assert_eq!(t, 0.5);
+}
+
+#[test]
+fn test_ray_triangle_miss() {
+    // outside of the triangle
+    assert!(ray_triangle_intersect([0.75, 0.75, 1.0], [0.0, 0.0, -1.0], V0, V1, V2).is_none());
+    // behind the origin
+    assert!(ray_triangle_intersect([0.25, 0.25, 1.0], [0.0, 0.0, 1.0], V0, V1, V2).is_none());
+    // parallel
+    assert!(ray_triangle_intersect([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], V0, V1, V2).is_none());
+}
+
+#[test]
+fn test_ray_cast_nearest() {
+    let obj = ObjObject::parse_str(LAYERS).unwrap();
+
+    let hit = obj.ray_cast([0.75, 0.25, 5.0], [0.0, 0.0, -1.0]).unwrap();
+    assert_eq!(hit.face, 2);
+    assert_eq!(hit.object, "front");
+    assert_eq!(hit.group, "glass");
+    assert_eq!(hit.distance, 3.0);
+    assert_eq!((hit.u, hit.v), (0.5, 0.25));
+
+    let hit = obj.ray_cast([0.25, 0.75, 1.0], [0.0, 0.0, -1.0]).unwrap();
+    assert_eq!(hit.face, 1);
+    assert_eq!(hit.object, "back");
+    assert_eq!(hit.group, "default");
+    assert_eq!(hit.distance, 1.0);
+
+    assert!(obj.ray_cast([2.0, 2.0, 5.0], [0.0, 0.0, -1.0]).is_none());
+}
@@ -0,0 +1,57 @@
+use polypath::{ObjObject, ParseOptions, geometry};
+
+// vertices 1, 4 and 6 are never used by a face, 7 only by a point
+const SPARSE: &str = "
+v 9 9 9 0.1 0.1 0.1
+v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 8 8 8 0.2 0.2 0.2
+v 1 1 0 0 0 1
+v 7 7 7 0.3 0.3 0.3
+v 2 2 2 1 1 1
+v 0 1 0 1 1 0
+f 2 3 5 8
+l 2 5
+p 7
+";
+
+#[test]
+fn test_find_isolated_vertices() {
+    let obj = ObjObject::parse_str(SPARSE).unwrap();
+    assert_eq!(geometry::find_isolated_vertices(&obj), [0, 3, 5]);
+
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    assert!(geometry::find_isolated_vertices(&obj).is_empty());
+}
+
+#[test]
+fn test_remove_isolated_vertices() {
+    let options = ParseOptions::new().keep_polygons(true);
+    let mut obj = ObjObject::parse_with(std::io::Cursor::new(SPARSE), &options).unwrap();
+    let (before, _) = obj.vertices();
+
+    geometry::remove_isolated_vertices(&mut obj);
+
+    assert_eq!(obj.unique_vertex_count(), 5);
+    assert!(geometry::find_isolated_vertices(&obj).is_empty());
+
+    // faces still reference the same positions and colors
+    let (after, _) = obj.vertices();
+    assert_eq!(after, before);
+
+    let mut corners = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            for polygon in group.polygons_iter() {
+                corners.extend(polygon.corners().iter().map(|c| c.position));
+            }
+        }
+    }
+    assert_eq!(corners, [1, 2, 3, 5]);
+    assert_eq!(obj.line_elements(), [vec![1, 3]]);
+    assert_eq!(obj.point_elements(), [vec![4]]);
+
+    // removing again does not change anything
+    geometry::remove_isolated_vertices(&mut obj);
+    assert_eq!(obj.unique_vertex_count(), 5);
+}
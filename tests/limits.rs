@@ -0,0 +1,187 @@
+use std::io::{BufReader, Cursor, Read};
+
+use polypath::{Error, Line, LineParser, ObjObject, ParseLimits, ParseOptions};
+
+/// An endless stream without a single newline, counting how many bytes were read from it.
+struct Endless {
+    read: usize,
+}
+
+impl Read for Endless {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        buf.fill(b'1');
+        self.read += buf.len();
+        Ok(buf.len())
+    }
+}
+
+#[test]
+fn test_line_too_long() {
+    let limits = ParseLimits {
+        max_line_len: 1024 * 1024,
+        ..Default::default()
+    };
+
+    let mut source = Endless { read: 0 };
+    let reader = BufReader::with_capacity(8 * 1024, &mut source);
+
+    let res = ObjObject::parse_with_limits(reader, &limits);
+    assert!(matches!(
+        res,
+        Err(Error::LineTooLong {
+            line: 1,
+            limit: 1048576
+        })
+    ));
+
+    // errors as soon as the limit is reached, instead of buffering the whole line
+    assert!(source.read <= limits.max_line_len + 8 * 1024);
+}
+
+#[test]
+fn test_line_too_long_later_line() {
+    let limits = ParseLimits {
+        max_line_len: 16,
+        ..Default::default()
+    };
+
+    let data = "v 0 0 0\nv 1 0 0\nv 0.000000 1.000000 0.000000\n";
+
+    let res = ObjObject::parse_with_limits(Cursor::new(data), &limits);
+    assert!(matches!(
+        res,
+        Err(Error::LineTooLong { line: 3, limit: 16 })
+    ));
+}
+
+#[test]
+fn test_within_limits() {
+    let limits = ParseLimits {
+        max_line_len: 128,
+        max_face_vertices: 4,
+        max_vertices: 16,
+        max_faces: 36,
+        max_file_size: 4096,
+    };
+
+    let file = std::fs::File::open("./meshes/cubes.obj").unwrap();
+    let obj = ObjObject::parse_with_limits(BufReader::new(file), &limits).unwrap();
+    assert_eq!(obj.face_count(), 36);
+
+    let options = ParseOptions::new().limits(limits);
+    let obj = ObjObject::read_from_file_with("./meshes/cubes.obj", &options).unwrap();
+    assert_eq!(obj.face_count(), 36);
+}
+
+#[test]
+fn test_too_many_face_vertices() {
+    let limits = ParseLimits {
+        max_face_vertices: 4,
+        ..Default::default()
+    };
+
+    let mut data = String::from("v 0 0 0\nv 1 0 0\nv 0 1 0\nf");
+    for _ in 0..100_000 {
+        data.push_str(" 1");
+    }
+
+    let res = ObjObject::parse_with_limits(Cursor::new(data), &limits);
+    assert!(matches!(
+        res,
+        Err(Error::AtLine { line: 4, source })
+            if matches!(*source, Error::TooManyFaceVertices { count: 100_000, limit: 4 })
+    ));
+
+    let limits = ParseLimits {
+        max_face_vertices: 3,
+        ..Default::default()
+    };
+
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 1 2 4 3\n";
+    let res = ObjObject::parse_with_limits(Cursor::new(data), &limits);
+    assert!(matches!(
+        res,
+        Err(Error::AtLine { line: 6, source })
+            if matches!(*source, Error::TooManyFaceVertices { count: 4, limit: 3 })
+    ));
+}
+
+#[test]
+fn test_single_line_100mb() {
+    let limits = ParseLimits {
+        max_line_len: 64 * 1024,
+        ..Default::default()
+    };
+
+    let mut source = Endless { read: 0 };
+    let reader = BufReader::with_capacity(8 * 1024, (&mut source).take(100 * 1024 * 1024));
+
+    let res = ObjObject::parse_with_limits(reader, &limits);
+    assert!(matches!(res, Err(Error::LineTooLong { line: 1, .. })));
+
+    // fails after reading about the limit, not the whole 100 MB
+    assert!(source.read <= limits.max_line_len + 8 * 1024);
+}
+
+#[test]
+fn test_too_many_vertices() {
+    let limits = ParseLimits {
+        max_vertices: 3,
+        ..Default::default()
+    };
+
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+    assert!(ObjObject::parse_with_limits(Cursor::new(data), &limits).is_ok());
+
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\n";
+    let res = ObjObject::parse_with_limits(Cursor::new(data), &limits);
+    assert!(matches!(
+        res,
+        Err(Error::AtLine { line: 4, source })
+            if matches!(*source, Error::TooManyVertices { limit: 3 })
+    ));
+
+    // the streaming parser enforces the same limits
+    let options = ParseOptions::new().limits(limits);
+    let mut parser = LineParser::new(Cursor::new(data), &options);
+    for _ in 0..3 {
+        assert!(matches!(parser.next_line(), Ok(Some(Line::Vertex(_)))));
+    }
+    assert!(parser.next_line().is_err());
+}
+
+#[test]
+fn test_too_many_faces() {
+    let limits = ParseLimits {
+        max_faces: 2,
+        ..Default::default()
+    };
+
+    // a quad counts as two triangles
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 4 3\n";
+    assert!(ObjObject::parse_with_limits(Cursor::new(data), &limits).is_ok());
+
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 1 2 4 3\n";
+    let res = ObjObject::parse_with_limits(Cursor::new(data), &limits);
+    assert!(matches!(
+        res,
+        Err(Error::AtLine { line: 6, source })
+            if matches!(*source, Error::TooManyFaces { limit: 2 })
+    ));
+}
+
+#[test]
+fn test_file_too_large() {
+    let options = ParseOptions::new().limits(ParseLimits {
+        max_file_size: 1024,
+        ..Default::default()
+    });
+
+    let size = std::fs::metadata("./meshes/cheburashka.obj").unwrap().len();
+    let res = ObjObject::read_from_file_with("./meshes/cheburashka.obj", &options);
+    assert!(matches!(
+        res,
+        Err(Error::File { source, .. })
+            if matches!(*source, Error::FileTooLarge { size: s, limit: 1024 } if s == size)
+    ));
+}
@@ -0,0 +1,76 @@
+use std::io::{BufReader, Cursor};
+
+use polypath::{Error, MaterialLibrary, ObjObject};
+
+const QUAD: &str = "# line endings
+mtllib scene.mtl
+o quad
+
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vn 0 0 1
+g front
+usemtl red
+f 1/1/1 2/1/1 \\
+3/1/1 4/1/1
+l 1 2";
+
+const STYLES: &[(&str, &str)] = &[("LF", "\n"), ("CRLF", "\r\n"), ("CR", "\r")];
+
+fn written(obj: &ObjObject) -> String {
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    String::from_utf8(written).unwrap()
+}
+
+#[test]
+fn test_line_endings() {
+    let expected = written(&ObjObject::parse_str(QUAD).unwrap());
+    assert!(expected.contains("usemtl red\n"));
+
+    for (style, terminator) in STYLES {
+        let data = QUAD.replace('\n', terminator);
+
+        let obj = ObjObject::parse_str(&data).unwrap();
+        assert_eq!(obj.face_count(), 2, "{style}");
+        assert_eq!(written(&obj), expected, "{style}");
+
+        // every buffer size splits the terminators at different positions
+        for capacity in 1..=8 {
+            let reader = BufReader::with_capacity(capacity, Cursor::new(&data));
+            let obj = ObjObject::parse(reader).unwrap();
+            assert_eq!(written(&obj), expected, "{style} with capacity {capacity}");
+        }
+    }
+}
+
+#[test]
+fn test_line_endings_line_numbers() {
+    for (style, terminator) in STYLES {
+        let data = ["v 0 0 0", "", "v 1 0", "v 0 1 0"].join(terminator);
+
+        for err in [
+            ObjObject::parse_str(&data).unwrap_err(),
+            ObjObject::parse(BufReader::with_capacity(1, Cursor::new(&data))).unwrap_err(),
+        ] {
+            assert!(
+                matches!(err, Error::AtLine { line: 3, .. }),
+                "{style}: {err:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_mtl_line_endings() {
+    for (style, terminator) in STYLES {
+        let data = ["newmtl red", "Kd 1 0 0", "newmtl blue", "Kd 0 0 1"].join(terminator);
+
+        let mtl = MaterialLibrary::parse(Cursor::new(data)).unwrap();
+        assert_eq!(mtl.materials().len(), 2, "{style}");
+        assert_eq!(mtl.material("blue").unwrap().diffuse, Some([0.0, 0.0, 1.0]));
+    }
+}
@@ -0,0 +1,147 @@
+use std::io::Cursor;
+
+use polypath::{Error, Line, LineParser, ObjObject, ParseOptions, QuadSplit};
+
+const STATEMENTS: &str = "
+# comment
+mtllib scene.mtl
+o quad
+v 0 0 0
+v 1 0 0 0.5
+vt 0 0
+vn 0 0 1
+
+v 1 1 0
+v 0 1 0
+g front
+usemtl red
+s 1
+f -4/1/1 -3/1/1 -2/1/1 -1/1/1
+l 1 2
+p 3
+";
+
+#[test]
+fn test_line_events() {
+    let mut parser = LineParser::new(Cursor::new(STATEMENTS), &ParseOptions::default());
+
+    let mut events = Vec::new();
+    while let Some(line) = parser.next_line().unwrap() {
+        let event = match line {
+            Line::MaterialLib(name) => format!("mtllib {name}"),
+            Line::Object(name) => format!("o {name}"),
+            Line::Group(name) => format!("g {name}"),
+            Line::MaterialUse(name) => format!("usemtl {name}"),
+            Line::TextureMapLib(name) => format!("maplib {name}"),
+            Line::TextureMapUse(name) => format!("usemap {name:?}"),
+            Line::DisplayAttribute(attribute) => format!("{attribute:?}"),
+            Line::Vertex(vertex) => format!("v {:?} {}", vertex.position, vertex.w),
+            Line::TextureCoord(uv) => format!("vt {uv:?}"),
+            Line::Normal(normal) => format!("vn {normal:?}"),
+            Line::SmoothGroup(group) => format!("s {group:?}"),
+            Line::Faces(faces, corners) => {
+                assert_eq!(corners.len(), 4);
+                let faces = faces
+                    .iter()
+                    .map(|f| (f.indices(), f.texture_indices(), f.normal_indices()))
+                    .collect::<Vec<_>>();
+                format!("f {faces:?}")
+            }
+            Line::PolyLine(indices, _) => format!("l {indices:?}"),
+            Line::PointElement(indices) => format!("p {indices:?}"),
+            Line::FreeForm(statement) => format!("free-form {statement}"),
+        };
+        events.push(event);
+    }
+
+    assert_eq!(
+        events,
+        [
+            "mtllib scene.mtl",
+            "o quad",
+            "v [0.0, 0.0, 0.0] 1",
+            "v [1.0, 0.0, 0.0] 0.5",
+            "vt [0.0, 0.0, 0.0]",
+            "vn [0.0, 0.0, 1.0]",
+            "v [1.0, 1.0, 0.0] 1",
+            "v [0.0, 1.0, 0.0] 1",
+            "g front",
+            "usemtl red",
+            "s Some(1)",
+            "f [([1, 2, 3], Some([1, 1, 1]), Some([1, 1, 1])), ([1, 3, 4], Some([1, 1, 1]), Some([1, 1, 1]))]",
+            "l [1, 2]",
+            "p [3]",
+        ]
+    );
+    assert_eq!(parser.line_number(), 17);
+}
+
+#[test]
+fn test_line_error_continues() {
+    let data = "v 0 0 0\nv 1 0\nunknown\nv 0 1 0\n";
+    let mut parser = LineParser::new(Cursor::new(data), &ParseOptions::default());
+
+    assert!(matches!(parser.next_line(), Ok(Some(Line::Vertex(_)))));
+    assert!(matches!(
+        parser.next_line(),
+        Err(Error::AtLine { line: 2, .. })
+    ));
+    let Err(Error::AtLine { line: 3, source }) = parser.next_line() else {
+        panic!("unknown line should be an error");
+    };
+    assert!(matches!(*source, Error::UnknownLine(_)));
+    assert!(matches!(parser.next_line(), Ok(Some(Line::Vertex(_)))));
+    assert!(matches!(parser.next_line(), Ok(None)));
+}
+
+/// Collects the corner positions of every triangle, as returned by a `LineParser`.
+fn parsed_triangles(reader: impl std::io::BufRead, options: &ParseOptions) -> Vec<[[f32; 3]; 3]> {
+    let mut parser = LineParser::new(reader, options);
+
+    let mut positions = Vec::new();
+    let mut triangles = Vec::new();
+    while let Some(line) = parser.next_line().unwrap() {
+        match line {
+            Line::Vertex(vertex) => positions.push(vertex.position),
+            Line::Faces(faces, _) => triangles.extend(
+                faces
+                    .iter()
+                    .map(|f| f.indices().map(|i| positions[i as usize - 1])),
+            ),
+            _ => {}
+        }
+    }
+    triangles
+}
+
+#[test]
+fn test_matches_obj_object() {
+    // the split of the quads depends on their positions
+    let skewed = "v 0 0 0\nv 4 0 0\nv 1 4 0\nv 0 1 1\nf 1 2 3 4\nf -1 -2 -3 -4\n";
+
+    let mut sources = vec![String::from(skewed)];
+    for mesh in ["./meshes/cubes.obj", "./meshes/cheburashka.obj"] {
+        sources.push(std::fs::read_to_string(mesh).unwrap());
+    }
+
+    for mode in [
+        QuadSplit::Fixed02,
+        QuadSplit::ShortestDiagonal,
+        QuadSplit::MostPlanar,
+    ] {
+        let options = ParseOptions::new().quad_split(mode);
+
+        for data in &sources {
+            let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+
+            let mut faces = Vec::new();
+            for object in obj.objects_iter() {
+                for group in object.group_iter() {
+                    faces.extend(group.faces_iter().map(|f| f.vert_positions));
+                }
+            }
+
+            assert_eq!(parsed_triangles(Cursor::new(data), &options), faces);
+        }
+    }
+}
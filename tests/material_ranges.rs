@@ -0,0 +1,187 @@
+use polypath::{ObjObject, ParseOptions};
+
+// a cube, whose six faces use three different materials inside one group
+const CUBE: &str = "
+mtllib cube.mtl
+v 0 0 0
+v 0 0 1
+v 0 1 0
+v 0 1 1
+v 1 0 0
+v 1 0 1
+v 1 1 0
+v 1 1 1
+g cube
+usemtl red
+f 1 3 7 5
+f 2 6 8 4
+usemtl green
+f 1 2 4 3
+usemtl unused
+usemtl blue
+f 5 7 8 6
+usemtl green
+f 3 4 8 7
+f 1 5 6 2
+";
+
+#[test]
+fn test_material_ranges() {
+    let obj = ObjObject::parse_str(CUBE).unwrap();
+
+    let object = obj.objects_iter().next().unwrap();
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(group.name(), "cube");
+    assert_eq!(group.face_count(), 12);
+    assert_eq!(group.mtluse(), Some("red"));
+
+    assert_eq!(
+        group.material_ranges().collect::<Vec<_>>(),
+        [
+            (0..4, Some("red")),
+            (4..6, Some("green")),
+            (6..8, Some("blue")),
+            (8..12, Some("green")),
+        ]
+    );
+}
+
+#[test]
+fn test_material_index_per_face() {
+    let obj = ObjObject::parse_str(CUBE).unwrap();
+
+    let table = obj.material_table();
+    let names = table
+        .iter()
+        .map(|m| m.mtluse.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["blue", "green", "red"]);
+
+    let (blue, green, red) = (0, 1, 2);
+    let expected = [
+        red, red, red, red, green, green, blue, blue, green, green, green, green,
+    ];
+    assert_eq!(obj.face_materials(), expected);
+
+    let (vertices, materials) = obj.vertices();
+    assert_eq!(materials.len(), 3);
+    let per_face = vertices
+        .chunks_exact(3)
+        .map(|face| face[0].material_index)
+        .collect::<Vec<_>>();
+    assert_eq!(per_face, expected);
+}
+
+#[test]
+fn test_material_ranges_write_back() {
+    for keep_polygons in [false, true] {
+        let options = ParseOptions {
+            keep_polygons,
+            ..Default::default()
+        };
+        let obj = ObjObject::parse_bytes_with(CUBE.as_bytes(), &options).unwrap();
+
+        let mut written = Vec::new();
+        obj.write_to_writer(&mut written).unwrap();
+        let reread = ObjObject::parse_str(std::str::from_utf8(&written).unwrap()).unwrap();
+
+        assert_eq!(reread.face_materials(), obj.face_materials());
+        assert_eq!(reread.material_table(), obj.material_table());
+    }
+}
+
+// a single material use, that stays in effect for all following groups and objects
+const SHARED_MATERIAL: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+usemtl metal
+g first
+f 1 2 3
+g second
+f 1 2 3
+o other
+f 1 2 3
+g third
+usemtl wood
+f 1 2 3
+g fourth
+f 1 2 3
+";
+
+fn group_materials(obj: &ObjObject) -> Vec<(String, Option<String>)> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.push((group.name().to_owned(), group.mtluse().map(str::to_owned)));
+        }
+    }
+    out
+}
+
+fn mtluses(obj: &ObjObject) -> Vec<Option<String>> {
+    group_materials(obj)
+        .into_iter()
+        .map(|(_, mtl)| mtl)
+        .collect()
+}
+
+#[test]
+fn test_material_persists_across_groups() {
+    let obj = ObjObject::parse_str(SHARED_MATERIAL).unwrap();
+
+    let expected = [
+        ("first", Some("metal")),
+        ("second", Some("metal")),
+        ("default", Some("metal")),
+        ("third", Some("wood")),
+        ("fourth", Some("wood")),
+    ];
+    assert_eq!(
+        group_materials(&obj),
+        expected.map(|(name, mtl)| (name.to_owned(), mtl.map(str::to_owned)))
+    );
+    assert_eq!(obj.face_materials(), [0, 0, 0, 1, 1]);
+}
+
+#[test]
+fn test_material_change_mid_group_persists() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+g first
+usemtl red
+f 1 2 3
+usemtl blue
+f 1 2 3
+g second
+f 1 2 3
+usemtl green
+g third
+f 1 2 3
+";
+    let obj = ObjObject::parse_str(data).unwrap();
+
+    assert_eq!(
+        mtluses(&obj),
+        [Some("red"), Some("blue"), Some("green")].map(|mtl| mtl.map(str::to_owned))
+    );
+
+    // writing back keeps the same materials per face
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let reread = ObjObject::parse_str(std::str::from_utf8(&written).unwrap()).unwrap();
+    assert_eq!(reread.face_materials(), obj.face_materials());
+}
+
+#[test]
+fn test_reset_material_per_group() {
+    let options = ParseOptions::default().reset_mtl_per_group(true);
+    let obj = ObjObject::parse_bytes_with(SHARED_MATERIAL.as_bytes(), &options).unwrap();
+
+    assert_eq!(
+        mtluses(&obj),
+        [Some("metal"), None, None, Some("wood"), None].map(|mtl| mtl.map(str::to_owned))
+    );
+}
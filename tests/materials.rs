@@ -0,0 +1,765 @@
+use std::io::Cursor;
+
+use polypath::{
+    Error, Material, MaterialIdentOwned, MaterialLibrary, ObjObject, ParseOptions, ParseWarning,
+    TextureMap, TextureOptions, UnresolvedMaterial,
+};
+
+// first use order: (b, metal), (a, wood), (b, None), (a, glass)
+const MATERIALS: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+
+o first
+mtllib b.mtl
+g g1
+usemtl metal
+f 1 2 3
+g g2
+f 1 2 3
+
+o second
+mtllib a.mtl
+g g3
+usemtl wood
+f 1 2 3
+f 1 3 2
+g g4
+usemtl glass
+f 1 2 3
+";
+
+/// Parses [`MATERIALS`] with materials and material libraries scoped to their group and object.
+fn parse_materials() -> ObjObject {
+    let options = ParseOptions::default()
+        .reset_mtl_per_group(true)
+        .reset_mtllib_per_object(true);
+
+    ObjObject::parse_with(Cursor::new(MATERIALS), &options).unwrap()
+}
+
+fn ident(mtllib: Option<&str>, mtluse: Option<&str>) -> MaterialIdentOwned {
+    MaterialIdentOwned {
+        mtllib: mtllib.map(String::from),
+        mtluse: mtluse.map(String::from),
+    }
+}
+
+#[test]
+fn test_material_table_order() {
+    let obj = parse_materials();
+
+    let table = obj.material_table();
+    assert_eq!(
+        table,
+        [
+            ident(Some("a.mtl"), Some("glass")),
+            ident(Some("a.mtl"), Some("wood")),
+            ident(Some("b.mtl"), Some("metal")),
+            ident(Some("b.mtl"), None),
+        ]
+    );
+
+    let (_, materials) = obj.vertices();
+    let materials = materials
+        .into_iter()
+        .map(MaterialIdentOwned::from)
+        .collect::<Vec<_>>();
+    assert_eq!(materials, table);
+
+    assert_eq!(obj.face_materials(), [2, 3, 1, 1, 0]);
+}
+
+#[test]
+fn test_material_ids_agree() {
+    for obj in [
+        parse_materials(),
+        ObjObject::parse(Cursor::new(MATERIALS)).unwrap(),
+        ObjObject::read_from_file("./meshes/cubes.obj").unwrap(),
+    ] {
+        let table = obj.material_table();
+        let face_materials = obj.face_materials();
+
+        let (vertices, materials) = obj.vertices();
+        assert_eq!(materials.len(), table.len());
+        assert_eq!(vertices.len(), face_materials.len() * 3);
+
+        for (face, material) in vertices.chunks_exact(3).zip(&face_materials) {
+            for v in face {
+                assert_eq!(v.material_index, *material);
+            }
+        }
+
+        let (indicies, unique, materials) = obj.vertices_indexed();
+        assert_eq!(materials.len(), table.len());
+
+        for (face, material) in indicies.chunks_exact(3).zip(&face_materials) {
+            for i in face {
+                assert_eq!(unique[*i].material_index, *material);
+            }
+        }
+    }
+}
+
+const LIBRARY: &str = "
+# two materials
+newmtl red
+Ka 0.1
+Kd 1.0 0.0 0.0
+Ks 0.5 0.5 0.5
+Ns 96.0
+Ni 1.45
+d 0.75
+illum 2
+map_Kd textures/red.png
+map_Ks -s 2 2 1 red_spec.png
+Ke 0 0 0
+
+newmtl glass
+Tr 0.9
+";
+
+#[test]
+fn test_parse_library() {
+    let mtl = MaterialLibrary::parse(Cursor::new(LIBRARY)).unwrap();
+    assert_eq!(mtl.materials().len(), 2);
+
+    let red = mtl.material("red").unwrap();
+    assert_eq!(red.ambient, Some([0.1; 3]));
+    assert_eq!(red.diffuse, Some([1.0, 0.0, 0.0]));
+    assert_eq!(red.specular, Some([0.5; 3]));
+    assert_eq!(red.shininess, Some(96.0));
+    assert_eq!(red.optical_density, Some(1.45));
+    assert_eq!(red.dissolve, Some(0.75));
+    assert_eq!(red.illumination_model, Some(2));
+    let diffuse_map = red.diffuse_map.as_ref().unwrap();
+    assert_eq!(diffuse_map.path, "textures/red.png");
+    assert_eq!(diffuse_map.options, TextureOptions::default());
+    let specular_map = red.specular_map.as_ref().unwrap();
+    assert_eq!(specular_map.path, "red_spec.png");
+    assert_eq!(specular_map.options.scale, Some([2.0, 2.0, 1.0]));
+
+    let glass = mtl.material("glass").unwrap();
+    assert!((glass.dissolve.unwrap() - 0.1).abs() < 1e-6);
+    assert_eq!(glass.diffuse, None);
+
+    assert!(mtl.material("blue").is_none());
+}
+
+// as exported by Blender
+const BLENDER: &str = "# Blender 4.2.0 MTL File: 'scene.blend'
+# www.blender.org
+
+newmtl Glass
+Ns 900.000000
+Ka 1.000000 1.000000 1.000000
+Kd 0.800000 0.900000 1.000000
+Ks 0.500000 0.500000 0.500000
+Ke 0.000000 0.000000 0.000000
+Ni 1.450000
+d 0.250000
+illum 4
+
+newmtl Lamp
+Ns 250.000000
+Ka 1.000000 1.000000 1.000000
+Kd 0.800000 0.800000 0.800000
+Ks 0.500000 0.500000 0.500000
+Ke 1.000000 0.700000 0.300000
+Ni 1.000000
+d 1.000000
+illum 2
+map_Kd textures/lamp_albedo.png
+map_Bump -bm 1.000000 textures/lamp_normal.png
+
+newmtl Metal
+Ns 360.000000
+Ka 1.000000 1.000000 1.000000
+Kd 0.600000 0.600000 0.650000
+Ks 0.900000 0.900000 0.900000
+Ke 0.000000 0.000000 0.000000
+Ni 1.500000
+d 1.000000
+illum 3
+Pm 1.000000
+";
+
+#[test]
+fn test_parse_blender_library() {
+    let mtl = MaterialLibrary::parse(Cursor::new(BLENDER)).unwrap();
+    let names = mtl
+        .materials()
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Glass", "Lamp", "Metal"]);
+
+    let glass = mtl.material("Glass").unwrap();
+    assert_eq!(glass.shininess, Some(900.0));
+    assert_eq!(glass.diffuse, Some([0.8, 0.9, 1.0]));
+    assert_eq!(glass.emissive, Some([0.0; 3]));
+    assert_eq!(glass.optical_density, Some(1.45));
+    assert_eq!(glass.dissolve, Some(0.25));
+    assert_eq!(glass.illumination_model, Some(4));
+    assert_eq!(glass.diffuse_map, None);
+
+    let lamp = mtl.material("Lamp").unwrap();
+    assert_eq!(lamp.emissive, Some([1.0, 0.7, 0.3]));
+    assert_eq!(lamp.dissolve, Some(1.0));
+    assert_eq!(
+        lamp.diffuse_map.as_ref().map(|map| map.path.as_str()),
+        Some("textures/lamp_albedo.png")
+    );
+    let bump_map = lamp.bump_map.as_ref().unwrap();
+    assert_eq!(bump_map.path, "textures/lamp_normal.png");
+    assert_eq!(bump_map.options.bump_multiplier, Some(1.0));
+
+    let metal = mtl.material("Metal").unwrap();
+    assert_eq!(metal.specular, Some([0.9; 3]));
+    assert_eq!(metal.illumination_model, Some(3));
+    assert_eq!(metal.metallic, Some(1.0));
+
+    assert!(mtl.ignored_lines().is_empty());
+}
+
+#[test]
+fn test_parse_pbr_library() {
+    let data = "
+newmtl classic
+Kd 0.5 0.5 0.5
+map_Kd diffuse.png
+
+newmtl pbr
+Kd 0.8 0.2 0.1
+Ks 0.5 0.5 0.5
+Ke 0.1 0.1 0.1
+d 0.9
+illum 2
+Pr 0.35
+Pm 0.8
+Ps 0.1
+Pc 0.25
+Pcr 0.05
+aniso 0.5
+anisor 0.75
+sharpness 60
+map_Kd -s 2 2 textures/base color.png
+map_Ke emissive.png
+map_Pr roughness.png
+map_Pm -imfchan b metallic.png
+map_Ps sheen.png
+norm -bm 0.5 normal.png
+";
+    let mtl = MaterialLibrary::parse(Cursor::new(data)).unwrap();
+
+    // classic materials are unaffected
+    let classic = mtl.material("classic").unwrap();
+    assert_eq!(
+        *classic,
+        Material {
+            name: String::from("classic"),
+            diffuse: Some([0.5; 3]),
+            diffuse_map: Some(TextureMap {
+                path: String::from("diffuse.png"),
+                options: TextureOptions::default(),
+            }),
+            ..Default::default()
+        }
+    );
+
+    let pbr = mtl.material("pbr").unwrap();
+    assert_eq!(pbr.diffuse, Some([0.8, 0.2, 0.1]));
+    assert_eq!(pbr.specular, Some([0.5; 3]));
+    assert_eq!(pbr.emissive, Some([0.1; 3]));
+    assert_eq!(pbr.dissolve, Some(0.9));
+    assert_eq!(pbr.illumination_model, Some(2));
+    assert_eq!(pbr.roughness, Some(0.35));
+    assert_eq!(pbr.metallic, Some(0.8));
+    assert_eq!(pbr.sheen, Some(0.1));
+    assert_eq!(pbr.clearcoat_thickness, Some(0.25));
+    assert_eq!(pbr.clearcoat_roughness, Some(0.05));
+    assert_eq!(pbr.anisotropy, Some(0.5));
+    assert_eq!(pbr.anisotropy_rotation, Some(0.75));
+
+    let path = |map: &Option<TextureMap>| map.as_ref().map(|map| map.path.clone());
+    assert_eq!(
+        path(&pbr.diffuse_map).as_deref(),
+        Some("textures/base color.png")
+    );
+    assert_eq!(path(&pbr.emissive_map).as_deref(), Some("emissive.png"));
+    assert_eq!(path(&pbr.roughness_map).as_deref(), Some("roughness.png"));
+    assert_eq!(path(&pbr.metallic_map).as_deref(), Some("metallic.png"));
+    assert_eq!(path(&pbr.sheen_map).as_deref(), Some("sheen.png"));
+    assert_eq!(path(&pbr.normal_map).as_deref(), Some("normal.png"));
+    assert_eq!(
+        pbr.metallic_map.as_ref().unwrap().options.channel,
+        Some('b')
+    );
+    assert_eq!(
+        pbr.normal_map.as_ref().unwrap().options.bump_multiplier,
+        Some(0.5)
+    );
+
+    // unsupported statements are collected, not fatal
+    assert_eq!(
+        mtl.ignored_lines(),
+        [ParseWarning::IgnoredLine {
+            line: 19,
+            data: String::from("sharpness 60"),
+        }]
+    );
+}
+
+#[test]
+fn test_parse_transparency() {
+    let data = "
+newmtl tr_only
+Tr 0.25
+Tf 0.5 1.0 0.5
+
+newmtl d_first
+d 0.9
+Tr 0.9
+
+newmtl tr_first
+Tr 0.9
+d 0.9
+";
+    let mtl = MaterialLibrary::parse(Cursor::new(data)).unwrap();
+
+    let tr_only = mtl.material("tr_only").unwrap();
+    assert_eq!(tr_only.dissolve, Some(0.75));
+    assert_eq!(tr_only.transparency, Some(0.25));
+    assert_eq!(tr_only.transmission_filter, Some([0.5, 1.0, 0.5]));
+
+    // the opacity (d) takes precedence, no matter the order
+    for name in ["d_first", "tr_first"] {
+        let material = mtl.material(name).unwrap();
+        assert_eq!(material.dissolve, Some(0.9));
+        assert_eq!(material.transparency, Some(0.9));
+    }
+    assert!(mtl.ignored_lines().is_empty());
+}
+
+#[test]
+fn test_parse_texture_maps() {
+    let data = "
+newmtl textured
+map_Kd -s 2 2 2 textures/my diffuse.png
+map_Ka -o 0.5 -clamp on ambient.png
+map_Ks -mm 0.1 -blendu off -blendv on specular map.png
+map_Ns -t -0.5 0.25 -imfchan r shininess.png
+map_d -s 2 -o -1 -1 alpha.png
+bump -bm 0.5 -texres 512 bump.png
+disp -boost 1.5 -cc off disp.png
+decal decal.png
+refl -type sphere C:\\Textures\\sky.png
+";
+    let mtl = MaterialLibrary::parse(Cursor::new(data)).unwrap();
+    let material = mtl.material("textured").unwrap();
+
+    assert_eq!(
+        material.diffuse_map,
+        Some(TextureMap {
+            path: String::from("textures/my diffuse.png"),
+            options: TextureOptions {
+                scale: Some([2.0; 3]),
+                ..Default::default()
+            },
+        })
+    );
+
+    let ambient_map = material.ambient_map.as_ref().unwrap();
+    assert_eq!(ambient_map.path, "ambient.png");
+    assert_eq!(ambient_map.options.offset, Some([0.5, 0.0, 0.0]));
+    assert_eq!(ambient_map.options.clamp, Some(true));
+
+    let specular_map = material.specular_map.as_ref().unwrap();
+    assert_eq!(specular_map.path, "specular map.png");
+    assert_eq!(specular_map.options.base_gain, Some([0.1, 1.0]));
+    assert_eq!(specular_map.options.blend_u, Some(false));
+    assert_eq!(specular_map.options.blend_v, Some(true));
+
+    let shininess_map = material.shininess_map.as_ref().unwrap();
+    assert_eq!(shininess_map.path, "shininess.png");
+    assert_eq!(shininess_map.options.turbulence, Some([-0.5, 0.25, 0.0]));
+    assert_eq!(shininess_map.options.channel, Some('r'));
+
+    let dissolve_map = material.dissolve_map.as_ref().unwrap();
+    assert_eq!(dissolve_map.path, "alpha.png");
+    assert_eq!(dissolve_map.options.scale, Some([2.0, 1.0, 1.0]));
+    assert_eq!(dissolve_map.options.offset, Some([-1.0, -1.0, 0.0]));
+
+    let bump_map = material.bump_map.as_ref().unwrap();
+    assert_eq!(bump_map.path, "bump.png");
+    assert_eq!(bump_map.options.bump_multiplier, Some(0.5));
+    assert_eq!(bump_map.options.resolution, Some(512.0));
+
+    let displacement_map = material.displacement_map.as_ref().unwrap();
+    assert_eq!(displacement_map.options.boost, Some(1.5));
+    assert_eq!(displacement_map.options.color_correction, Some(false));
+
+    assert_eq!(material.decal_map.as_ref().unwrap().path, "decal.png");
+
+    let reflection_map = material.reflection_map.as_ref().unwrap();
+    assert_eq!(reflection_map.path, "C:\\Textures\\sky.png");
+    assert_eq!(reflection_map.options.kind.as_deref(), Some("sphere"));
+}
+
+#[test]
+fn test_parse_texture_map_errors() {
+    for (data, expected) in [
+        ("map_Kd -s 2 2 2", "Unexpected end-of-line"),
+        ("map_Kd -s", "Unexpected end-of-line"),
+        ("map_Kd -s x a.png", "Invalid float"),
+        (
+            "map_Kd -clamp maybe a.png",
+            "Invalid texture map option: [maybe]",
+        ),
+        (
+            "map_Kd -imfchan x a.png",
+            "Invalid texture map option: [-imfchan x]",
+        ),
+        (
+            "map_Kd -unknown a.png",
+            "Invalid texture map option: [-unknown]",
+        ),
+    ] {
+        let data = format!("newmtl a\n{data}\n");
+        let Err(Error::AtLine { line, source }) = MaterialLibrary::parse(Cursor::new(&data)) else {
+            panic!("expected an error for [{data}]");
+        };
+        assert_eq!(line, 2);
+        assert_eq!(source.to_string().trim_end(), expected, "{data}");
+    }
+}
+
+#[test]
+fn test_parse_trailing_comments() {
+    let data = "newmtl red # the name ends before the comment
+illum 2 # highlight on
+Kd 1 0 0 # red
+Ns 10#shiny
+map_Kd red.png # diffuse
+";
+    let library = MaterialLibrary::parse(Cursor::new(data)).unwrap();
+    let material = library.material("red").unwrap();
+
+    assert_eq!(material.illumination_model, Some(2));
+    assert_eq!(material.diffuse, Some([1.0, 0.0, 0.0]));
+    assert_eq!(material.shininess, Some(10.0));
+    assert_eq!(material.diffuse_map.as_ref().unwrap().path, "red.png");
+    assert!(library.ignored_lines().is_empty());
+}
+
+#[test]
+fn test_parse_library_errors() {
+    let err = MaterialLibrary::parse(Cursor::new("Kd 1 0 0\n")).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::AtLine { line: 1, source } if matches!(*source, Error::MissingNewMtl)
+    ));
+
+    let err = MaterialLibrary::parse(Cursor::new("newmtl a\nKd 1 0\n")).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::AtLine { line: 2, source } if matches!(*source, Error::UnexpectedEoL)
+    ));
+}
+
+#[test]
+fn test_load_materials() {
+    let dir = std::env::temp_dir().join(format!("polypath_mtl_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.mtl"), "newmtl a\nKd 1 0 0\n").unwrap();
+    std::fs::write(dir.join("b.mtl"), "newmtl b\nKd 0 1 0\n").unwrap();
+
+    let obj = ObjObject::parse_str(
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\no first\nmtllib a.mtl\nusemtl a\nf 1 2 3\no second\nmtllib a.mtl b.mtl\nusemtl b\nf 1 2 3\n",
+    )
+    .unwrap();
+    let libraries = obj.load_materials(&dir);
+    let missing = obj.load_materials(&dir.join("missing"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let libraries = libraries.unwrap();
+    assert_eq!(libraries.len(), 2);
+    assert_eq!(libraries[0].materials()[0].name, "a");
+    assert_eq!(
+        libraries[1].material("b").unwrap().diffuse,
+        Some([0.0, 1.0, 0.0])
+    );
+
+    let Err(Error::File { path, source }) = missing else {
+        panic!("missing file should be reported");
+    };
+    assert!(path.ends_with("missing/a.mtl"));
+    assert!(matches!(*source, Error::Io(_)));
+}
+
+#[test]
+fn test_multiple_mtllibs() {
+    let obj = ObjObject::parse_str(
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nmtllib a.mtl  b.mtl\nmtllib c.mtl a.mtl\nusemtl red\nf 1 2 3\no second\nmtllib b.mtl\nusemtl red\nf 1 2 3\n",
+    )
+    .unwrap();
+
+    let objects = obj.objects_iter().collect::<Vec<_>>();
+    assert_eq!(objects[0].mtllibs(), ["a.mtl", "b.mtl", "c.mtl"]);
+    assert_eq!(objects[0].mtllib(), Some("a.mtl"));
+    // the second object also references the libraries of the first one
+    assert_eq!(objects[1].mtllibs(), ["a.mtl", "b.mtl", "c.mtl"]);
+
+    // a material use is identified by the first material lib of its object
+    let table = obj.material_table();
+    assert_eq!(table.len(), 1);
+    assert_eq!(table[0].mtllib.as_deref(), Some("a.mtl"));
+    assert_eq!(table[0].mtluse.as_deref(), Some("red"));
+
+    // all libraries are written as a single statement
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(written.contains("mtllib a.mtl b.mtl c.mtl\n"));
+
+    let reread = ObjObject::parse_str(&written).unwrap();
+    assert_eq!(
+        reread.objects_iter().next().unwrap().mtllibs(),
+        ["a.mtl", "b.mtl", "c.mtl"]
+    );
+}
+
+#[test]
+fn test_mtllib_file_scoped() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+mtllib x.mtl
+o a
+usemtl red
+f 1 2 3
+o b
+f 1 2 3
+# belongs to b, but stays in effect for c
+mtllib y.mtl
+o c
+f 1 2 3
+";
+
+    let obj = ObjObject::parse_str(data).unwrap();
+    let mtllibs = obj
+        .objects_iter()
+        .map(|o| (o.name().to_owned(), o.mtllibs().to_vec()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        mtllibs,
+        [
+            (String::from("a"), vec![String::from("x.mtl")]),
+            (
+                String::from("b"),
+                vec![String::from("x.mtl"), String::from("y.mtl")]
+            ),
+            (
+                String::from("c"),
+                vec![String::from("x.mtl"), String::from("y.mtl")]
+            ),
+        ]
+    );
+    assert!(obj.objects_iter().all(|o| o.mtllib() == Some("x.mtl")));
+
+    // writing back keeps the libraries of every object
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let reread = ObjObject::parse_str(std::str::from_utf8(&written).unwrap()).unwrap();
+    assert_eq!(reread.material_table(), obj.material_table());
+    assert!(
+        reread
+            .objects_iter()
+            .zip(obj.objects_iter())
+            .all(|(a, b)| a.mtllibs() == b.mtllibs())
+    );
+}
+
+#[test]
+fn test_mtllib_between_objects() {
+    // the implicit object contains a face, before the first library is referenced
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+f 1 2 3
+mtllib x.mtl
+o a
+f 1 2 3
+";
+
+    let options = ParseOptions::default().reset_mtllib_per_object(true);
+    for (options, expected) in [(ParseOptions::default(), Some("x.mtl")), (options, None)] {
+        let obj = ObjObject::parse_bytes_with(data.as_bytes(), &options).unwrap();
+        let objects = obj.objects_iter().collect::<Vec<_>>();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].mtllib(), Some("x.mtl"));
+        assert_eq!(objects[1].name(), "a");
+        assert_eq!(objects[1].mtllib(), expected);
+    }
+}
+
+#[test]
+fn test_read_from_file_with_materials() {
+    let dir = std::env::temp_dir().join(format!("polypath_with_mtl_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("textures")).unwrap();
+
+    // the cubes, referencing a sibling library and one that was never shipped
+    let cubes = std::fs::read_to_string("./meshes/cubes.obj").unwrap();
+    std::fs::write(
+        dir.join("cubes.obj"),
+        format!("mtllib cubes.mtl missing.mtl\nusemtl red\n{cubes}"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("cubes.mtl"),
+        "newmtl red\nKd 1 0 0\nmap_Kd textures/red paint.png\n",
+    )
+    .unwrap();
+
+    let path = dir.join("cubes.obj");
+    let res = ObjObject::read_from_file_with_materials(&path, &ParseOptions::default());
+    let strict = ObjObject::read_from_file_with_materials(
+        &path,
+        &ParseOptions::default().require_mtllibs(true),
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let (obj, materials) = res.unwrap();
+    assert_eq!(
+        obj.face_count(),
+        ObjObject::read_from_file("./meshes/cubes.obj")
+            .unwrap()
+            .face_count()
+    );
+    assert_eq!(materials.libraries().len(), 1);
+    assert_eq!(materials.missing(), [dir.join("missing.mtl")]);
+
+    let (library, red) = materials.material("red").unwrap();
+    assert_eq!(library.path(), Some(dir.join("cubes.mtl").as_path()));
+    assert_eq!(red.diffuse, Some([1.0, 0.0, 0.0]));
+
+    let texture = library
+        .texture_path(red.diffuse_map.as_ref().unwrap())
+        .unwrap();
+    assert!(texture.is_absolute());
+    assert_eq!(
+        texture,
+        std::path::absolute(dir.join("textures/red paint.png")).unwrap()
+    );
+
+    assert!(materials.material("blue").is_none());
+
+    let Err(Error::File { path, source }) = strict else {
+        panic!("a missing library should be an error, got {strict:?}");
+    };
+    assert!(path.ends_with("missing.mtl"));
+    assert!(matches!(*source, Error::Io(_)));
+}
+
+#[test]
+fn test_resolve_materials() {
+    const SCENE: &str = "mtllib scene.mtl
+v 0 0 0
+v 1 0 0
+v 0 1 0
+o first
+usemtl red
+f 1 2 3
+usemtl Blue
+f 1 2 3
+o second
+mtllib gone.mtl
+usemtl ghost
+f 1 2 3
+o third
+usemtl unknown
+f 1 2 3
+";
+
+    let dir = std::env::temp_dir().join(format!("polypath_resolve_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("scene.obj"), SCENE).unwrap();
+    std::fs::write(
+        dir.join("resolved.obj"),
+        SCENE.split("o second").next().unwrap(),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("scene.mtl"),
+        "newmtl red\nKd 1 0 0\nnewmtl blue\nKd 0 0 1\n",
+    )
+    .unwrap();
+
+    // every object only references its own libraries, so the ident of `ghost` refers to the missing library
+    let options = ParseOptions::default().reset_mtllib_per_object(true);
+    let scene = ObjObject::read_from_file_with_materials(dir.join("scene.obj"), &options);
+    let resolved = ObjObject::read_from_file_with_materials(dir.join("resolved.obj"), &options);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let (obj, materials) = scene.unwrap();
+    assert_eq!(materials.missing(), [dir.join("gone.mtl")]);
+
+    let (_, idents) = obj.vertices();
+    let resolve = |name: &str| {
+        let ident = idents
+            .iter()
+            .find(|ident| ident.mtluse == Some(name))
+            .unwrap();
+        materials.resolve(ident).map(|material| material.diffuse)
+    };
+    assert_eq!(resolve("red"), Some(Some([1.0, 0.0, 0.0])));
+    assert_eq!(resolve("Blue"), None);
+    assert_eq!(resolve("ghost"), None);
+
+    let Err(Error::UnresolvedMaterials(unresolved)) = obj.vertices_with_materials(&materials)
+    else {
+        panic!("expected unresolved materials");
+    };
+    assert_eq!(
+        unresolved,
+        [
+            UnresolvedMaterial {
+                mtllib: Some(String::from("gone.mtl")),
+                mtluse: String::from("ghost"),
+                missing_library: true,
+            },
+            UnresolvedMaterial {
+                mtllib: Some(String::from("scene.mtl")),
+                mtluse: String::from("Blue"),
+                missing_library: false,
+            },
+            UnresolvedMaterial {
+                mtllib: None,
+                mtluse: String::from("unknown"),
+                missing_library: false,
+            },
+        ]
+    );
+    let message = Error::UnresolvedMaterials(unresolved).to_string();
+    assert!(message.contains("material [ghost] not found, library [gone.mtl] is missing"));
+    assert!(message.contains("material [Blue] not found in library [scene.mtl]"));
+    assert!(message.contains("material [unknown] not found in any library"));
+
+    // names differing in case only match, if requested
+    let (obj, materials) = resolved.unwrap();
+    assert!(obj.vertices_with_materials(&materials).is_err());
+
+    let materials = materials.case_insensitive(true);
+    let (vertices, resolved) = obj.vertices_with_materials(&materials).unwrap();
+    assert_eq!(vertices.len(), 6);
+
+    let diffuse = vertices
+        .iter()
+        .map(|vertex| resolved[vertex.material_index].unwrap().diffuse.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(diffuse[..3], [[1.0, 0.0, 0.0]; 3]);
+    assert_eq!(diffuse[3..], [[0.0, 0.0, 1.0]; 3]);
+}
@@ -0,0 +1,147 @@
+use polypath::{MaterialConflict, ObjObject, ParseOptions};
+
+const CONTINUED: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+o scene
+g body
+usemtl red
+f 1 2 3
+g wheels
+usemtl black
+f 1 2 4
+l 1 2
+g body
+usemtl red
+f 2 3 4
+p 1
+g wheels
+f 1 3 4 2
+g body
+usemtl blue
+f 1 2 3
+";
+
+fn groups(obj: &ObjObject) -> Vec<(String, usize, Vec<[u32; 3]>)> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            let faces = group
+                .faces_iter()
+                .map(|face| {
+                    face.vert_positions
+                        .map(|p| p[0].to_bits() + 2 * p[1].to_bits())
+                })
+                .collect();
+            out.push((group.name().to_owned(), group.face_count(), faces));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_merge_named_groups() {
+    let mut obj = ObjObject::parse_str(CONTINUED).unwrap();
+    assert_eq!(obj.group_count(), 5);
+
+    let before = groups(&obj);
+    let conflicts = obj.merge_named_groups();
+    let after = groups(&obj);
+
+    assert_eq!(obj.group_count(), 2);
+    assert_eq!(obj.face_count(), 6);
+    assert_eq!(after[0].0, "body");
+    assert_eq!(after[1].0, "wheels");
+
+    // every face of a name is reachable exactly once, in file order
+    for (name, count, faces) in &after {
+        let expected = before
+            .iter()
+            .filter(|(n, ..)| n == name)
+            .flat_map(|(_, _, faces)| faces.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(faces, &expected);
+        assert_eq!(*count, expected.len());
+    }
+
+    // the continued wheels inherit red from the body part before them, the last body part switches to blue
+    let conflict = |group: &str, mtluse: &str, conflicting: &str| MaterialConflict {
+        object: String::from("scene"),
+        group: String::from(group),
+        mtluse: Some(String::from(mtluse)),
+        conflicting: Some(String::from(conflicting)),
+    };
+    assert_eq!(
+        conflicts,
+        [
+            conflict("body", "red", "blue"),
+            conflict("wheels", "black", "red")
+        ]
+    );
+
+    let object = obj.objects_iter().next().unwrap();
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(
+        groups[0].material_ranges().collect::<Vec<_>>(),
+        [(0..2, Some("red")), (2..3, Some("blue"))]
+    );
+    assert_eq!(
+        groups[1].material_ranges().collect::<Vec<_>>(),
+        [(0..1, Some("black")), (1..3, Some("red"))]
+    );
+    assert_eq!(groups[1].lines_iter().count(), 1);
+    assert_eq!(groups[0].points_iter().count(), 1);
+
+    // merging again does not change anything
+    assert!(obj.merge_named_groups().is_empty());
+    assert_eq!(self::groups(&obj), after);
+}
+
+#[test]
+fn test_merge_keeps_polygons_and_materials() {
+    let options = ParseOptions::default().keep_polygons(true);
+    let mut merged = ObjObject::parse_bytes_with(CONTINUED.as_bytes(), &options).unwrap();
+    let face_materials = merged.face_materials();
+
+    merged.merge_named_groups();
+
+    // materials move with their faces
+    let order = [0, 2, 5, 1, 3, 4];
+    assert_eq!(
+        merged.face_materials(),
+        order.map(|i| face_materials[i]).to_vec()
+    );
+
+    let polygons = |obj: &ObjObject| {
+        let mut out = Vec::new();
+        for object in obj.objects_iter() {
+            for group in object.group_iter() {
+                out.extend(
+                    group
+                        .polygons_iter()
+                        .map(|p| (group.name().to_owned(), p.corner_count())),
+                );
+            }
+        }
+        out
+    };
+    assert_eq!(
+        polygons(&merged),
+        [
+            (String::from("body"), 3),
+            (String::from("body"), 3),
+            (String::from("body"), 3),
+            (String::from("wheels"), 3),
+            (String::from("wheels"), 4),
+        ]
+    );
+
+    // the merged groups are written as one group each
+    let mut written = Vec::new();
+    merged.write_to_writer(&mut written).unwrap();
+    let reread = ObjObject::parse_str(std::str::from_utf8(&written).unwrap()).unwrap();
+    assert_eq!(reread.group_count(), 2);
+    assert_eq!(reread.face_materials(), merged.face_materials());
+}
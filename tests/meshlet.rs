@@ -0,0 +1,376 @@
+use polypath::{ObjObject, VertexTextureData, meshlet};
+
+fn indexed_mesh(path: &str) -> (Vec<u32>, Vec<VertexTextureData>) {
+    let obj = ObjObject::read_from_file(path).unwrap();
+    let (indicies, vertices, _) = obj.vertices_indexed();
+
+    let indicies = indicies
+        .into_iter()
+        .map(|i| u32::try_from(i).unwrap())
+        .collect();
+
+    (indicies, vertices)
+}
+
+fn check_face_ids<const V: usize, const T: usize>(
+    indices: &[u32],
+    vertices: &[VertexTextureData],
+    cone_threshold: f32,
+) {
+    let (meshlets, face_ids) =
+        meshlet::build_meshlets_with_face_ids::<V, T, _>(indices, vertices, cone_threshold);
+    assert_eq!(meshlets.len(), face_ids.len());
+
+    let mut seen = vec![0u32; indices.len() / 3];
+
+    for (meshlet, ids) in meshlets.iter().zip(&face_ids) {
+        let triangles = &meshlet.triangles[..meshlet.triangle_count as usize];
+
+        for (triangle, id) in triangles.iter().zip(ids) {
+            seen[*id as usize] += 1;
+
+            // the meshlet triangle references the same vertices as the source face
+            let source = &indices[*id as usize * 3..*id as usize * 3 + 3];
+            let resolved = triangle.map(|i| meshlet.vertices[i as usize]);
+            assert_eq!(resolved, source);
+        }
+    }
+
+    // every source face ends up in exactly one meshlet
+    assert!(seen.iter().all(|count| *count == 1));
+
+    // the meshlets themselves are unaffected by recording face ids
+    let plain = meshlet::build_meshlets::<V, T, _>(indices, vertices, cone_threshold);
+    assert_eq!(plain.len(), meshlets.len());
+    for (a, b) in plain.iter().zip(&meshlets) {
+        assert_eq!(a.triangles, b.triangles);
+        assert_eq!(a.vertices, b.vertices);
+    }
+}
+
+#[test]
+fn test_face_ids_cheburashka() {
+    let (indices, vertices) = indexed_mesh("./meshes/cheburashka.obj");
+
+    for cone_threshold in [0.1, 0.5, 0.9] {
+        check_face_ids::<64, 124>(&indices, &vertices, cone_threshold);
+    }
+
+    check_face_ids::<32, 16>(&indices, &vertices, 0.5);
+}
+
+#[test]
+fn test_face_ids_cubes() {
+    let (indices, vertices) = indexed_mesh("./meshes/cubes.obj");
+
+    check_face_ids::<64, 124>(&indices, &vertices, 0.5);
+    check_face_ids::<8, 4>(&indices, &vertices, 0.9);
+    check_face_ids::<3, 1>(&indices, &vertices, 0.9);
+}
+
+#[test]
+fn test_face_ids_empty() {
+    let (meshlets, face_ids) =
+        meshlet::build_meshlets_with_face_ids::<64, 124, VertexTextureData>(&[], &[], 0.5);
+
+    assert!(meshlets.is_empty());
+    assert!(face_ids.is_empty());
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_glam_vertices() {
+    let (indices, vertices) = indexed_mesh("./meshes/cubes.obj");
+    let positions = vertices
+        .iter()
+        .copied()
+        .map(glam::Vec3::from)
+        .collect::<Vec<_>>();
+
+    let from_glam = meshlet::build_meshlets::<64, 124, _>(&indices, &positions, 0.5);
+    let from_obj = meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5);
+    assert_eq!(from_glam.len(), from_obj.len());
+    for (a, b) in from_glam.iter().zip(&from_obj) {
+        assert_eq!(a.vertices, b.vertices);
+        assert_eq!(a.triangles, b.triangles);
+        assert_eq!(a.cone, b.cone);
+    }
+
+    let vertex = vertices[0].vertex;
+    assert_eq!(glam::Vec3::from(vertex).to_array(), vertex.position);
+    assert_eq!(glam::Vec3A::from(vertex).to_array(), vertex.position);
+}
+
+fn check_apex<const V: usize, const T: usize>(
+    indices: &[u32],
+    vertices: &[VertexTextureData],
+    cone_threshold: f32,
+) {
+    let meshlets = meshlet::build_meshlets::<V, T, _>(indices, vertices, cone_threshold);
+
+    for meshlet in &meshlets {
+        assert_eq!(
+            meshlet.apex,
+            meshlet::compute_meshlet_cone_apex(meshlet, vertices)
+        );
+
+        let center = meshlet.bounding.center;
+        let distance = meshlet
+            .apex
+            .iter()
+            .zip(center)
+            .map(|(a, c)| (a - c) * (a - c))
+            .sum::<f32>()
+            .sqrt();
+
+        // the apex lies on the cone axis, at most radius / cos(angle) away from the center
+        let [_, _, _, sin] = meshlet.cone;
+        let cos = sin.mul_add(-sin, 1.0).sqrt().max(cone_threshold);
+        assert!(
+            distance <= meshlet.bounding.radius / cos * 1.001 + 1e-6,
+            "{distance} > {} / {cos}",
+            meshlet.bounding.radius
+        );
+    }
+}
+
+#[test]
+fn test_apex_cheburashka() {
+    let (indices, vertices) = indexed_mesh("./meshes/cheburashka.obj");
+
+    for cone_threshold in [0.1, 0.5, 0.9] {
+        check_apex::<64, 124>(&indices, &vertices, cone_threshold);
+    }
+}
+
+#[test]
+fn test_apex_flat() {
+    // a flat square, split into two triangles
+    let obj = ObjObject::parse_str("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n").unwrap();
+    let (indices, vertices, _) = obj.vertices_indexed();
+    let indices = indices
+        .into_iter()
+        .map(|i| u32::try_from(i).unwrap())
+        .collect::<Vec<_>>();
+
+    let meshlets = meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5);
+    assert_eq!(meshlets.len(), 1);
+
+    // every triangle lies in the same plane, so the apex is the center of the bounding sphere
+    let meshlet = &meshlets[0];
+    let distance = meshlet
+        .apex
+        .iter()
+        .zip(meshlet.bounding.center)
+        .map(|(a, c)| (a - c) * (a - c))
+        .sum::<f32>()
+        .sqrt();
+    assert!(distance < 1e-6, "{distance}");
+    assert!(distance <= meshlet.bounding.radius);
+}
+
+#[test]
+fn test_validate_cheburashka() {
+    let (indices, vertices) = indexed_mesh("./meshes/cheburashka.obj");
+
+    for meshlet in meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5) {
+        meshlet::validate_meshlet(&meshlet, &vertices).unwrap();
+    }
+}
+
+#[test]
+fn test_validate_violations() {
+    use polypath::{Error, bounding::Sphere, meshlet::MeshletViolation};
+
+    let obj = ObjObject::parse_str("v 0 0 0\nv 1 0 0\nv 0 2 0\nf 1 2 3\n").unwrap();
+    let (_, vertices, _) = obj.vertices_indexed();
+
+    let mut meshlet = meshlet::Meshlet::<4, 2> {
+        bounding: Sphere {
+            center: [0.0; 3],
+            radius: 1.0,
+        },
+        vertices: [0, 1, 2, u32::MAX],
+        triangles: [[0, 1, 4], [2, 2, 3]],
+        vertex_count: 4,
+        triangle_count: 2,
+        ..Default::default()
+    };
+
+    let Err(Error::InvalidMeshlet(violations)) = meshlet::validate_meshlet(&meshlet, &vertices)
+    else {
+        panic!("expected an invalid meshlet");
+    };
+
+    assert_eq!(
+        violations,
+        [
+            MeshletViolation::TriangleIndexOutOfRange {
+                triangle: 0,
+                index: 4
+            },
+            MeshletViolation::DegenerateTriangle { triangle: 1 },
+            MeshletViolation::VertexOutsideBounds {
+                vertex: 2,
+                distance: 1.0
+            },
+            MeshletViolation::VertexIndexOutOfRange {
+                vertex: 3,
+                index: u32::MAX
+            },
+        ]
+    );
+
+    meshlet.vertex_count = 5;
+    meshlet.triangle_count = 3;
+    let Err(Error::InvalidMeshlet(violations)) = meshlet::validate_meshlet(&meshlet, &vertices)
+    else {
+        panic!("expected an invalid meshlet");
+    };
+    assert_eq!(
+        violations[..2],
+        [
+            MeshletViolation::VertexCountExceeded {
+                count: 5,
+                capacity: 4
+            },
+            MeshletViolation::TriangleCountExceeded {
+                count: 3,
+                capacity: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_build_meshlets_from_obj() {
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+    let (indices, vertices) = indexed_mesh("./meshes/cheburashka.obj");
+
+    let from_obj = meshlet::build_meshlets_from_obj::<64, 124>(&obj, 0.5);
+    let plain = meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5);
+
+    assert_eq!(from_obj.len(), plain.len());
+    for (a, b) in from_obj.iter().zip(&plain) {
+        assert_eq!(a.vertices, b.vertices);
+        assert_eq!(a.triangles, b.triangles);
+        assert_eq!(a.triangle_count, b.triangle_count);
+    }
+}
+
+#[test]
+fn test_aabb_cheburashka() {
+    let (indices, vertices) = indexed_mesh("./meshes/cheburashka.obj");
+
+    for meshlet in meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5) {
+        assert_eq!(meshlet.aabb, meshlet::meshlet_aabb(&meshlet, &vertices));
+        assert!(!meshlet.aabb.is_empty());
+
+        for i in &meshlet.vertices[..meshlet.vertex_count as usize] {
+            let position = vertices[*i as usize].vertex.position;
+            assert!(meshlet.aabb.contains_point(position));
+        }
+    }
+}
+
+#[test]
+fn test_lod_tree_cheburashka() {
+    // the mesh does not share any vertices, and its faces are not ordered, so neighbouring meshlets have to be made first
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+    let (vertices, _) = obj.vertices();
+    let (indices, vertices) =
+        polypath::opt::weld_preserving_seams(&vertices, 1e-6, 180.0, Some(1.0));
+    let mut indices = indices
+        .into_iter()
+        .map(|i| u32::try_from(i).unwrap())
+        .collect::<Vec<_>>();
+    polypath::opt::optimize_forsyth(&mut indices, vertices.len());
+
+    let base = meshlet::build_meshlets::<64, 124, _>(&indices, &vertices, 0.5);
+
+    let tree = meshlet::build_meshlet_lod_tree(&base, &vertices, 3);
+    assert_eq!(tree.levels.len(), 4);
+    assert_eq!(tree.levels.len(), tree.nodes.len());
+    assert!(tree.levels.len() > 1);
+
+    // level 0 are the base meshlets
+    assert_eq!(tree.levels[0].len(), base.len());
+    for (a, b) in tree.levels[0].iter().zip(&base) {
+        assert_eq!(a.vertices, b.vertices);
+        assert_eq!(a.triangles, b.triangles);
+    }
+    assert!(
+        tree.nodes[0]
+            .iter()
+            .all(|n| n.children.is_empty() && n.lod_error == 0.0)
+    );
+
+    let triangle_count = |level: &[meshlet::Meshlet<64, 124>]| {
+        level
+            .iter()
+            .map(|m| m.triangle_count as usize)
+            .sum::<usize>()
+    };
+
+    for level in 1..tree.levels.len() {
+        let (meshlets, nodes) = (&tree.levels[level], &tree.nodes[level]);
+        assert_eq!(meshlets.len(), nodes.len());
+        // every level roughly halves the triangles
+        let (count, previous) = (
+            triangle_count(meshlets),
+            triangle_count(&tree.levels[level - 1]),
+        );
+        assert!(count * 10 < previous * 7, "{count} of {previous}");
+
+        for meshlet in meshlets {
+            meshlet::validate_meshlet(meshlet, &vertices).unwrap();
+        }
+
+        // every meshlet of the previous level is simplified in exactly one group
+        let mut parents = vec![None; tree.levels[level - 1].len()];
+        for node in nodes {
+            assert!(!node.children.is_empty());
+
+            for child in &node.children {
+                // the error never decreases towards coarser levels
+                assert!(node.lod_error >= tree.nodes[level - 1][*child].lod_error);
+
+                match parents[*child] {
+                    None => parents[*child] = Some(&node.children),
+                    Some(children) => assert_eq!(children, &node.children),
+                }
+            }
+        }
+        assert!(parents.iter().all(Option::is_some));
+    }
+}
+
+#[test]
+fn test_lod_tree_single_meshlet() {
+    let (indices, vertices) = indexed_mesh("./meshes/cubes.obj");
+    let base = meshlet::build_meshlets::<255, 255, _>(&indices[..6], &vertices, 0.1);
+    assert_eq!(base.len(), 1);
+
+    // there is nothing left to merge
+    let tree = meshlet::build_meshlet_lod_tree(&base, &vertices, 4);
+    assert_eq!(tree.levels.len(), 1);
+    assert_eq!(tree.nodes.len(), 1);
+}
+
+#[test]
+fn test_build_meshlets_degenerate() {
+    // the first two vertices are merged, so the first face references the same vertex twice
+    let obj =
+        ObjObject::parse_str("v 0 0 0\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n").unwrap();
+
+    let meshlets = meshlet::build_meshlets_from_obj::<64, 124>(&obj, 0.5);
+    assert_eq!(meshlets.len(), 1);
+    assert_eq!(meshlets[0].triangle_count, 1);
+    assert_eq!(meshlets[0].vertex_count, 3);
+
+    let (indices, vertices) = (vec![0, 0, 1, 0, 1, 2], obj.vertices_indexed().1);
+    let (meshlets, face_ids) =
+        meshlet::build_meshlets_with_face_ids::<64, 124, _>(&indices, &vertices, 0.5);
+    assert_eq!(meshlets[0].triangle_count, 1);
+    assert_eq!(face_ids[0][0], 1);
+}
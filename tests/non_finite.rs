@@ -0,0 +1,137 @@
+use polypath::{Error, Line, LineParser, ObjObject, ParseOptions, ParseWarning};
+
+const NON_FINITE: &str = "
+v 0 0 0
+v 1 0 0
+v nan 0 0
+v 0 1 0
+vn inf 0 0
+vn 0 0 1
+vt 0 -inf
+f 1//2 2//2 4//2
+f 1//1 2//1 3//1
+";
+
+fn non_finite_line(data: &str) -> Option<(String, usize)> {
+    match ObjObject::parse_with(std::io::Cursor::new(data), &ParseOptions::default()) {
+        Err(Error::NonFiniteValue { statement, line }) => Some((statement, line)),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_reject_non_finite() {
+    assert_eq!(
+        non_finite_line("v 0 0 0\nv nan 0 0\n"),
+        Some(("v nan 0 0".to_string(), 2))
+    );
+    assert_eq!(
+        non_finite_line("vn inf 0 0\n"),
+        Some(("vn inf 0 0".to_string(), 1))
+    );
+    assert_eq!(
+        non_finite_line("vt 0.5 -inf\n"),
+        Some(("vt 0.5 -inf".to_string(), 1))
+    );
+    assert_eq!(
+        non_finite_line("v 0 0 0 NaN\n"),
+        Some(("v 0 0 0 NaN".to_string(), 1))
+    );
+    assert_eq!(
+        non_finite_line("v 0 0 0 1 inf 0\n"),
+        Some(("v 0 0 0 1 inf 0".to_string(), 1))
+    );
+}
+
+#[test]
+fn test_allow_non_finite() {
+    let data = "v nan 0 0\nv 1 0 0\nv 0 1 0\nvn inf 0 0\nf 1//1 2//1 3//1\n";
+
+    // rejected by default, even without any normals
+    assert!(matches!(
+        ObjObject::parse_str("v nan 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n"),
+        Err(Error::NonFiniteValue { line: 1, .. })
+    ));
+    assert!(matches!(
+        ObjObject::parse_str(data),
+        Err(Error::NonFiniteValue { line: 1, .. })
+    ));
+
+    // but kept as they are on request
+    let options = ParseOptions::default().reject_non_finite(false);
+    for obj in [
+        ObjObject::parse_with(std::io::Cursor::new(data), &options).unwrap(),
+        ObjObject::parse_with_warnings_and_options(std::io::Cursor::new(data), &options)
+            .unwrap()
+            .obj,
+    ] {
+        let object = obj.objects_iter().next().unwrap();
+        let group = object.group_iter().next().unwrap();
+        let face = group.faces_iter().next().unwrap();
+        assert!(face.vert_positions[0][0].is_nan());
+        assert!(face.vert_normals.unwrap()[0][0].is_infinite());
+    }
+}
+
+#[test]
+fn test_replace_non_finite() {
+    let result = ObjObject::parse_with_warnings_and_options(
+        std::io::Cursor::new(NON_FINITE),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result.obj.unique_vertex_count(), 4);
+    assert_eq!(result.obj.face_count(), 2);
+    assert_eq!(
+        result.warnings,
+        [
+            ParseWarning::NonFiniteValue {
+                line: 4,
+                statement: "v nan 0 0".to_string(),
+            },
+            ParseWarning::NonFiniteValue {
+                line: 6,
+                statement: "vn inf 0 0".to_string(),
+            },
+            // the placeholder of a normal has no length
+            ParseWarning::ZeroNormal { line: 6 },
+            ParseWarning::NonFiniteValue {
+                line: 8,
+                statement: "vt 0 -inf".to_string(),
+            },
+        ]
+    );
+
+    let object = result.obj.objects_iter().next().unwrap();
+    let group = object.group_iter().next().unwrap();
+    let mut faces = group.faces_iter();
+
+    // indices after a replaced statement still reference the same vertex data
+    let face = faces.next().unwrap();
+    assert_eq!(face.vert_positions[2], [0.0, 1.0, 0.0]);
+    assert_eq!(face.vert_normals.unwrap()[0], [0.0, 0.0, 1.0]);
+
+    // and the replaced statements are kept as placeholders
+    let face = faces.next().unwrap();
+    assert_eq!(face.vert_positions[2], [0.0, 0.0, 0.0]);
+    assert_eq!(face.vert_normals.unwrap()[0], [0.0, 0.0, 0.0]);
+
+    // outside of strict mode, without collecting the warnings
+    let options = ParseOptions::default().strict(false);
+    let obj = ObjObject::parse_with(std::io::Cursor::new(NON_FINITE), &options).unwrap();
+    assert_eq!(obj.unique_vertex_count(), 4);
+}
+
+#[test]
+fn test_line_parser_non_finite() {
+    let data = "v 0 0 0\nv 0 inf 0\nv 1 0 0\n";
+    let mut parser = LineParser::new(std::io::Cursor::new(data), &ParseOptions::default());
+
+    assert!(matches!(parser.next_line(), Ok(Some(Line::Vertex(_)))));
+    assert!(matches!(
+        parser.next_line(),
+        Err(Error::NonFiniteValue { line: 2, .. })
+    ));
+    assert!(matches!(parser.next_line(), Ok(Some(Line::Vertex(_)))));
+}
@@ -0,0 +1,111 @@
+use polypath::{ObjObject, ParseOptions, bounding::build_aabb, geometry};
+
+// a large square and a small triangle far away, the square dominates the center of mass
+const SHAPES: &str = "
+v 0 0 0
+v 4 0 0
+v 4 4 0
+v 0 4 0
+v 10 0 2
+v 11 0 2
+v 10 1 2
+vn 0 0 1
+f 1//1 2//1 3//1 4//1
+f 5//1 6//1 7//1
+";
+
+fn positions(obj: &ObjObject) -> Vec<[f32; 3]> {
+    obj.vertices().0.iter().map(|v| v.vertex.position).collect()
+}
+
+fn normals(obj: &ObjObject) -> Vec<Option<[f32; 3]>> {
+    obj.vertices().0.iter().map(|v| v.vertex.normal).collect()
+}
+
+fn assert_close(a: [f32; 3], b: [f32; 3]) {
+    for i in 0..3 {
+        assert!((a[i] - b[i]).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn test_center_of_mass() {
+    let obj = ObjObject::parse_str(SHAPES).unwrap();
+
+    // square: area 16 at (2, 2, 0), triangle: area 0.5 at (10.33, 0.33, 2)
+    let expected = [
+        (16.0 * 2.0 + 0.5 * 31.0 / 3.0) / 16.5,
+        (16.0 * 2.0 + 0.5 / 3.0) / 16.5,
+        0.5 * 2.0 / 16.5,
+    ];
+    assert_close(geometry::center_of_mass(&obj), expected);
+
+    let empty = ObjObject::parse_str("v 1 1 1\n").unwrap();
+    assert_eq!(geometry::center_of_mass(&empty), [0.0; 3]);
+}
+
+#[test]
+fn test_normalize_to_unit_cube() {
+    let mut obj = ObjObject::parse_str(SHAPES).unwrap();
+    geometry::normalize_to_unit_cube(&mut obj);
+
+    let aabb = build_aabb(positions(&obj).into_iter());
+    // the longest side is 11 wide
+    assert_close(aabb.min, [-0.5, -2.0 / 11.0, -1.0 / 11.0]);
+    assert_close(aabb.max, [0.5, 2.0 / 11.0, 1.0 / 11.0]);
+
+    // a single position only gets moved
+    let mut point = ObjObject::parse_str("v 3 4 5\nf 1 1 1\n").unwrap();
+    geometry::normalize_to_unit_cube(&mut point);
+    assert_eq!(positions(&point), [[0.0; 3]; 3]);
+}
+
+#[test]
+fn test_translate_and_scale() {
+    let mut obj = ObjObject::parse_str(SHAPES).unwrap();
+
+    geometry::translate(&mut obj, [1.0, -1.0, 0.5]);
+    assert_eq!(positions(&obj)[1], [5.0, -1.0, 0.5]);
+
+    geometry::scale_uniform(&mut obj, 2.0);
+    assert_eq!(positions(&obj)[1], [10.0, -2.0, 1.0]);
+    assert!(normals(&obj).iter().all(|n| *n == Some([0.0, 0.0, 1.0])));
+
+    // mirroring flips the normals
+    geometry::scale_uniform(&mut obj, -0.5);
+    assert_eq!(positions(&obj)[1], [-5.0, 1.0, -0.5]);
+    assert!(normals(&obj).iter().all(|n| *n == Some([0.0, 0.0, -1.0])));
+}
+
+#[test]
+fn test_translate_and_scale_keep_attributes_consistent() {
+    let data = "v 0 0 0\nv 1 0 0\nv 1 1 0\nvt 0 0\nvt 1 0\nvt 1 1\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1\n";
+    let options = ParseOptions::new().keep_f64_positions(true);
+    let mut obj = ObjObject::parse_bytes_with(data.as_bytes(), &options).unwrap();
+    geometry::generate_tangents(&mut obj).unwrap();
+
+    geometry::translate(&mut obj, [1.0, -1.0, 0.5]);
+    geometry::scale_uniform(&mut obj, -2.0);
+
+    // the double precision positions are transformed as well
+    let expected = [[-2.0, 2.0, -1.0], [-4.0, 2.0, -1.0], [-4.0, 0.0, -1.0]];
+    assert_eq!(obj.positions_f64().unwrap(), expected);
+    assert_eq!(positions(&obj), expected.map(|p| p.map(|v| v as f32)));
+
+    // mirrored tangents match regenerated ones
+    let tangents = obj
+        .vertices()
+        .0
+        .iter()
+        .map(|v| v.vertex.tangent)
+        .collect::<Vec<_>>();
+    assert_eq!(tangents[0], Some([-1.0, 0.0, 0.0, -1.0]));
+    geometry::generate_tangents(&mut obj).unwrap();
+    let regenerated = obj
+        .vertices()
+        .0
+        .iter()
+        .map(|v| v.vertex.tangent)
+        .collect::<Vec<_>>();
+    assert_eq!(tangents, regenerated);
+}
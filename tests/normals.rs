@@ -0,0 +1,104 @@
+use std::io::Cursor;
+
+use polypath::{
+    ObjObject, ParseOptions,
+    geometry::{generate_flat_normals, generate_normals},
+};
+
+// a tent, folded along the shared edge 1-2
+const TENT: &str = "
+v 0 0 0
+v 0 1 0
+v 1 0 1
+v -1 0 1
+f 1 2 3
+f 2 1 4
+";
+
+fn normals(obj: &ObjObject) -> Vec<[[f32; 3]; 3]> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter().map(|face| face.vert_normals.unwrap()));
+        }
+    }
+    out
+}
+
+fn assert_close(a: [f32; 3], b: [f32; 3]) {
+    assert!(
+        a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-6),
+        "{a:?} != {b:?}"
+    );
+}
+
+#[test]
+fn test_generate_normals() {
+    let mut obj = ObjObject::parse_str(TENT).unwrap();
+    generate_normals(&mut obj);
+
+    let normals = normals(&obj);
+    let s = std::f32::consts::FRAC_1_SQRT_2;
+
+    // the shared vertices average both faces, equally weighted by their interior angles
+    assert_close(normals[0][0], [0.0, 0.0, -1.0]);
+    assert_close(normals[0][1], [0.0, 0.0, -1.0]);
+    // the others only get the normal of their own face
+    assert_close(normals[0][2], [s, 0.0, -s]);
+    assert_close(normals[1][2], [-s, 0.0, -s]);
+
+    // shared vertices share their normal
+    assert_eq!(normals[0][0], normals[1][1]);
+}
+
+#[test]
+fn test_generate_normals_angle_weighted() {
+    // the vertex at the origin has a 90° angle in the first face, and a 45° angle in the second
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\nv 1 0 1\nf 1 2 3\nf 1 4 5\n";
+    let mut obj = ObjObject::parse_str(data).unwrap();
+    generate_normals(&mut obj);
+
+    let normal = normals(&obj)[0][0];
+    // face normals are +z (weight pi/2) and +y (weight pi/4)
+    let expected = [0.0, 1.0, 2.0].map(|c: f32| c / 5.0f32.sqrt());
+    assert_close(normal, expected);
+}
+
+#[test]
+fn test_generate_flat_normals() {
+    let options = ParseOptions::new().keep_polygons(true);
+    let data = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 0 0 1\nf 1 2 3 4\nf 1 2 5\n";
+    let mut obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+    generate_flat_normals(&mut obj);
+
+    let normals = normals(&obj);
+    assert_eq!(normals.len(), 3);
+    assert_eq!(normals[0], [[0.0, 0.0, 1.0]; 3]);
+    assert_eq!(normals[1], [[0.0, 0.0, 1.0]; 3]);
+    assert_eq!(normals[2], [[0.0, -1.0, 0.0]; 3]);
+
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let polygon = group.polygons_iter().next().unwrap();
+    assert!(
+        polygon
+            .vertices()
+            .all(|vertex| vertex.normal == Some([0.0, 0.0, 1.0]))
+    );
+}
+
+#[test]
+fn test_existing_normals_kept() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 1 0 0\nf 1//1 2//1 3//1\n";
+
+    for generate in [generate_normals, generate_flat_normals] {
+        let mut obj = ObjObject::parse_str(data).unwrap();
+        generate(&mut obj);
+        assert_eq!(normals(&obj), [[[1.0, 0.0, 0.0]; 3]]);
+    }
+}
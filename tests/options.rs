@@ -0,0 +1,188 @@
+use std::io::Cursor;
+
+use polypath::{Error, ObjObject, ParseOptions, ParseWarning, QuadSplit};
+
+// unknown lines and partial colors, which only parse with non-default options
+const QUIRKY: &str = "
+v 0 0 0 1 0 0
+v 1 0 0
+v 1 1 1
+v 0 1 0
+bogus on
+usemtl a
+usemtl b
+f 1 2 3 4
+";
+
+fn first_face(obj: &ObjObject) -> polypath::Face {
+    obj.objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap()
+}
+
+#[test]
+fn test_default_options() {
+    let options = ParseOptions::new();
+    assert_eq!(options, ParseOptions::default());
+    assert!(options.strict);
+    assert!(options.keep_colors);
+    assert_eq!(options.quad_split, QuadSplit::Fixed02);
+
+    assert!(ObjObject::parse_with(Cursor::new(QUIRKY), &options).is_err());
+}
+
+#[test]
+fn test_strict() {
+    let colorless = ParseOptions::new().keep_colors(false);
+
+    let err = ObjObject::parse_with(Cursor::new(QUIRKY), &colorless).unwrap_err();
+    assert!(matches!(err, Error::AtLine { line: 6, .. }), "{err:?}");
+
+    let obj = ObjObject::parse_with(Cursor::new(QUIRKY), &colorless.strict(false)).unwrap();
+    assert_eq!(obj.face_count(), 2);
+
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    // the second material use replaces the first, which has no faces
+    assert_eq!(group.mtluse(), Some("b"));
+}
+
+#[test]
+fn test_allow_unknown_lines() {
+    let options = ParseOptions::new()
+        .keep_colors(false)
+        .allow_unknown_lines(true);
+    assert!(options.strict);
+
+    let obj = ObjObject::parse_with(Cursor::new(QUIRKY), &options).unwrap();
+    assert_eq!(obj.face_count(), 2);
+
+    let result = ObjObject::parse_with_warnings_and_options(Cursor::new(QUIRKY), &options).unwrap();
+    assert!(matches!(
+        &result.warnings[..],
+        [ParseWarning::IgnoredLine { line: 6, .. }]
+    ));
+
+    // every other issue still aborts parsing
+    let non_finite = QUIRKY.replace("v 1 1 1", "v 1 nan 1");
+    let err = ObjObject::parse_with(Cursor::new(non_finite), &options).unwrap_err();
+    assert!(
+        matches!(err, Error::NonFiniteValue { line: 4, .. }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_keep_colors() {
+    let data = "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nf 1 2 3\n";
+
+    let obj = ObjObject::parse_with(Cursor::new(data), &ParseOptions::new()).unwrap();
+    assert!(first_face(&obj).vert_colors.is_some());
+
+    let options = ParseOptions::new().keep_colors(false);
+    let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+    assert_eq!(first_face(&obj).vert_colors, None);
+
+    // colors for only some vertices are fine, if colors are not kept
+    let lenient = ParseOptions::new().keep_colors(false).strict(false);
+    assert!(ObjObject::parse_with(Cursor::new(QUIRKY), &lenient).is_ok());
+    assert!(
+        ObjObject::parse_with(Cursor::new(QUIRKY), &ParseOptions::new().strict(false)).is_err()
+    );
+}
+
+#[test]
+fn test_keep_normals_and_texture_coords() {
+    let data =
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvn 0 0 1\nf 1/1/1 2/2/1 3/-1/-1\nl 1/1 2/2\n";
+
+    let obj = ObjObject::parse_with(Cursor::new(data), &ParseOptions::new()).unwrap();
+    assert!(first_face(&obj).vert_normals.is_some());
+    assert!(first_face(&obj).vert_uv_coords.is_some());
+
+    let positions_only = ParseOptions::new()
+        .keep_normals(false)
+        .keep_texture_coords(false)
+        .keep_polygons(true);
+    let obj = ObjObject::parse_with(Cursor::new(data), &positions_only).unwrap();
+    let face = first_face(&obj);
+    assert_eq!(face.vert_normals, None);
+    assert_eq!(face.vert_uv_coords, None);
+    assert_eq!(face.vert_positions[2], [0.0, 1.0, 0.0]);
+    assert_eq!(obj.texture_coord_iter().count(), 0);
+
+    let group = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let polygon = group.polygons_iter().next().unwrap();
+    assert!(
+        polygon
+            .vertices()
+            .all(|v| v.normal.is_none() && v.texture_coord.is_none())
+    );
+    assert!(
+        group
+            .lines_iter()
+            .all(|segment| segment.vert_uv_coords.is_none())
+    );
+
+    // the same as a file without any normals and texture coords
+    let plain = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nl 1 2\n";
+    let expected = ObjObject::parse_str(plain).unwrap();
+    assert_eq!(obj.vertices(), expected.vertices());
+
+    // only skipping normals keeps the texture coords
+    let obj =
+        ObjObject::parse_with(Cursor::new(data), &ParseOptions::new().keep_normals(false)).unwrap();
+    assert_eq!(first_face(&obj).vert_normals, None);
+    assert!(first_face(&obj).vert_uv_coords.is_some());
+}
+
+#[test]
+fn test_quad_split() {
+    let options = ParseOptions::new().keep_colors(false).strict(false);
+
+    let fixed = ObjObject::parse_with(Cursor::new(QUIRKY), &options).unwrap();
+    let shortest = ObjObject::parse_with(
+        Cursor::new(QUIRKY),
+        &options.quad_split(QuadSplit::ShortestDiagonal),
+    )
+    .unwrap();
+
+    assert_eq!(first_face(&fixed).vert_positions[0], [0.0, 0.0, 0.0]);
+    assert_eq!(first_face(&shortest).vert_positions[0], [1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_read_from_file_with() {
+    let options = ParseOptions::new().keep_polygons(true);
+
+    let obj = ObjObject::read_from_file_with("./meshes/cubes.obj", &options).unwrap();
+    let default = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    assert_eq!(obj.face_count(), default.face_count());
+    let polygons = obj
+        .objects_iter()
+        .flat_map(|o| {
+            o.group_iter()
+                .map(|g| g.polygon_count())
+                .collect::<Vec<_>>()
+        })
+        .sum::<usize>();
+    assert!(polygons > 0);
+}
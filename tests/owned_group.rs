@@ -0,0 +1,77 @@
+use polypath::{ObjObject, OwnedGroup};
+
+const TWO_GROUPS: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+v 5 5 5
+vt 0 0
+vt 1 0
+vt 0 1
+vn 0 0 1
+vn 0 1 0
+g first
+usemtl red
+f 1/1/1 2/2/1 3/3/1
+g second
+usemtl blue
+s 2
+f 5//2 4//2 2//2
+";
+
+fn faces_of(group: &OwnedGroup) -> Vec<[polypath::VertexData; 3]> {
+    group.faces_iter().map(|face| face.vertices()).collect()
+}
+
+#[test]
+fn test_to_owned() {
+    let obj = ObjObject::parse_str(TWO_GROUPS).unwrap();
+
+    let object = obj.objects_iter().next().unwrap();
+    for group in object.group_iter() {
+        let owned = group.to_owned();
+
+        assert_eq!(owned.name(), group.name());
+        assert_eq!(owned.mtluse(), group.mtluse());
+        assert_eq!(owned.face_count(), group.face_count());
+
+        let expected = group
+            .faces_iter()
+            .map(|face| face.vertices())
+            .collect::<Vec<_>>();
+        assert_eq!(faces_of(&owned), expected);
+        assert_eq!(owned.as_group_ref().aabb(), group.aabb());
+    }
+}
+
+#[test]
+fn test_to_owned_remaps() {
+    let obj = ObjObject::parse_str(TWO_GROUPS).unwrap();
+    let group = obj.find_group_by_name("second").unwrap().to_owned();
+
+    // only the used positions and normals are copied, in the order they are first used
+    assert_eq!(
+        group.positions(),
+        [[5.0, 5.0, 5.0], [1.0, 1.0, 0.0], [1.0, 0.0, 0.0]]
+    );
+    assert_eq!(group.normals(), [[0.0, 1.0, 0.0]]);
+    assert!(group.texture_coords().is_empty());
+    assert_eq!(group.faces()[0].indices(), [1, 2, 3]);
+    assert_eq!(group.as_group_ref().smooth_group(), Some(2));
+}
+
+#[test]
+fn test_to_owned_outlives_obj() {
+    let (owned, face_count) = {
+        let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+        let object = obj.objects_iter().next().unwrap();
+        let group = object.group_iter().next().unwrap();
+        let owned = group.to_owned();
+        assert_eq!(owned.positions().len(), obj.referenced_vertex_count());
+        (owned, obj.face_count())
+    };
+
+    let handle = std::thread::spawn(move || owned.faces_iter().count());
+    assert_eq!(handle.join().unwrap(), face_count);
+}
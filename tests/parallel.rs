@@ -0,0 +1,53 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use common::assert_same;
+use polypath::{Error, ObjObject, ParseLimits, ParseOptions};
+
+#[test]
+fn test_parse_parallel() {
+    for mesh in [
+        "./meshes/armadillo.obj",
+        "./meshes/cheburashka.obj",
+        "./meshes/cubes.obj",
+    ] {
+        let expected = ObjObject::read_from_file(mesh).unwrap();
+
+        for n_threads in [0, 1, 4] {
+            let obj = ObjObject::parse_parallel(mesh, n_threads).unwrap();
+
+            assert_same(&obj, &expected);
+            assert_eq!(obj.material_table(), expected.material_table());
+        }
+    }
+}
+
+#[test]
+fn test_parse_parallel_missing_file() {
+    let res = ObjObject::parse_parallel("./meshes/missing.obj", 2);
+
+    let Err(Error::File { path, source }) = res else {
+        panic!("expected an error, got {res:?}");
+    };
+    assert!(path.ends_with("missing.obj"));
+    assert!(matches!(*source, Error::Io(_)));
+}
+
+#[test]
+fn test_parse_parallel_with() {
+    let options = ParseOptions::default().keep_normals(false);
+    let expected = ObjObject::read_from_file_with("./meshes/cheburashka.obj", &options).unwrap();
+    let obj = ObjObject::parse_parallel_with("./meshes/cheburashka.obj", 2, &options).unwrap();
+    assert_same(&obj, &expected);
+
+    let options = ParseOptions::default().limits(ParseLimits {
+        max_file_size: 1024,
+        ..Default::default()
+    });
+    let res = ObjObject::parse_parallel_with("./meshes/cheburashka.obj", 2, &options);
+    let Err(Error::File { source, .. }) = res else {
+        panic!("expected an error, got {res:?}");
+    };
+    assert!(matches!(*source, Error::FileTooLarge { limit: 1024, .. }));
+}
@@ -0,0 +1,138 @@
+use std::io::Cursor;
+
+use polypath::{Error, ObjObject, ParseLimits, ParseOptions};
+
+const CUBES: &str = include_str!("../meshes/cubes.obj");
+const CHEBURASHKA: &[u8] = include_bytes!("../meshes/cheburashka.obj");
+
+#[test]
+fn test_parse_str_matches_file() {
+    let from_file = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let from_str = ObjObject::parse_str(CUBES).unwrap();
+
+    assert_eq!(from_str.object_count(), from_file.object_count());
+    assert_eq!(from_str.group_count(), from_file.group_count());
+    assert_eq!(from_str.vertices().0, from_file.vertices().0);
+}
+
+#[test]
+fn test_parse_bytes_matches_file() {
+    let from_file = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+    let from_bytes = ObjObject::parse_bytes(CHEBURASHKA).unwrap();
+
+    assert_eq!(from_bytes.face_count(), from_file.face_count());
+    assert_eq!(from_bytes.vertices().0, from_file.vertices().0);
+}
+
+#[test]
+fn test_parse_str_round_trip() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\no tri\nf 1//1 2//1 3//1\n";
+
+    let obj = ObjObject::parse_str(data).unwrap();
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert_eq!(
+        face.vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    assert_eq!(face.vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+
+    // the same content as bytes parses the same
+    let bytes = ObjObject::parse_bytes(data.as_bytes()).unwrap();
+    assert_eq!(bytes.vertices().0, obj.vertices().0);
+}
+
+#[test]
+fn test_parse_str_error() {
+    assert!(ObjObject::parse_str("v 0 0\n").is_err());
+    assert!(ObjObject::parse_bytes(b"unknown\n").is_err());
+    assert!(ObjObject::parse_bytes(&[0xff, 0xfe]).is_err());
+}
+
+#[test]
+fn test_parse_bytes_invalid_utf8() {
+    let data = b"v 0 0 0\nv 1 0 0\nv 0 1 0\no caf\xe9\nf 1 2 3\n";
+
+    // rejected by default, same as when reading
+    assert!(matches!(ObjObject::parse_bytes(data), Err(Error::Io(_))));
+    assert!(ObjObject::parse(Cursor::new(data)).is_err());
+
+    let options = ParseOptions::new().lossy_utf8(true);
+    for obj in [
+        ObjObject::parse_bytes_with(data, &options).unwrap(),
+        ObjObject::parse_with(Cursor::new(data), &options).unwrap(),
+    ] {
+        assert_eq!(obj.face_count(), 1);
+        assert_eq!(obj.objects_iter().next().unwrap().name(), "caf\u{fffd}");
+    }
+}
+
+#[test]
+fn test_latin1_names() {
+    // Latin-1 encoded object and group names, and a Latin-1 comment
+    let path = "./meshes/latin1.obj";
+    assert!(ObjObject::read_from_file(path).is_err());
+
+    let options = ParseOptions::new().lossy_utf8(true);
+    let obj = ObjObject::read_from_file_with(path, &options).unwrap();
+    assert_eq!(obj.face_count(), 1);
+
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.name(), "Gr\u{fffd}\u{fffd}e");
+    assert_eq!(object.group_iter().next().unwrap().name(), "K\u{fffd}che");
+
+    // numbers are not affected by lossy decoding
+    let data = b"v 0 0 0\nv 1 0 0\nv 0 1\xb7 0\n";
+    assert!(matches!(
+        ObjObject::parse_bytes_with(data, &options),
+        Err(Error::AtLine { line: 3, .. })
+    ));
+}
+
+#[test]
+fn test_parse_bytes_lines() {
+    // CRLF line endings, continuations and no trailing newline
+    let data = "v 0 0 0\r\nv 1 \\\r\n0 0\r\nv 0 1 0\r\nf 1 2 \\\n3";
+
+    let from_bytes = ObjObject::parse_str(data).unwrap();
+    let from_reader = ObjObject::parse(Cursor::new(data)).unwrap();
+    assert_eq!(from_bytes.face_count(), 1);
+    assert_eq!(from_bytes.vertices().0, from_reader.vertices().0);
+
+    // line numbers count physical lines
+    let err = ObjObject::parse_str("v 0 \\\n0 0\nv 1 0\n").unwrap_err();
+    assert!(matches!(err, Error::AtLine { line: 3, .. }));
+}
+
+#[test]
+fn test_parse_bytes_line_too_long() {
+    let options = ParseOptions::new().limits(ParseLimits {
+        max_line_len: 16,
+        ..Default::default()
+    });
+
+    let data = b"v 0 0 0\nv 0.000000 1.000000 0.000000\n";
+    assert!(matches!(
+        ObjObject::parse_bytes_with(data, &options),
+        Err(Error::LineTooLong { line: 2, limit: 16 })
+    ));
+
+    // joined lines are limited as a whole
+    let data = b"v 0.000 \\\n0.000 \\\n0.000\n";
+    assert!(matches!(
+        ObjObject::parse_bytes_with(data, &options),
+        Err(Error::LineTooLong { line: 3, limit: 16 })
+    ));
+    assert!(matches!(
+        ObjObject::parse_with(Cursor::new(data), &options),
+        Err(Error::LineTooLong { line: 3, limit: 16 })
+    ));
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "ply")]
+
+use std::io::Cursor;
+
+use polypath::{Error, ObjObject, ParseOptions, PlyObject};
+
+const POSITIONS: [[f32; 3]; 4] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+];
+const TEXTURE_COORDS: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+const COLORS: [[u8; 3]; 4] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+
+const HEADER: &str = "element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property float s
+property float t
+property uchar red
+property uchar green
+property uchar blue
+element face 2
+property list uchar int vertex_indices
+element edge 1
+property int vertex1
+property int vertex2
+end_header
+";
+
+/// A quad and a triangle, with normals, texture coords, colors and an edge element that gets skipped.
+fn ascii() -> String {
+    let mut data = format!("ply\nformat ascii 1.0\ncomment a quad\n{HEADER}");
+    for ((position, [s, t]), [r, g, b]) in POSITIONS.iter().zip(TEXTURE_COORDS).zip(COLORS) {
+        let [x, y, z] = position;
+        data += &format!("{x} {y} {z} 0 0 1 {s} {t} {r} {g} {b}\n");
+    }
+    data += "4 0 1 2 3\n3 0 2 3\n0 2\n";
+    data
+}
+
+/// The same as [`ascii`], in a binary format.
+fn binary(big_endian: bool) -> Vec<u8> {
+    let format = if big_endian {
+        "binary_big_endian"
+    } else {
+        "binary_little_endian"
+    };
+    let mut data = format!("ply\nformat {format} 1.0\n{HEADER}").into_bytes();
+
+    let float = |data: &mut Vec<u8>, value: f32| {
+        data.extend(if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        });
+    };
+    let int = |data: &mut Vec<u8>, value: i32| {
+        data.extend(if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        });
+    };
+
+    for ((position, texture_coord), color) in POSITIONS.iter().zip(TEXTURE_COORDS).zip(COLORS) {
+        for value in position
+            .iter()
+            .chain(&[0.0, 0.0, 1.0])
+            .chain(&texture_coord)
+        {
+            float(&mut data, *value);
+        }
+        data.extend(color);
+    }
+    for face in [&[0, 1, 2, 3][..], &[0, 2, 3]] {
+        data.push(u8::try_from(face.len()).unwrap());
+        for index in face {
+            int(&mut data, *index);
+        }
+    }
+    int(&mut data, 0);
+    int(&mut data, 2);
+
+    data
+}
+
+fn assert_quad(ply: &PlyObject) {
+    assert_eq!(ply.positions, POSITIONS);
+    assert_eq!(ply.normals, [[0.0, 0.0, 1.0]; 4]);
+    assert_eq!(ply.texture_coords, TEXTURE_COORDS);
+    assert_eq!(
+        ply.colors,
+        [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0]
+        ]
+    );
+    assert_eq!(ply.faces, [vec![0, 1, 2, 3], vec![0, 2, 3]]);
+}
+
+#[test]
+fn test_parse_ply() {
+    assert_quad(&PlyObject::parse(Cursor::new(ascii())).unwrap());
+    assert_quad(&PlyObject::parse(Cursor::new(ascii().replace('\n', "\r\n"))).unwrap());
+    assert_quad(&PlyObject::parse(Cursor::new(binary(false))).unwrap());
+    assert_quad(&PlyObject::parse(Cursor::new(binary(true))).unwrap());
+}
+
+#[test]
+fn test_parse_ply_point_cloud() {
+    let data = "ply\nformat ascii 1.0\nelement vertex 2\nproperty double x\nproperty double y\nproperty double z\nproperty double u\nend_header\n1 2 3 4\n5 6 7 8\n";
+    let ply = PlyObject::parse(Cursor::new(data)).unwrap();
+
+    assert_eq!(ply.positions, [[1.0, 2.0, 3.0], [5.0, 6.0, 7.0]]);
+    // only one texture coord component
+    assert!(ply.texture_coords.is_empty());
+    assert!(ply.normals.is_empty());
+    assert!(ply.faces.is_empty());
+
+    let obj = ObjObject::try_from(ply).unwrap();
+    assert_eq!(obj.face_count(), 0);
+}
+
+#[test]
+fn test_read_ply() {
+    let path = std::env::temp_dir().join(format!("polypath_quad_{}.ply", std::process::id()));
+    std::fs::write(&path, binary(false)).unwrap();
+    let res = ObjObject::read_ply(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let obj = res.unwrap();
+    assert_eq!(obj.face_count(), 3);
+
+    let faces = obj.faces_iter().collect::<Vec<_>>();
+    assert_eq!(
+        faces[0].vert_positions,
+        [POSITIONS[0], POSITIONS[1], POSITIONS[2]]
+    );
+    assert_eq!(
+        faces[1].vert_positions,
+        [POSITIONS[0], POSITIONS[2], POSITIONS[3]]
+    );
+    assert_eq!(
+        faces[2].vert_uv_coords,
+        Some([TEXTURE_COORDS[0], TEXTURE_COORDS[2], TEXTURE_COORDS[3]])
+    );
+    assert_eq!(faces[2].vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+    assert_eq!(
+        faces[0].vert_colors,
+        Some([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    );
+
+    let Err(Error::File { source, .. }) = ObjObject::read_ply("./meshes/missing.ply") else {
+        panic!("expected a file error");
+    };
+    assert!(matches!(*source, Error::Io(_)));
+}
+
+#[test]
+fn test_from_ply_with() {
+    let ply = PlyObject::parse(Cursor::new(ascii())).unwrap();
+
+    let options = ParseOptions::default()
+        .keep_polygons(true)
+        .keep_normals(false)
+        .keep_colors(false);
+    let obj = ObjObject::from_ply_with(ply.clone(), &options).unwrap();
+    assert_eq!(obj.face_count(), 3);
+    assert!(obj.faces_iter().all(|face| face.vert_normals.is_none()));
+    assert!(obj.faces_iter().all(|face| face.vert_colors.is_none()));
+
+    let mut invalid = ply.clone();
+    invalid.faces.push(vec![0, 1, 4]);
+    let err = ObjObject::from_ply_with(invalid.clone(), &ParseOptions::default()).unwrap_err();
+    assert!(matches!(err, Error::InvalidPly(_)));
+    assert!(matches!(
+        ObjObject::try_from(invalid),
+        Err(Error::InvalidPly(_))
+    ));
+
+    let mut non_finite = ply;
+    non_finite.positions[1][2] = f32::INFINITY;
+    assert!(matches!(
+        ObjObject::try_from(non_finite.clone()),
+        Err(Error::NonFiniteValue { line: 0, .. })
+    ));
+    let obj = ObjObject::from_ply_with(non_finite, &ParseOptions::default().strict(false)).unwrap();
+    assert_eq!(obj.faces_iter().next().unwrap().vert_positions[1][2], 0.0);
+}
+
+#[test]
+fn test_parse_ply_errors() {
+    let parse = |data: &str| PlyObject::parse(Cursor::new(data)).unwrap_err().to_string();
+
+    assert!(parse("obj\n").contains("does not start with ply"));
+    assert!(parse("ply\nformat ascii 1.0\n").contains("end_header"));
+    assert!(parse("ply\nend_header\n").contains("missing format"));
+    assert!(parse("ply\nformat ascii 2.0\nend_header\n").contains("on line 2"));
+    assert!(parse("ply\nformat ascii 1.0\nproperty float x\nend_header\n").contains("on line 3"));
+    assert!(
+        parse("ply\nformat ascii 1.0\nelement vertex 1\nproperty half x\nend_header\n")
+            .contains("unknown property type [half]")
+    );
+    assert!(
+        parse("ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n")
+            .contains("without x, y and z")
+    );
+
+    // out of range index, a face with only two vertices, a missing value and a missing line
+    let data = ascii();
+    assert!(parse(&data.replace("3 0 2 3", "3 0 2 4")).contains("face index [4] is out of range"));
+    assert!(parse(&data.replace("3 0 2 3", "2 0 2")).contains("on line 27"));
+    assert!(parse(&data.replace("3 0 2 3", "3 0 2")).contains("on line 27"));
+    assert!(parse(&data.replace("0 2\n", "")).contains("ends before all [1] edge elements"));
+
+    // a truncated binary file
+    let mut data = binary(false);
+    data.truncate(data.len() - 3);
+    assert!(matches!(
+        PlyObject::parse(Cursor::new(data)),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+}
@@ -0,0 +1,191 @@
+use std::{fmt::Write as _, io::Cursor};
+
+use polypath::{ObjObject, ParseOptions, PolygonCorner};
+
+const POLYGONS: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+v 2 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+g quads
+f 1/1/1 2/2/1 3/3/1 4/4/1
+s 1
+f 2/1/1 5/2/1 6/3/1 3/4/1
+g mixed
+f 1/1/1 2/2/1 3/3/1
+f -6/1/1 -5/2/1 -2/3/1 -1/4/1 -3/3/1 -4/4/1
+";
+
+fn keep_polygons() -> ParseOptions {
+    ParseOptions {
+        keep_polygons: true,
+        ..Default::default()
+    }
+}
+
+// writes every polygon back out, with separate attributes for every corner
+fn write_polygons(obj: &ObjObject) -> String {
+    let mut out = String::new();
+    let mut index = 0;
+
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            _ = writeln!(&mut out, "g {}", group.name());
+
+            for polygon in group.polygons_iter() {
+                let mut face = String::from("f");
+
+                for vertex in polygon.vertices() {
+                    let [x, y, z] = vertex.position;
+                    let [u, v] = vertex.texture_coord.unwrap();
+                    let [nx, ny, nz] = vertex.normal.unwrap();
+                    _ = writeln!(&mut out, "v {x} {y} {z}\nvt {u} {v}\nvn {nx} {ny} {nz}");
+
+                    index += 1;
+                    _ = write!(&mut face, " {index}/{index}/{index}");
+                }
+
+                out += &face;
+                out += "\n";
+            }
+        }
+    }
+
+    out
+}
+
+fn polygon_vertices(obj: &ObjObject) -> Vec<Vec<polypath::VertexData>> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.polygons_iter().map(|p| p.vertices().collect()));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_keep_polygons() {
+    let obj = ObjObject::parse_with(Cursor::new(POLYGONS), &keep_polygons()).unwrap();
+
+    let object = obj.objects_iter().next().unwrap();
+    let groups = object.group_iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 2);
+
+    // the triangulated faces are still there
+    assert_eq!(groups[0].face_count(), 4);
+    assert_eq!(groups[1].face_count(), 5);
+
+    assert_eq!(groups[0].polygon_count(), 2);
+    assert_eq!(groups[1].polygon_count(), 2);
+
+    let quads = groups[0].polygons_iter().collect::<Vec<_>>();
+    assert_eq!(
+        quads[0]
+            .corners()
+            .iter()
+            .map(|c| c.position)
+            .collect::<Vec<_>>(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(
+        quads[1].corners()[1],
+        PolygonCorner {
+            position: 5,
+            texture_coord: Some(2),
+            normal: Some(1),
+        }
+    );
+    assert_eq!(quads[0].smooth_group(), None);
+    assert_eq!(quads[1].smooth_group(), Some(1));
+
+    let mixed = groups[1].polygons_iter().collect::<Vec<_>>();
+    assert_eq!(mixed[0].corner_count(), 3);
+    assert_eq!(
+        mixed[1]
+            .corners()
+            .iter()
+            .map(|c| c.position)
+            .collect::<Vec<_>>(),
+        [1, 2, 5, 6, 4, 3]
+    );
+}
+
+#[test]
+fn test_polygons_not_kept_by_default() {
+    let obj = ObjObject::parse(Cursor::new(POLYGONS)).unwrap();
+
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            assert_eq!(group.polygon_count(), 0);
+            assert_eq!(group.polygons_iter().count(), 0);
+        }
+    }
+    assert_eq!(obj.face_count(), 9);
+}
+
+#[test]
+fn test_polygon_round_trip() {
+    let obj = ObjObject::parse_with(Cursor::new(POLYGONS), &keep_polygons()).unwrap();
+
+    let written = write_polygons(&obj);
+    let reread = ObjObject::parse_with(Cursor::new(&written), &keep_polygons()).unwrap();
+
+    assert_eq!(polygon_vertices(&reread), polygon_vertices(&obj));
+
+    let counts = |obj: &ObjObject| {
+        let mut counts = Vec::new();
+        for object in obj.objects_iter() {
+            for group in object.group_iter() {
+                counts.extend(group.polygons_iter().map(|p| p.corner_count()));
+            }
+        }
+        counts
+    };
+    assert_eq!(counts(&reread), [4, 4, 3, 6]);
+    assert_eq!(reread.face_count(), obj.face_count());
+}
+
+#[test]
+fn test_polygons_follow_transforms() {
+    // both objects share the quad positions
+    let data = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+o a
+f 1 2 3 4
+o b
+f 1 2 3 4
+";
+
+    let mut obj = ObjObject::parse_with(Cursor::new(data), &keep_polygons()).unwrap();
+    obj.set_object_transform(
+        "b",
+        [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 5.0, 1.0],
+        ],
+    );
+    obj.apply_object_transforms();
+
+    let vertices = polygon_vertices(&obj);
+    assert_eq!(vertices[0][2].position, [1.0, 1.0, 0.0]);
+    assert_eq!(vertices[1][2].position, [1.0, 1.0, 5.0]);
+
+    // polygons and triangles agree
+    let b = obj.objects_iter().nth(1).unwrap();
+    let group = b.group_iter().next().unwrap();
+    let face = group.faces_iter().next().unwrap();
+    assert_eq!(face.vert_positions[0], vertices[1][0].position);
+}
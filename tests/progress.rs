@@ -0,0 +1,124 @@
+use std::{io::Cursor, ops::ControlFlow};
+
+use polypath::{Error, LineParser, ObjObject, ParseOptions, ParseProgress};
+
+const ARMADILLO: &str = "./meshes/armadillo.obj";
+
+#[test]
+fn test_progress_reports() {
+    let size = std::fs::metadata(ARMADILLO).unwrap().len();
+
+    let mut reports: Vec<ParseProgress> = Vec::new();
+    let obj =
+        ObjObject::read_from_file_with_progress(ARMADILLO, &ParseOptions::default(), |progress| {
+            reports.push(progress);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+    assert!(reports.len() > 1);
+    assert!(
+        reports
+            .iter()
+            .all(|progress| progress.total_bytes == Some(size))
+    );
+    assert!(reports.windows(2).all(|pair| {
+        pair[0].bytes_read <= pair[1].bytes_read
+            && pair[0].vertices <= pair[1].vertices
+            && pair[0].faces <= pair[1].faces
+    }));
+
+    let last = reports.last().unwrap();
+    assert_eq!(last.bytes_read, size);
+    assert_eq!(last.vertices, obj.unique_vertex_count());
+    assert_eq!(last.faces, obj.face_count());
+}
+
+#[test]
+fn test_progress_cancelled() {
+    let size = std::fs::metadata(ARMADILLO).unwrap().len();
+
+    let mut reports = 0;
+    let mut bytes_read = 0;
+    let res =
+        ObjObject::read_from_file_with_progress(ARMADILLO, &ParseOptions::default(), |progress| {
+            reports += 1;
+            bytes_read = progress.bytes_read;
+            ControlFlow::Break(())
+        });
+
+    let Err(Error::File { source, .. }) = res else {
+        panic!("expected an error, got {res:?}");
+    };
+    assert!(matches!(*source, Error::Cancelled), "{source:?}");
+
+    // stops at the first report, long before the end of the file
+    assert_eq!(reports, 1);
+    assert!(bytes_read < size / 10);
+}
+
+#[test]
+fn test_progress_small_input() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+
+    let mut reports = Vec::new();
+    let obj =
+        ObjObject::parse_with_progress(Cursor::new(data), &ParseOptions::default(), |progress| {
+            reports.push(progress);
+            ControlFlow::Break(())
+        })
+        .unwrap();
+
+    // only the final report, which can not cancel parsing anymore
+    assert_eq!(obj.face_count(), 1);
+    assert_eq!(
+        reports,
+        [ParseProgress {
+            bytes_read: data.len() as u64,
+            total_bytes: None,
+            vertices: 3,
+            faces: 1,
+        }]
+    );
+}
+
+#[test]
+fn test_progress_interval() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 2 4 3\n";
+
+    let mut reports = Vec::new();
+    let options = ParseOptions::new().progress_interval(2);
+    ObjObject::parse_with_progress(Cursor::new(data), &options, |progress| {
+        reports.push((progress.vertices, progress.faces));
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+
+    // every 2 statements, and once at the end
+    assert_eq!(reports, [(2, 0), (4, 0), (4, 2), (4, 2)]);
+
+    let mut reports = 0;
+    let options = ParseOptions::new().progress_interval(0);
+    ObjObject::parse_with_progress(Cursor::new(data), &options, |_| {
+        reports += 1;
+        ControlFlow::Break(())
+    })
+    .unwrap();
+
+    assert_eq!(reports, 1);
+}
+
+#[test]
+fn test_line_parser_bytes_read() {
+    let data = "v 0 0 0\r\nv 1 0 0\nf 1 2 1\n";
+
+    let mut parser = LineParser::new(Cursor::new(data), &ParseOptions::default());
+    assert_eq!(parser.bytes_read(), 0);
+
+    let mut bytes_read = Vec::new();
+    while parser.next_line().unwrap().is_some() {
+        bytes_read.push(parser.bytes_read());
+    }
+
+    assert_eq!(bytes_read, [9, 17, 25]);
+}
@@ -0,0 +1,112 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    io::Cursor,
+};
+
+use polypath::ObjObject;
+
+/// Counts the allocations of the current thread, so tests running in parallel do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_reparse_does_not_allocate() {
+    let data = std::fs::read("./meshes/cheburashka.obj").unwrap();
+
+    let before = allocations();
+    let mut obj = ObjObject::parse(Cursor::new(&data)).unwrap();
+    assert!(allocations() > before);
+
+    let faces = obj.face_count();
+    let groups = obj.group_count();
+
+    // let all buffers grow to their final size
+    for _ in 0..3 {
+        obj.reparse(Cursor::new(&data)).unwrap();
+    }
+
+    for _ in 0..3 {
+        let before = allocations();
+        obj.reparse(Cursor::new(&data)).unwrap();
+        assert_eq!(allocations() - before, 0);
+    }
+
+    assert_eq!(obj.face_count(), faces);
+    assert_eq!(obj.group_count(), groups);
+}
+
+#[test]
+fn test_reparse_replaces_content() {
+    let mut obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    obj.reparse(Cursor::new(
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\ng single\nf 1 2 3\n",
+    ))
+    .unwrap();
+
+    assert_eq!(obj.object_count(), 1);
+    assert_eq!(obj.group_count(), 1);
+    assert_eq!(obj.face_count(), 1);
+
+    let object = obj.objects_iter().next().unwrap();
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(group.name(), "single");
+    assert_eq!(
+        group.faces_iter().next().unwrap().vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+}
+
+#[test]
+fn test_reparse_error_leaves_empty() {
+    let mut obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    let res = obj.reparse(Cursor::new("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nunknown\n"));
+    assert!(res.is_err());
+
+    assert_eq!(obj.object_count(), 0);
+    assert_eq!(obj.group_count(), 0);
+    assert_eq!(obj.face_count(), 0);
+    assert!(obj.vertices().0.is_empty());
+
+    // still usable afterwards
+    obj.reparse(Cursor::new("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n"))
+        .unwrap();
+    assert_eq!(obj.face_count(), 1);
+}
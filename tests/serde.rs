@@ -0,0 +1,127 @@
+#![cfg(feature = "serde")]
+
+use polypath::{Error, Face, MaterialIdent, ObjObject, VertexTextureData};
+
+fn faces(obj: &ObjObject) -> Vec<Face> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter());
+        }
+    }
+    out
+}
+
+fn assert_same(obj: &ObjObject, other: &ObjObject) {
+    assert_eq!(obj.object_count(), other.object_count());
+    assert_eq!(obj.group_count(), other.group_count());
+    assert_eq!(obj.vertices(), other.vertices());
+
+    for (face, other) in faces(obj).iter().zip(faces(other)) {
+        assert_eq!(face.vert_positions, other.vert_positions);
+        assert_eq!(face.vert_normals, other.vert_normals);
+        assert_eq!(face.vert_uv_coords, other.vert_uv_coords);
+        assert_eq!(face.smooth_group, other.smooth_group);
+    }
+}
+
+#[test]
+fn test_obj_round_trip() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    let json = serde_json::to_string(&obj).unwrap();
+    assert_same(&obj, &serde_json::from_str(&json).unwrap());
+
+    let bytes = bincode::serialize(&obj).unwrap();
+    assert_same(&obj, &bincode::deserialize(&bytes).unwrap());
+}
+
+#[test]
+fn test_obj_validated() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let json = serde_json::to_value(&obj).unwrap();
+
+    // faces referencing missing vertices
+    let mut invalid = json.clone();
+    invalid["vertices"] = serde_json::json!([]);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    // a group past the last face
+    let mut invalid = json.clone();
+    invalid["groups"][0]["finish"] = serde_json::json!(usize::MAX);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    // an object past the last group
+    let mut invalid = json.clone();
+    invalid["objects"][0]["finish"] = serde_json::json!(obj.group_count() + 1);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    assert!(serde_json::from_value::<ObjObject>(json).is_ok());
+}
+
+#[test]
+fn test_obj_validated_elements() {
+    let obj = ObjObject::parse_str("v 0 0 0\nv 1 0 0\nl 1 2\np 1").unwrap();
+    let json = serde_json::to_value(&obj).unwrap();
+
+    // a line element without a segment
+    let mut invalid = json.clone();
+    invalid["line_elements"] = serde_json::json!([[1]]);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    let mut invalid = json.clone();
+    invalid["line_elements"] = serde_json::json!([[]]);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    // a point element without vertices
+    let mut invalid = json.clone();
+    invalid["point_elements"] = serde_json::json!([[]]);
+    assert!(serde_json::from_value::<ObjObject>(invalid).is_err());
+
+    assert!(serde_json::from_value::<ObjObject>(json).is_ok());
+}
+
+#[test]
+fn test_vertex_round_trip() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let (vertices, materials) = obj.vertices();
+
+    let json = serde_json::to_string(&vertices).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<VertexTextureData>>(&json).unwrap(),
+        vertices
+    );
+
+    let bytes = bincode::serialize(&materials).unwrap();
+    assert_eq!(
+        bincode::deserialize::<Vec<MaterialIdent>>(&bytes).unwrap(),
+        materials
+    );
+
+    let face = faces(&obj)[0];
+    let json = serde_json::to_string(&face).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Face>(&json).unwrap().vertices(),
+        face.vertices()
+    );
+}
+
+#[test]
+fn test_error_round_trip() {
+    let errors = [
+        ObjObject::parse_str("v 0 0 x\n").unwrap_err(),
+        ObjObject::parse_str("v 0 0 0\nf 1 2 x\n").unwrap_err(),
+        ObjObject::parse_str("unknown\n").unwrap_err(),
+        ObjObject::read_from_file("./meshes/missing.obj").unwrap_err(),
+    ];
+
+    for error in errors {
+        let json = serde_json::to_string(&error).unwrap();
+        let deserialized: Error = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.to_string(), error.to_string());
+
+        let bytes = bincode::serialize(&error).unwrap();
+        let deserialized: Error = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(deserialized.to_string(), error.to_string());
+    }
+}
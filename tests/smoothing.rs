@@ -0,0 +1,101 @@
+use std::io::Cursor;
+
+use polypath::ObjObject;
+
+const SMOOTHING: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+f 1 2 3
+s 1
+f 1 2 3
+s off
+f 1 2 3
+s 3
+f 1 2 3 4
+o second
+f 1 2 3
+s 0
+f 1 2 3
+s 2
+g switched
+f 1 2 3
+s 5
+f 1 2 3
+";
+
+#[test]
+fn test_smooth_groups() {
+    let obj = ObjObject::parse(Cursor::new(SMOOTHING)).unwrap();
+
+    let mut groups = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            groups.extend(group.faces_iter().map(|f| f.smooth_group));
+        }
+    }
+
+    assert_eq!(
+        groups,
+        [
+            None,
+            Some(1),
+            None,
+            // quads keep the smoothing group for both triangles
+            Some(3),
+            Some(3),
+            // the smoothing group does not reset with a new object
+            Some(3),
+            None,
+            Some(2),
+            Some(5),
+        ]
+    );
+}
+
+#[test]
+fn test_smooth_group_invalid() {
+    assert!(ObjObject::parse(Cursor::new("s on\n")).is_err());
+}
+
+#[test]
+fn test_group_smooth_groups() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+g flat
+f 1 2 3
+s 1
+g round
+f 1 2 3
+s 2
+f 1 2 3
+g late
+s 4
+f 1 2 3
+s off
+o other
+f 1 2 3
+";
+
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    let mut groups = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            groups.push((group.name().to_owned(), group.smooth_group()));
+        }
+    }
+
+    assert_eq!(
+        groups,
+        [
+            (String::from("flat"), None),
+            (String::from("round"), Some(1)),
+            (String::from("late"), Some(4)),
+            (String::from("default"), None),
+        ]
+    );
+}
@@ -0,0 +1,187 @@
+#![cfg(feature = "stl")]
+
+use polypath::{Error, ObjObject, ParseOptions};
+
+/// Two triangles of a quad sharing an edge, in two solids.
+const ASCII: &str = "solid first
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+endsolid first
+solid second part
+  facet normal 0.0 0.0 1.0
+    outer loop
+      vertex 0 0 0
+      vertex 1.0 1.0 0.0
+      vertex 0e0 1e0 0e0
+    endloop
+  endfacet
+endsolid second part
+";
+
+#[test]
+fn test_parse_stl_ascii() {
+    let obj = ObjObject::parse_stl(ASCII.as_bytes()).unwrap();
+
+    assert_eq!(obj.face_count(), 2);
+    assert_eq!(
+        obj.object_names().collect::<Vec<_>>(),
+        ["first", "second part"]
+    );
+
+    let faces = obj.faces_iter().collect::<Vec<_>>();
+    assert_eq!(
+        faces[1].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    assert_eq!(faces[0].vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+    assert_eq!(obj.referenced_vertex_count(), 6);
+
+    // welding shares the positions of the common edge
+    let options = ParseOptions::default().weld_epsilon(Some(0.0));
+    let welded = ObjObject::parse_stl_with(ASCII.as_bytes(), &options).unwrap();
+    assert_eq!(welded.referenced_vertex_count(), 4);
+
+    let options = ParseOptions::default().keep_normals(false);
+    let obj = ObjObject::parse_stl_with(ASCII.as_bytes(), &options).unwrap();
+    assert!(obj.faces_iter().all(|face| face.vert_normals.is_none()));
+
+    // CRLF line endings, and no final endsolid
+    let data = ASCII
+        .replace('\n', "\r\n")
+        .replace("endsolid second part\r\n", "");
+    assert_eq!(
+        ObjObject::parse_stl(data.as_bytes()).unwrap().face_count(),
+        2
+    );
+}
+
+#[test]
+fn test_stl_binary_round_trip() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    let mut data = Vec::new();
+    obj.write_stl_binary_to_writer(&mut data).unwrap();
+    assert_eq!(data.len(), 84 + 50 * obj.face_count());
+    assert!(!data.starts_with(b"solid"));
+
+    let read = ObjObject::parse_stl(data.as_slice()).unwrap();
+    assert_eq!(read.face_count(), obj.face_count());
+    for (read, face) in read.faces_iter().zip(obj.faces_iter()) {
+        assert_eq!(read.vert_positions, face.vert_positions);
+        assert_eq!(read.vert_normals, face.vert_normals);
+    }
+
+    // some exporters start binary headers with `solid` as well
+    data[..5].copy_from_slice(b"solid");
+    let read = ObjObject::parse_stl(data.as_slice()).unwrap();
+    assert_eq!(read.face_count(), obj.face_count());
+}
+
+#[test]
+fn test_write_stl_calculates_normals() {
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 1 0 0\nvn 0 1 0\nf 1 2 3\nf 1//1 3//2 2//1\n";
+    let obj = ObjObject::parse_str(data).unwrap();
+
+    let path = std::env::temp_dir().join(format!("polypath_normals_{}.stl", std::process::id()));
+    obj.write_stl_binary(&path).unwrap();
+    let res = ObjObject::read_stl(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    // the corners of the second face have different normals, so both follow the winding order
+    let normals = res
+        .unwrap()
+        .faces_iter()
+        .map(|face| face.vert_normals.unwrap()[0])
+        .collect::<Vec<_>>();
+    assert_eq!(normals, [[0.0, 0.0, 1.0], [0.0, 0.0, -1.0]]);
+}
+
+#[test]
+fn test_parse_stl_errors() {
+    let parse = |data: &[u8]| ObjObject::parse_stl(data).unwrap_err().to_string();
+
+    assert!(parse(b"solid a\nfacet normal 0 0\n").contains("on line 2"));
+    assert!(parse(b"solid a\nvertex 0 0 0\n").contains("on line 2"));
+    assert!(parse(ASCII.replace("outer loop", "inner loop").as_bytes()).contains("on line 3"));
+    assert!(
+        parse(ASCII.replacen("      vertex 1 1 0\n", "", 1).as_bytes())
+            .contains("facet with only [2] vertices")
+    );
+    assert!(
+        parse(
+            ASCII
+                .replacen("vertex 1 0 0\n", "vertex 1 0 0\nvertex 1 0 0\n", 1)
+                .as_bytes()
+        )
+        .contains("facet with more than 3 vertices")
+    );
+    assert!(parse(b"solid a\nfacet normal 0 0 1\n").contains("ends inside of a facet"));
+
+    // a truncated binary file
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let mut data = Vec::new();
+    obj.write_stl_binary_to_writer(&mut data).unwrap();
+    assert!(parse(&data[..data.len() - 1]).contains("ends before all [36] triangles"));
+    assert!(parse(&data[..40]).contains("ends inside of the header"));
+
+    let limits = ParseOptions::default().limits(polypath::ParseLimits {
+        max_faces: 10,
+        ..Default::default()
+    });
+    assert!(matches!(
+        ObjObject::parse_stl_with(data.as_slice(), &limits),
+        Err(Error::TooManyFaces { limit: 10 })
+    ));
+    // checked before reading the triangles of a binary file
+    let mut header = data[..84].to_vec();
+    header[80..].copy_from_slice(&1_000_000u32.to_le_bytes());
+    assert!(matches!(
+        ObjObject::parse_stl_with(header.as_slice(), &limits),
+        Err(Error::TooManyFaces { limit: 10 })
+    ));
+
+    let limits = ParseOptions::default().limits(polypath::ParseLimits {
+        max_file_size: 100,
+        ..Default::default()
+    });
+    assert!(matches!(
+        ObjObject::parse_stl_with(data.as_slice(), &limits),
+        Err(Error::FileTooLarge { limit: 100, .. })
+    ));
+    assert!(matches!(
+        ObjObject::parse_stl_with(ASCII.as_bytes(), &limits),
+        Err(Error::FileTooLarge { limit: 100, .. })
+    ));
+}
+
+#[test]
+fn test_parse_stl_non_finite() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+    let mut data = Vec::new();
+    obj.write_stl_binary_to_writer(&mut data).unwrap();
+
+    // the x coordinate of the second vertex of the first triangle
+    data[84 + 24..][..4].copy_from_slice(&f32::NAN.to_le_bytes());
+
+    // binary data has no lines
+    let err = ObjObject::parse_stl(data.as_slice()).unwrap_err();
+    let Error::NonFiniteValue { statement, line } = err else {
+        panic!("expected a non-finite value, got {err:?}");
+    };
+    assert_eq!(line, 0);
+    assert!(statement.starts_with("v NaN"));
+
+    let options = ParseOptions::default().strict(false);
+    let read = ObjObject::parse_stl_with(data.as_slice(), &options).unwrap();
+    assert_eq!(read.face_count(), obj.face_count());
+    assert_eq!(read.faces_iter().next().unwrap().vert_positions[1][0], 0.0);
+
+    let options = ParseOptions::default().reject_non_finite(false);
+    let read = ObjObject::parse_stl_with(data.as_slice(), &options).unwrap();
+    assert!(read.faces_iter().next().unwrap().vert_positions[1][0].is_nan());
+}
@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use polypath::{Error, Face, ObjObject, geometry::generate_tangents};
+
+// a unit quad in the xy plane, u along x and v along y
+const QUAD: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+f 1/1/1 2/2/1 3/3/1 4/4/1
+";
+
+// the same quad, with u mirrored
+const MIRRORED: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 1 0
+vt 0 0
+vt 0 1
+vt 1 1
+f 1/1 2/2 3/3 4/4
+";
+
+fn faces(obj: &ObjObject) -> Vec<Face> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter());
+        }
+    }
+    out
+}
+
+#[test]
+fn test_generate_tangents() {
+    let mut obj = ObjObject::parse(Cursor::new(QUAD)).unwrap();
+    assert!(faces(&obj).iter().all(|face| face.vert_tangents.is_none()));
+
+    generate_tangents(&mut obj).unwrap();
+
+    let faces = faces(&obj);
+    assert_eq!(faces.len(), 2);
+    for face in &faces {
+        assert_eq!(face.vert_tangents, Some([[1.0, 0.0, 0.0, 1.0]; 3]));
+
+        for vertex in face.vertices() {
+            assert_eq!(vertex.tangent, Some([1.0, 0.0, 0.0, 1.0]));
+        }
+    }
+}
+
+#[test]
+fn test_generate_tangents_mirrored() {
+    let mut obj = ObjObject::parse(Cursor::new(MIRRORED)).unwrap();
+    generate_tangents(&mut obj).unwrap();
+
+    for face in faces(&obj) {
+        assert_eq!(face.vert_tangents, Some([[-1.0, 0.0, 0.0, -1.0]; 3]));
+    }
+}
+
+#[test]
+fn test_generate_tangents_missing_texture_coords() {
+    let mut obj = ObjObject::parse(Cursor::new("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n")).unwrap();
+
+    assert!(matches!(
+        generate_tangents(&mut obj),
+        Err(Error::MissingTextureCoords)
+    ));
+    assert!(faces(&obj)[0].vert_tangents.is_none());
+}
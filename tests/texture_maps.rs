@@ -0,0 +1,97 @@
+use polypath::ObjObject;
+
+const TEXTURE_MAPS: &str = "
+maplib bricks.map wood.map
+v 0 0 0
+v 1 0 0
+v 0 1 0
+o house
+g walls
+usemap bricks
+f 1 2 3
+g roof
+f 1 2 3
+usemap wood
+f 1 2 3
+g door
+usemap off
+f 1 2 3
+o garden
+maplib grass.map
+f 1 2 3
+";
+
+fn texture_maps(obj: &ObjObject) -> Vec<(String, Option<String>, usize)> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.push((
+                group.name().to_owned(),
+                group.usemap().map(str::to_owned),
+                group.face_count(),
+            ));
+        }
+    }
+    out
+}
+
+fn maplibs(obj: &ObjObject) -> Vec<Vec<String>> {
+    obj.objects_iter()
+        .map(|object| object.maplibs().to_vec())
+        .collect()
+}
+
+#[test]
+fn test_texture_maps() {
+    let obj = ObjObject::parse_str(TEXTURE_MAPS).unwrap();
+
+    // switching the texture map inside of roof continues it as a new group
+    let expected = [
+        ("walls", Some("bricks"), 1),
+        ("roof", Some("bricks"), 1),
+        ("roof", Some("wood"), 1),
+        ("door", None, 1),
+        ("default", None, 1),
+    ];
+    assert_eq!(
+        texture_maps(&obj),
+        expected.map(|(name, map, faces)| (name.to_owned(), map.map(str::to_owned), faces))
+    );
+
+    assert_eq!(
+        maplibs(&obj),
+        [
+            vec!["bricks.map", "wood.map"],
+            vec!["bricks.map", "wood.map", "grass.map"]
+        ]
+    );
+    assert_eq!(
+        obj.objects_iter().next().unwrap().maplib(),
+        Some("bricks.map")
+    );
+}
+
+#[test]
+fn test_texture_maps_write_back() {
+    let obj = ObjObject::parse_str(TEXTURE_MAPS).unwrap();
+
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(written.contains("usemap off\n"));
+
+    let reread = ObjObject::parse_str(&written).unwrap();
+    assert_eq!(texture_maps(&reread), texture_maps(&obj));
+    assert_eq!(maplibs(&reread), maplibs(&obj));
+}
+
+#[test]
+fn test_merge_keeps_texture_maps() {
+    let mut obj = ObjObject::parse_str(TEXTURE_MAPS).unwrap();
+    assert!(obj.merge_named_groups().is_empty());
+
+    assert_eq!(
+        texture_maps(&obj),
+        texture_maps(&ObjObject::parse_str(TEXTURE_MAPS).unwrap())
+    );
+}
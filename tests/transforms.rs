@@ -0,0 +1,238 @@
+use std::io::Cursor;
+
+use polypath::{ObjObject, WriteConfig};
+
+// both objects share the first two positions and the normal
+const SHARED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vn 0 0 1
+o a
+f 1//1 2//1 3//1
+o b
+f 1//1 2//1 4//1
+";
+
+const fn translation(x: f32, y: f32, z: f32) -> [[f32; 4]; 4] {
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [x, y, z, 1.0],
+    ]
+}
+
+// 90 degrees around the x axis
+const ROTATION_X: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, -1.0, 0.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn faces(obj: &ObjObject) -> Vec<polypath::Face> {
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter());
+        }
+    }
+    out
+}
+
+#[test]
+fn test_set_object_transform() {
+    let mut obj = ObjObject::parse(Cursor::new(SHARED)).unwrap();
+
+    assert!(obj.set_object_transform("b", ROTATION_X));
+    assert!(obj.set_object_transform(0, translation(1.0, 2.0, 3.0)));
+    assert!(!obj.set_object_transform("c", ROTATION_X));
+    assert!(!obj.set_object_transform(2, ROTATION_X));
+
+    let transforms = obj
+        .objects_iter()
+        .map(|o| o.object_transform())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        transforms,
+        [Some(translation(1.0, 2.0, 3.0)), Some(ROTATION_X)]
+    );
+
+    // nothing gets baked until requested
+    assert_eq!(faces(&obj)[0].vert_positions[1], [1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_apply_duplicates_shared_attributes() {
+    let mut obj = ObjObject::parse(Cursor::new(SHARED)).unwrap();
+
+    obj.set_object_transform("a", translation(10.0, 0.0, 0.0));
+    obj.set_object_transform("b", ROTATION_X);
+    obj.apply_object_transforms();
+
+    assert!(obj.objects_iter().all(|o| o.object_transform().is_none()));
+
+    let faces = faces(&obj);
+    assert_eq!(
+        faces[0].vert_positions,
+        [[10.0, 0.0, 0.0], [11.0, 0.0, 0.0], [10.0, 1.0, 0.0]]
+    );
+    assert_eq!(faces[0].vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+
+    assert_eq!(
+        faces[1].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 0.0, 1.0]]
+    );
+    assert_eq!(faces[1].vert_normals, Some([[0.0, -1.0, 0.0]; 3]));
+
+    // applying again does not change anything
+    obj.apply_object_transforms();
+    assert_eq!(self::faces(&obj)[1].vert_positions, faces[1].vert_positions);
+}
+
+#[test]
+fn test_apply_keeps_untransformed_objects() {
+    let mut obj = ObjObject::parse(Cursor::new(SHARED)).unwrap();
+
+    obj.set_object_transform("b", translation(0.0, 0.0, 5.0));
+    obj.apply_object_transforms();
+
+    let faces = faces(&obj);
+    assert_eq!(
+        faces[0].vert_positions,
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+    assert_eq!(
+        faces[1].vert_positions,
+        [[0.0, 0.0, 5.0], [1.0, 0.0, 5.0], [1.0, 1.0, 5.0]]
+    );
+    assert_eq!(faces[1].vert_normals, Some([[0.0, 0.0, 1.0]; 3]));
+}
+
+#[test]
+fn test_apply_moves_point_elements() {
+    let mut obj = ObjObject::parse(Cursor::new(
+        "
+v 1 0 0
+v 0 1 0
+o a
+p 1 2
+o b
+p 2
+",
+    ))
+    .unwrap();
+
+    obj.set_object_transform("a", translation(10.0, 0.0, 0.0));
+    obj.apply_object_transforms();
+
+    let points = obj
+        .objects_iter()
+        .map(|object| {
+            object
+                .group_iter()
+                .flat_map(|group| group.points_iter().map(|p| p.position).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the second position is shared with the untransformed object b
+    assert_eq!(
+        points,
+        [
+            vec![[11.0, 0.0, 0.0], [10.0, 1.0, 0.0]],
+            vec![[0.0, 1.0, 0.0]]
+        ]
+    );
+}
+
+#[test]
+fn test_apply_moves_line_elements() {
+    let mut obj = ObjObject::parse(Cursor::new(
+        "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+o a
+l 1 2 3
+o b
+l 3 1
+",
+    ))
+    .unwrap();
+
+    obj.set_object_transform("a", translation(0.0, 0.0, 2.0));
+    obj.apply_object_transforms();
+
+    let lines = obj
+        .objects_iter()
+        .map(|object| {
+            object
+                .group_iter()
+                .flat_map(|group| {
+                    group
+                        .lines_iter()
+                        .map(|l| l.vert_positions)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the first and third position are shared with the untransformed object b
+    assert_eq!(
+        lines,
+        [
+            vec![
+                [[0.0, 0.0, 2.0], [1.0, 0.0, 2.0]],
+                [[1.0, 0.0, 2.0], [0.0, 1.0, 2.0]]
+            ],
+            vec![[[0.0, 1.0, 0.0], [0.0, 0.0, 0.0]]]
+        ]
+    );
+}
+
+/// The positions and normals of the corners of a face.
+type Corners = ([[f32; 3]; 3], Option<[[f32; 3]; 3]>);
+
+fn corners(obj: &ObjObject) -> Vec<Corners> {
+    faces(obj)
+        .iter()
+        .map(|face| (face.vert_positions, face.vert_normals))
+        .collect()
+}
+
+fn write_parse(obj: &ObjObject, bake_object_transforms: bool) -> ObjObject {
+    let config = WriteConfig {
+        bake_object_transforms,
+        ..Default::default()
+    };
+    let mut written = Vec::new();
+    obj.write_to_writer_with_config(&mut written, &config)
+        .unwrap();
+
+    ObjObject::parse(Cursor::new(written)).unwrap()
+}
+
+#[test]
+fn test_write_baked_and_raw() {
+    let mut obj = ObjObject::parse(Cursor::new(SHARED)).unwrap();
+    obj.set_object_transform("a", translation(10.0, 0.0, 0.0));
+    obj.set_object_transform("b", ROTATION_X);
+
+    // the raw data is written as stored, without the transforms
+    let raw = write_parse(&obj, false);
+    assert_eq!(raw.unique_vertex_count(), 4);
+    assert_eq!(corners(&raw), corners(&obj));
+
+    // baking writes the shared positions and normals once for every transform
+    let baked = write_parse(&obj, true);
+    assert!(obj.objects_iter().all(|o| o.object_transform().is_some()));
+    assert!(baked.objects_iter().all(|o| o.object_transform().is_none()));
+    assert_eq!(baked.unique_vertex_count(), 6);
+
+    obj.apply_object_transforms();
+    assert_eq!(corners(&baked), corners(&obj));
+}
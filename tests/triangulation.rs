@@ -0,0 +1,146 @@
+use std::io::Cursor;
+
+use polypath::{ObjObject, ParseOptions, QuadSplit};
+
+// folded along 1-3, which is also the shorter diagonal
+const FOLDED: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 1
+v 0 1 0
+f 1 2 3 4
+";
+
+// 0-2 is the shorter diagonal, but splitting along 1-3 is more planar
+const SKEWED: &str = "
+v 0 0 0
+v 4 0 0
+v 1 4 0
+v 0 1 1
+f 1 2 3 4
+";
+
+const SPLIT_02: [[[f32; 3]; 3]; 2] = [
+    [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
+    [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [0.0, 1.0, 0.0]],
+];
+
+const SPLIT_13: [[[f32; 3]; 3]; 2] = [
+    [[1.0, 0.0, 0.0], [1.0, 1.0, 1.0], [0.0, 1.0, 0.0]],
+    [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]],
+];
+
+fn triangles(data: &str, quad_split: QuadSplit) -> Vec<[[f32; 3]; 3]> {
+    let options = ParseOptions {
+        quad_split,
+        ..Default::default()
+    };
+    let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+
+    let mut out = Vec::new();
+    for object in obj.objects_iter() {
+        for group in object.group_iter() {
+            out.extend(group.faces_iter().map(|face| face.vert_positions));
+        }
+    }
+    out
+}
+
+#[test]
+fn test_quad_split_folded() {
+    assert_eq!(triangles(FOLDED, QuadSplit::Fixed02), SPLIT_02);
+    assert_eq!(triangles(FOLDED, QuadSplit::ShortestDiagonal), SPLIT_13);
+    assert_eq!(triangles(FOLDED, QuadSplit::MostPlanar), SPLIT_13);
+}
+
+#[test]
+fn test_quad_split_modes_differ() {
+    let fixed = triangles(SKEWED, QuadSplit::Fixed02);
+    assert_eq!(
+        fixed[0],
+        [[0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [1.0, 4.0, 0.0]]
+    );
+
+    assert_eq!(triangles(SKEWED, QuadSplit::ShortestDiagonal), fixed);
+
+    let planar = triangles(SKEWED, QuadSplit::MostPlanar);
+    assert_eq!(
+        planar[0],
+        [[4.0, 0.0, 0.0], [1.0, 4.0, 0.0], [0.0, 1.0, 1.0]]
+    );
+    assert_eq!(
+        planar[1],
+        [[4.0, 0.0, 0.0], [0.0, 1.0, 1.0], [0.0, 0.0, 0.0]]
+    );
+}
+
+#[test]
+fn test_quad_split_default() {
+    assert_eq!(ParseOptions::default().quad_split, QuadSplit::Fixed02);
+
+    let obj = ObjObject::parse(Cursor::new(FOLDED)).unwrap();
+    let face = obj
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert_eq!(face.vert_positions, SPLIT_02[0]);
+}
+
+// an n-gon, where the position, uv and normal of corner i all encode i
+fn polygon(n: usize, texture: bool, normal: bool) -> String {
+    let mut data = String::new();
+    let mut face = String::from("f");
+
+    for i in 1..=n {
+        data += &format!("v {i} 0 0\nvt {i} 0\nvn 0 0 {i}\n");
+
+        face += &match (texture, normal) {
+            (false, false) => format!(" {i}"),
+            (true, false) => format!(" {i}/{i}"),
+            (false, true) => format!(" {i}//{i}"),
+            (true, true) => format!(" {i}/{i}/{i}"),
+        };
+    }
+
+    data + &face + "\n"
+}
+
+#[test]
+fn test_polygon_fan() {
+    for n in [5, 6, 8] {
+        for (texture, normal) in [(false, false), (true, false), (false, true), (true, true)] {
+            let obj = ObjObject::parse(Cursor::new(polygon(n, texture, normal))).unwrap();
+            assert_eq!(obj.face_count(), n - 2);
+
+            let mut faces = Vec::new();
+            for object in obj.objects_iter() {
+                for group in object.group_iter() {
+                    faces.extend(group.faces_iter());
+                }
+            }
+            assert_eq!(faces.len(), n - 2);
+
+            for (i, face) in faces.iter().enumerate() {
+                // fan around the first corner: (1, i + 2, i + 3)
+                #[allow(clippy::cast_precision_loss)]
+                let corners = [1.0, i as f32 + 2.0, i as f32 + 3.0];
+
+                assert_eq!(face.vert_positions, corners.map(|c| [c, 0.0, 0.0]));
+                assert_eq!(
+                    face.vert_uv_coords,
+                    texture.then(|| corners.map(|c| [c, 0.0]))
+                );
+                assert_eq!(
+                    face.vert_normals,
+                    normal.then(|| corners.map(|c| [0.0, 0.0, c]))
+                );
+            }
+        }
+    }
+}
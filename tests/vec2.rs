@@ -0,0 +1,59 @@
+use polypath::{ObjObject, Vec2};
+
+#[test]
+fn test_operators() {
+    let a = Vec2::new(1.0, 2.0);
+    let b = Vec2::new(4.0, -2.0);
+
+    assert_eq!(a + b, Vec2::new(5.0, 0.0));
+    assert_eq!(a - b, Vec2::new(-3.0, 4.0));
+    assert_eq!(a * 2.0, Vec2::new(2.0, 4.0));
+    assert_eq!(a / 2.0, Vec2::new(0.5, 1.0));
+    assert_eq!(-a, Vec2::new(-1.0, -2.0));
+    assert_eq!(a.dot(&b), 0.0);
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(c, b);
+    c *= 4.0;
+    c /= 2.0;
+    assert_eq!(c, b * 2.0);
+}
+
+#[test]
+fn test_length_lerp() {
+    let v = Vec2::new(3.0, 4.0);
+
+    assert_eq!(v.length(), 5.0);
+    assert_eq!(v.length_squared(), 25.0);
+    assert_eq!(v.normalized(), Vec2::new(0.6, 0.8));
+    assert_eq!(v.distance(Vec2::zero()), 5.0);
+
+    assert_eq!(Vec2::zero().lerp(v, 0.5), Vec2::new(1.5, 2.0));
+}
+
+#[test]
+fn test_conversions() {
+    let v = Vec2::from((1.0, 2.0));
+
+    assert_eq!(v, Vec2::from([1.0, 2.0]));
+    assert_eq!(v, Vec2::from_array([1.0, 2.0]));
+    assert_eq!(<(f32, f32)>::from(v), (1.0, 2.0));
+    assert_eq!(<[f32; 2]>::from(v), [1.0, 2.0]);
+    assert_eq!(v.to_array(), [1.0, 2.0]);
+}
+
+#[test]
+fn test_texture_coord_iter() {
+    let obj = ObjObject::parse_str("vt 0.5 0.25\nvt 1\nvt 0 1 0.5\n").unwrap();
+
+    assert_eq!(
+        obj.texture_coord_iter().collect::<Vec<_>>(),
+        [
+            Vec2::new(0.5, 0.25),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0)
+        ]
+    );
+}
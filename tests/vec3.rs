@@ -0,0 +1,58 @@
+use polypath::Vec3;
+
+#[test]
+fn test_operators() {
+    let a = Vec3::new(1.0, 2.0, 3.0);
+    let b = Vec3::new(4.0, -2.0, 0.5);
+
+    assert_eq!(a + b, Vec3::new(5.0, 0.0, 3.5));
+    assert_eq!(a - b, Vec3::new(-3.0, 4.0, 2.5));
+    assert_eq!(a * 2.0, Vec3::new(2.0, 4.0, 6.0));
+    assert_eq!(a / 2.0, Vec3::new(0.5, 1.0, 1.5));
+    assert_eq!(-a, Vec3::new(-1.0, -2.0, -3.0));
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(c, b);
+    c *= 4.0;
+    c /= 2.0;
+    assert_eq!(c, b * 2.0);
+}
+
+#[test]
+fn test_length() {
+    let v = Vec3::new(2.0, 3.0, 6.0);
+
+    assert_eq!(v.length(), 7.0);
+    assert_eq!(v.length_squared(), 49.0);
+    assert_eq!(v.normalized().length(), 1.0);
+
+    assert_eq!(v.clamp_length(14.0), v);
+    assert_eq!(v.clamp_length(3.5), v / 2.0);
+}
+
+#[test]
+fn test_lerp_reflect() {
+    let a = Vec3::new(0.0, 0.0, 0.0);
+    let b = Vec3::new(2.0, 4.0, -2.0);
+
+    assert_eq!(a.lerp(b, 0.0), a);
+    assert_eq!(a.lerp(b, 0.5), Vec3::new(1.0, 2.0, -1.0));
+    assert_eq!(a.lerp(b, 1.0), b);
+
+    let up = Vec3::new(0.0, 1.0, 0.0);
+    assert_eq!(
+        Vec3::new(1.0, -1.0, 0.0).reflect(up),
+        Vec3::new(1.0, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn test_arrays() {
+    let v = Vec3::from_array([1.0, 2.0, 3.0]);
+
+    assert_eq!(v, Vec3::from([1.0, 2.0, 3.0]));
+    assert_eq!(v.to_array(), [1.0, 2.0, 3.0]);
+    assert_eq!(<[f32; 3]>::from(v), [1.0, 2.0, 3.0]);
+}
@@ -0,0 +1,73 @@
+use polypath::opt::{acmr, optimize_forsyth};
+
+// a grid of `size` x `size` quads, with rows of triangles in a scattered order
+fn grid(size: u32) -> Vec<u32> {
+    let mut rows = Vec::new();
+    for y in 0..size {
+        let mut row = Vec::new();
+        for x in 0..size {
+            let i = y * (size + 1) + x;
+            let j = i + size + 1;
+            row.extend([i, i + 1, j, i + 1, j + 1, j]);
+        }
+        rows.push(row);
+    }
+
+    // interleave the rows, so consecutive triangles share no vertices
+    let mut indices = Vec::new();
+    for x in 0..rows[0].len() / 3 {
+        for row in &rows {
+            indices.extend_from_slice(&row[x * 3..x * 3 + 3]);
+        }
+    }
+    indices
+}
+
+fn sorted_triangles(indices: &[u32]) -> Vec<[u32; 3]> {
+    let mut triangles = indices
+        .chunks_exact(3)
+        .map(|t| <[u32; 3]>::try_from(t).unwrap())
+        .collect::<Vec<_>>();
+    triangles.sort_unstable();
+    triangles
+}
+
+#[test]
+fn test_acmr() {
+    assert!(acmr(&[], 16).abs() < f32::EPSILON);
+    assert!((acmr(&[0, 1, 2], 16) - 3.0).abs() < f32::EPSILON);
+    assert!((acmr(&[0, 1, 2, 2, 1, 3], 16) - 2.0).abs() < f32::EPSILON);
+    // without a cache every vertex is a miss
+    assert!((acmr(&[0, 1, 2, 2, 1, 3], 0) - 3.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_forsyth_keeps_triangles() {
+    let mut indices = grid(16);
+    let original = sorted_triangles(&indices);
+
+    optimize_forsyth(&mut indices, 17 * 17);
+
+    assert_eq!(indices.len(), original.len() * 3);
+    // the winding is kept, so the triangles are identical
+    assert_eq!(sorted_triangles(&indices), original);
+}
+
+#[test]
+fn test_forsyth_improves_acmr() {
+    let mut indices = grid(32);
+    let before = acmr(&indices, 16);
+
+    optimize_forsyth(&mut indices, 33 * 33);
+    let after = acmr(&indices, 16);
+
+    assert!(after < before, "{after} >= {before}");
+    assert!(after < 1.0, "{after}");
+}
+
+#[test]
+fn test_forsyth_empty() {
+    let mut indices = Vec::new();
+    optimize_forsyth(&mut indices, 0);
+    assert!(indices.is_empty());
+}
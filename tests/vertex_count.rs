@@ -0,0 +1,34 @@
+use polypath::ObjObject;
+
+// a quad sharing two vertices between its triangles, a vertex only used by a point, and one never used
+const SHARED: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 2 2
+v 3 3 3
+f 1 2 3 4
+p 5
+";
+
+#[test]
+fn test_vertex_counts() {
+    let obj = ObjObject::parse_str(SHARED).unwrap();
+
+    assert_eq!(obj.face_count(), 2);
+    assert_eq!(obj.unique_vertex_count(), 6);
+    assert_eq!(obj.referenced_vertex_count(), 4);
+
+    #[allow(deprecated)]
+    let raw = obj.vert_count();
+    assert_eq!(raw, 6);
+}
+
+#[test]
+fn test_vertex_counts_mesh() {
+    let obj = ObjObject::read_from_file("./meshes/cheburashka.obj").unwrap();
+
+    assert!(obj.referenced_vertex_count() <= obj.unique_vertex_count());
+    assert!(obj.unique_vertex_count() < obj.face_count() * 3);
+}
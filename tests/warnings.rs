@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use polypath::{ObjObject, ParseWarning};
+
+const QUIRKY: &str = "
+mtllib first.mtl
+v 0 0 0
+v 1 0 0
+v 0 1 0
+vn 0 0 1
+vn 0 0 0
+mtllib second.mtl
+bogus on
+g group
+usemtl a
+usemtl b
+f 1//1 2//1 3//2
+";
+
+#[test]
+fn test_parse_with_warnings() {
+    let result = ObjObject::parse_with_warnings(Cursor::new(QUIRKY)).unwrap();
+
+    assert_eq!(
+        result.warnings,
+        [
+            ParseWarning::ZeroNormal { line: 7 },
+            ParseWarning::IgnoredLine {
+                line: 9,
+                data: String::from("bogus on"),
+            },
+        ]
+    );
+
+    // every material lib is kept, a material use replaces one without any faces
+    let obj = result.obj;
+    let object = obj.objects_iter().next().unwrap();
+    assert_eq!(object.mtllib(), Some("first.mtl"));
+    assert_eq!(object.mtllibs(), ["first.mtl", "second.mtl"]);
+    let group = object.group_iter().next().unwrap();
+    assert_eq!(group.mtluse(), Some("b"));
+    assert_eq!(group.face_count(), 1);
+}
+
+#[test]
+fn test_parse_still_fails() {
+    assert!(ObjObject::parse(Cursor::new(QUIRKY)).is_err());
+}
+
+#[test]
+fn test_no_warnings() {
+    let file = std::fs::read("./meshes/cubes.obj").unwrap();
+    let result = ObjObject::parse_with_warnings(Cursor::new(&file)).unwrap();
+
+    assert!(result.warnings.is_empty());
+    assert_eq!(
+        result.obj.face_count(),
+        ObjObject::parse(Cursor::new(&file)).unwrap().face_count()
+    );
+}
+
+#[test]
+fn test_fatal_errors_remain() {
+    // malformed data of a known line can not be skipped
+    assert!(ObjObject::parse_with_warnings(Cursor::new("v 0 0 x\n")).is_err());
+}
+
+#[test]
+fn test_warning_display() {
+    let warning = ParseWarning::IgnoredLine {
+        line: 3,
+        data: String::from("curv"),
+    };
+
+    assert_eq!(
+        warning.to_string(),
+        "on line 3: Ignored unknown line: [curv]\n"
+    );
+}
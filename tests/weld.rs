@@ -0,0 +1,225 @@
+use std::{collections::HashSet, io::Cursor};
+
+use polypath::{ObjObject, ParseOptions, opt};
+
+const CUBE: &str = "
+v 0 0 0
+v 0 0 1
+v 0 1 0
+v 0 1 1
+v 1 0 0
+v 1 0 1
+v 1 1 0
+v 1 1 1
+vn 0 0 1
+vn 0 0 -1
+vn 0 1 0
+vn 0 -1 0
+vn 1 0 0
+vn -1 0 0
+f 1//2 7//2 5//2
+f 1//2 3//2 7//2
+f 1//6 4//6 3//6
+f 1//6 2//6 4//6
+f 3//3 8//3 7//3
+f 3//3 4//3 8//3
+f 5//5 7//5 8//5
+f 5//5 8//5 6//5
+f 1//4 5//4 6//4
+f 1//4 6//4 2//4
+f 2//1 6//1 8//1
+f 2//1 8//1 4//1
+";
+
+// two triangles, whose shared edge is coincident, but not bitwise equal
+const CRACKED: &str = "
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1.0000001 0.0000001 0
+v 0.0000001 1.0000001 0
+v 1 1 0
+vn 0 0 1
+f 1//1 2//1 3//1
+f 4//1 6//1 5//1
+";
+
+fn positions(vertices: &[polypath::VertexTextureData]) -> HashSet<[u32; 3]> {
+    vertices
+        .iter()
+        .map(|v| v.vertex.position.map(f32::to_bits))
+        .collect()
+}
+
+#[test]
+fn test_weld_cube_keeps_hard_edges() {
+    let obj = ObjObject::parse(Cursor::new(CUBE)).unwrap();
+    let (vertices, _) = obj.vertices();
+
+    let (indicies, welded) = opt::weld_preserving_seams(&vertices, 0.001, 30.0, None);
+
+    assert_eq!(indicies.len(), 36);
+    assert_eq!(welded.len(), 24);
+    assert_eq!(positions(&welded).len(), 8);
+
+    for (i, v) in indicies.iter().zip(&vertices) {
+        assert_eq!(welded[*i].vertex.normal, v.vertex.normal);
+    }
+}
+
+#[test]
+fn test_weld_closes_crack() {
+    let obj = ObjObject::parse(Cursor::new(CRACKED)).unwrap();
+    let (vertices, _) = obj.vertices();
+
+    // without tolerance, nothing gets merged
+    let (_, welded) = opt::weld_preserving_seams(&vertices, 0.0, 30.0, None);
+    assert_eq!(welded.len(), 6);
+
+    let (indicies, welded) = opt::weld_preserving_seams(&vertices, 0.0001, 30.0, None);
+    assert_eq!(welded.len(), 4);
+    assert_eq!(positions(&welded).len(), 4);
+
+    // the shared edge references the same vertices
+    assert_eq!(indicies[1], indicies[3]);
+    assert_eq!(indicies[2], indicies[5]);
+}
+
+// the same quad twice, the second copy with slightly different positions, normals and uvs
+const NOISY: &str = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0.00001 0 0
+v 1 -0.00001 0
+v 1 1 0.00001
+v 0 1.00001 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vt 0.00001 0
+vt 1 0
+vt 1 1.00001
+vt 0 1
+vn 0 0 1
+vn 0.00001 0 1
+vn 0 0 -1
+f 1/1/1 2/2/1 3/3/1 4/4/1
+f 5/5/2 6/6/2 7/7/2 8/8/2
+f 1/1/3 2/2/3 3/3/3
+";
+
+#[test]
+fn test_weld_vertices() {
+    let obj = ObjObject::parse_str(NOISY).unwrap();
+    let (vertices, _) = obj.vertices();
+    assert_eq!(vertices.len(), 15);
+
+    // without tolerance, only identical vertices get merged
+    let (indices, welded) = opt::weld_vertices(&vertices, 0.0, 0.0, 0.0);
+    assert_eq!(indices.len(), 15);
+    assert_eq!(welded.len(), 11);
+
+    let (indices, welded) = opt::weld_vertices(&vertices, 0.001, 0.001, 0.001);
+    assert_eq!(indices.len(), 15);
+    // the flipped normals of the last face keep its vertices apart
+    assert_eq!(welded.len(), 7);
+    assert_eq!(indices[..6], indices[6..12]);
+    assert!(indices[12..].iter().all(|i| *i >= 4));
+
+    // merged vertices keep the attributes of the first one
+    for (i, v) in indices[..6].iter().zip(&vertices) {
+        assert_eq!(welded[*i as usize], *v);
+    }
+
+    // a uv difference above the tolerance prevents merging
+    let (_, welded) = opt::weld_vertices(&vertices, 0.001, 0.001, 0.000_001);
+    assert_eq!(welded.len(), 9);
+}
+
+#[test]
+fn test_weld_positions() {
+    let mut obj = ObjObject::parse(Cursor::new(CRACKED)).unwrap();
+    let before = obj
+        .faces_iter()
+        .map(|face| face.vert_positions)
+        .collect::<Vec<_>>();
+
+    // without tolerance, nothing gets merged
+    assert_eq!(polypath::geometry::weld_positions(&mut obj, 0.0), 0);
+
+    assert_eq!(polypath::geometry::weld_positions(&mut obj, 0.0001), 2);
+    assert_eq!(obj.unique_vertex_count(), 4);
+
+    // the faces reference the first of the merged positions
+    let after = obj
+        .faces_iter()
+        .map(|face| face.vert_positions)
+        .collect::<Vec<_>>();
+    assert_eq!(after[0], before[0]);
+    assert_eq!(after[1][0], before[0][1]);
+    assert_eq!(after[1][2], before[0][2]);
+    assert_eq!(after[1][1], before[1][1]);
+}
+
+#[test]
+fn test_weld_positions_keeps_colors() {
+    let data = "v 0 0 0 1 0 0\nv 1 0 0 1 0 0\nv 0 1 0 1 0 0\nv 0 0 0 0 1 0\nf 1 2 3\nf 4 2 3\n";
+    let mut obj = ObjObject::parse(Cursor::new(data)).unwrap();
+
+    // same position, but a different color
+    assert_eq!(polypath::geometry::weld_positions(&mut obj, 0.001), 0);
+    assert_eq!(obj.unique_vertex_count(), 4);
+}
+
+#[test]
+fn test_weld_while_parsing() {
+    let options = ParseOptions::new()
+        .weld_epsilon(Some(0.0001))
+        .generate_normals(true);
+    let data = "v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1.0000001 0.0000001 0\nv 0.0000001 1.0000001 0\nv 1 1 0\nf 1 2 3\nf 4 6 5\n";
+    let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+
+    assert_eq!(obj.unique_vertex_count(), 4);
+    assert_eq!(options.weld_epsilon, Some(polypath::WeldEpsilon(0.0001)));
+    assert_ne!(options, ParseOptions::new());
+
+    // smooth normals across the closed crack
+    let normals = obj
+        .faces_iter()
+        .flat_map(|face| face.vert_normals.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(normals.len(), 6);
+    assert_eq!(normals[1], normals[3]);
+    assert_eq!(normals[2], normals[5]);
+
+    // the default options keep everything as written
+    let obj = ObjObject::parse(Cursor::new(data)).unwrap();
+    assert_eq!(obj.unique_vertex_count(), 6);
+    assert!(obj.faces_iter().all(|face| face.vert_normals.is_none()));
+}
+
+#[test]
+fn test_weld_infinite_positions() {
+    let options = ParseOptions::new()
+        .strict(false)
+        .reject_non_finite(false)
+        .weld_epsilon(Some(0.01));
+    let data = "v inf 0 0\nv -inf 0 0\nv 0 1e30 0\nv 0 1e30 0\nf 1 2 3\nf 1 2 4\n";
+    let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+
+    // infinite positions are never within any tolerance, huge ones are welded as usual
+    assert_eq!(obj.unique_vertex_count(), 3);
+
+    let mut obj = ObjObject::parse_with(Cursor::new(data), &options.weld_epsilon(None)).unwrap();
+    assert_eq!(polypath::geometry::weld_positions(&mut obj, 0.01), 1);
+
+    // only the two corners at the huge position are shared
+    let (vertices, _) = obj.vertices();
+    let (_, welded) = opt::weld_vertices(&vertices, 0.01, 0.01, 0.01);
+    assert_eq!(welded.len(), 5);
+    let (_, welded) = opt::weld_preserving_seams(&vertices, 0.01, 30.0, None);
+    assert_eq!(welded.len(), 5);
+}
@@ -1,6 +1,9 @@
-use std::{fmt::Write as _, io::Write as _};
+use std::{
+    fmt::Write as _,
+    io::{BufWriter, Cursor, Write as _},
+};
 
-use polypath::{ObjObject, VertexTextureData, opt};
+use polypath::{ObjObject, ParseOptions, VertexTextureData, WriteConfig, opt, write};
 
 #[test]
 fn test_write_back() {
@@ -18,50 +21,9 @@ fn test_write_back() {
 
     println!("indicies: {}  --  verts: {}", indicies.len(), verts.len());
 
-    write_indexed_to_file(verts, indicies);
-}
-
-fn write_indexed_to_file(verts: Vec<VertexTextureData>, indicies: Vec<usize>) {
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open("./meshes/test_output.obj")
-        .unwrap();
-
-    let mut normal = false;
-    let mut str = String::with_capacity(1024 * 512 * 512);
-    for vert in verts.iter().map(|v| v.vertex) {
-        _ = writeln!(
-            &mut str,
-            "v {} {} {}",
-            vert.position.0, vert.position.1, vert.position.2
-        );
-
-        if let Some((n1, n2, n3)) = vert.normal {
-            _ = writeln!(&mut str, "vn {n1} {n2} {n3}");
-            normal = true;
-        }
-    }
-
-    file.write(str.as_bytes()).unwrap();
-    str.clear();
-
-    for chunk in indicies.chunks_exact(3) {
-        let pos1 = chunk[0] + 1;
-        let pos2 = chunk[1] + 1;
-        let pos3 = chunk[2] + 1;
-
-        if normal {
-            _ = writeln!(&mut str, "f {pos1}//{pos1} {pos2}//{pos2} {pos3}//{pos3}");
-        } else {
-            _ = writeln!(&mut str, "f {pos1} {pos2} {pos3}");
-        }
-    }
-
-    file.write(str.as_bytes()).unwrap();
-
-    str.clear();
+    let (_, materials) = obj.vertices();
+    let file = std::fs::File::create("./meshes/test_output.obj").unwrap();
+    write::write_indexed(BufWriter::new(file), &verts, &indicies, &materials).unwrap();
 }
 
 fn _vertex_to_file(verts: Vec<VertexTextureData>) {
@@ -82,18 +44,18 @@ fn _vertex_to_file(verts: Vec<VertexTextureData>) {
         let p2 = vert[1].vertex.position;
         let p3 = vert[2].vertex.position;
 
-        _ = writeln!(&mut str, "v {} {} {}", p1.0, p1.1, p1.2);
-        _ = writeln!(&mut str, "v {} {} {}", p2.0, p2.1, p2.2);
-        _ = writeln!(&mut str, "v {} {} {}\n", p3.0, p3.1, p3.2);
+        _ = writeln!(&mut str, "v {} {} {}", p1[0], p1[1], p1[2]);
+        _ = writeln!(&mut str, "v {} {} {}", p2[0], p2[1], p2[2]);
+        _ = writeln!(&mut str, "v {} {} {}\n", p3[0], p3[1], p3[2]);
 
         if let (Some(n1), Some(n2), Some(n3)) = (
             vert[0].vertex.normal,
             vert[1].vertex.normal,
             vert[2].vertex.normal,
         ) {
-            _ = writeln!(&mut str, "vn {} {} {}", n1.0, n1.1, n1.2);
-            _ = writeln!(&mut str, "vn {} {} {}", n2.0, n2.1, n2.2);
-            _ = writeln!(&mut str, "vn {} {} {}\n", n3.0, n3.1, n3.2);
+            _ = writeln!(&mut str, "vn {} {} {}", n1[0], n1[1], n1[2]);
+            _ = writeln!(&mut str, "vn {} {} {}", n2[0], n2[1], n2[2]);
+            _ = writeln!(&mut str, "vn {} {} {}\n", n3[0], n3[1], n3[2]);
 
             normals = true;
         }
@@ -109,7 +71,7 @@ fn _vertex_to_file(verts: Vec<VertexTextureData>) {
 
         i += 3;
 
-        file.write(str.as_bytes()).unwrap();
+        file.write_all(str.as_bytes()).unwrap();
 
         str.clear();
     }
@@ -135,10 +97,10 @@ fn _object_to_file(obj: &ObjObject) {
                 let mut normals = false;
 
                 for v in f.vertices() {
-                    let (p1, p2, p3) = v.position;
+                    let [p1, p2, p3] = v.position;
                     _ = writeln!(&mut str, "v {p1} {p2} {p3}");
 
-                    if let Some((n1, n2, n3)) = v.normal {
+                    if let Some([n1, n2, n3]) = v.normal {
                         _ = writeln!(&mut str, "vn {n1} {n2} {n3}\n");
                         normals = true;
                     }
@@ -155,9 +117,300 @@ fn _object_to_file(obj: &ObjObject) {
 
                 i += 3;
 
-                file.write(str.as_bytes()).unwrap();
+                file.write_all(str.as_bytes()).unwrap();
                 str.clear();
             }
         }
     }
 }
+
+fn write_parse(obj: &ObjObject, options: &ParseOptions) -> ObjObject {
+    let mut written = Vec::new();
+    obj.write_to_writer(&mut written).unwrap();
+
+    ObjObject::parse_with(Cursor::new(written), options).unwrap()
+}
+
+fn assert_same(a: &ObjObject, b: &ObjObject) {
+    assert_eq!(a.object_count(), b.object_count());
+    assert_eq!(a.group_count(), b.group_count());
+    assert_eq!(a.face_count(), b.face_count());
+    assert_eq!(a.vertices(), b.vertices());
+
+    for (o1, o2) in a.objects_iter().zip(b.objects_iter()) {
+        assert_eq!(o1.name(), o2.name());
+        assert_eq!(o1.mtllib(), o2.mtllib());
+        // the pools are written in order, so every face keeps its v/vt/vn indices
+        assert_eq!(o1.faces(), o2.faces());
+
+        for (g1, g2) in o1.group_iter().zip(o2.group_iter()) {
+            assert_eq!(g1.name(), g2.name());
+            assert_eq!(g1.mtluse(), g2.mtluse());
+            assert_eq!(g1.smooth_group(), g2.smooth_group());
+            assert_eq!(g1.line_count(), g2.line_count());
+            assert_eq!(g1.point_element_count(), g2.point_element_count());
+            assert!(
+                g1.faces_iter()
+                    .map(|f| f.smooth_group)
+                    .eq(g2.faces_iter().map(|f| f.smooth_group))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parse_write_parse() {
+    for mesh in ["./meshes/cheburashka.obj", "./meshes/cubes.obj"] {
+        let obj = ObjObject::read_from_file(mesh).unwrap();
+        let reread = write_parse(&obj, &ParseOptions::default());
+
+        assert_same(&obj, &reread);
+    }
+}
+
+#[test]
+fn test_write_hierarchy() {
+    let data = "
+mtllib scene.mtl
+v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 0 1 0 0 0 1
+v 1 1 0 1 1 1
+vt 0 0
+vt 1 0
+vt 0 1
+vn 0 0 1
+o first
+f 1/1/1 2/2/1 3/3/1
+g second
+usemtl red
+s 2
+f 1//1 2//1 4//1 3//1
+l 1/1 2/2 3/3
+o third
+mtllib other.mtl
+s off
+f 1/1 2/2 3/3
+p 1 2 -1
+";
+
+    let obj = ObjObject::parse_str(data).unwrap();
+    let reread = write_parse(&obj, &ParseOptions::default());
+
+    assert_same(&obj, &reread);
+    assert_eq!(reread.object_count(), 2);
+    assert_eq!(reread.group_count(), 3);
+}
+
+/// Every vertex of [`ObjObject::vertices`], with its `mtluse` instead of its `material_index`, in a fixed order.
+fn sorted_vertices(obj: &ObjObject) -> Vec<(String, Option<&str>)> {
+    let (vertices, materials) = obj.vertices();
+    let mut vertices = vertices
+        .iter()
+        .map(|v| {
+            (
+                format!("{:?}", v.vertex),
+                materials[v.material_index].mtluse,
+            )
+        })
+        .collect::<Vec<_>>();
+    vertices.sort();
+
+    vertices
+}
+
+#[test]
+fn test_write_indexed() {
+    let data = "
+mtllib scene.mtl
+v 0 0 0 1 0 0
+v 1 0 0 0 1 0
+v 0 1 0 0 0 1
+v 1 1 0 1 1 1
+vt 0 0
+vt 1 0
+vt 0 1
+vt 1 1
+vn 0 0 1
+usemtl red
+f 1/1/1 2/2/1 3/3/1
+usemtl blue
+f 2/2/1 4/4/1 3/3/1
+usemtl red
+f 1/1/1 3/3/1 4/4/1
+";
+
+    for obj in [
+        ObjObject::parse_str(data).unwrap(),
+        ObjObject::read_from_file("./meshes/cubes.obj").unwrap(),
+    ] {
+        let (indices, vertices, materials) = obj.vertices_indexed();
+
+        let mut written = Vec::new();
+        write::write_indexed(&mut written, &vertices, &indices, &materials).unwrap();
+        let reread = ObjObject::parse_bytes(&written).unwrap();
+
+        assert_eq!(reread.face_count(), obj.face_count());
+        assert_eq!(sorted_vertices(&reread), sorted_vertices(&obj));
+    }
+
+    let obj = ObjObject::parse_str(data).unwrap();
+    let (indices, vertices, materials) = obj.vertices_indexed();
+
+    let mut written = Vec::new();
+    write::write_indexed(&mut written, &vertices, &indices, &materials).unwrap();
+    let written = String::from_utf8(written).unwrap();
+
+    assert!(written.starts_with("mtllib scene.mtl\n"), "{written}");
+    assert_eq!(written.matches("usemtl red").count(), 2, "{written}");
+    assert_eq!(written.matches("usemtl blue").count(), 1, "{written}");
+    assert!(written.contains("f 1/1/1 2/2/2 3/3/3\n"), "{written}");
+    assert!(written.contains("v 1.000000 1.000000 0.000000 1.000000 1.000000 1.000000\n"));
+}
+
+#[test]
+fn test_write_keeps_polygons() {
+    let data = "
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+f 1 2 3 4
+f 2 5 3
+f 1 2 5 3 4
+";
+    let options = ParseOptions {
+        keep_polygons: true,
+        ..Default::default()
+    };
+
+    let obj = ObjObject::parse_with(Cursor::new(data), &options).unwrap();
+    let reread = write_parse(&obj, &options);
+    assert_same(&obj, &reread);
+
+    let group = reread
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap();
+    let corners = group
+        .polygons_iter()
+        .map(|p| p.corner_count())
+        .collect::<Vec<_>>();
+    assert_eq!(corners, [4, 3, 5]);
+}
+
+#[test]
+fn test_write_to_file() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    let path = std::env::temp_dir().join(format!("polypath_write_{}.obj", std::process::id()));
+    obj.write_to_file(&path).unwrap();
+    let reread = ObjObject::read_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_same(&obj, &reread.unwrap());
+}
+
+#[test]
+fn test_write_to() {
+    let obj = ObjObject::read_from_file("./meshes/cubes.obj").unwrap();
+
+    let mut written = Vec::new();
+    obj.write_to(&mut written).unwrap();
+    let mut expected = Vec::new();
+    obj.write_to_writer(&mut expected).unwrap();
+    assert_eq!(written, expected);
+
+    let reread = ObjObject::parse_with(Cursor::new(written), &ParseOptions::default()).unwrap();
+    assert_same(&obj, &reread);
+}
+
+fn write_string(obj: &ObjObject, config: &WriteConfig) -> String {
+    let mut written = Vec::new();
+    obj.write_to_writer_with_config(&mut written, config)
+        .unwrap();
+
+    String::from_utf8(written).unwrap()
+}
+
+const SINGLE_NORMAL: &str = "
+v 0.1 0 0
+v 1 0.3333333 0
+v 0 1 0
+vt 0.5 0.5
+vt 0.25 0.75
+vn 0 0 1
+vn 0 0 1
+f 1/1/1 2/2/2 3/1/1
+";
+
+#[test]
+fn test_write_float_precision() {
+    let obj = ObjObject::parse_str(SINGLE_NORMAL).unwrap();
+
+    let default = write_string(&obj, &WriteConfig::default());
+    assert!(default.starts_with("v 0.100000 0.000000 0.000000\nv 1.000000 0.333333 0.000000\n"));
+
+    let config = WriteConfig {
+        float_precision: 2,
+        ..Default::default()
+    };
+    let written = write_string(&obj, &config);
+    assert!(written.starts_with("v 0.10 0.00 0.00\nv 1.00 0.33 0.00\n"));
+    assert!(written.contains("\nvn 0.00 0.00 1.00\n"));
+    assert!(written.contains("\nf 1/1/1 2/2/2 3/1/1\n"));
+}
+
+#[test]
+fn test_write_compact() {
+    let obj = ObjObject::parse_str(SINGLE_NORMAL).unwrap();
+
+    let config = WriteConfig {
+        compact: true,
+        ..Default::default()
+    };
+    let written = write_string(&obj, &config);
+
+    // all normals are the same, but the texture coords differ
+    assert!(!written.contains("vn "));
+    assert!(written.contains("vt "));
+    assert!(written.contains("\nf 1/1 2/2 3/1\n"));
+
+    let reread = ObjObject::parse_str(&written).unwrap();
+    let face = reread
+        .objects_iter()
+        .next()
+        .unwrap()
+        .group_iter()
+        .next()
+        .unwrap()
+        .faces_iter()
+        .next()
+        .unwrap();
+    assert_eq!(face.vert_normals, None);
+    assert_eq!(
+        face.vert_uv_coords,
+        Some([[0.5, 0.5], [0.25, 0.75], [0.5, 0.5]])
+    );
+}
+
+#[test]
+fn test_write_comments() {
+    let obj = ObjObject::parse_str(SINGLE_NORMAL).unwrap();
+
+    let config = WriteConfig {
+        include_comments: true,
+        ..Default::default()
+    };
+    let written = write_string(&obj, &config);
+    assert!(written.starts_with("# vertices: 3\n# texture coords: 2\n# normals: 2\n# faces: 1\n"));
+
+    // comments are skipped when parsing
+    assert_eq!(ObjObject::parse_str(&written).unwrap().face_count(), 1);
+
+    assert!(!write_string(&obj, &WriteConfig::default()).contains('#'));
+}